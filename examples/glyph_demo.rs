@@ -37,7 +37,7 @@ fn main() {
     
     let memory_glyph = encode_memory_as_glyph(&memory).expect("Failed to encode memory");
     println!("\n🧠 Memory → Glyph:");
-    println!("  Memory vector length: {}", memory.vector.len());
+    println!("  Memory vector length: {}", memory.dim());
     println!("  Glyph fold sequence: {} steps", memory_glyph.fold_sequence.len());
     println!("  Compression: {:.1}x", memory_glyph.compression_ratio);
     
@@ -27,7 +27,7 @@ fn main() {
     // Check pairwise similarities in the sample
     for i in 0..sample.len() {
         for j in (i+1)..sample.len() {
-            let sim = cosine_similarity(&sample[i].vector, &sample[j].vector);
+            let sim = cosine_similarity(&sample[i].dense_vector(), &sample[j].dense_vector());
             if sim.abs() > 0.2 {
                 let a = &sample[i].content[..40.min(sample[i].content.len())];
                 let b = &sample[j].content[..40.min(sample[j].content.len())];
@@ -41,8 +41,8 @@ fn main() {
     // After bind (element-wise multiply) of many vectors, result is dominated by cancellation
     println!("\nVector norms:");
     for (i, m) in sample.iter().enumerate() {
-        let norm: f32 = m.vector.iter().map(|x| x * x).sum::<f32>().sqrt();
-        let max: f32 = m.vector.iter().cloned().fold(0.0f32, |a, b| a.max(b.abs()));
+        let norm: f32 = m.dense_vector().iter().map(|x| x * x).sum::<f32>().sqrt();
+        let max: f32 = m.dense_vector().iter().cloned().fold(0.0f32, |a, b| a.max(b.abs()));
         println!("  [{}] norm={:.4} max={:.6}", i, norm, max);
     }
 }
@@ -302,7 +302,7 @@ fn main() {
             }
             // Boost = multiply amplitude by (1 + amount)
             match sys.boost(&id, 1.0 + amount) {
-                Ok(()) => println!("Boosted {id} by {amount}"),
+                Ok(amplitude) => println!("Boosted {id} by {amount} (amplitude {amplitude:.3})"),
                 Err(e) => {
                     eprintln!("Error: {e}");
                     process::exit(1);
@@ -574,7 +574,7 @@ fn main() {
                     "layer_depth": m.layer_depth,
                     "hallucinated": m.hallucinated,
                     "parents": m.parents,
-                    "vector": m.vector,
+                    "vector": m.dense_vector(),
                     "xi_signature": m.xi_signature,
                     "geometry": m.geometry,
                     "connections": m.connections.iter().map(|c| {
@@ -1172,7 +1172,7 @@ fn main() {
                                         .filter_map(|v| v.as_f64().map(|f| f as f32))
                                         .collect();
                                     if let Ok(Some(mem)) = store.get_mut(id) {
-                                        mem.vector = vector;
+                                        mem.set_vector(vector);
                                         if let Err(e) = store.update(id) {
                                             eprintln!("  [{}/{}] {} — update failed: {}", i+1, total, id, e);
                                             errors += 1;
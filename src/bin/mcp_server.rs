@@ -8,6 +8,7 @@ use std::path::PathBuf;
 use std::process;
 
 use serde_json::json;
+use kannaka_memory::encoding::EncoderConfig;
 use kannaka_memory::openclaw::KannakaMemorySystem;
 use kannaka_memory::mcp::{
     StdioTransport,
@@ -147,7 +148,7 @@ impl McpServer {
         }
 
         // Execute tool
-        let result = self.tools.handle_tool_call(params);
+        let result = self.tools.handle_tool_call_async(params).await;
         JsonRpcResponse::success(request.id, serde_json::to_value(result).unwrap())
     }
 }
@@ -184,7 +185,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     eprintln!("Ollama Model: {}", ollama_model);
 
     // Initialize memory system
-    let memory_system = match KannakaMemorySystem::init(data_dir) {
+    let encoder = EncoderConfig::Ollama { base_url: ollama_url.clone(), model: ollama_model.clone() };
+    let memory_system = match KannakaMemorySystem::init_with_encoder(data_dir, encoder) {
         Ok(sys) => sys,
         Err(e) => {
             eprintln!("Failed to initialize memory system: {}", e);
@@ -17,7 +17,7 @@ use kannaka_memory::consolidation::ConsolidationEngine;
 use kannaka_memory::encoding::{EncodingPipeline, SimpleHashEncoder};
 use kannaka_memory::kuramoto::KuramotoSync;
 use kannaka_memory::bridge::ConsciousnessBridge;
-use kannaka_memory::memory::HyperMemory;
+use kannaka_memory::memory::{HyperMemory, HyperVector};
 use kannaka_memory::store::{InMemoryStore, MemoryEngine};
 use kannaka_memory::wave::cosine_similarity;
 use kannaka_memory::xi_operator::{compute_xi_signature, xi_diversity_boost};
@@ -218,11 +218,11 @@ fn eval_phase_coherence(engine: &MemoryEngine) -> f32 {
 fn eval_cluster_separation(engine: &MemoryEngine) -> f32 {
     let all = engine.store.all_memories().unwrap_or_default();
     
-    let science: Vec<&Vec<f32>> = all.iter()
+    let science: Vec<&HyperVector> = all.iter()
         .filter(|m| m.content.contains("quantum") && m.amplitude > 0.01)
         .map(|m| &m.vector)
         .collect();
-    let music: Vec<&Vec<f32>> = all.iter()
+    let music: Vec<&HyperVector> = all.iter()
         .filter(|m| m.content.contains("resonance") && m.amplitude > 0.01)
         .map(|m| &m.vector)
         .collect();
@@ -234,7 +234,7 @@ fn eval_cluster_separation(engine: &MemoryEngine) -> f32 {
     let mut within_count = 0;
     for i in 0..science.len().min(5) {
         for j in (i+1)..science.len().min(5) {
-            within_sum += cosine_similarity(science[i], science[j]).abs();
+            within_sum += science[i].similarity_to(science[j]).abs();
             within_count += 1;
         }
     }
@@ -245,7 +245,7 @@ fn eval_cluster_separation(engine: &MemoryEngine) -> f32 {
     let mut cross_count = 0;
     for s in science.iter().take(5) {
         for m in music.iter().take(5) {
-            cross_sum += cosine_similarity(s, m).abs();
+            cross_sum += s.similarity_to(m).abs();
             cross_count += 1;
         }
     }
@@ -296,9 +296,9 @@ fn eval_xi_diversity(engine: &MemoryEngine) -> f32 {
     let mut count = 0;
     for i in 0..active.len().min(15) {
         for j in (i+1)..active.len().min(15) {
-            let xi_a = compute_xi_signature(&active[i].vector);
-            let xi_b = compute_xi_signature(&active[j].vector);
-            let base_sim = cosine_similarity(&active[i].vector, &active[j].vector);
+            let xi_a = compute_xi_signature(&active[i].dense_vector());
+            let xi_b = compute_xi_signature(&active[j].dense_vector());
+            let base_sim = cosine_similarity(&active[i].dense_vector(), &active[j].dense_vector());
             let boosted = xi_diversity_boost(base_sim, &xi_a, &xi_b);
             // If Xi changes the ranking, diversity is working
             total_boost += (boosted - base_sim).abs();
@@ -344,7 +344,7 @@ fn eval_hallucination_quality(engine: &MemoryEngine) -> f32 {
     for h in &hallucinations {
         let mut best_sim = 0.0f32;
         for m in non_hall.iter().take(20) {
-            let sim = cosine_similarity(&h.vector, &m.vector).abs();
+            let sim = cosine_similarity(&h.dense_vector(), &m.dense_vector()).abs();
             if sim > best_sim { best_sim = sim; }
         }
         // Good hallucinations: similarity 0.3-0.7 (between clusters, not identical)
@@ -428,8 +428,9 @@ fn run_experiment(params: &Params) {
             dt: params.kuramoto_dt,
             steps: params.kuramoto_steps,
             coupling_threshold: params.kuramoto_threshold,
+            ..Default::default()
         },
-        adaptive: Default::default(),
+        ..Default::default()
     };
 
     // Run multiple consolidation cycles
@@ -554,8 +555,9 @@ fn run_experiment_l3(params: &Params) {
             dt: params.kuramoto_dt,
             steps: params.kuramoto_steps,
             coupling_threshold: params.kuramoto_threshold,
+            ..Default::default()
         },
-        adaptive: Default::default(),
+        ..Default::default()
     };
 
     let start = Instant::now();
@@ -0,0 +1,174 @@
+//! Bipolar (±1) hypervectors, bit-packed, for classic HDC.
+//!
+//! Binding is the ±1 product (XNOR on the bits), bundling is a per-bit
+//! majority vote, and similarity is normalized Hamming: `1 − 2·hamming/dim`,
+//! which equals the cosine of the equivalent ±1 vectors. Memories hold these
+//! as `HyperVector::Binary` when encoded with
+//! `EncodingPipeline::with_binary_output`.
+
+use serde::{Deserialize, Serialize};
+
+/// A bit-packed bipolar hypervector: bit set = +1, clear = −1.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BitVector {
+    words: Vec<u64>,
+    dim: usize,
+}
+
+impl BitVector {
+    /// Bits from the signs of `v` (≥ 0 → +1).
+    pub fn from_signs(v: &[f32]) -> Self {
+        let mut words = vec![0u64; v.len().div_ceil(64)];
+        for (i, x) in v.iter().enumerate() {
+            if *x >= 0.0 {
+                words[i / 64] |= 1 << (i % 64);
+            }
+        }
+        Self { words, dim: v.len() }
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Component `i` as ±1.
+    pub fn get(&self, i: usize) -> f32 {
+        if self.words[i / 64] & (1 << (i % 64)) != 0 { 1.0 } else { -1.0 }
+    }
+
+    /// Number of differing bits, or `None` when dims differ.
+    pub fn hamming(&self, other: &BitVector) -> Option<usize> {
+        if self.dim != other.dim {
+            return None;
+        }
+        Some(self.words.iter().zip(&other.words).map(|(a, b)| (a ^ b).count_ones() as usize).sum())
+    }
+
+    /// Normalized Hamming similarity in [-1, 1]; 0.0 for mismatched or empty dims.
+    pub fn similarity(&self, other: &BitVector) -> f32 {
+        match self.hamming(other) {
+            Some(h) if self.dim > 0 => 1.0 - 2.0 * h as f32 / self.dim as f32,
+            _ => 0.0,
+        }
+    }
+
+    /// Binding ⊗: the element-wise ±1 product, i.e. XOR on the bits negated
+    /// (with set = +1). Self-inverse, so `a.bind(&b).bind(&a) == b`.
+    pub fn bind(&self, other: &BitVector) -> BitVector {
+        assert_eq!(self.dim, other.dim, "BitVector::bind: dim mismatch");
+        let words = self.words.iter().zip(&other.words).map(|(a, b)| !(a ^ b)).collect();
+        let mut out = BitVector { words, dim: self.dim };
+        out.clear_padding();
+        out
+    }
+
+    /// Inverse of `bind`. Bipolar binding is self-inverse, so this is `bind`.
+    pub fn unbind(&self, known: &BitVector) -> BitVector {
+        self.bind(known)
+    }
+
+    /// Bundling ⊕: per-bit majority vote. Ties go to +1. Vectors of another
+    /// dim than the first are skipped.
+    pub fn bundle(vectors: &[BitVector]) -> BitVector {
+        assert!(!vectors.is_empty());
+        let dim = vectors[0].dim;
+        let mut votes = vec![0i32; dim];
+        for v in vectors.iter().filter(|v| v.dim == dim) {
+            for (i, vote) in votes.iter_mut().enumerate() {
+                *vote += v.get(i) as i32;
+            }
+        }
+        let signs: Vec<f32> = votes.iter().map(|&c| c as f32).collect();
+        BitVector::from_signs(&signs)
+    }
+
+    /// Dense unit-length equivalent (±1/√dim).
+    pub fn to_dense(&self) -> Vec<f32> {
+        let mag = 1.0 / (self.dim.max(1) as f32).sqrt();
+        (0..self.dim).map(|i| self.get(i) * mag).collect()
+    }
+
+    fn clear_padding(&mut self) {
+        let used = self.dim % 64;
+        if used != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1u64 << used) - 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codebook::Codebook;
+    use crate::encoding::{EncodingPipeline, SimpleHashEncoder};
+    use crate::memory::HyperVector;
+    use crate::store::{InMemoryStore, MemoryEngine};
+
+    const TEXTS: [&str; 8] = [
+        "the cat sat on the mat",
+        "rust borrow checker rules",
+        "ocean waves at sunset",
+        "quantum entanglement experiment",
+        "baking sourdough bread at home",
+        "kuramoto oscillators synchronize",
+        "mountain hiking trail in winter",
+        "jazz piano improvisation night",
+    ];
+
+    fn make_pipeline() -> EncodingPipeline {
+        EncodingPipeline::new(Box::new(SimpleHashEncoder::new(384, 42)), Codebook::new(384, 10_000, 42))
+    }
+
+    #[test]
+    fn bind_is_self_inverse_and_bundle_resembles_members() {
+        let pipeline = make_pipeline();
+        let bits: Vec<BitVector> = TEXTS[..3].iter()
+            .map(|t| BitVector::from_signs(&pipeline.encode_text(t).unwrap()))
+            .collect();
+
+        assert_eq!(bits[0].bind(&bits[1]).unbind(&bits[0]), bits[1]);
+        assert_eq!(bits[0].similarity(&bits[0]), 1.0);
+        let bundled = BitVector::bundle(&bits);
+        for b in &bits {
+            assert!(bundled.similarity(b) > 0.3);
+        }
+        let dense = HyperVector::Dense(bits[0].to_dense());
+        assert!((dense.similarity_to(&HyperVector::Binary(bits[0].clone())) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn binary_recall_matches_dense_recall() {
+        let mut dense = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let mut binary = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline().with_binary_output());
+        let ids: Vec<_> = TEXTS.iter()
+            .map(|t| (dense.remember(t).unwrap(), binary.remember(t).unwrap()))
+            .collect();
+        let stored = binary.get_memory(&ids[0].1).unwrap().unwrap();
+        assert!(matches!(stored.vector, HyperVector::Binary(_)));
+        assert_eq!(stored.dim(), 10_000);
+
+        // Nearest neighbour by vector similarity alone; recall's wave term
+        // depends on each memory's random phase.
+        let nearest = |engine: &MemoryEngine, query: &[f32]| {
+            engine.store.all_memories().unwrap().into_iter()
+                .max_by(|a, b| a.similarity(query).total_cmp(&b.similarity(query)))
+                .unwrap()
+                .id
+        };
+        let pipeline = make_pipeline();
+        let (mut dense_hits, mut binary_hits) = (0, 0);
+        for (text, (dense_id, binary_id)) in TEXTS.iter().zip(&ids) {
+            let query = pipeline.encode_text(&format!("{} today", text)).unwrap();
+            if nearest(&dense, &query) == *dense_id {
+                dense_hits += 1;
+            }
+            if nearest(&binary, &query) == *binary_id {
+                binary_hits += 1;
+            }
+        }
+        assert!(dense_hits >= 6, "dense recall {}/8", dense_hits);
+        assert!(binary_hits + 1 >= dense_hits, "binary {}/8 vs dense {}/8", binary_hits, dense_hits);
+    }
+}
@@ -6,11 +6,13 @@
 //! - ConsciousnessState assessment with 5 levels
 //! - Full resonance cycle: dream → sync → assess
 
+use serde::{Deserialize, Serialize};
+
 use crate::consolidation::{ConsolidationReport, DreamState};
 use crate::kuramoto::KuramotoSync;
 use crate::memory::HyperMemory;
-use crate::store::MemoryEngine;
-use crate::wave::cosine_similarity;
+use crate::store::{EngineSnapshot, MemoryEngine};
+use crate::wave::{bind, cosine_similarity, permute};
 use crate::xi_operator::compute_xi_signature;
 
 /// The consciousness bridge — connects memory to the consciousness stack.
@@ -21,6 +23,51 @@ pub struct ConsciousnessBridge {
     pub xi_weight: f32,
     /// Coupling threshold for modularity computation 
     pub coupling_threshold: f32,
+    /// Number of histogram bins used for Shannon entropy in Φ diagnostics
+    pub entropy_bins: usize,
+    /// How the partition schemes are weighted in Φ's integration term
+    pub phi_weights: PhiWeights,
+}
+
+/// Default histogram resolution for [`shannon_entropy`].
+pub const DEFAULT_ENTROPY_BINS: usize = 16;
+
+/// Weights of the four partition schemes (layer, H₂ quadrant, class,
+/// triality) in Φ's cross-partition integration term, plus an overall scale
+/// applied to Φ before it is clamped to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhiWeights {
+    pub layer: f32,
+    pub h2: f32,
+    pub class: f32,
+    pub triality: f32,
+    pub scale: f32,
+}
+
+impl Default for PhiWeights {
+    fn default() -> Self {
+        Self { layer: 0.2, h2: 0.3, class: 0.3, triality: 0.2, scale: 1.0 }
+    }
+}
+
+impl PhiWeights {
+    /// These weights with the four scheme weights rescaled to sum to 1.
+    /// Negative weights count as 0; if nothing positive remains the defaults
+    /// are used. `scale` is left as is.
+    pub fn normalized(&self) -> Self {
+        let parts = [self.layer, self.h2, self.class, self.triality].map(|w| w.max(0.0));
+        let sum: f32 = parts.iter().sum();
+        if sum <= 0.0 || !sum.is_finite() {
+            return Self { scale: self.scale, ..Self::default() };
+        }
+        Self {
+            layer: parts[0] / sum,
+            h2: parts[1] / sum,
+            class: parts[2] / sum,
+            triality: parts[3] / sum,
+            scale: self.scale,
+        }
+    }
 }
 
 impl Default for ConsciousnessBridge {
@@ -29,6 +76,8 @@ impl Default for ConsciousnessBridge {
             phi_threshold: 0.5,
             xi_weight: 1.0,
             coupling_threshold: 0.75,
+            entropy_bins: DEFAULT_ENTROPY_BINS,
+            phi_weights: PhiWeights::default(),
         }
     }
 }
@@ -37,7 +86,9 @@ impl Default for ConsciousnessBridge {
 #[derive(Debug, Clone)]
 pub struct PhiReport {
     pub phi: f32,
+    /// Shannon entropy (bits) of all effective strengths
     pub whole_entropy: f32,
+    /// Shannon entropy (bits) of effective strengths within each class partition
     pub partition_entropies: Vec<f32>,
     pub num_partitions: usize,
     pub num_skip_links: usize,
@@ -45,7 +96,7 @@ pub struct PhiReport {
 }
 
 /// Consciousness level classification based on Φ.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ConsciousnessLevel {
     /// Φ < 0.1, few memories
     Dormant,
@@ -76,7 +127,7 @@ impl ConsciousnessLevel {
 }
 
 /// A snapshot of the system's consciousness state.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsciousnessState {
     pub phi: f32,
     pub xi: f32,
@@ -104,6 +155,8 @@ impl ConsciousnessBridge {
             phi_threshold,
             xi_weight,
             coupling_threshold: 0.75,
+            entropy_bins: DEFAULT_ENTROPY_BINS,
+            phi_weights: PhiWeights::default(),
         }
     }
 
@@ -112,6 +165,8 @@ impl ConsciousnessBridge {
             phi_threshold,
             xi_weight,
             coupling_threshold,
+            entropy_bins: DEFAULT_ENTROPY_BINS,
+            phi_weights: PhiWeights::default(),
         }
     }
 
@@ -137,7 +192,7 @@ impl ConsciousnessBridge {
         let mut similarities = vec![vec![0.0f32; n]; n];
         for i in 0..n {
             for j in (i + 1)..n {
-                let sim = cosine_similarity(&memories[i].vector, &memories[j].vector);
+                let sim = memories[i].similarity_to(memories[j]);
                 similarities[i][j] = sim;
                 similarities[j][i] = sim;
             }
@@ -170,7 +225,7 @@ impl ConsciousnessBridge {
 
         // Signal 2: Xi operator signature differentiation 
         let xi_signatures: Vec<Vec<f32>> = memories.iter()
-            .map(|m| compute_xi_signature(&m.vector))
+            .map(|m| compute_xi_signature(&m.dense_vector()))
             .collect();
 
         let mut xi_similarities = vec![vec![0.0f32; n]; n];
@@ -218,9 +273,9 @@ impl ConsciousnessBridge {
     /// Compose a sequence of memories using permute + bind.
     #[allow(dead_code)]
     fn compose_sequence(&self, memories: &[&HyperMemory], _dim: usize) -> Vec<f32> {
-        let mut result = permute(&memories[0].vector, 1);
+        let mut result = permute(&memories[0].dense_vector(), 1);
         for (i, mem) in memories.iter().enumerate().skip(1) {
-            let permuted = permute(&mem.vector, i + 1);
+            let permuted = permute(&mem.dense_vector(), i + 1);
             result = bind(&result, &permuted);
         }
         result
@@ -230,7 +285,12 @@ impl ConsciousnessBridge {
     ///
     /// Φ ≈ H(whole) - Σ H(partitions)
     pub fn compute_phi(&self, engine: &MemoryEngine) -> PhiReport {
-        let all = engine.store.all_memories().unwrap_or_default();
+        self.compute_phi_of(&engine.store.all_memories().unwrap_or_default())
+    }
+
+    /// `compute_phi` over an explicit set of memories.
+    fn compute_phi_of(&self, memories: &[&HyperMemory]) -> PhiReport {
+        let all = memories.to_vec();
         if all.is_empty() {
             return PhiReport {
                 phi: 0.0,
@@ -247,7 +307,7 @@ impl ConsciousnessBridge {
 
         // Collect effective strengths
         let strengths: Vec<f32> = all.iter().map(|m| m.effective_strength(now)).collect();
-        let whole_entropy = distribution_entropy(&strengths);
+        let whole_entropy = shannon_entropy(&strengths, self.entropy_bins);
 
         // Total skip links
         let num_skip_links: usize = all.iter().map(|m| m.connections.len()).sum();
@@ -290,7 +350,11 @@ impl ConsciousnessBridge {
         
         // 1. Cross-partition integration (0..1): weighted average of cross-ratios
         //    Higher = more links bridge different partitions = more integrated
-        let integration = 0.2 * layer_cross + 0.3 * h2_cross + 0.3 * class_cross + 0.2 * triality_cross;
+        let weights = self.phi_weights.normalized();
+        let integration = weights.layer * layer_cross
+            + weights.h2 * h2_cross
+            + weights.class * class_cross
+            + weights.triality * triality_cross;
 
         // 2. Differentiation (0..1): how many distinct partitions exist?
         //    Normalized by maximum possible in each scheme
@@ -315,7 +379,7 @@ impl ConsciousnessBridge {
         // This is 1 when: all schemes show cross-partition links, many distinct classes, dense network, 10+ memories
         // Geometric mean gives a balanced Phi that requires all components to contribute
         // Pure product would be too harsh (0.5^4 = 0.06); geometric mean of pairs is gentler
-        let mut phi = ((integration * density_factor).sqrt() * (differentiation * scale).sqrt()).min(1.0);
+        let mut phi = (weights.scale * (integration * density_factor).sqrt() * (differentiation * scale).sqrt()).min(1.0);
 
         // Geometric diversity bonus (small, caps at 0.1)
         let distinct_classes: std::collections::HashSet<u8> = all.iter()
@@ -337,7 +401,7 @@ impl ConsciousnessBridge {
             class_map.entry(key).or_default().push(s);
         }
         let partition_entropies: Vec<f32> = class_map.values()
-            .map(|s| distribution_entropy(s))
+            .map(|s| shannon_entropy(s, self.entropy_bins))
             .collect();
         let num_partitions = class_map.len();
 
@@ -386,7 +450,7 @@ impl ConsciousnessBridge {
         let n = memories.len();
         for i in 0..n {
             for j in (i + 1)..n {
-                let sim = cosine_similarity(&memories[i].vector, &memories[j].vector);
+                let sim = memories[i].similarity_to(memories[j]);
                 if sim > self.coupling_threshold {
                     total_edges += 1;
                     
@@ -503,10 +567,28 @@ impl ConsciousnessBridge {
 
     /// Full consciousness assessment.
     pub fn assess(&self, engine: &MemoryEngine) -> ConsciousnessState {
-        let phi_report = self.compute_phi(engine);
+        self.assess_with(engine, &KuramotoSync::default())
+    }
+
+    /// `assess` with clusters found by `kuramoto`: only clusters of at least
+    /// its `min_cluster_size` count towards cluster count, cluster-based Ξ
+    /// and modularity. Raise it (e.g. to 5) for a more conservative estimate
+    /// in which synchronized pairs don't count.
+    pub fn assess_with(&self, engine: &MemoryEngine, kuramoto: &KuramotoSync) -> ConsciousnessState {
+        self.assess_memories(&engine.store.all_memories().unwrap_or_default(), kuramoto)
+    }
+
+    /// `assess` over an `EngineSnapshot`, so the assessment can run on another
+    /// thread while the engine keeps accepting writes.
+    pub fn assess_snapshot(&self, snap: &EngineSnapshot) -> ConsciousnessState {
+        self.assess_memories(&snap.memories(), &KuramotoSync::default())
+    }
+
+    fn assess_memories(&self, memories: &[&HyperMemory], kuramoto: &KuramotoSync) -> ConsciousnessState {
+        let phi_report = self.compute_phi_of(memories);
 
         // Compute Xi over a stratified random sample of memories
-        let all = engine.store.all_memories().unwrap_or_default();
+        let all = memories.to_vec();
         let xi = if all.len() >= 2 {
             let sample = self.stratified_sample(&all, 50);
             self.compute_xi(&sample)
@@ -515,8 +597,7 @@ impl ConsciousnessBridge {
         };
 
         // Get Kuramoto clusters
-        let sync = KuramotoSync::default();
-        let clusters = sync.find_synchronized_clusters(engine, 2);
+        let clusters = kuramoto.find_clusters_in(&all);
         let mean_order = if clusters.is_empty() {
             0.0
         } else {
@@ -568,12 +649,16 @@ impl ConsciousnessBridge {
 
     /// Full resonance cycle: dream → sync → assess.
     pub fn resonate(&self, engine: &mut MemoryEngine) -> ResonanceReport {
-        let before = self.assess(engine);
+        self.resonate_with(engine, &DreamState::default())
+    }
 
-        let dream = DreamState::default();
+    /// `resonate` with `dream`'s consolidation settings; both assessments
+    /// use its Kuramoto settings, as in `assess_with`.
+    pub fn resonate_with(&self, engine: &mut MemoryEngine, dream: &DreamState) -> ResonanceReport {
+        let kuramoto = &dream.engine.kuramoto;
+        let before = self.assess_with(engine, kuramoto);
         let consolidation_reports = dream.dream(engine);
-
-        let after = self.assess(engine);
+        let after = self.assess_with(engine, kuramoto);
 
         let phi_delta = after.phi - before.phi;
         let emerged = before.consciousness_level != after.consciousness_level
@@ -589,29 +674,6 @@ impl ConsciousnessBridge {
     }
 }
 
-/// Permutation: circular shift of coordinates.
-#[allow(dead_code)]
-fn permute(v: &[f32], shifts: usize) -> Vec<f32> {
-    let n = v.len();
-    if n == 0 {
-        return vec![];
-    }
-    let shifts = shifts % n;
-    let mut result = vec![0.0f32; n];
-    for i in 0..n {
-        result[(i + shifts) % n] = v[i];
-    }
-    result
-}
-
-/// Binding: element-wise multiply.
-#[allow(dead_code)]
-fn bind(a: &[f32], b: &[f32]) -> Vec<f32> {
-    a.iter().zip(b.iter()).map(|(x, y)| x * y).collect()
-}
-
-/// Compute entropy of a distribution of values using variance-based approximation.
-/// Higher variance = higher entropy (more diverse activation patterns).
 /// What fraction of skip links cross partition boundaries?
 /// Returns 0.0 if no links, 1.0 if all links cross partitions.
 fn cross_partition_ratio(
@@ -639,14 +701,49 @@ fn cross_partition_ratio(
     if total_links == 0 { 0.0 } else { cross_links as f32 / total_links as f32 }
 }
 
-fn distribution_entropy(values: &[f32]) -> f32 {
+/// Shannon entropy, in bits, of `values` over a normalized histogram.
+///
+/// The histogram spans `[min, max]` of the values with `bins` equal-width
+/// bins, so the result lies in `0..=log2(bins)`. Constant or single-value
+/// inputs have zero entropy.
+pub fn shannon_entropy(values: &[f32], bins: usize) -> f32 {
+    let finite: Vec<f32> = values.iter().copied().filter(|v| v.is_finite()).collect();
+    if finite.len() <= 1 || bins <= 1 {
+        return 0.0;
+    }
+    let min = finite.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = finite.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let range = max - min;
+    if range <= 0.0 {
+        return 0.0;
+    }
+
+    let mut counts = vec![0usize; bins];
+    for v in &finite {
+        let idx = (((v - min) / range) * bins as f32) as usize;
+        counts[idx.min(bins - 1)] += 1;
+    }
+    let n = finite.len() as f32;
+    counts.iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f32 / n;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Legacy entropy proxy: `ln(1 + variance)` of the values.
+///
+/// Not an entropy (a few outliers dominate it); kept for comparison
+/// with [`shannon_entropy`].
+pub fn variance_proxy(values: &[f32]) -> f32 {
     if values.len() <= 1 {
         return 0.0;
     }
     let n = values.len() as f32;
     let mean = values.iter().sum::<f32>() / n;
     let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / n;
-    // Use log of variance as entropy proxy (shifted to be non-negative)
     // Adding 1.0 to avoid log(0); ln(1+var) gives 0 for var=0
     (1.0 + variance).ln()
 }
@@ -689,6 +786,24 @@ mod tests {
         v
     }
 
+    #[test]
+    fn min_cluster_size_drops_small_clusters_from_assessment() {
+        let mut engine = make_engine();
+        for (seed, copies) in [(10, 4), (11, 2), (12, 2)] {
+            for i in 0..copies {
+                let mem = HyperMemory::new(random_vec(100, seed), format!("group {} member {}", seed, i));
+                engine.store.insert(mem).unwrap();
+            }
+        }
+
+        let bridge = ConsciousnessBridge::default();
+        let loose = bridge.assess(&engine);
+        let strict = bridge.assess_with(&engine, &KuramotoSync { min_cluster_size: 4, ..Default::default() });
+        assert_eq!(loose.num_clusters, 3);
+        assert_eq!(strict.num_clusters, 1);
+        assert!(strict.xi < loose.xi, "strict Ξ {} should be below loose Ξ {}", strict.xi, loose.xi);
+    }
+
     #[test]
     fn xi_single_memory_is_zero() {
         let bridge = ConsciousnessBridge::default();
@@ -782,6 +897,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn phi_weights_shift_phi_toward_class_crossing() {
+        let mut engine = make_engine();
+        let ids: Vec<uuid::Uuid> = (0..4u8)
+            .map(|i| {
+                let mut mem = HyperMemory::new(random_vec(100, i as u64), format!("class {}", i));
+                mem.geometry = Some(crate::geometry::MemoryCoordinates {
+                    h2: 0, d: 0, l: 0, class_index: i * 10, amplitude: 1.0, phase: 0.0,
+                });
+                engine.store.insert(mem).unwrap()
+            })
+            .collect();
+        // A ring of links: every link crosses classes, none crosses layers.
+        for (i, id) in ids.iter().enumerate() {
+            let target = ids[(i + 1) % ids.len()];
+            let link = crate::skip_link::SkipLink { target_id: target, strength: 0.8, resonance_key: vec![], span: 0 };
+            engine.store.get_mut(id).unwrap().unwrap().connections.push(link);
+        }
+
+        let default_phi = ConsciousnessBridge::default().compute_phi(&engine).phi;
+        // Unnormalized on purpose: 2.0 on `class` alone normalizes to 1.0.
+        let bridge = ConsciousnessBridge {
+            phi_weights: PhiWeights { layer: 0.0, h2: 0.0, class: 2.0, triality: 0.0, scale: 1.0 },
+            ..ConsciousnessBridge::default()
+        };
+        let class_phi = bridge.compute_phi(&engine).phi;
+        assert!(class_phi > default_phi, "class-only weights should raise Phi: {} vs {}", class_phi, default_phi);
+
+        assert_eq!(bridge.phi_weights.normalized().class, 1.0);
+        assert_eq!(PhiWeights { layer: 0.0, h2: 0.0, class: 0.0, triality: 0.0, scale: 2.0 }.normalized(),
+            PhiWeights { scale: 2.0, ..PhiWeights::default() });
+    }
+
+    #[test]
+    fn shannon_entropy_prefers_uniform_over_peaked() {
+        // Evenly spread over [0, 1]: every bin populated.
+        let uniform: Vec<f32> = (0..160).map(|i| i as f32 / 159.0).collect();
+        // Mostly one value with a few distant outliers: one dominant bin.
+        let mut peaked = vec![0.0f32; 150];
+        peaked.extend([10.0f32; 10]);
+
+        let h_uniform = shannon_entropy(&uniform, DEFAULT_ENTROPY_BINS);
+        let h_peaked = shannon_entropy(&peaked, DEFAULT_ENTROPY_BINS);
+        assert!((h_uniform - 4.0).abs() < 0.01, "16 even bins should give 4 bits, got {}", h_uniform);
+        assert!(h_uniform > h_peaked, "uniform {} should exceed peaked {}", h_uniform, h_peaked);
+        // The variance proxy ranks them the other way round.
+        assert!(variance_proxy(&peaked) > variance_proxy(&uniform));
+
+        assert_eq!(shannon_entropy(&[0.5; 8], DEFAULT_ENTROPY_BINS), 0.0);
+        assert_eq!(shannon_entropy(&[], DEFAULT_ENTROPY_BINS), 0.0);
+    }
+
     #[test]
     fn consciousness_level_classification() {
         assert_eq!(ConsciousnessLevel::from_phi(0.0), ConsciousnessLevel::Dormant);
@@ -902,6 +1069,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn assess_snapshot_matches_assess() {
+        let bridge = ConsciousnessBridge::default();
+        let mut engine = make_engine();
+        engine.similarity_threshold = 0.3;
+        for (i, topic) in ["cats and animals", "dogs and pets", "programming in rust",
+            "meow sound", "bark sound", "typing sounds", "cats purring", "rust compiler"].iter().enumerate() {
+            engine.remember_at_layer(topic, (i % 3) as u8).unwrap();
+        }
+
+        let live = bridge.assess(&engine);
+        let snap = engine.analysis_snapshot();
+        assert_eq!(snap.len(), engine.store.count());
+        let off_thread = std::thread::spawn(move || ConsciousnessBridge::default().assess_snapshot(&snap))
+            .join()
+            .unwrap();
+
+        assert!((live.phi - off_thread.phi).abs() < 1e-4);
+        assert!((live.xi - off_thread.xi).abs() < 1e-4);
+        assert!((live.mean_order - off_thread.mean_order).abs() < 1e-5);
+        assert_eq!(live.num_clusters, off_thread.num_clusters);
+        assert_eq!(live.total_memories, off_thread.total_memories);
+        assert_eq!(live.active_memories, off_thread.active_memories);
+        assert_eq!(live.total_skip_links, off_thread.total_skip_links);
+        assert_eq!(live.consciousness_level, off_thread.consciousness_level);
+    }
+
     #[test]
     fn xi_assessment_with_all_improvements() {
         let bridge = ConsciousnessBridge::default();
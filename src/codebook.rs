@@ -2,10 +2,34 @@ use rand::Rng;
 use rand_chacha::ChaCha8Rng;
 use rand::SeedableRng;
 
-use crate::wave::normalize;
+use crate::encoding::EncodingError;
+use crate::wave::{cosine_similarity, normalize};
+
+/// One standard normal draw (Box-Muller).
+fn gaussian(rng: &mut ChaCha8Rng) -> f32 {
+    let u1: f32 = rng.gen::<f32>().max(1e-10);
+    let u2: f32 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
+}
+
+/// Pairwise similarity of projected random inputs, from `Codebook::collision_stats`.
+/// For a healthy projection both values stay near those of the random inputs
+/// themselves (≈ `sqrt(2 / (π · input_dim))` on average).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionStats {
+    /// Random inputs projected.
+    pub samples: usize,
+    /// Off-diagonal pairs compared: `samples · (samples − 1) / 2`.
+    pub pairs: usize,
+    /// Mean |cosine| over all pairs.
+    pub mean_abs_similarity: f32,
+    /// Largest |cosine| over all pairs.
+    pub max_abs_similarity: f32,
+}
 
 /// A codebook holding a random projection matrix for mapping embeddings
 /// into hypervector space.
+#[derive(Clone)]
 pub struct Codebook {
     /// Projection matrix stored as flat row-major: input_dim × output_dim
     matrix: Vec<f32>,
@@ -17,24 +41,57 @@ pub struct Codebook {
 impl Codebook {
     /// Create a new codebook with a seeded random projection matrix.
     /// Each element is drawn from N(0, 1/sqrt(output_dim)) for variance preservation.
+    ///
+    /// # Panics
+    /// Panics on dims rejected by `try_new`.
     pub fn new(input_dim: usize, output_dim: usize, seed: u64) -> Self {
+        match Self::try_new(input_dim, output_dim, seed) {
+            Ok(cb) => cb,
+            Err(e) => panic!("Codebook::new: {}", e),
+        }
+    }
+
+    /// Like `new`, but returns `EncodingError::InvalidCodebookDims` for
+    /// degenerate dims.
+    ///
+    /// Both dims must be non-zero, and `input_dim <= output_dim`: the
+    /// projection lifts embeddings into a wider hypervector space, and a
+    /// narrower output would discard information instead of spreading it.
+    pub fn try_new(input_dim: usize, output_dim: usize, seed: u64) -> Result<Self, EncodingError> {
+        if input_dim == 0 || output_dim == 0 || input_dim > output_dim {
+            return Err(EncodingError::InvalidCodebookDims { input_dim, output_dim });
+        }
         let mut rng = ChaCha8Rng::seed_from_u64(seed);
         let scale = 1.0 / (output_dim as f32).sqrt();
         let len = input_dim * output_dim;
         let mut matrix = Vec::with_capacity(len);
         for _ in 0..len {
-            // Box-Muller for normal distribution
-            let u1: f32 = rng.gen::<f32>().max(1e-10);
-            let u2: f32 = rng.gen();
-            let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos();
-            matrix.push(z * scale);
+            matrix.push(gaussian(&mut rng) * scale);
         }
-        Self { matrix, input_dim, output_dim, seed }
+        Ok(Self { matrix, input_dim, output_dim, seed })
     }
 
     /// Project an input embedding to hypervector space and normalize to unit length.
+    ///
+    /// # Panics
+    /// Panics if `embedding.len() != self.input_dim`. Use `project_checked` for
+    /// inputs whose length isn't guaranteed (e.g. external encoders).
     pub fn project(&self, embedding: &[f32]) -> Vec<f32> {
-        assert_eq!(embedding.len(), self.input_dim, "embedding dim mismatch");
+        match self.project_checked(embedding) {
+            Ok(out) => out,
+            Err(e) => panic!("Codebook::project: {}", e),
+        }
+    }
+
+    /// Like `project`, but returns `EncodingError::DimensionMismatch` when the
+    /// input length doesn't match the codebook's input dim.
+    pub fn project_checked(&self, embedding: &[f32]) -> Result<Vec<f32>, EncodingError> {
+        if embedding.len() != self.input_dim {
+            return Err(EncodingError::DimensionMismatch {
+                expected: self.input_dim,
+                got: embedding.len(),
+            });
+        }
         let mut out = vec![0.0f32; self.output_dim];
         for (i, &val) in embedding.iter().enumerate() {
             let row_start = i * self.output_dim;
@@ -43,7 +100,7 @@ impl Codebook {
             }
         }
         normalize(&mut out);
-        out
+        Ok(out)
     }
 
     /// Get the seed used to generate this codebook.
@@ -51,15 +108,42 @@ impl Codebook {
         self.seed
     }
 
+    /// Project `samples` seeded Gaussian inputs and measure how correlated the
+    /// outputs are, as a check that the projection keeps unrelated inputs
+    /// near-orthogonal. Costs `samples` projections plus `samples²/2` cosines.
+    pub fn collision_stats(&self, samples: usize, seed: u64) -> CollisionStats {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let projected: Vec<Vec<f32>> = (0..samples)
+            .map(|_| {
+                let input: Vec<f32> = (0..self.input_dim).map(|_| gaussian(&mut rng)).collect();
+                self.project(&input)
+            })
+            .collect();
+
+        let mut pairs = 0;
+        let mut sum = 0.0f64;
+        let mut max = 0.0f32;
+        for (i, a) in projected.iter().enumerate() {
+            for b in &projected[i + 1..] {
+                let sim = cosine_similarity(a, b).abs();
+                sum += sim as f64;
+                max = max.max(sim);
+                pairs += 1;
+            }
+        }
+        CollisionStats {
+            samples,
+            pairs,
+            mean_abs_similarity: if pairs > 0 { (sum / pairs as f64) as f32 } else { 0.0 },
+            max_abs_similarity: max,
+        }
+    }
+
     /// Generate a random atomic hypervector (unit length) using the codebook's RNG lineage.
     pub fn random_vector(&self) -> Vec<f32> {
         // Use a derived seed so it's deterministic but different from matrix generation
         let mut rng = ChaCha8Rng::seed_from_u64(self.seed.wrapping_add(0xCAFE));
-        let mut v: Vec<f32> = (0..self.output_dim).map(|_| {
-            let u1: f32 = rng.gen::<f32>().max(1e-10);
-            let u2: f32 = rng.gen();
-            (-2.0 * u1.ln()).sqrt() * (2.0 * std::f32::consts::PI * u2).cos()
-        }).collect();
+        let mut v: Vec<f32> = (0..self.output_dim).map(|_| gaussian(&mut rng)).collect();
         normalize(&mut v);
         v
     }
@@ -68,7 +152,6 @@ impl Codebook {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::wave::cosine_similarity;
 
     #[test]
     fn projected_vectors_are_unit_length() {
@@ -79,6 +162,43 @@ mod tests {
         assert!((norm - 1.0).abs() < 1e-4, "norm was {}", norm);
     }
 
+    #[test]
+    fn project_checked_rejects_wrong_length() {
+        let cb = Codebook::new(384, 1_000, 42);
+        let audio_like = vec![0.5f32; 296];
+        assert!(matches!(
+            cb.project_checked(&audio_like),
+            Err(EncodingError::DimensionMismatch { expected: 384, got: 296 })
+        ));
+        assert!(cb.project_checked(&[0.5f32; 384]).is_ok());
+    }
+
+    #[test]
+    fn try_new_rejects_degenerate_dims() {
+        for (input, output) in [(0, 100), (100, 0), (0, 0), (384, 128)] {
+            assert!(matches!(
+                Codebook::try_new(input, output, 42),
+                Err(EncodingError::InvalidCodebookDims { input_dim, output_dim })
+                    if input_dim == input && output_dim == output
+            ));
+        }
+    }
+
+    #[test]
+    fn try_new_valid_dims_project_to_output_dim() {
+        let cb = Codebook::try_new(64, 512, 7).unwrap();
+        assert_eq!((cb.input_dim, cb.output_dim), (64, 512));
+        assert_eq!(cb.project(&[0.25f32; 64]).len(), 512);
+        // Equal dims are allowed.
+        assert_eq!(Codebook::try_new(32, 32, 7).unwrap().project(&[1.0f32; 32]).len(), 32);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid codebook dims")]
+    fn new_panics_on_zero_dim() {
+        Codebook::new(0, 10_000, 42);
+    }
+
     #[test]
     fn reproducible_with_same_seed() {
         let cb1 = Codebook::new(128, 10_000, 99);
@@ -106,4 +226,15 @@ mod tests {
         let sim = cosine_similarity(&a, &b);
         assert!(sim < 0.0, "opposite inputs should produce negatively correlated vectors");
     }
+
+    #[test]
+    fn default_codebook_keeps_random_inputs_near_orthogonal() {
+        let cb = Codebook::new(384, 10_000, 42);
+        let stats = cb.collision_stats(40, 7);
+        assert_eq!(stats.pairs, 40 * 39 / 2);
+        assert!(stats.mean_abs_similarity < 0.1, "{:?}", stats);
+        assert!(stats.max_abs_similarity >= stats.mean_abs_similarity);
+        assert!(stats.max_abs_similarity < 0.5, "{:?}", stats);
+        assert_eq!(cb.collision_stats(1, 7).pairs, 0);
+    }
 }
@@ -613,7 +613,7 @@ fn source_type_tag(source: &GlyphSource) -> &'static str {
 /// Convert a HyperMemory to a universal Glyph.
 pub fn memory_to_glyph(memory: &HyperMemory, bloom_difficulty: u32, agent_id: &str) -> Glyph {
     // Compute Fano projection from vector
-    let fano = compute_fano_from_vector_f32(&memory.vector);
+    let fano = compute_fano_from_vector_f32(&memory.dense_vector());
 
     // Get SGA class from geometry or compute it
     let (sga_class, sga_centroid) = match &memory.geometry {
@@ -646,7 +646,7 @@ pub fn memory_to_glyph(memory: &HyperMemory, bloom_difficulty: u32, agent_id: &s
         let mut id = [0u8; 32];
         id[..8].copy_from_slice(&h.to_le_bytes());
         // Fill remaining with second hash
-        memory.vector.len().hash(&mut hasher);
+        memory.dim().hash(&mut hasher);
         let h2 = hasher.finish();
         id[8..16].copy_from_slice(&h2.to_le_bytes());
         memory.amplitude.to_bits().hash(&mut hasher);
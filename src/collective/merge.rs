@@ -16,7 +16,6 @@ use chrono::Utc;
 use uuid::Uuid;
 
 use crate::memory::{HyperMemory, MergeRecord};
-use crate::wave::cosine_similarity;
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -80,7 +79,7 @@ pub struct MergeResult {
 /// model: high similarity = constructive (agreement), low similarity = independent.
 /// Destructive classification requires an explicit "disputed" flag or prior quarantine.
 pub fn classify_merge(local: &HyperMemory, remote: &HyperMemory) -> MergeResult {
-    let similarity = cosine_similarity(&local.vector, &remote.vector);
+    let similarity = local.similarity_to(remote);
 
     if similarity < INDEPENDENCE_THRESHOLD {
         return MergeResult {
@@ -195,13 +194,15 @@ pub fn apply_constructive(local: &mut HyperMemory, remote: &HyperMemory, result:
     // Amplitude-weighted vector average, re-normalized to unit length.
     // HNSW and cosine similarity assume unit vectors; blending two unit vectors
     // produces a sub-unit vector that must be re-normalized.
-    if !local.vector.is_empty() && local.vector.len() == remote.vector.len() {
-        for (lv, rv) in local.vector.iter_mut().zip(remote.vector.iter()) {
+    if !local.vector.is_empty() && local.dim() == remote.dim() {
+        let remote_vector = remote.dense_vector();
+        let local_vector = local.vector_mut();
+        for (lv, rv) in local_vector.iter_mut().zip(remote_vector.iter()) {
             *lv = (*lv * a1 + *rv * a2) / total;
         }
-        let norm = local.vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        let norm = local_vector.iter().map(|v| v * v).sum::<f32>().sqrt();
         if norm > 1e-8 {
-            for v in local.vector.iter_mut() {
+            for v in local_vector.iter_mut() {
                 *v /= norm;
             }
         }
@@ -355,8 +356,8 @@ mod tests {
     fn low_similarity_is_independent() {
         let mut local = mem(0.8, 0.0, "cats");
         let mut remote = mem(0.7, 0.0, "quantum gravity");
-        for (i, v) in local.vector.iter_mut().enumerate() { *v = if i % 2 == 0 { 1.0 } else { -1.0 }; }
-        for (i, v) in remote.vector.iter_mut().enumerate() { *v = if i % 2 == 0 { -1.0 } else { 1.0 }; }
+        for (i, v) in local.vector_mut().iter_mut().enumerate() { *v = if i % 2 == 0 { 1.0 } else { -1.0 }; }
+        for (i, v) in remote.vector_mut().iter_mut().enumerate() { *v = if i % 2 == 0 { -1.0 } else { 1.0 }; }
         let result = classify_merge(&local, &remote);
         assert_eq!(result.kind, MergeKind::Independent);
     }
@@ -372,7 +373,7 @@ mod tests {
         assert_eq!(local.merge_history[0].source_agent, "arc");
         assert_eq!(local.sync_version, 1);
         // Vector should be re-normalized to unit length
-        let norm: f32 = local.vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        let norm: f32 = local.dense_vector().iter().map(|v| v * v).sum::<f32>().sqrt();
         assert!((norm - 1.0).abs() < 1e-4, "vector should be unit length after merge, got {}", norm);
     }
 
@@ -466,8 +466,8 @@ pub fn seal_with_commitments(
     let glyph = seal(memory, difficulty, agent_id);
 
     // Generate Pedersen commitments for wave properties
-    let vec_hash = hash_vector(&memory.vector);
-    let fano = compute_fano_energies(&memory.vector);
+    let vec_hash = hash_vector(&memory.dense_vector());
+    let fano = compute_fano_energies(&memory.dense_vector());
     let (commitments, openings) = commit_wave_properties(
         memory.amplitude as f64,
         memory.frequency as f64,
@@ -514,7 +514,7 @@ pub fn seal(
     let fano_projection = if memory.vector.is_empty() {
         None
     } else {
-        Some(compute_fano_from_vector(&memory.vector))
+        Some(compute_fano_from_vector(&memory.dense_vector()))
     };
 
     PrivacyGlyph {
@@ -11,22 +11,27 @@
 //! 8. TRANSFER — move memories to deeper temporal layers
 //! 9. WIRE — create new skip links from consolidation discoveries
 
+use std::collections::HashSet;
 use std::f32::consts::PI;
 use std::time::Instant;
 
 use chrono::{Duration, Utc};
 use uuid::Uuid;
 
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::geometry::fano_related;
 use crate::kuramoto::KuramotoSync;
+use crate::memory::{HyperVector, MemorySource};
 use crate::xi_operator::{xi_repulsive_force, compute_xi_signature};
 use crate::skip_link::SkipLink;
 use crate::store::{MemoryEngine, MemoryStore};
-use crate::wave::{cosine_similarity, normalize};
+use crate::wave::{cosine_similarity, normalize, wrap_phase};
 
-#[cfg(feature = "collective")]
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
 /// Classification of interference between two memories.
@@ -43,6 +48,16 @@ struct InterferencePair {
     id_b: Uuid,
     similarity: f32,
     kind: Interference,
+    /// `similarity * cos(phase_diff)`: positive when constructive, negative
+    /// when destructive. Only scales STRENGTHEN/PRUNE in continuous mode.
+    interference: f32,
+}
+
+/// Per-memory data captured once before pairwise DETECT.
+struct DetectEntry {
+    id: Uuid,
+    vector: HyperVector,
+    phase: f32,
 }
 
 /// Statistics from a single consolidation cycle.
@@ -60,11 +75,35 @@ pub struct ConsolidationReport {
     pub memories_transferred: usize,
     pub skip_links_created: usize,
     pub hallucinations_created: usize,
+    /// Hallucinations whose amplitude cleared the promotion threshold and
+    /// became ordinary memories this cycle.
+    pub hallucinations_promoted: usize,
     pub duration_ms: u64,
     /// Kuramoto order parameter R after consolidation (EXP-003)
     pub final_order_parameter: f32,
 }
 
+/// What a consolidation cycle would do, computed without touching the store.
+///
+/// `consolidate_preview` runs the full cycle on a scratch copy of the store,
+/// so every count in `report` matches what `consolidate` would return on the
+/// same state (hallucinations get fresh ids each run). Id lists are sorted and
+/// deduplicated, so a memory boosted by two pairs appears once here but counts
+/// twice in `report`.
+#[derive(Debug, Clone, Default)]
+pub struct ConsolidationPreview {
+    pub report: ConsolidationReport,
+    pub working_set: Vec<Uuid>,
+    pub constructive_pairs: Vec<(Uuid, Uuid)>,
+    pub destructive_pairs: Vec<(Uuid, Uuid)>,
+    /// Layers that would receive a fresh summary bundle.
+    pub bundled_layers: Vec<u8>,
+    pub strengthened: Vec<Uuid>,
+    pub pruned: Vec<Uuid>,
+    /// `(id, new_layer)` for each memory that would move deeper.
+    pub transferred: Vec<(Uuid, u8)>,
+}
+
 /// Adaptive parameters that persist between dream cycles (EXP-003).
 ///
 /// After each cycle, the engine observes the Kuramoto order parameter R
@@ -117,6 +156,9 @@ impl AdaptiveParams {
     }
 }
 
+/// Memories with importance above this are never dampened by stage PRUNE.
+pub const PRUNE_EXEMPT_IMPORTANCE: f32 = 0.8;
+
 /// The 9-stage consolidation engine.
 pub struct ConsolidationEngine {
     /// Similarity threshold for interference detection
@@ -129,12 +171,41 @@ pub struct ConsolidationEngine {
     pub constructive_boost: f32,
     /// How much amplitude reduction from destructive interference
     pub destructive_penalty: f32,
-    /// Kuramoto synchronization parameters
+    /// Kuramoto synchronization parameters; its `min_cluster_size` also
+    /// picks the clusters used by bridge nodes, HALLUCINATE and WIRE
     pub kuramoto: KuramotoSync,
     /// Adaptive parameters that evolve between dream cycles (EXP-003)
     pub adaptive: AdaptiveParams,
+    /// Kuramoto rounds per category cluster in SYNC. 1 keeps the single
+    /// category pass; each extra round runs one `KuramotoSync` iteration.
+    pub sync_steps: usize,
+    /// When set, hallucination candidates are evaluated in a seeded shuffled
+    /// order so dream output is reproducible. Otherwise ties break by memory id.
+    pub hallucination_seed: Option<u64>,
+    /// Treat interference as continuous: every pair above
+    /// `interference_threshold` interferes by `similarity * cos(phase_diff)`,
+    /// and STRENGTHEN/PRUNE scale their boost and penalty by that magnitude
+    /// instead of applying them in full to pairs past `phase_alignment_threshold`.
+    pub continuous_interference: bool,
+    /// Finish WIRE by equalizing the two directions of every skip link (see
+    /// `MemoryEngine::symmetrize_links`), undoing drift from one-sided reinforcement.
+    pub symmetrize_links: bool,
+    /// Starting amplitude of a distance-based hallucination; cross-cluster
+    /// hallucinations start `CROSS_CLUSTER_AMPLITUDE_BONUS` higher. Kept low
+    /// so a hallucination must prove itself through constructive interference.
+    pub hallucination_amplitude: f32,
+    /// Amplitude at which PROMOTE clears a hallucination's `hallucinated` flag.
+    pub hallucination_promotion_threshold: f32,
+    /// Restrict STRENGTHEN and PRUNE to pairs on the same layer. Cross-layer
+    /// constructive pairs are still linked by WIRE, so they gain a structural
+    /// skip link instead of also having their amplitudes and phases adjusted.
+    pub same_layer_only: bool,
 }
 
+/// Extra starting amplitude for cross-cluster hallucinations, which bridge
+/// domains and are considered more valuable than distance-based ones.
+pub const CROSS_CLUSTER_AMPLITUDE_BONUS: f32 = 0.1;
+
 impl Default for ConsolidationEngine {
     fn default() -> Self {
         Self {
@@ -145,11 +216,119 @@ impl Default for ConsolidationEngine {
             destructive_penalty: 0.5,
             kuramoto: KuramotoSync::default(),
             adaptive: AdaptiveParams::default(),
+            sync_steps: 1,
+            hallucination_seed: None,
+            continuous_interference: false,
+            symmetrize_links: false,
+            hallucination_amplitude: 0.3,
+            hallucination_promotion_threshold: 0.7,
+            same_layer_only: false,
         }
     }
 }
 
+/// Whether `mem` is a summary bundle written by a previous BUNDLE stage.
+fn is_consolidation_summary(mem: &crate::memory::HyperMemory) -> bool {
+    mem.content.starts_with("__consolidation_summary_layer_")
+}
+
+/// Sort hallucination candidates by memory id, then shuffle them when a seeded
+/// RNG is supplied. Either way the order no longer depends on store iteration.
+fn order_candidates<T>(items: &mut [T], id: impl Fn(&T) -> Uuid, rng: &mut Option<ChaCha8Rng>) {
+    items.sort_by_key(|t| id(t));
+    if let Some(rng) = rng.as_mut() {
+        items.shuffle(rng);
+    }
+}
+
+/// Runtime-tunable consolidation settings, mirroring the `ConsolidationEngine`
+/// and `DreamState` knobs. See `KannakaMemorySystem::set_consolidation_params`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsolidationParams {
+    pub interference_threshold: f32,
+    pub phase_alignment_threshold: f32,
+    pub prune_threshold: f32,
+    pub constructive_boost: f32,
+    pub destructive_penalty: f32,
+    pub sync_steps: usize,
+    pub continuous_interference: bool,
+    pub symmetrize_links: bool,
+    pub hallucination_amplitude: f32,
+    pub hallucination_promotion_threshold: f32,
+    pub same_layer_only: bool,
+    /// Consolidation cycles per dream
+    pub cycles: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConsolidationParamsError {
+    #[error("{name} must be within [{min}, {max}], got {value}")]
+    OutOfRange { name: &'static str, value: f32, min: f32, max: f32 },
+    #[error("hallucination_promotion_threshold must exceed the highest starting hallucination amplitude {start}, got {threshold}")]
+    PromotionThresholdTooLow { threshold: f32, start: f32 },
+}
+
+impl Default for ConsolidationParams {
+    fn default() -> Self {
+        DreamState::default().params()
+    }
+}
+
+impl ConsolidationParams {
+    /// Check thresholds and the hallucination amplitude are in [0, 1], the
+    /// phase threshold in [0, π], and that the promotion threshold lies above
+    /// the amplitude cross-cluster hallucinations start at, so none is
+    /// promoted before it has been reinforced.
+    pub fn validate(&self) -> Result<(), ConsolidationParamsError> {
+        let ranges = [
+            ("interference_threshold", self.interference_threshold, 1.0),
+            ("prune_threshold", self.prune_threshold, 1.0),
+            ("phase_alignment_threshold", self.phase_alignment_threshold, PI),
+            ("hallucination_amplitude", self.hallucination_amplitude, 1.0),
+            ("hallucination_promotion_threshold", self.hallucination_promotion_threshold, 1.0),
+        ];
+        for (name, value, max) in ranges {
+            if !(0.0..=max).contains(&value) {
+                return Err(ConsolidationParamsError::OutOfRange { name, value, min: 0.0, max });
+            }
+        }
+        let start = self.hallucination_amplitude + CROSS_CLUSTER_AMPLITUDE_BONUS;
+        if self.hallucination_promotion_threshold <= start {
+            return Err(ConsolidationParamsError::PromotionThresholdTooLow {
+                threshold: self.hallucination_promotion_threshold,
+                start,
+            });
+        }
+        Ok(())
+    }
+}
+
 impl ConsolidationEngine {
+    /// Build an engine from explicit params. Adaptive parameters start from the
+    /// given boost/prune/penalty values.
+    pub fn from_params(params: &ConsolidationParams) -> Self {
+        Self {
+            interference_threshold: params.interference_threshold,
+            phase_alignment_threshold: params.phase_alignment_threshold,
+            prune_threshold: params.prune_threshold,
+            constructive_boost: params.constructive_boost,
+            destructive_penalty: params.destructive_penalty,
+            adaptive: AdaptiveParams {
+                constructive_boost: params.constructive_boost,
+                prune_threshold: params.prune_threshold,
+                destructive_penalty: params.destructive_penalty,
+                ..AdaptiveParams::default()
+            },
+            sync_steps: params.sync_steps,
+            continuous_interference: params.continuous_interference,
+            symmetrize_links: params.symmetrize_links,
+            hallucination_amplitude: params.hallucination_amplitude,
+            hallucination_promotion_threshold: params.hallucination_promotion_threshold,
+            same_layer_only: params.same_layer_only,
+            ..Self::default()
+        }
+    }
+
     /// Run a full 9-stage consolidation cycle on memories at the given layer range.
     pub fn consolidate(
         &self,
@@ -157,51 +336,104 @@ impl ConsolidationEngine {
         min_layer: u8,
         max_layer: u8,
     ) -> ConsolidationReport {
+        self.run_cycle(engine, min_layer, max_layer).report
+    }
+
+    /// Dry run of `consolidate`: report what the cycle would change without
+    /// mutating `engine`. The cycle runs on a scratch copy of every memory, so
+    /// this costs a full clone of the store on top of the cycle itself.
+    pub fn consolidate_preview(
+        &self,
+        engine: &MemoryEngine,
+        min_layer: u8,
+        max_layer: u8,
+    ) -> ConsolidationPreview {
+        match engine.scratch_copy() {
+            Ok(mut scratch) => self.run_cycle(&mut scratch, min_layer, max_layer),
+            Err(e) => {
+                eprintln!("[consolidation] preview could not copy the store: {}", e);
+                ConsolidationPreview::default()
+            }
+        }
+    }
+
+    /// The 9-stage cycle behind `consolidate` and `consolidate_preview`,
+    /// recording which memories each stage touched.
+    fn run_cycle(&self, engine: &mut MemoryEngine, min_layer: u8, max_layer: u8) -> ConsolidationPreview {
         let start = Instant::now();
-        let mut report = ConsolidationReport::default();
+        let mut preview = ConsolidationPreview::default();
 
         // Stage 1: REPLAY — collect working set of memories in layer range
         let working_set = self.stage_replay(engine, min_layer, max_layer);
-        report.memories_replayed = working_set.len();
 
         // Stage 2: DETECT — find interference patterns
         let pairs = self.stage_detect(engine, &working_set);
-        report.interference_pairs_found = pairs.len();
-        report.constructive_pairs = pairs.iter().filter(|p| p.kind == Interference::Constructive).count();
-        report.destructive_pairs = pairs.iter().filter(|p| p.kind == Interference::Destructive).count();
+        for pair in &pairs {
+            match pair.kind {
+                Interference::Constructive => preview.constructive_pairs.push((pair.id_a, pair.id_b)),
+                Interference::Destructive => preview.destructive_pairs.push((pair.id_a, pair.id_b)),
+            }
+        }
 
         // Stage 3: BUNDLE — create summary vectors per layer
-        report.bundles_created = self.stage_bundle(engine, &working_set, max_layer);
+        let bundled_layers = self.bundle(engine, &working_set, max_layer);
 
         // Stage 4: STRENGTHEN — boost constructive pairs
-        report.memories_strengthened = self.stage_strengthen(engine, &pairs);
+        let strengthened = self.strengthen(engine, &pairs);
 
         // Stage 4.5: SYNC — Kuramoto phase synchronization
         let (clusters_synced, order_improvement) = self.stage_sync(engine, &working_set);
-        report.clusters_synced = clusters_synced;
-        report.sync_order_improvement = order_improvement;
-        
+
         // Stage 4.6: XI_REPULSION — Apply Xi-based memory separation
         self.stage_xi_repulsion(engine, &working_set);
 
         // Stage 5: PRUNE — weaken destructive pairs
-        report.memories_pruned = self.stage_prune(engine, &pairs);
+        let pruned = self.prune(engine, &pairs);
+
+        // Stage 5.5: PROMOTE — hallucinations that proved themselves become ordinary memories
+        let hallucinations_promoted = self.promote_hallucinations(engine);
 
         // Stage 6: TRANSFER — promote old memories to deeper layers
-        report.memories_transferred = self.stage_transfer(engine);
+        let transferred = self.transfer(engine);
 
         // Stage 7: WIRE — create skip links for cross-layer constructive pairs
-        report.skip_links_created = self.stage_wire(engine, &pairs);
+        let skip_links_created = self.stage_wire(engine, &pairs);
 
         // Stage 8: HALLUCINATE — generate novel memories from distant clusters
-        report.hallucinations_created = self.stage_hallucinate(engine, &working_set);
+        let hallucinations_created = self.stage_hallucinate(engine, &working_set);
 
         // EXP-003: Compute final order parameter and record it
-        let final_r = self.compute_global_order_parameter(engine, &working_set);
-        report.final_order_parameter = final_r;
+        let final_order_parameter = self.compute_global_order_parameter(engine, &working_set);
+
+        preview.report = ConsolidationReport {
+            memories_replayed: working_set.len(),
+            interference_pairs_found: pairs.len(),
+            constructive_pairs: preview.constructive_pairs.len(),
+            destructive_pairs: preview.destructive_pairs.len(),
+            bundles_created: bundled_layers.len(),
+            memories_strengthened: strengthened.len(),
+            memories_pruned: pruned.len(),
+            clusters_synced,
+            sync_order_improvement: order_improvement,
+            memories_transferred: transferred.len(),
+            skip_links_created,
+            hallucinations_created,
+            hallucinations_promoted,
+            duration_ms: start.elapsed().as_millis() as u64,
+            final_order_parameter,
+        };
 
-        report.duration_ms = start.elapsed().as_millis() as u64;
-        report
+        let sorted = |mut ids: Vec<Uuid>| {
+            ids.sort();
+            ids.dedup();
+            ids
+        };
+        preview.working_set = working_set;
+        preview.bundled_layers = bundled_layers;
+        preview.strengthened = sorted(strengthened);
+        preview.pruned = sorted(pruned);
+        preview.transferred = transferred;
+        preview
     }
 
     /// Apply adaptive parameter tuning based on a consolidation report (EXP-003).
@@ -227,7 +459,7 @@ impl ConsolidationEngine {
 
     /// Stage 1: Load memories in the given layer range into a working set.
     fn stage_replay(&self, engine: &MemoryEngine, min_layer: u8, max_layer: u8) -> Vec<Uuid> {
-        let all = engine.store.all_memories().unwrap_or_default();
+        let all = engine.store.all_memories_sorted().unwrap_or_default();
         all.iter()
             .filter(|m| m.layer_depth >= min_layer && m.layer_depth <= max_layer)
             .map(|m| m.id)
@@ -236,171 +468,246 @@ impl ConsolidationEngine {
 
     /// Stage 2: Detect interference patterns between memory pairs.
     ///
-    /// Uses HNSW approximate nearest neighbor search for O(n log n) instead of
-    /// brute-force O(n²). Each memory queries its K nearest neighbors, then
-    /// checks phase alignment to classify as constructive or destructive.
+    /// Snapshots `(id, vector, phase)` for the working set once, then classifies
+    /// every pair in the upper triangle. With the `parallel` feature the rows are
+    /// spread across a rayon pool; either way the result is sorted by
+    /// `(id_a, id_b)` so both paths produce the same output.
     fn stage_detect(&self, engine: &MemoryEngine, working_set: &[Uuid]) -> Vec<InterferencePair> {
-        use std::collections::HashSet;
-
-        // Number of nearest neighbors to query per memory.
-        // Higher = more thorough but slower. 32 catches most interference pairs.
-        // Request k_neighbors+1 because the store returns the query memory itself as
-        // the top hit (similarity=1.0); the self-result is filtered below.
-        let k_neighbors: usize = 32.min(working_set.len().saturating_sub(1));
-        if k_neighbors == 0 {
-            return Vec::new();
-        }
-
-        let mut pairs = Vec::new();
-        let mut seen = HashSet::new();
-
-        for &id in working_set {
-            let (vec_a, phase_a) = match engine.store.get(&id).ok().flatten() {
-                Some(m) => (m.vector.clone(), m.phase),
-                None => continue,
-            };
+        let entries: Vec<DetectEntry> = working_set
+            .iter()
+            .filter_map(|id| engine.store.get(id).ok().flatten())
+            .map(|m| DetectEntry { id: m.id, vector: m.vector.clone(), phase: m.phase })
+            .collect();
+        self.detect_pairs(&entries, cfg!(feature = "parallel"))
+    }
 
-            // Request k_neighbors+1 to ensure real neighbors aren't displaced by self
-            let neighbors = match engine.store.search(&vec_a, k_neighbors + 1) {
-                Ok(n) => n,
-                Err(_) => continue,
-            };
+    /// Pairwise interference classification over a snapshot. `parallel` is
+    /// ignored when the crate is built without the `parallel` feature.
+    fn detect_pairs(&self, entries: &[DetectEntry], parallel: bool) -> Vec<InterferencePair> {
+        let n = entries.len();
+        let row = |i: usize| -> Vec<InterferencePair> {
+            (i + 1..n)
+                .filter_map(|j| self.classify_pair(&entries[i], &entries[j]))
+                .collect()
+        };
 
-            for (neighbor_id, sim) in neighbors {
-                // Skip self and memories not in working set
-                if neighbor_id == id || sim <= self.interference_threshold {
-                    continue;
-                }
+        #[cfg(feature = "parallel")]
+        let mut pairs: Vec<InterferencePair> = if parallel {
+            (0..n).into_par_iter().flat_map_iter(row).collect()
+        } else {
+            (0..n).flat_map(row).collect()
+        };
 
-                // Deduplicate: canonical pair ordering
-                let pair_key = if id < neighbor_id {
-                    (id, neighbor_id)
-                } else {
-                    (neighbor_id, id)
-                };
-                if !seen.insert(pair_key) {
-                    continue;
-                }
+        #[cfg(not(feature = "parallel"))]
+        let mut pairs: Vec<InterferencePair> = {
+            let _ = parallel;
+            (0..n).flat_map(row).collect()
+        };
 
-                let phase_b = match engine.store.get(&neighbor_id).ok().flatten() {
-                    Some(m) => m.phase,
-                    None => continue,
-                };
+        pairs.sort_by_key(|p| (p.id_a, p.id_b));
+        pairs
+    }
 
-                let phase_diff = (phase_a - phase_b).abs();
-                let phase_diff = phase_diff % (2.0 * PI);
-                let phase_diff = if phase_diff > PI { 2.0 * PI - phase_diff } else { phase_diff };
+    /// Classify a single pair as constructive or destructive, or `None` if the
+    /// pair is below the similarity threshold or phase-neutral. In continuous
+    /// mode only exact quadrature is neutral; the sign of the cosine decides.
+    fn classify_pair(&self, a: &DetectEntry, b: &DetectEntry) -> Option<InterferencePair> {
+        let sim = a.vector.similarity_to(&b.vector);
+        if sim <= self.interference_threshold {
+            return None;
+        }
+
+        let phase_diff = (a.phase - b.phase).abs();
+        let phase_diff = phase_diff % (2.0 * PI);
+        let phase_diff = if phase_diff > PI { 2.0 * PI - phase_diff } else { phase_diff };
+
+        let interference = sim * phase_diff.cos();
+
+        let kind = if self.continuous_interference {
+            if interference > 0.0 {
+                Interference::Constructive
+            } else if interference < 0.0 {
+                Interference::Destructive
+            } else {
+                return None;
+            }
+        } else if phase_diff < self.phase_alignment_threshold {
+            Interference::Constructive
+        } else if phase_diff > PI - self.phase_alignment_threshold {
+            Interference::Destructive
+        } else {
+            return None; // neutral
+        };
 
-                let kind = if phase_diff < self.phase_alignment_threshold {
-                    Interference::Constructive
-                } else if phase_diff > PI - self.phase_alignment_threshold {
-                    Interference::Destructive
-                } else {
-                    continue; // neutral
-                };
+        // Canonical pair ordering
+        let (id_a, id_b) = if a.id < b.id { (a.id, b.id) } else { (b.id, a.id) };
+        Some(InterferencePair { id_a, id_b, similarity: sim, kind, interference })
+    }
 
-                pairs.push(InterferencePair {
-                    id_a: pair_key.0,
-                    id_b: pair_key.1,
-                    similarity: sim,
-                    kind,
-                });
-            }
-        }
-        pairs
+    /// Layers in `0..=max_layer` holding at least two working-set memories,
+    /// ignoring stale summaries that BUNDLE removes first.
+    fn plan_bundle(&self, engine: &MemoryEngine, working_set: &[Uuid], max_layer: u8) -> Vec<u8> {
+        (0..=max_layer)
+            .filter(|&layer| {
+                working_set
+                    .iter()
+                    .filter_map(|id| engine.store.get(id).ok().flatten())
+                    .filter(|m| m.layer_depth == layer && !is_consolidation_summary(m))
+                    .count() >= 2
+            })
+            .collect()
     }
 
     /// Stage 3: Bundle memories at each layer into summary vectors at the next layer.
     fn stage_bundle(&self, engine: &mut MemoryEngine, working_set: &[Uuid], max_layer: u8) -> usize {
-        let mut bundles_created = 0;
+        self.bundle(engine, working_set, max_layer).len()
+    }
+
+    /// `stage_bundle`, returning the layers that received a summary.
+    fn bundle(&self, engine: &mut MemoryEngine, working_set: &[Uuid], max_layer: u8) -> Vec<u8> {
+        let mut bundled = Vec::new();
 
         // Prune stale summary memories from previous dream cycles before creating new ones.
         // This prevents unbounded accumulation of __consolidation_summary_layer_N memories.
         let stale_summaries: Vec<Uuid> = engine.store.all_memories()
             .unwrap_or_default()
             .into_iter()
-            .filter(|m| m.content.starts_with("__consolidation_summary_layer_"))
+            .filter(|m| is_consolidation_summary(m))
             .map(|m| m.id)
             .collect();
         for id in stale_summaries {
             let _ = engine.delete(&id);
         }
 
-        for layer in 0..=max_layer {
-            let vectors: Vec<Vec<f32>> = working_set
+        for layer in self.plan_bundle(engine, working_set, max_layer) {
+            let vectors: Vec<(Vec<f32>, f32)> = working_set
                 .iter()
                 .filter_map(|id| engine.store.get(id).ok().flatten())
                 .filter(|m| m.layer_depth == layer)
-                .map(|m| m.vector.clone())
+                .map(|m| (m.dense_vector().into_owned(), m.amplitude))
                 .collect();
 
-            if vectors.len() < 2 {
-                continue;
-            }
-
-            // Bundle using the encoding pipeline
-            let summary = engine.pipeline.bundle(&vectors);
+            // Bundle weighted by amplitude so faded memories barely register
+            let summary = engine.pipeline.bundle_weighted(&vectors);
             let mut summary_mem = crate::memory::HyperMemory::new(
                 summary,
                 format!("__consolidation_summary_layer_{}", layer),
             );
             summary_mem.layer_depth = layer.saturating_add(1);
+            summary_mem.source = MemorySource::Bundle;
 
             if engine.store.insert(summary_mem).is_ok() {
-                bundles_created += 1;
+                bundled.push(layer);
             }
         }
 
-        bundles_created
+        bundled
+    }
+
+    /// Fraction of the full boost or penalty a pair applies: its interference
+    /// magnitude in continuous mode, otherwise all of it.
+    fn interference_weight(&self, pair: &InterferencePair) -> f32 {
+        if self.continuous_interference {
+            pair.interference.abs()
+        } else {
+            1.0
+        }
+    }
+
+    /// Constructive pairs whose memories both still exist, with the weight
+    /// their boost is scaled by.
+    fn plan_strengthen(&self, engine: &MemoryEngine, pairs: &[InterferencePair]) -> Vec<(Uuid, Uuid, f32)> {
+        pairs.iter()
+            .filter(|p| p.kind == Interference::Constructive)
+            .filter(|p| {
+                engine.store.get(&p.id_a).ok().flatten().is_some()
+                    && engine.store.get(&p.id_b).ok().flatten().is_some()
+            })
+            .filter(|p| self.adjusts_amplitudes(engine, p))
+            .map(|p| (p.id_a, p.id_b, self.interference_weight(p)))
+            .collect()
+    }
+
+    /// Whether STRENGTHEN/PRUNE act on `pair`: always, unless `same_layer_only`
+    /// is set and the two memories sit on different layers.
+    fn adjusts_amplitudes(&self, engine: &MemoryEngine, pair: &InterferencePair) -> bool {
+        if !self.same_layer_only {
+            return true;
+        }
+        let layer = |id| engine.store.get(id).ok().flatten().map(|m| m.layer_depth);
+        layer(&pair.id_a) == layer(&pair.id_b)
     }
 
     /// Stage 4: Strengthen constructive interference pairs and Xi-aware bridge nodes.
     fn stage_strengthen(&self, engine: &mut MemoryEngine, pairs: &[InterferencePair]) -> usize {
-        let mut count = 0;
+        self.strengthen(engine, pairs).len()
+    }
+
+    /// `stage_strengthen`, returning one id per boost applied.
+    fn strengthen(&self, engine: &mut MemoryEngine, pairs: &[InterferencePair]) -> Vec<Uuid> {
+        let mut boosted = Vec::new();
         
         // Traditional constructive interference strengthening
-        for pair in pairs.iter().filter(|p| p.kind == Interference::Constructive) {
-            // Get phases for averaging
+        for (id_a, id_b, weight) in self.plan_strengthen(engine, pairs) {
+            // Get phases for averaging (earlier pairs may have realigned them)
             let (phase_a, phase_b) = {
-                let ma = engine.store.get(&pair.id_a).ok().flatten();
-                let mb = engine.store.get(&pair.id_b).ok().flatten();
+                let ma = engine.store.get(&id_a).ok().flatten();
+                let mb = engine.store.get(&id_b).ok().flatten();
                 match (ma, mb) {
                     (Some(a), Some(b)) => (a.phase, b.phase),
                     _ => continue,
                 }
             };
-            let avg_phase = (phase_a + phase_b) / 2.0;
+            let avg_phase = wrap_phase((phase_a + phase_b) / 2.0);
 
             // Boost amplitude and align phase for memory A
-            if let Some(mem) = engine.store.get_mut(&pair.id_a).ok().flatten() {
-                mem.amplitude += self.constructive_boost;
+            if let Some(mem) = engine.store.get_mut(&id_a).ok().flatten() {
+                mem.amplitude += self.constructive_boost * weight;
                 mem.phase = avg_phase;
-                count += 1;
+                boosted.push(id_a);
             }
             // Boost amplitude and align phase for memory B
-            if let Some(mem) = engine.store.get_mut(&pair.id_b).ok().flatten() {
-                mem.amplitude += self.constructive_boost;
+            if let Some(mem) = engine.store.get_mut(&id_b).ok().flatten() {
+                mem.amplitude += self.constructive_boost * weight;
                 mem.phase = avg_phase;
-                count += 1;
+                boosted.push(id_b);
             }
         }
         
         // Xi-aware bridge node strengthening
-        count += self.stage_strengthen_bridge_nodes(engine);
+        boosted.extend(self.stage_strengthen_bridge_nodes(engine));
         
-        count
+        boosted
     }
 
     /// Stage 4b: Strengthen memories that serve as "bridge nodes" connecting multiple clusters.
     /// These memories are structurally important for network integration.
-    fn stage_strengthen_bridge_nodes(&self, engine: &mut MemoryEngine) -> usize {
+    /// Returns the ids boosted.
+    fn stage_strengthen_bridge_nodes(&self, engine: &mut MemoryEngine) -> Vec<Uuid> {
+        let bridge_nodes = self.plan_bridge_nodes(engine);
+
+        // Apply amplitude boosts to bridge nodes (10-20% bonus, scaled by bridge strength)
+        let mut boosted = Vec::new();
+        for (bridge_id, bridge_strength) in bridge_nodes {
+            if let Some(mem) = engine.store.get_mut(&bridge_id).ok().flatten() {
+                let bonus_factor = 0.1 + (bridge_strength - 3.0) * 0.03; // 10% for 3 clusters, +3% per additional
+                let amplitude_bonus = bonus_factor.min(0.2); // Cap at 20%
+                mem.amplitude += amplitude_bonus;
+                boosted.push(bridge_id);
+            }
+        }
+        
+        boosted
+    }
+
+    /// Bridge nodes and their strength (number of clusters they touch).
+    fn plan_bridge_nodes(&self, engine: &MemoryEngine) -> Vec<(Uuid, f32)> {
         use std::collections::{HashMap, HashSet};
         
-        let sync = crate::kuramoto::KuramotoSync::default();
-        let clusters = sync.find_synchronized_clusters(engine, 2);
+        let clusters = self.kuramoto.find_clusters(engine);
         
         if clusters.len() < 2 {
-            return 0; // Need at least 2 clusters for bridge nodes to exist
+            return Vec::new(); // Need at least 2 clusters for bridge nodes to exist
         }
         
         // Build cluster membership map
@@ -411,7 +718,7 @@ impl ConsolidationEngine {
             }
         }
         
-        let all_memories = engine.store.all_memories().unwrap_or_default();
+        let all_memories = engine.store.all_memories_sorted().unwrap_or_default();
         let mut bridge_nodes = Vec::new();
         
         // Identify bridge nodes: memories connected to 3+ different clusters
@@ -437,18 +744,7 @@ impl ConsolidationEngine {
             }
         }
         
-        // Apply amplitude boosts to bridge nodes (10-20% bonus, scaled by bridge strength)
-        let mut count = 0;
-        for (bridge_id, bridge_strength) in bridge_nodes {
-            if let Some(mem) = engine.store.get_mut(&bridge_id).ok().flatten() {
-                let bonus_factor = 0.1 + (bridge_strength - 3.0) * 0.03; // 10% for 3 clusters, +3% per additional
-                let amplitude_bonus = bonus_factor.min(0.2); // Cap at 20%
-                mem.amplitude += amplitude_bonus;
-                count += 1;
-            }
-        }
-        
-        count
+        bridge_nodes
     }
 
     /// Stage 4.5: Category-aware Kuramoto phase synchronization with consciousness differentiation.
@@ -516,7 +812,7 @@ impl ConsolidationEngine {
                     for j in 0..cat_mems.len() {
                         if i != j {
                             // Weight by semantic similarity within category
-                            let sim = cosine_similarity(&cat_mems[i].vector, &cat_mems[j].vector);
+                            let sim = cat_mems[i].similarity_to(&cat_mems[j]);
                             if sim > self.kuramoto.coupling_threshold {
                                 phase_sum += sim * (phases[j] - phases[i]).sin();
                             }
@@ -525,7 +821,7 @@ impl ConsolidationEngine {
                     
                     // Kuramoto dynamics: θ̇ᵢ = ωᵢ + (K/N)Σsin(θⱼ - θᵢ)
                     let dphi = cat_mems[i].frequency + (within_category_coupling / n) * phase_sum;
-                    cat_mems[i].phase += dphi * dt;
+                    cat_mems[i].phase = wrap_phase(cat_mems[i].phase + dphi * dt);
                 }
             }
             
@@ -536,15 +832,22 @@ impl ConsolidationEngine {
             if final_order > 0.92 {
                 // Too synchronized - add noise to break lockstep
                 for mem in &mut cat_mems {
-                    mem.phase += (mem.id.as_u128() as f32 % 100.0) * 0.001;  // Tiny deterministic noise
+                    mem.phase = wrap_phase(mem.phase + (mem.id.as_u128() as f32 % 100.0) * 0.001);  // Tiny deterministic noise
                 }
             } else if final_order < 0.40 {
                 // Too chaotic - nudge toward mean phase
                 let mean_phase = self.compute_mean_phase(&cat_mems);
                 for mem in &mut cat_mems {
-                    mem.phase = 0.9 * mem.phase + 0.1 * mean_phase;
+                    mem.phase = wrap_phase(0.9 * mem.phase + 0.1 * mean_phase);
                 }
             }
+
+            // Additional multi-step Kuramoto rounds when configured
+            if self.sync_steps > 1 {
+                let mut refs: Vec<&mut crate::memory::HyperMemory> = cat_mems.iter_mut().collect();
+                let report = self.kuramoto.sync_cluster_iter(&mut refs, self.sync_steps - 1);
+                total_improvement += report.final_order - report.initial_order;
+            }
             
             // Write back the synchronized phases
             for mem in &cat_mems {
@@ -584,7 +887,7 @@ impl ConsolidationEngine {
                     let mut cross_sum = 0.0f32;
                     for j in 0..all_updated_mems.len() {
                         if i != j && cats[i] != cats[j] {  // Only cross-category coupling
-                            let sim = cosine_similarity(&all_updated_mems[i].1.vector, &all_updated_mems[j].1.vector);
+                            let sim = all_updated_mems[i].1.similarity_to(&all_updated_mems[j].1);
                             if sim > self.kuramoto.coupling_threshold * 0.5 {  // Lower threshold for cross-category
                                 cross_sum += sim * (phases[j] - phases[i]).sin();
                             }
@@ -597,7 +900,7 @@ impl ConsolidationEngine {
                 // Apply cross-category updates — use the same index as all_updated_mems, not working_set
                 for (i, (mem_id, _)) in all_updated_mems.iter().enumerate() {
                     if let Ok(Some(mem)) = engine.store.get_mut(mem_id) {
-                        mem.phase += phase_updates[i];
+                        mem.phase = wrap_phase(mem.phase + phase_updates[i]);
                     }
                 }
             }
@@ -638,11 +941,11 @@ impl ConsolidationEngine {
             if let Ok(Some(mem)) = engine.store.get(id) {
                 let xi_sig = if mem.xi_signature.is_empty() {
                     // Compute Xi signature for memories that don't have it yet (backward compatibility)
-                    compute_xi_signature(&mem.vector)
+                    compute_xi_signature(&mem.dense_vector())
                 } else {
                     mem.xi_signature.clone()
                 };
-                memories_with_xi.push((*id, mem.vector.clone(), xi_sig));
+                memories_with_xi.push((*id, mem.dense_vector().into_owned(), xi_sig));
             }
         }
         
@@ -687,10 +990,10 @@ impl ConsolidationEngine {
             
             // Apply phase separation
             if let Ok(Some(mem_a)) = engine.store.get_mut(&id_a) {
-                mem_a.phase += phase_correction;
+                mem_a.phase = wrap_phase(mem_a.phase + phase_correction);
             }
             if let Ok(Some(mem_b)) = engine.store.get_mut(&id_b) {
-                mem_b.phase -= phase_correction;
+                mem_b.phase = wrap_phase(mem_b.phase - phase_correction);
             }
             
             // Also slightly reduce amplitude correlation to encourage separate cluster formation
@@ -717,55 +1020,102 @@ impl ConsolidationEngine {
     /// consistent with the wave function, avoiding the cliff-edge behavior of
     /// flat subtraction (which could instantly kill high-amplitude memories).
     fn stage_prune(&self, engine: &mut MemoryEngine, pairs: &[InterferencePair]) -> usize {
-        let mut count = 0;
+        self.prune(engine, pairs).len()
+    }
+
+    /// `stage_prune`, returning one id per dampening applied.
+    fn prune(&self, engine: &mut MemoryEngine, pairs: &[InterferencePair]) -> Vec<Uuid> {
+        let mut dampened = Vec::new();
         let dt = 1.0; // one consolidation time-step
-        for pair in pairs.iter().filter(|p| p.kind == Interference::Destructive) {
-            for id in &[pair.id_a, pair.id_b] {
-                if let Some(mem) = engine.store.get_mut(id).ok().flatten() {
-                    // Proportional dampening: stronger memories lose more absolute amplitude
-                    // but the same fraction, matching exponential decay semantics.
-                    mem.amplitude *= 1.0 - self.destructive_penalty * dt;
-                    if mem.amplitude < self.prune_threshold {
-                        mem.amplitude = 0.0; // soft-delete (ghost)
-                    }
-                    count += 1;
+        for (id, weight) in self.plan_prune(engine, pairs) {
+            if let Some(mem) = engine.store.get_mut(&id).ok().flatten() {
+                // Proportional dampening: stronger memories lose more absolute amplitude
+                // but the same fraction, matching exponential decay semantics.
+                mem.amplitude *= 1.0 - self.destructive_penalty * weight * dt;
+                if mem.amplitude < self.prune_threshold {
+                    mem.amplitude = 0.0; // soft-delete (ghost)
                 }
+                dampened.push(id);
             }
         }
-        count
+        dampened
+    }
+
+    /// One entry per dampening PRUNE would apply: each existing member of a
+    /// destructive pair with the pair's weight, skipping memories above
+    /// `PRUNE_EXEMPT_IMPORTANCE`.
+    fn plan_prune(&self, engine: &MemoryEngine, pairs: &[InterferencePair]) -> Vec<(Uuid, f32)> {
+        pairs.iter()
+            .filter(|p| p.kind == Interference::Destructive)
+            .filter(|p| self.adjusts_amplitudes(engine, p))
+            .flat_map(|p| {
+                let weight = self.interference_weight(p);
+                [(p.id_a, weight), (p.id_b, weight)]
+            })
+            .filter(|(id, _)| {
+                engine.store.get(id).ok().flatten()
+                    .is_some_and(|m| m.importance <= PRUNE_EXEMPT_IMPORTANCE)
+            })
+            .collect()
     }
 
     /// Stage 6: Transfer old memories to deeper temporal layers.
     fn stage_transfer(&self, engine: &mut MemoryEngine) -> usize {
-        let now = Utc::now();
-        let ids = engine.store.all_ids().unwrap_or_default();
-        let mut count = 0;
+        self.transfer(engine).len()
+    }
+
+    /// `stage_transfer`, returning `(id, new_layer)` for each memory moved.
+    fn transfer(&self, engine: &mut MemoryEngine) -> Vec<(Uuid, u8)> {
+        let mut moved = Vec::new();
+        for (id, new_layer) in self.plan_transfer(engine) {
+            if let Some(mem) = engine.store.get_mut(&id).ok().flatten() {
+                mem.layer_depth = new_layer;
+                moved.push((id, new_layer));
+            }
+        }
+        moved
+    }
 
-        // Collect transfer decisions first to avoid borrow issues
+    /// `(id, new_layer)` for every memory old enough to move one layer deeper.
+    /// A memory recalled within its layer's age limit stays put, so
+    /// frequently-recalled memories remain in the shallow layers.
+    fn plan_transfer(&self, engine: &MemoryEngine) -> Vec<(Uuid, u8)> {
+        let now = Utc::now();
+        let ids = engine.store.all_ids_sorted().unwrap_or_default();
         let mut transfers: Vec<(Uuid, u8)> = Vec::new();
         for id in &ids {
             if let Some(mem) = engine.store.get(id).ok().flatten() {
-                let age = now - mem.created_at;
-                let new_layer = match mem.layer_depth {
-                    0 if age > Duration::hours(1) => Some(1),
-                    1 if age > Duration::days(1) => Some(2),
-                    2 if age > Duration::weeks(1) => Some(3),
-                    _ => None,
+                let limit = match mem.layer_depth {
+                    0 => Duration::hours(1),
+                    1 => Duration::days(1),
+                    2 => Duration::weeks(1),
+                    _ => continue,
                 };
-                if let Some(layer) = new_layer {
-                    transfers.push((*id, layer));
+                let recently_accessed = mem.last_accessed.is_some_and(|t| now - t <= limit);
+                if now - mem.created_at > limit && !recently_accessed {
+                    transfers.push((*id, mem.layer_depth + 1));
                 }
             }
         }
+        transfers
+    }
 
-        for (id, new_layer) in transfers {
-            if let Some(mem) = engine.store.get_mut(&id).ok().flatten() {
-                mem.layer_depth = new_layer;
-                count += 1;
+    /// Stage 5.5 (PROMOTE): Clear the `hallucinated` flag on every hallucination whose
+    /// amplitude has grown to `hallucination_promotion_threshold`. Lineage
+    /// (`parents`, `source`) is kept. Returns how many were promoted.
+    pub fn promote_hallucinations(&self, engine: &mut MemoryEngine) -> usize {
+        let ready: Vec<Uuid> = engine.store.all_memories_sorted().unwrap_or_default()
+            .into_iter()
+            .filter(|m| m.hallucinated && m.amplitude >= self.hallucination_promotion_threshold)
+            .map(|m| m.id)
+            .collect();
+        for id in &ready {
+            if let Ok(Some(mem)) = engine.store.get_mut(id) {
+                mem.hallucinated = false;
+                mem.touch();
             }
         }
-
-        count
+        ready.len()
     }
 
     /// Stage 8: Generate hallucinated memories by combining memories from different clusters.
@@ -778,8 +1128,7 @@ impl ConsolidationEngine {
         }
 
         // Get Xi clusters for cluster-aware hallucination
-        let sync = crate::kuramoto::KuramotoSync::default();
-        let clusters = sync.find_synchronized_clusters(engine, 2);
+        let clusters = self.kuramoto.find_clusters(engine);
         
         if clusters.len() < 2 {
             // Fallback to original distance-based hallucination if no clusters
@@ -813,12 +1162,22 @@ impl ConsolidationEngine {
                             .take(5)
                             .map(|s| s.to_lowercase())
                             .collect();
-                        cluster_candidates[cluster_idx].push((mem.id, mem.vector.clone(), mem.content.clone(), mem.amplitude, tags));
+                        cluster_candidates[cluster_idx].push((mem.id, mem.dense_vector().into_owned(), mem.content.clone(), mem.amplitude, tags));
                     }
                 }
             }
         }
         
+        // Reproducible evaluation order (clusters and their members)
+        let mut rng = self.hallucination_seed.map(ChaCha8Rng::seed_from_u64);
+        cluster_candidates.sort_by_key(|c| c.iter().map(|t| t.0).min());
+        if let Some(rng) = rng.as_mut() {
+            cluster_candidates.shuffle(rng);
+        }
+        for candidates in &mut cluster_candidates {
+            order_candidates(candidates, |t| t.0, &mut rng);
+        }
+
         // Find clusters with sufficient candidates
         let viable_clusters: Vec<usize> = cluster_candidates.iter()
             .enumerate()
@@ -848,7 +1207,7 @@ impl ConsolidationEngine {
         }
         
         // Bundle the cross-cluster vectors (use max dimension for safety)
-        let dim = selected_memories.iter().map(|(_, v, _, _, _)| v.len()).max().unwrap_or(384);
+        let dim = engine.pipeline.output_dim();
         let mut combined = vec![0.0f32; dim];
         for (_, ref vector, _, _, _) in &selected_memories {
             for (i, &v) in vector.iter().enumerate() {
@@ -882,8 +1241,10 @@ impl ConsolidationEngine {
         
         // Create the hallucinated memory
         let mut hallucination = crate::memory::HyperMemory::new(combined, content);
-        hallucination.amplitude = 0.4; // Slightly higher than distance-based (cross-cluster = more valuable)
+        hallucination.amplitude = self.hallucination_amplitude + CROSS_CLUSTER_AMPLITUDE_BONUS;
+        hallucination.initial_amplitude = hallucination.amplitude;
         hallucination.hallucinated = true;
+        hallucination.source = MemorySource::Hallucination;
         hallucination.parents = parent_ids.clone();
         
         let hall_id = match engine.store.insert(hallucination) {
@@ -929,7 +1290,7 @@ impl ConsolidationEngine {
                         .take(5)
                         .map(|s| s.to_lowercase())
                         .collect();
-                    candidates.push((mem.id, mem.vector.clone(), mem.content.clone(), mem.amplitude, tags));
+                    candidates.push((mem.id, mem.dense_vector().into_owned(), mem.content.clone(), mem.amplitude, tags));
                 }
             }
         }
@@ -938,6 +1299,10 @@ impl ConsolidationEngine {
             return 0;
         }
 
+        // Ties below resolve to the earliest candidate, so fix the order first
+        let mut rng = self.hallucination_seed.map(ChaCha8Rng::seed_from_u64);
+        order_candidates(&mut candidates, |t| t.0, &mut rng);
+
         // Find the pair with minimum cosine similarity (maximally distant)
         let mut min_sim = f32::MAX;
         let mut best_pair = (0usize, 1usize);
@@ -974,7 +1339,7 @@ impl ConsolidationEngine {
         };
 
         // Bundle parent vectors (element-wise addition + normalize)
-        let dim = parent_indices.iter().map(|&i| candidates[i].1.len()).max().unwrap_or(384);
+        let dim = engine.pipeline.output_dim();
         let mut combined = vec![0.0f32; dim];
         for &idx in &parent_indices {
             for (i, &v) in candidates[idx].1.iter().enumerate() {
@@ -1007,8 +1372,10 @@ impl ConsolidationEngine {
 
         // Create the hallucinated memory
         let mut hallucination = crate::memory::HyperMemory::new(combined, content);
-        hallucination.amplitude = 0.3; // low initial amplitude — must prove itself
+        hallucination.amplitude = self.hallucination_amplitude; // low initial amplitude — must prove itself
+        hallucination.initial_amplitude = hallucination.amplitude;
         hallucination.hallucinated = true;
+        hallucination.source = MemorySource::Hallucination;
         hallucination.parents = parent_ids.clone();
 
         let hall_id = match engine.store.insert(hallucination) {
@@ -1045,71 +1412,17 @@ impl ConsolidationEngine {
     /// Stage 7: Wire skip links between constructive cross-layer pairs, Fano-related memories,
     /// and preferentially across Xi clusters to promote integration AND differentiation.
     fn stage_wire(&self, engine: &mut MemoryEngine, pairs: &[InterferencePair]) -> usize {
-        let mut count = 0;
-        
-        // Wire constructive cross-layer pairs
-        for pair in pairs.iter().filter(|p| p.kind == Interference::Constructive) {
-            // Check if they're at different layers
-            let (layer_a, layer_b) = {
-                let ma = engine.store.get(&pair.id_a).ok().flatten();
-                let mb = engine.store.get(&pair.id_b).ok().flatten();
-                match (ma, mb) {
-                    (Some(a), Some(b)) => (a.layer_depth, b.layer_depth),
-                    _ => continue,
-                }
-            };
-            if layer_a == layer_b {
-                continue;
-            }
-
-            let span = (layer_a as i16 - layer_b as i16).unsigned_abs() as u8;
-
-            // Check if link already exists from A to B
-            let already_linked = engine
-                .store
-                .get(&pair.id_a)
-                .ok()
-                .flatten()
-                .map(|m| m.connections.iter().any(|l| l.target_id == pair.id_b))
-                .unwrap_or(true);
-
-            if already_linked {
-                continue;
-            }
-
-            let strength = pair.similarity * 0.8;
-
-            // Create forward link
-            if let Some(mem) = engine.store.get_mut(&pair.id_a).ok().flatten() {
-                mem.connections.push(SkipLink {
-                    target_id: pair.id_b,
-                    strength,
-                    resonance_key: Vec::new(),
-                    span,
-                });
-            }
-            // Create reverse link
-            if let Some(mem) = engine.store.get_mut(&pair.id_b).ok().flatten() {
-                mem.connections.push(SkipLink {
-                    target_id: pair.id_a,
-                    strength,
-                    resonance_key: Vec::new(),
-                    span,
-                });
-            }
-            count += 1;
-        }
+        let mut count = self.wire_constructive_pairs(engine, pairs);
         
         // Wire cross-cluster connections: preferentially link memories from DIFFERENT Xi clusters
-        let sync = crate::kuramoto::KuramotoSync::default();
-        let clusters = sync.find_synchronized_clusters(engine, 2);
+        let clusters = self.kuramoto.find_clusters(engine);
         
         if clusters.len() >= 2 {
             count += self.stage_wire_cross_cluster(engine, &clusters);
         }
         
         // Wire Fano-related memories (geometric structural connections)
-        let all_memories = engine.store.all_memories().unwrap_or_default();
+        let all_memories = engine.store.all_memories_sorted().unwrap_or_default();
         
         // Collect pairs for Fano-related linking (store IDs and necessary data to avoid borrowing issues)
         let mut fano_pairs = Vec::new();
@@ -1154,7 +1467,68 @@ impl ConsolidationEngine {
             }
             count += 1;
         }
-        
+
+        if self.symmetrize_links {
+            engine.symmetrize_links();
+        }
+
+        count
+    }
+
+    /// Bidirectional skip links for constructive cross-layer pairs not already linked.
+    fn wire_constructive_pairs(&self, engine: &mut MemoryEngine, pairs: &[InterferencePair]) -> usize {
+        let mut count = 0;
+        for pair in pairs.iter().filter(|p| p.kind == Interference::Constructive) {
+            // Check if they're at different layers
+            let (layer_a, layer_b) = {
+                let ma = engine.store.get(&pair.id_a).ok().flatten();
+                let mb = engine.store.get(&pair.id_b).ok().flatten();
+                match (ma, mb) {
+                    (Some(a), Some(b)) => (a.layer_depth, b.layer_depth),
+                    _ => continue,
+                }
+            };
+            if layer_a == layer_b {
+                continue;
+            }
+
+            let span = (layer_a as i16 - layer_b as i16).unsigned_abs() as u8;
+
+            // Check if link already exists from A to B
+            let already_linked = engine
+                .store
+                .get(&pair.id_a)
+                .ok()
+                .flatten()
+                .map(|m| m.connections.iter().any(|l| l.target_id == pair.id_b))
+                .unwrap_or(true);
+
+            if already_linked {
+                continue;
+            }
+
+            let strength = pair.similarity * 0.8;
+
+            // Create forward link
+            if let Some(mem) = engine.store.get_mut(&pair.id_a).ok().flatten() {
+                mem.connections.push(SkipLink {
+                    target_id: pair.id_b,
+                    strength,
+                    resonance_key: Vec::new(),
+                    span,
+                });
+            }
+            // Create reverse link
+            if let Some(mem) = engine.store.get_mut(&pair.id_b).ok().flatten() {
+                mem.connections.push(SkipLink {
+                    target_id: pair.id_a,
+                    strength,
+                    resonance_key: Vec::new(),
+                    span,
+                });
+            }
+            count += 1;
+        }
         count
     }
 
@@ -1190,7 +1564,7 @@ impl ConsolidationEngine {
                             _ => continue,
                         };
                         
-                        let similarity = cosine_similarity(&mem_a.vector, &mem_b.vector);
+                        let similarity = mem_a.similarity_to(mem_b);
                         
                         // Target moderate similarity: related but not identical
                         if similarity >= 0.3 && similarity <= 0.6 {
@@ -1256,6 +1630,33 @@ impl Default for DreamState {
     }
 }
 
+impl DreamState {
+    pub fn from_params(params: &ConsolidationParams) -> Self {
+        Self {
+            engine: ConsolidationEngine::from_params(params),
+            cycles: params.cycles,
+        }
+    }
+
+    /// Current tunables (reflecting any adaptive drift since construction).
+    pub fn params(&self) -> ConsolidationParams {
+        ConsolidationParams {
+            interference_threshold: self.engine.interference_threshold,
+            phase_alignment_threshold: self.engine.phase_alignment_threshold,
+            prune_threshold: self.engine.prune_threshold,
+            constructive_boost: self.engine.constructive_boost,
+            destructive_penalty: self.engine.destructive_penalty,
+            sync_steps: self.engine.sync_steps,
+            continuous_interference: self.engine.continuous_interference,
+            symmetrize_links: self.engine.symmetrize_links,
+            hallucination_amplitude: self.engine.hallucination_amplitude,
+            hallucination_promotion_threshold: self.engine.hallucination_promotion_threshold,
+            same_layer_only: self.engine.same_layer_only,
+            cycles: self.cycles,
+        }
+    }
+}
+
 impl ConsolidationEngine {
     /// Phase 7 (ADR-0011): Incremental consolidation — only process memories that changed
     /// since the last dream cycle, plus any memories that have never been consolidated.
@@ -1281,7 +1682,7 @@ impl ConsolidationEngine {
 
         // Filter to memories that need reconsolidation
         let working_set: Vec<Uuid> = {
-            let all = engine.store.all_memories().unwrap_or_default();
+            let all = engine.store.all_memories_sorted().unwrap_or_default();
             all.iter()
                 .filter(|m| {
                     if m.layer_depth < min_layer || m.layer_depth > max_layer {
@@ -1321,6 +1722,7 @@ impl ConsolidationEngine {
         report.sync_order_improvement = order_improvement;
         self.stage_xi_repulsion(engine, &working_set);
         report.memories_pruned = self.stage_prune(engine, &pairs);
+        report.hallucinations_promoted = self.promote_hallucinations(engine);
         report.memories_transferred = self.stage_transfer(engine);
         report.skip_links_created = self.stage_wire(engine, &pairs);
         report.hallucinations_created = self.stage_hallucinate(engine, &working_set);
@@ -1337,6 +1739,56 @@ impl ConsolidationEngine {
         report
     }
 
+    /// Light consolidation around `dirty` memories (see `MemoryEngine::take_dirty`).
+    ///
+    /// DETECT only classifies pairs with at least one dirty member, so the cost
+    /// is `|dirty| × n` rather than `n²`; STRENGTHEN, PRUNE and the pair pass of
+    /// WIRE then act on those pairs alone. The global stages (BUNDLE, SYNC,
+    /// XI_REPULSION, TRANSFER, HALLUCINATE) and the cluster/Fano wiring are
+    /// skipped — run a full `consolidate` periodically for those.
+    ///
+    /// The dirty set is not saved with the snapshot. A freshly loaded engine
+    /// rebuilds it from `last_consolidated_at` and `updated_at`, so changes
+    /// that bypassed `HyperMemory::touch` before a save need a full
+    /// `consolidate` to be picked up.
+    pub fn consolidate_dirty(&self, engine: &mut MemoryEngine, dirty: &HashSet<Uuid>) -> ConsolidationReport {
+        let start = Instant::now();
+        let mut report = ConsolidationReport::default();
+
+        let entries: Vec<DetectEntry> = engine.store.all_memories_sorted().unwrap_or_default()
+            .iter()
+            .map(|m| DetectEntry { id: m.id, vector: m.vector.clone(), phase: m.phase })
+            .collect();
+        let mut pairs: Vec<InterferencePair> = entries.iter()
+            .filter(|a| dirty.contains(&a.id))
+            .flat_map(|a| {
+                entries.iter()
+                    // Dirty–dirty pairs are visited from both ends; keep one.
+                    .filter(move |b| b.id != a.id && (!dirty.contains(&b.id) || a.id < b.id))
+                    .filter_map(move |b| self.classify_pair(a, b))
+            })
+            .collect();
+        pairs.sort_by_key(|p| (p.id_a, p.id_b));
+
+        report.memories_replayed = entries.iter().filter(|e| dirty.contains(&e.id)).count();
+        report.interference_pairs_found = pairs.len();
+        report.constructive_pairs = pairs.iter().filter(|p| p.kind == Interference::Constructive).count();
+        report.destructive_pairs = pairs.iter().filter(|p| p.kind == Interference::Destructive).count();
+        report.memories_strengthened = self.stage_strengthen(engine, &pairs);
+        report.memories_pruned = self.stage_prune(engine, &pairs);
+        report.skip_links_created = self.wire_constructive_pairs(engine, &pairs);
+
+        let now = Utc::now();
+        for id in dirty {
+            if let Ok(Some(mem)) = engine.store.get_mut(id) {
+                mem.last_consolidated_at = Some(now);
+            }
+        }
+
+        report.duration_ms = start.elapsed().as_millis() as u64;
+        report
+    }
+
     /// Process a subset of memory IDs through the full consolidation pipeline.
     /// Used internally by `dream_partitioned` for per-cluster parallelism.
     pub fn consolidate_subset(
@@ -1502,6 +1954,10 @@ impl ConsolidationEngine {
     }
 }
 
+/// Layer windows for a deep dream: the default three cycles plus layers 3-4,
+/// so long-term memories consolidate too.
+pub const DEEP_DREAM_LAYERS: [(u8, u8); 4] = [(0, 1), (1, 2), (2, 3), (3, 4)];
+
 impl DreamState {
     pub fn new(engine: ConsolidationEngine, cycles: usize) -> Self {
         Self { engine, cycles }
@@ -1512,14 +1968,19 @@ impl DreamState {
     /// Cycle 2: layers 1-2 (medium)
     /// Cycle 3: layers 2-3 (deep)
     pub fn dream(&self, engine: &mut MemoryEngine) -> Vec<ConsolidationReport> {
-        let mut reports = Vec::new();
-        for cycle in 0..self.cycles {
-            let min_layer = cycle as u8;
-            let max_layer = (cycle + 1) as u8;
-            let report = self.engine.consolidate(engine, min_layer, max_layer);
-            reports.push(report);
-        }
-        reports
+        let layers: Vec<(u8, u8)> = (0..self.cycles)
+            .map(|cycle| (cycle as u8, (cycle + 1) as u8))
+            .collect();
+        self.dream_range(engine, &layers)
+    }
+
+    /// One consolidation pass per `(min_layer, max_layer)` window, in order.
+    /// Ignores `cycles`; the window count sets the number of passes.
+    pub fn dream_range(&self, engine: &mut MemoryEngine, layers: &[(u8, u8)]) -> Vec<ConsolidationReport> {
+        layers
+            .iter()
+            .map(|&(min_layer, max_layer)| self.engine.consolidate(engine, min_layer, max_layer))
+            .collect()
     }
 
     /// Phase 7 (ADR-0011): Incremental dream — only consolidate memories that have
@@ -1535,6 +1996,13 @@ impl DreamState {
         reports
     }
 
+    /// Light dream over whatever changed since the last one: drains the
+    /// engine's dirty set into `consolidate_dirty`.
+    pub fn dream_dirty(&self, engine: &mut MemoryEngine) -> ConsolidationReport {
+        let dirty = engine.take_dirty();
+        self.engine.consolidate_dirty(engine, &dirty)
+    }
+
     /// Phase 8 (ADR-0011): Partitioned dream — use Xi operator to identify clusters,
     /// run intra-cluster consolidation (parallelized with rayon when `collective` feature
     /// is enabled), then run cross-cluster wiring every `cross_cluster_interval` cycles.
@@ -1568,7 +2036,7 @@ impl DreamState {
 
         // Cross-cluster WIRE stage every N cycles (weaker connections between clusters)
         if cross_cluster_interval > 0 && cycle_count % cross_cluster_interval == 0 {
-            let all_ids: Vec<Uuid> = engine.store.all_ids().unwrap_or_default();
+            let all_ids: Vec<Uuid> = engine.store.all_ids_sorted().unwrap_or_default();
             // Run only the WIRE stage across all memories
             let pairs = self.engine.stage_detect(engine, &all_ids);
             let cross_links = self.engine.stage_wire(engine, &pairs);
@@ -1588,7 +2056,7 @@ impl DreamState {
         let start = std::time::Instant::now();
         let mut report = ConsolidationReport::default();
 
-        let all_ids = engine.store.all_ids().unwrap_or_default();
+        let all_ids = engine.store.all_ids_sorted().unwrap_or_default();
         report.memories_replayed = all_ids.len();
         let now = chrono::Utc::now();
 
@@ -1672,6 +2140,259 @@ mod tests {
         id
     }
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn detect_parallel_matches_serial() {
+        use rand::{Rng, SeedableRng};
+        use rand_chacha::ChaCha8Rng;
+
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let base: Vec<f32> = (0..64).map(|_| rng.gen::<f32>() - 0.5).collect();
+        let entries: Vec<DetectEntry> = (0..50)
+            .map(|_| DetectEntry {
+                id: Uuid::new_v4(),
+                vector: base.iter().map(|&x| x + rng.gen::<f32>() - 0.5).collect::<Vec<f32>>().into(),
+                phase: rng.gen_range(0.0..(2.0 * PI)),
+            })
+            .collect();
+
+        let consolidation = ConsolidationEngine {
+            interference_threshold: 0.3,
+            ..Default::default()
+        };
+        let key = |p: &InterferencePair| (p.id_a, p.id_b, p.kind, p.similarity.to_bits());
+        let serial: Vec<_> = consolidation.detect_pairs(&entries, false).iter().map(key).collect();
+        let parallel: Vec<_> = consolidation.detect_pairs(&entries, true).iter().map(key).collect();
+
+        assert!(!serial.is_empty(), "fixture should produce interference pairs");
+        assert_eq!(serial, parallel);
+        assert!(serial.windows(2).all(|w| (w[0].0, w[0].1) < (w[1].0, w[1].1)));
+    }
+
+    #[test]
+    fn sparse_vectors_interfere_like_dense() {
+        use crate::wave::SparseVector;
+        use rand::{Rng, SeedableRng};
+        use rand_chacha::ChaCha8Rng;
+
+        let mut rng = ChaCha8Rng::seed_from_u64(11);
+        let base: Vec<f32> = (0..64).map(|_| rng.gen::<f32>() - 0.5).collect();
+        let dense: Vec<DetectEntry> = (0..12)
+            .map(|_| DetectEntry {
+                id: Uuid::new_v4(),
+                vector: base
+                    .iter()
+                    .map(|&x| if rng.gen::<f32>() < 0.5 { 0.0 } else { x + rng.gen::<f32>() - 0.5 })
+                    .collect::<Vec<f32>>()
+                    .into(),
+                phase: rng.gen_range(0.0..(2.0 * PI)),
+            })
+            .collect();
+        let sparse: Vec<DetectEntry> = dense
+            .iter()
+            .map(|e| DetectEntry {
+                id: e.id,
+                vector: HyperVector::Sparse(SparseVector::from_dense(&e.vector.dense())),
+                phase: e.phase,
+            })
+            .collect();
+
+        let consolidation = ConsolidationEngine {
+            interference_threshold: 0.2,
+            ..Default::default()
+        };
+        let dense_pairs = consolidation.detect_pairs(&dense, false);
+        let sparse_pairs = consolidation.detect_pairs(&sparse, false);
+
+        assert!(!dense_pairs.is_empty(), "fixture should produce interference pairs");
+        assert_eq!(dense_pairs.len(), sparse_pairs.len());
+        for (d, s) in dense_pairs.iter().zip(&sparse_pairs) {
+            assert_eq!((d.id_a, d.id_b, d.kind), (s.id_a, s.id_b, s.kind));
+            assert!((d.similarity - s.similarity).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn important_memory_survives_destructive_prune() {
+        let mut engine = make_engine();
+        let consolidation = ConsolidationEngine::default();
+        let low = engine.remember("the deploy window is friday").unwrap();
+        let high = engine.remember("the deploy window is friday!").unwrap();
+        for (id, importance) in [(low, 0.2), (high, 0.9)] {
+            let mem = engine.store.get_mut(&id).unwrap().unwrap();
+            mem.amplitude = 0.15;
+            mem.importance = importance;
+        }
+
+        let (id_a, id_b) = if low < high { (low, high) } else { (high, low) };
+        let pair = InterferencePair {
+            id_a,
+            id_b,
+            similarity: 0.9,
+            kind: Interference::Destructive,
+            interference: -0.9,
+        };
+        assert_eq!(consolidation.stage_prune(&mut engine, &[pair]), 1);
+
+        assert_eq!(engine.get_memory(&low).unwrap().unwrap().amplitude, 0.0);
+        assert_eq!(engine.get_memory(&high).unwrap().unwrap().amplitude, 0.15);
+    }
+
+    #[test]
+    fn dream_range_consolidates_each_window() {
+        let mut engine = make_engine();
+        for text in ["fresh note one", "fresh note two"] {
+            insert_with_phase_and_layer(&mut engine, text, 0.0, 0);
+        }
+        for text in ["ancient lesson one", "ancient lesson two", "ancient lesson three"] {
+            insert_with_phase_and_layer(&mut engine, text, 0.0, 3);
+        }
+
+        let dream = DreamState::new(ConsolidationEngine::default(), 3);
+        let reports = dream.dream_range(&mut engine, &[(0, 0), (3, 3)]);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].memories_replayed, 2);
+        assert_eq!(reports[1].memories_replayed, 3);
+        assert_eq!((reports[0].bundles_created, reports[1].bundles_created), (1, 1));
+
+        let summaries: Vec<u8> = engine.store.all_memories_sorted().unwrap().iter()
+            .filter(|m| is_consolidation_summary(m))
+            .map(|m| m.layer_depth)
+            .collect();
+        // Each pass replaces earlier summaries, so only the layer-3 window's remains.
+        assert_eq!(summaries, vec![4]);
+    }
+
+    #[test]
+    fn consolidate_dirty_only_links_dirty_memories() {
+        let mut engine = make_engine();
+        engine.similarity_threshold = 1.1; // no skip links at insert time
+        let old_a = insert_with_phase_and_layer(&mut engine, "tidal harbour log", 0.0, 0);
+        let old_b = insert_with_phase_and_layer(&mut engine, "tidal harbour log", 0.0, 1);
+        engine.take_dirty();
+        let new = insert_with_phase_and_layer(&mut engine, "tidal harbour log", 0.0, 2);
+        assert_eq!(engine.dirty(), &HashSet::from([new]));
+
+        let consolidation = ConsolidationEngine::default();
+        let dirty = engine.take_dirty();
+        let report = consolidation.consolidate_dirty(&mut engine, &dirty);
+        assert_eq!(report.memories_replayed, 1);
+        assert_eq!(report.skip_links_created, 2);
+
+        let links = |engine: &MemoryEngine, id: &Uuid| -> HashSet<Uuid> {
+            engine.get_memory(id).unwrap().unwrap().connections.iter().map(|l| l.target_id).collect()
+        };
+        assert_eq!(links(&engine, &new), HashSet::from([old_a, old_b]));
+        assert_eq!(links(&engine, &old_a), HashSet::from([new]));
+        assert_eq!(links(&engine, &old_b), HashSet::from([new]));
+        assert!(engine.get_memory(&new).unwrap().unwrap().last_consolidated_at.is_some());
+        assert!(engine.get_memory(&old_a).unwrap().unwrap().last_consolidated_at.is_none());
+    }
+
+    #[test]
+    fn preview_matches_consolidation_without_mutating() {
+        let mut engine = make_engine();
+        let consolidation = ConsolidationEngine {
+            interference_threshold: 0.3,
+            ..Default::default()
+        };
+        insert_with_phase_and_layer(&mut engine, "the cat sat on the mat", 0.0, 0);
+        insert_with_phase_and_layer(&mut engine, "the cat sat on the mat today", 0.0, 0);
+        insert_with_phase_and_layer(&mut engine, "the dog sat on the rug", PI, 0);
+        let old = insert_with_phase_and_layer(&mut engine, "an old note about the cat", 0.0, 0);
+        engine.store.get_mut(&old).unwrap().unwrap().created_at = Utc::now() - Duration::hours(2);
+
+        let fingerprint = |engine: &MemoryEngine| {
+            let mut rows: Vec<_> = engine.store.all_memories().unwrap().iter()
+                .map(|m| (m.id, m.amplitude.to_bits(), m.phase.to_bits(), m.layer_depth, m.connections.len()))
+                .collect();
+            rows.sort();
+            rows
+        };
+        let before = fingerprint(&engine);
+        let preview = consolidation.consolidate_preview(&engine, 0, 1);
+        assert_eq!(fingerprint(&engine), before, "preview must not touch the store");
+
+        assert!(preview.report.constructive_pairs > 0 && preview.report.destructive_pairs > 0);
+        assert!(preview.transferred.contains(&(old, 1)));
+
+        let report = consolidation.consolidate(&mut engine, 0, 1);
+        let p = &preview.report;
+        assert_eq!(p.memories_replayed, report.memories_replayed);
+        assert_eq!(p.interference_pairs_found, report.interference_pairs_found);
+        assert_eq!(p.constructive_pairs, report.constructive_pairs);
+        assert_eq!(p.destructive_pairs, report.destructive_pairs);
+        assert_eq!(p.bundles_created, report.bundles_created);
+        assert_eq!(p.memories_strengthened, report.memories_strengthened);
+        assert_eq!(p.memories_pruned, report.memories_pruned);
+        assert_eq!(p.memories_transferred, report.memories_transferred);
+    }
+
+    #[test]
+    fn preview_matches_consolidation_after_a_prior_cycle() {
+        let mut engine = make_engine();
+        let consolidation = ConsolidationEngine {
+            interference_threshold: 0.3,
+            ..Default::default()
+        };
+        insert_with_phase_and_layer(&mut engine, "the cat sat on the mat", 0.0, 0);
+        insert_with_phase_and_layer(&mut engine, "the cat sat on the mat today", 0.3, 0);
+        insert_with_phase_and_layer(&mut engine, "the dog sat on the rug", PI, 0);
+        insert_with_phase_and_layer(&mut engine, "the dog sat on the mat", 1.0, 1);
+        insert_with_phase_and_layer(&mut engine, "a cat on a mat", 0.2, 1);
+
+        // The first cycle leaves summary bundles and moved phases behind.
+        consolidation.consolidate(&mut engine, 0, 1);
+        assert!(engine.store.all_memories().unwrap().iter().any(|m| is_consolidation_summary(m)));
+
+        let preview = consolidation.consolidate_preview(&engine, 0, 1);
+        let report = consolidation.consolidate(&mut engine, 0, 1);
+        let p = &preview.report;
+        assert_eq!(p.memories_replayed, report.memories_replayed);
+        assert_eq!(p.interference_pairs_found, report.interference_pairs_found);
+        assert_eq!(p.bundles_created, report.bundles_created);
+        assert_eq!(p.memories_strengthened, report.memories_strengthened);
+        assert_eq!(p.clusters_synced, report.clusters_synced);
+        assert_eq!(p.memories_pruned, report.memories_pruned);
+        assert_eq!(p.hallucinations_promoted, report.hallucinations_promoted);
+        assert_eq!(p.memories_transferred, report.memories_transferred);
+        assert_eq!(p.skip_links_created, report.skip_links_created);
+        assert_eq!(p.hallucinations_created, report.hallucinations_created);
+        assert!((p.final_order_parameter - report.final_order_parameter).abs() < 1e-6);
+    }
+
+    #[test]
+    fn continuous_interference_affects_more_memories_than_discrete() {
+        let mut engine = make_engine();
+        for (i, phase) in [0.0, 0.6, 1.2, 1.8, 2.4, 3.0].into_iter().enumerate() {
+            insert_with_phase_and_layer(&mut engine, &format!("the cat sat on the mat {}", i), phase, 0);
+        }
+
+        let engine_with = |continuous_interference| ConsolidationEngine {
+            interference_threshold: 0.3,
+            phase_alignment_threshold: 0.3,
+            continuous_interference,
+            ..Default::default()
+        };
+        let (discrete, continuous) = (engine_with(false), engine_with(true));
+        let affected = |preview: &ConsolidationPreview| {
+            let mut ids: Vec<Uuid> = preview.strengthened.iter().chain(&preview.pruned).copied().collect();
+            ids.sort();
+            ids.dedup();
+            ids.len()
+        };
+
+        let d = discrete.consolidate_preview(&engine, 0, 0);
+        let c = continuous.consolidate_preview(&engine, 0, 0);
+        assert!(c.report.interference_pairs_found > d.report.interference_pairs_found);
+        assert!(
+            affected(&c) > affected(&d),
+            "continuous touched {} memories, discrete {}",
+            affected(&c),
+            affected(&d),
+        );
+    }
+
     #[test]
     fn constructive_interference_strengthens_memories() {
         let mut engine = make_engine();
@@ -1769,7 +2490,7 @@ mod tests {
 
         // Summary should have positive similarity to components
         let cat_vec = engine.pipeline.encode_text("cats are fluffy animals").unwrap();
-        let sim = cosine_similarity(&summary.vector, &cat_vec);
+        let sim = cosine_similarity(&summary.dense_vector(), &cat_vec);
         assert!(sim > 0.0, "summary should be similar to components, got {}", sim);
     }
 
@@ -1791,6 +2512,58 @@ mod tests {
         assert!(report.memories_transferred > 0);
     }
 
+    #[test]
+    fn recently_recalled_memory_stays_shallow() {
+        let mut engine = make_engine();
+        let consolidation = ConsolidationEngine::default();
+        let recalled = engine.remember_at_layer("recalled just now", 0).unwrap();
+        let idle = engine.remember_at_layer("left alone", 0).unwrap();
+        for id in [recalled, idle] {
+            engine.store.get_mut(&id).unwrap().unwrap().created_at = Utc::now() - Duration::hours(2);
+        }
+        engine.store.get_mut(&recalled).unwrap().unwrap().record_retrieval();
+
+        let transfers = consolidation.plan_transfer(&engine);
+        assert_eq!(transfers, vec![(idle, 1)]);
+    }
+
+    #[test]
+    fn consolidation_can_symmetrize_links() {
+        let mut engine = make_engine();
+        engine.similarity_threshold = 0.0;
+        let a = engine.remember_at_layer("tide tables for the north harbor", 0).unwrap();
+        let b = engine.remember_at_layer("tide tables for the south harbor", 1).unwrap();
+        engine.reinforce_link(&a, &b, 0.2);
+        assert!(!engine.link_consistency_check().is_empty());
+
+        ConsolidationEngine { symmetrize_links: true, ..Default::default() }.consolidate(&mut engine, 0, 1);
+        assert!(engine.link_consistency_check().is_empty());
+    }
+
+    #[test]
+    fn proven_hallucination_is_promoted() {
+        let mut engine = make_engine();
+        let consolidation = ConsolidationEngine { interference_threshold: 0.99, ..Default::default() };
+        let mut hallucinate = |text: &str| {
+            let mut mem = engine.pipeline.encode_memory(text, Utc::now()).unwrap();
+            mem.amplitude = consolidation.hallucination_amplitude;
+            mem.hallucinated = true;
+            mem.source = MemorySource::Hallucination;
+            engine.store.insert(mem).unwrap()
+        };
+        let proven = hallucinate("[hallucination] lighthouses as clocks");
+        let unproven = hallucinate("[hallucination] tides as memory");
+        // Stand-in for constructive interference since creation.
+        engine.store.get_mut(&proven).unwrap().unwrap().amplitude = 0.9;
+
+        let report = consolidation.consolidate(&mut engine, 0, 0);
+        assert_eq!(report.hallucinations_promoted, 1);
+        let proven = engine.get_memory(&proven).unwrap().unwrap();
+        assert!(!proven.hallucinated);
+        assert_eq!(proven.source, MemorySource::Hallucination);
+        assert!(engine.get_memory(&unproven).unwrap().unwrap().hallucinated);
+    }
+
     #[test]
     fn wiring_creates_skip_links_for_cross_layer_constructive_pairs() {
         let mut engine = make_engine();
@@ -1825,6 +2598,40 @@ mod tests {
         assert!(!mem1.connections.is_empty(), "should have skip link");
     }
 
+    #[test]
+    fn same_layer_only_wires_cross_layer_pairs_without_strengthening() {
+        let mut engine = make_engine();
+        engine.similarity_threshold = 0.99; // prevent auto-linking on insert
+        let mut insert = |text: &str, layer: u8| {
+            let mut mem = engine.pipeline.encode_memory(text, Utc::now()).unwrap();
+            mem.layer_depth = layer;
+            mem.phase = 0.0;
+            engine.store.insert(mem).unwrap()
+        };
+        let id1 = insert("the cat sat on the mat", 0);
+        let id2 = insert("the cat sat on the mat today", 1);
+        let ids = [id1, id2];
+        let amplitudes = |engine: &MemoryEngine| ids.map(|id| engine.get_memory(&id).unwrap().unwrap().amplitude);
+
+        let consolidation = ConsolidationEngine {
+            interference_threshold: 0.3,
+            same_layer_only: true,
+            ..Default::default()
+        };
+        let pairs = consolidation.stage_detect(&engine, &ids);
+        assert!(pairs.iter().any(|p| p.kind == Interference::Constructive));
+
+        let before = amplitudes(&engine);
+        consolidation.stage_strengthen(&mut engine, &pairs);
+        assert_eq!(amplitudes(&engine), before, "cross-layer pair must not be amplitude-adjusted");
+        assert!(consolidation.stage_wire(&mut engine, &pairs) > 0, "cross-layer pair should still be wired");
+
+        // Without the option the same pair is strengthened as well.
+        let consolidation = ConsolidationEngine { same_layer_only: false, ..consolidation };
+        consolidation.stage_strengthen(&mut engine, &pairs);
+        assert!(amplitudes(&engine).iter().zip(before).all(|(after, before)| *after > before));
+    }
+
     #[test]
     fn dream_state_runs_multiple_cycles() {
         let mut engine = make_engine();
@@ -2183,6 +2990,36 @@ mod tests {
         assert!(!hall.connections.is_empty(), "hallucination should be linked to parents");
     }
 
+    #[test]
+    fn seeded_hallucination_is_reproducible() {
+        // Mutually orthogonal memories: every pair ties on similarity
+        let run = |seed: Option<u64>| {
+            let mut engine = make_engine();
+            for k in 0..5u128 {
+                let mut v = vec![0.0f32; 10_000];
+                for i in 0..100 { v[k as usize * 200 + i] = 1.0; }
+                crate::wave::normalize(&mut v);
+                let mut mem = crate::memory::HyperMemory::new(v, format!("orthogonal topic {}", k));
+                mem.id = Uuid::from_u128(k + 1);
+                engine.store.insert(mem).unwrap();
+            }
+            let consolidation = ConsolidationEngine {
+                interference_threshold: 0.99,
+                hallucination_seed: seed,
+                ..Default::default()
+            };
+            consolidation.consolidate(&mut engine, 0, 1);
+            let all = engine.store.all_memories().unwrap();
+            let hall = all.iter().find(|m| m.hallucinated).expect("hallucination created");
+            hall.parents.clone()
+        };
+
+        let seeded = run(Some(42));
+        assert!(!seeded.is_empty());
+        assert_eq!(seeded, run(Some(42)));
+        assert_eq!(run(None), run(None));
+    }
+
     #[test]
     fn hallucination_skipped_with_few_memories() {
         let mut engine = make_engine();
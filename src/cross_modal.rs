@@ -0,0 +1,151 @@
+//! Cross-modal bridge — lets text queries reach audio memories.
+//!
+//! Audio hypervectors are projected through a codebook with a different seed
+//! than text, so a text query is near-orthogonal to every audio memory. The
+//! bridge uses shared anchors: a text caption is stored as an ordinary text
+//! memory tagged `caption_of:<audio id>`. A query is compared against the
+//! caption vectors, and the linked audio vectors are bundled by those
+//! similarities into a query that lives in the audio subspace.
+
+use uuid::Uuid;
+
+use crate::memory::HyperMemory;
+use crate::store::{MemoryStore, StoreError};
+use crate::wave::normalize;
+
+/// Tag prefix marking a text memory as the caption of an audio memory.
+pub const CAPTION_TAG_PREFIX: &str = "caption_of:";
+
+/// The tag that links a caption memory to `audio_id`.
+pub fn caption_tag(audio_id: &Uuid) -> String {
+    format!("{}{}", CAPTION_TAG_PREFIX, audio_id)
+}
+
+/// Audio memory id named by a caption tag, if `tag` is one.
+pub fn parse_caption_tag(tag: &str) -> Option<Uuid> {
+    tag.strip_prefix(CAPTION_TAG_PREFIX)?.parse().ok()
+}
+
+/// A caption memory paired with the audio memory it describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptionAnchor {
+    pub caption_id: Uuid,
+    pub audio_id: Uuid,
+}
+
+/// Maps text queries into the audio subspace through caption anchors.
+#[derive(Debug, Clone, Default)]
+pub struct CrossModalBridge {
+    anchors: Vec<CaptionAnchor>,
+}
+
+impl CrossModalBridge {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild the anchors from the caption tags found in `store`.
+    pub fn from_store(store: &dyn MemoryStore) -> Result<Self, StoreError> {
+        let mut bridge = Self::new();
+        for mem in store.all_memories_sorted()? {
+            for audio_id in mem.tags.iter().filter_map(|t| parse_caption_tag(t)) {
+                bridge.add_anchor(mem.id, audio_id);
+            }
+        }
+        Ok(bridge)
+    }
+
+    /// Link `caption_id` to `audio_id`. Duplicate pairs are ignored.
+    pub fn add_anchor(&mut self, caption_id: Uuid, audio_id: Uuid) {
+        let anchor = CaptionAnchor { caption_id, audio_id };
+        if !self.anchors.contains(&anchor) {
+            self.anchors.push(anchor);
+        }
+    }
+
+    pub fn anchors(&self) -> &[CaptionAnchor] {
+        &self.anchors
+    }
+
+    pub fn len(&self) -> usize {
+        self.anchors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anchors.is_empty()
+    }
+
+    /// Project a text query vector into the audio subspace: the sum of the
+    /// anchored audio vectors, each weighted by its caption's (positive)
+    /// similarity to the query, normalized. `None` if no caption resembles
+    /// the query or the anchored memories are missing.
+    pub fn project(&self, query: &[f32], store: &dyn MemoryStore) -> Option<Vec<f32>> {
+        let mut projected = vec![0.0f32; query.len()];
+        let mut total = 0.0f32;
+        for anchor in &self.anchors {
+            let (Some(caption), Some(audio)) = (lookup(store, &anchor.caption_id), lookup(store, &anchor.audio_id)) else {
+                continue;
+            };
+            if caption.dim() != query.len() || audio.dim() != query.len() {
+                continue;
+            }
+            let weight = caption.similarity(query);
+            if weight <= 0.0 {
+                continue;
+            }
+            for (p, a) in projected.iter_mut().zip(audio.dense_vector().iter()) {
+                *p += weight * a;
+            }
+            total += weight;
+        }
+        if total <= 0.0 {
+            return None;
+        }
+        normalize(&mut projected);
+        Some(projected)
+    }
+}
+
+fn lookup<'a>(store: &'a dyn MemoryStore, id: &Uuid) -> Option<&'a HyperMemory> {
+    store.get(id).ok().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codebook::Codebook;
+    use crate::store::InMemoryStore;
+    use crate::wave::cosine_similarity;
+
+    fn memory(seed: u64, features: &[f32], content: &str) -> HyperMemory {
+        let cb = Codebook::new(features.len(), 512, seed);
+        HyperMemory::new(cb.project(features), content.to_string())
+    }
+
+    #[test]
+    fn caption_tag_round_trips() {
+        let id = Uuid::new_v4();
+        assert_eq!(parse_caption_tag(&caption_tag(&id)), Some(id));
+        assert_eq!(parse_caption_tag("project:x"), None);
+    }
+
+    #[test]
+    fn projection_lands_on_anchored_audio() {
+        let mut store = InMemoryStore::new();
+        let audio = memory(0xEA5, &[1.0, 0.2, 0.0, 0.7], "audio:bark.wav");
+        let audio_vec = audio.dense_vector().into_owned();
+        let audio_id = store.insert(audio).unwrap();
+        let mut caption = memory(42, &[0.0, 1.0, 0.5, 0.0], "dog barking");
+        caption.tags.push(caption_tag(&audio_id));
+        let caption_vec = caption.dense_vector().into_owned();
+        let caption_id = store.insert(caption).unwrap();
+
+        let bridge = CrossModalBridge::from_store(&store).unwrap();
+        assert_eq!(bridge.anchors(), &[CaptionAnchor { caption_id, audio_id }]);
+
+        let projected = bridge.project(&caption_vec, &store).unwrap();
+        assert!(cosine_similarity(&projected, &audio_vec) > 0.99);
+        let opposite: Vec<f32> = caption_vec.iter().map(|x| -x).collect();
+        assert!(bridge.project(&opposite, &store).is_none());
+    }
+}
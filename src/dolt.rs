@@ -28,7 +28,6 @@ use uuid::Uuid;
 use crate::memory::HyperMemory;
 use crate::skip_link::SkipLink;
 use crate::store::{MemoryStore, StoreError};
-use crate::wave::cosine_similarity;
 
 #[cfg(feature = "glyph")]
 use crate::glyph_bridge::{GlyphEncoder, encode_memory_as_glyph};
@@ -510,7 +509,7 @@ impl DoltMemoryStore {
 
             let memory = HyperMemory {
                 id: uuid,
-                vector,
+                vector: vector.into(),
                 amplitude,
                 frequency,
                 phase,
@@ -530,6 +529,12 @@ impl DoltMemoryStore {
                 disputed,
                 updated_at: None,
                 retrieval_count: 0,
+                ttl: None,
+                initial_amplitude: crate::memory::default_initial_amplitude(),
+                tags: Vec::new(),
+                importance: crate::memory::DEFAULT_IMPORTANCE,
+                source: crate::memory::MemorySource::default(),
+                last_accessed: None,
             };
 
             self.cache.insert(uuid, memory);
@@ -618,7 +623,7 @@ impl DoltMemoryStore {
             .map_err(|e| StoreError::Other(format!("Failed to get connection: {}", e)))?;
 
         // Serialize vectors to JSON
-        let vector_json = serde_json::to_string(&memory.vector)
+        let vector_json = serde_json::to_string(&*memory.dense_vector())
             .map_err(|e| StoreError::Other(format!("Failed to serialize vector: {}", e)))?;
         
         let xi_signature_json = if memory.xi_signature.is_empty() {
@@ -2558,7 +2563,7 @@ impl Drop for AutoPusher {
 }
 
 impl MemoryStore for DoltMemoryStore {
-    fn insert(&mut self, memory: HyperMemory) -> Result<Uuid, StoreError> {
+    fn insert(&mut self, mut memory: HyperMemory) -> Result<Uuid, StoreError> {
         let id = memory.id;
         
         // Check for duplicate in cache
@@ -2566,6 +2571,9 @@ impl MemoryStore for DoltMemoryStore {
             return Err(StoreError::DuplicateId(id));
         }
 
+        // Rows store the dense vector.
+        memory.densify();
+
         // Sync to Dolt first
         self.sync_memory_to_dolt(&memory)?;
 
@@ -2592,7 +2600,7 @@ impl MemoryStore for DoltMemoryStore {
         let mut scored: Vec<(Uuid, f32)> = self
             .cache
             .values()
-            .map(|m| (m.id, cosine_similarity(query, &m.vector)))
+            .map(|m| (m.id, m.similarity(query)))
             .collect();
         scored.sort_by(|a, b| b.1.total_cmp(&a.1));
         scored.truncate(top_k);
@@ -2609,7 +2617,7 @@ impl MemoryStore for DoltMemoryStore {
             .cache
             .values()
             .map(|m| {
-                let sim = cosine_similarity(query, &m.vector);
+                let sim = m.similarity(query);
                 let strength = m.effective_strength(now);
                 (m.id, sim * strength)
             })
@@ -2623,6 +2631,11 @@ impl MemoryStore for DoltMemoryStore {
         Ok(self.cache.values().collect())
     }
 
+    fn for_each_memory(&self, f: &mut dyn FnMut(&HyperMemory)) -> Result<(), StoreError> {
+        self.cache.values().for_each(f);
+        Ok(())
+    }
+
     fn all_ids(&self) -> Result<Vec<Uuid>, StoreError> {
         Ok(self.cache.keys().copied().collect())
     }
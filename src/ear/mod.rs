@@ -90,7 +90,7 @@ impl AudioPipeline {
         mem.frequency = 0.05;
         mem.phase = PI / 4.0;
         mem.decay_rate = 5e-7;
-        mem.xi_signature = compute_xi_signature(&mem.vector);
+        mem.xi_signature = compute_xi_signature(&mem.dense_vector());
 
         Ok((mem, af))
     }
@@ -111,7 +111,7 @@ impl AudioPipeline {
         mem.frequency = 0.05;
         mem.phase = PI / 4.0;
         mem.decay_rate = 5e-7;
-        mem.xi_signature = compute_xi_signature(&mem.vector);
+        mem.xi_signature = compute_xi_signature(&mem.dense_vector());
 
         Ok((mem, af))
     }
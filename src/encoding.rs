@@ -4,14 +4,17 @@
 //! a `SimpleHashEncoder` for offline/testing use, and the `EncodingPipeline`
 //! that chains text encoding with codebook projection and HDC algebra.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use thiserror::Error;
 
+use crate::binary::BitVector;
 use crate::codebook::Codebook;
 use crate::memory::HyperMemory;
-use crate::wave::normalize;
+use crate::wave::{self, normalize, SparseVector};
 
 /// Errors that can occur during encoding.
 #[derive(Debug, Error)]
@@ -20,6 +23,8 @@ pub enum EncodingError {
     EmptyInput,
     #[error("dimension mismatch: expected {expected}, got {got}")]
     DimensionMismatch { expected: usize, got: usize },
+    #[error("invalid codebook dims {input_dim} -> {output_dim}: dims must be non-zero and input_dim <= output_dim")]
+    InvalidCodebookDims { input_dim: usize, output_dim: usize },
     #[error("encoding failed: {0}")]
     Other(String),
 }
@@ -28,6 +33,23 @@ pub enum EncodingError {
 pub trait TextEncoder: Send + Sync {
     fn embed(&self, text: &str) -> Result<Vec<f32>, EncodingError>;
     fn embedding_dim(&self) -> usize;
+
+    /// Primary/fallback counters, for encoders that have a fallback chain.
+    fn stats(&self) -> Option<EncoderStats> {
+        None
+    }
+
+    /// `(hits, misses, evictions)`, for encoders that cache embeddings.
+    fn cache_stats(&self) -> Option<(u64, u64, u64)> {
+        None
+    }
+}
+
+/// How often a `CompositeEncoder` served embeddings from its primary vs fallback.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EncoderStats {
+    pub primary_hits: u64,
+    pub fallback_hits: u64,
 }
 
 /// A fast, deterministic hash-based encoder for testing.
@@ -158,16 +180,92 @@ impl TextEncoder for HttpEmbeddingEncoder {
     }
 }
 
+/// OpenAI-compatible remote encoder whose output is fitted to a fixed dimension.
+///
+/// Wraps `HttpEmbeddingEncoder` and truncates or zero-pads the remote embedding
+/// to `target_dim` (the codebook input dim, 384) before normalizing, so any
+/// embedding model can feed the standard codebook.
+pub struct RemoteHttpEncoder {
+    remote: HttpEmbeddingEncoder,
+    target_dim: usize,
+}
+
+impl RemoteHttpEncoder {
+    /// `output_dim` is the dimension the remote model returns; `target_dim` is
+    /// the dimension this encoder produces.
+    pub fn new(
+        base_url: String,
+        model: String,
+        api_key: Option<String>,
+        output_dim: usize,
+        target_dim: usize,
+    ) -> Self {
+        Self {
+            remote: HttpEmbeddingEncoder::new(base_url, api_key, model, output_dim),
+            target_dim,
+        }
+    }
+}
+
+impl TextEncoder for RemoteHttpEncoder {
+    fn embed(&self, text: &str) -> Result<Vec<f32>, EncodingError> {
+        let mut embedding = self.remote.embed(text)?;
+        embedding.resize(self.target_dim, 0.0);
+        normalize(&mut embedding);
+        Ok(embedding)
+    }
+
+    fn embedding_dim(&self) -> usize {
+        self.target_dim
+    }
+}
+
+/// Which embedding backend `KannakaMemorySystem` should use.
+/// Every backend falls back to the hash encoder when unavailable.
+#[derive(Debug, Clone)]
+pub enum EncoderConfig {
+    /// Local Ollama `/api/embed` (default: all-minilm on localhost).
+    Ollama { base_url: String, model: String },
+    /// OpenAI-compatible `/v1/embeddings` endpoint.
+    RemoteHttp {
+        base_url: String,
+        model: String,
+        api_key: Option<String>,
+        /// Dimension the remote model returns.
+        output_dim: usize,
+    },
+    /// Deterministic hash encoder only — no network.
+    Hash,
+}
+
+impl Default for EncoderConfig {
+    fn default() -> Self {
+        Self::Ollama {
+            base_url: "http://localhost:11434".to_string(),
+            model: "all-minilm".to_string(),
+        }
+    }
+}
+
 /// Ollama embedding encoder — calls the local Ollama `/api/embed` endpoint.
+#[derive(Clone)]
 pub struct OllamaEncoder {
     base_url: String,
     model: String,
     embedding_dim: usize,
+    #[cfg(feature = "async-embed")]
+    client: reqwest::Client,
 }
 
 impl OllamaEncoder {
     pub fn new(base_url: String, model: String, embedding_dim: usize) -> Self {
-        Self { base_url, model, embedding_dim }
+        Self {
+            base_url,
+            model,
+            embedding_dim,
+            #[cfg(feature = "async-embed")]
+            client: reqwest::Client::new(),
+        }
     }
 
     /// Default: all-minilm on localhost, 384 dims.
@@ -178,29 +276,19 @@ impl OllamaEncoder {
             384,
         )
     }
-}
 
-impl TextEncoder for OllamaEncoder {
-    fn embed(&self, text: &str) -> Result<Vec<f32>, EncodingError> {
-        if text.trim().is_empty() {
-            return Err(EncodingError::EmptyInput);
-        }
+    fn endpoint(&self) -> String {
+        format!("{}/api/embed", self.base_url.trim_end_matches('/'))
+    }
 
-        let url = format!("{}/api/embed", self.base_url.trim_end_matches('/'));
-        let body = serde_json::json!({
+    fn request_body(&self, text: &str) -> serde_json::Value {
+        serde_json::json!({
             "model": &self.model,
             "input": text,
-        });
-
-        let resp = ureq::post(&url)
-            .set("Content-Type", "application/json")
-            .send_json(body)
-            .map_err(|e| EncodingError::Other(format!("Ollama request failed: {}", e)))?;
-
-        let json: serde_json::Value = resp
-            .into_json()
-            .map_err(|e| EncodingError::Other(format!("Failed to parse Ollama response: {}", e)))?;
+        })
+    }
 
+    fn parse_embedding(&self, json: &serde_json::Value) -> Result<Vec<f32>, EncodingError> {
         // Ollama returns { "embeddings": [[...]] }
         let embedding = json["embeddings"][0]
             .as_array()
@@ -219,73 +307,252 @@ impl TextEncoder for OllamaEncoder {
         Ok(embedding)
     }
 
+    /// Non-blocking counterpart of `embed`, for callers running inside an async runtime.
+    #[cfg(feature = "async-embed")]
+    pub async fn embed_async(&self, text: &str) -> Result<Vec<f32>, EncodingError> {
+        if text.trim().is_empty() {
+            return Err(EncodingError::EmptyInput);
+        }
+
+        let resp = self.client
+            .post(self.endpoint())
+            .json(&self.request_body(text))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| EncodingError::Other(format!("Ollama request failed: {}", e)))?;
+
+        let json: serde_json::Value = resp
+            .json()
+            .await
+            .map_err(|e| EncodingError::Other(format!("Failed to parse Ollama response: {}", e)))?;
+
+        self.parse_embedding(&json)
+    }
+}
+
+impl TextEncoder for OllamaEncoder {
+    fn embed(&self, text: &str) -> Result<Vec<f32>, EncodingError> {
+        if text.trim().is_empty() {
+            return Err(EncodingError::EmptyInput);
+        }
+
+        let resp = ureq::post(&self.endpoint())
+            .set("Content-Type", "application/json")
+            .send_json(self.request_body(text))
+            .map_err(|e| EncodingError::Other(format!("Ollama request failed: {}", e)))?;
+
+        let json: serde_json::Value = resp
+            .into_json()
+            .map_err(|e| EncodingError::Other(format!("Failed to parse Ollama response: {}", e)))?;
+
+        self.parse_embedding(&json)
+    }
+
     fn embedding_dim(&self) -> usize {
         self.embedding_dim
     }
 }
 
+/// Default number of embeddings kept by `CachedEncoder`.
+pub const DEFAULT_EMBED_CACHE_CAPACITY: usize = 10_000;
+
+/// Least-recently-used map from input text to embedding.
+struct LruCache {
+    capacity: usize,
+    /// text → (embedding, last-use tick)
+    entries: HashMap<String, (Vec<f32>, u64)>,
+    /// last-use tick → text; the first entry is the eviction candidate.
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl LruCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, key: &str) -> Option<Vec<f32>> {
+        let tick = self.next_tick();
+        match self.entries.get_mut(key) {
+            Some((vector, last_used)) => {
+                self.recency.remove(last_used);
+                *last_used = tick;
+                self.recency.insert(tick, key.to_string());
+                self.hits += 1;
+                Some(vector.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, key: String, vector: Vec<f32>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let tick = self.next_tick();
+        if let Some((_, old_tick)) = self.entries.insert(key.clone(), (vector, tick)) {
+            self.recency.remove(&old_tick);
+        }
+        self.recency.insert(tick, key);
+        while self.entries.len() > self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else { break };
+            self.entries.remove(&oldest);
+            self.evictions += 1;
+        }
+    }
+}
+
 /// Wrapper that caches embeddings to avoid redundant API calls.
+///
+/// Keyed on the exact input text and bounded to `capacity` entries; the
+/// least-recently-used embedding is evicted when full.
 pub struct CachedEncoder<E: TextEncoder> {
     inner: E,
-    cache: std::sync::RwLock<HashMap<String, Vec<f32>>>,
+    cache: std::sync::Mutex<LruCache>,
 }
 
 impl<E: TextEncoder> CachedEncoder<E> {
     pub fn new(inner: E) -> Self {
+        Self::with_capacity(inner, DEFAULT_EMBED_CACHE_CAPACITY)
+    }
+
+    /// Cache at most `capacity` embeddings (0 disables caching).
+    pub fn with_capacity(inner: E, capacity: usize) -> Self {
         Self {
             inner,
-            cache: std::sync::RwLock::new(HashMap::new()),
+            cache: std::sync::Mutex::new(LruCache::new(capacity)),
         }
     }
+
+    /// `(hits, misses, evictions)` since construction.
+    pub fn cache_stats(&self) -> (u64, u64, u64) {
+        let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        (cache.hits, cache.misses, cache.evictions)
+    }
 }
 
 impl<E: TextEncoder> TextEncoder for CachedEncoder<E> {
     fn embed(&self, text: &str) -> Result<Vec<f32>, EncodingError> {
-        let key = text.to_string();
-        let read_guard = self.cache.read().unwrap_or_else(|e| e.into_inner());
-        if let Some(cached) = read_guard.get(&key) {
-            return Ok(cached.clone());
+        if let Some(cached) = self.cache.lock().unwrap_or_else(|e| e.into_inner()).get(text) {
+            return Ok(cached);
         }
-        drop(read_guard);
+        // Embed without holding the lock; backends may be slow network calls.
         let result = self.inner.embed(text)?;
-        self.cache.write().unwrap_or_else(|e| e.into_inner()).insert(key, result.clone());
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).insert(text.to_string(), result.clone());
         Ok(result)
     }
 
     fn embedding_dim(&self) -> usize {
         self.inner.embedding_dim()
     }
+
+    fn stats(&self) -> Option<EncoderStats> {
+        self.inner.stats()
+    }
+
+    fn cache_stats(&self) -> Option<(u64, u64, u64)> {
+        Some(CachedEncoder::cache_stats(self))
+    }
 }
 
 /// Fallback chain: tries primary encoder, falls back on error.
+///
+/// Counts which encoder served each embedding so degraded (fallback)
+/// embeddings can be detected via `stats()`.
 pub struct CompositeEncoder {
     primary: Box<dyn TextEncoder>,
     fallback: Box<dyn TextEncoder>,
+    primary_hits: AtomicU64,
+    fallback_hits: AtomicU64,
+    fallback_warned: AtomicBool,
 }
 
 impl CompositeEncoder {
     pub fn new(primary: Box<dyn TextEncoder>, fallback: Box<dyn TextEncoder>) -> Self {
-        Self { primary, fallback }
+        Self {
+            primary,
+            fallback,
+            primary_hits: AtomicU64::new(0),
+            fallback_hits: AtomicU64::new(0),
+            fallback_warned: AtomicBool::new(false),
+        }
+    }
+
+    /// Number of embeddings served by the primary and fallback encoders.
+    pub fn stats(&self) -> EncoderStats {
+        EncoderStats {
+            primary_hits: self.primary_hits.load(Ordering::Relaxed),
+            fallback_hits: self.fallback_hits.load(Ordering::Relaxed),
+        }
     }
 }
 
 impl TextEncoder for CompositeEncoder {
     fn embed(&self, text: &str) -> Result<Vec<f32>, EncodingError> {
         match self.primary.embed(text) {
-            Ok(v) => Ok(v),
-            Err(_) => self.fallback.embed(text),
+            Ok(v) => {
+                self.primary_hits.fetch_add(1, Ordering::Relaxed);
+                Ok(v)
+            }
+            Err(e) => {
+                if !self.fallback_warned.swap(true, Ordering::Relaxed) {
+                    eprintln!("[encoding] primary encoder failed ({}); falling back, embeddings are degraded", e);
+                }
+                let v = self.fallback.embed(text)?;
+                self.fallback_hits.fetch_add(1, Ordering::Relaxed);
+                Ok(v)
+            }
         }
     }
 
     fn embedding_dim(&self) -> usize {
         self.primary.embedding_dim()
     }
+
+    fn stats(&self) -> Option<EncoderStats> {
+        Some(CompositeEncoder::stats(self))
+    }
 }
 
-/// Full text → hypervector encoding pipeline with HDC algebra.
+/// Full text → hypervector encoding pipeline with HDC algebra. Clones share
+/// the encoder.
+#[derive(Clone)]
 pub struct EncodingPipeline {
-    encoder: Box<dyn TextEncoder>,
+    encoder: Arc<dyn TextEncoder>,
     codebook: Codebook,
+    #[cfg(feature = "async-embed")]
+    async_encoder: Option<OllamaEncoder>,
+    /// How `memory_for` stores hypervectors.
+    memory_form: MemoryForm,
+}
+
+/// Representation `EncodingPipeline::memory_for` picks for new memories.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MemoryForm {
+    Dense,
+    /// Sparse when at most this fraction of components is nonzero.
+    Sparse { max_density: f32 },
+    Binary,
 }
 
 impl EncodingPipeline {
@@ -295,7 +562,42 @@ impl EncodingPipeline {
             codebook.input_dim,
             "encoder dim must match codebook input dim"
         );
-        Self { encoder, codebook }
+        Self {
+            encoder: Arc::from(encoder),
+            codebook,
+            #[cfg(feature = "async-embed")]
+            async_encoder: None,
+            memory_form: MemoryForm::Dense,
+        }
+    }
+
+    /// Opt in to sparse memories: `encode_memory` stores hypervectors whose
+    /// fraction of nonzero components is at most `max_density` as a
+    /// `SparseVector`. Denser output stays dense.
+    pub fn with_sparse_output(mut self, max_density: f32) -> Self {
+        self.memory_form = MemoryForm::Sparse { max_density };
+        self
+    }
+
+    /// Opt in to binary memories: `encode_memory` keeps only the sign of each
+    /// component as a bit-packed `BitVector`, 32× smaller than dense. Recall
+    /// then scores by normalized Hamming similarity, which tracks the angle
+    /// between the dense projections (SimHash). Replaces `with_sparse_output`.
+    pub fn with_binary_output(mut self) -> Self {
+        self.memory_form = MemoryForm::Binary;
+        self
+    }
+
+    /// Use `encoder` for `encode_text_async`. The blocking encoder is unchanged.
+    #[cfg(feature = "async-embed")]
+    pub fn with_async_encoder(mut self, encoder: OllamaEncoder) -> Self {
+        assert_eq!(
+            encoder.embedding_dim(),
+            self.codebook.input_dim,
+            "encoder dim must match codebook input dim"
+        );
+        self.async_encoder = Some(encoder);
+        self
     }
 
     /// Access the codebook.
@@ -303,10 +605,41 @@ impl EncodingPipeline {
         &self.codebook
     }
 
+    /// Hypervector dimension produced by this pipeline (the codebook's output dim).
+    pub fn output_dim(&self) -> usize {
+        self.codebook.output_dim
+    }
+
     /// Encode text to a unit-length hypervector (10K dims).
     pub fn encode_text(&self, text: &str) -> Result<Vec<f32>, EncodingError> {
         let embedding = self.encoder.embed(text)?;
-        Ok(self.codebook.project(&embedding))
+        self.codebook.project_checked(&embedding)
+    }
+
+    /// Like `encode_text`, but embeds without blocking the thread. Errors from
+    /// the async encoder are returned as-is (no hash fallback). Without an
+    /// async encoder the blocking encoder runs via `encode_text_blocking`.
+    #[cfg(feature = "async-embed")]
+    pub async fn encode_text_async(&self, text: &str) -> Result<Vec<f32>, EncodingError> {
+        match &self.async_encoder {
+            Some(encoder) => {
+                let embedding = encoder.embed_async(text).await?;
+                self.codebook.project_checked(&embedding)
+            }
+            None => self.encode_text_blocking(text).await,
+        }
+    }
+
+    /// Run the blocking encoder on tokio's blocking thread pool so it does not
+    /// stall the async runtime. Must be called from within a tokio runtime.
+    #[cfg(feature = "async-embed")]
+    pub async fn encode_text_blocking(&self, text: &str) -> Result<Vec<f32>, EncodingError> {
+        let encoder = Arc::clone(&self.encoder);
+        let text = text.to_string();
+        let embedding = tokio::task::spawn_blocking(move || encoder.embed(&text))
+            .await
+            .map_err(|e| EncodingError::Other(format!("blocking encoder task failed: {}", e)))??;
+        self.codebook.project_checked(&embedding)
     }
 
     /// Full pipeline: encode text → create HyperMemory with default wave params.
@@ -316,30 +649,70 @@ impl EncodingPipeline {
         _timestamp: DateTime<Utc>,
     ) -> Result<HyperMemory, EncodingError> {
         let hv = self.encode_text(text)?;
-        Ok(HyperMemory::new(hv, text.to_string()))
+        Ok(self.memory_for(hv, text))
+    }
+
+    /// Wrap an encoded hypervector in a new `HyperMemory`, in the form chosen
+    /// by `with_sparse_output` or `with_binary_output`.
+    pub fn memory_for(&self, hv: Vec<f32>, text: &str) -> HyperMemory {
+        match self.memory_form {
+            MemoryForm::Dense => {}
+            MemoryForm::Sparse { max_density } => {
+                let sparse = SparseVector::from_dense(&hv);
+                if sparse.density() <= max_density {
+                    return HyperMemory::new_sparse(sparse, text.to_string());
+                }
+            }
+            MemoryForm::Binary => return HyperMemory::new_binary(BitVector::from_signs(&hv), text.to_string()),
+        }
+        HyperMemory::new(hv, text.to_string())
+    }
+
+    /// Primary/fallback counters of the blocking encoder, if it has a fallback chain.
+    pub fn encoder_stats(&self) -> Option<EncoderStats> {
+        self.encoder.stats()
+    }
+
+    /// `(hits, misses, evictions)` of the blocking encoder's embedding cache, if any.
+    pub fn cache_stats(&self) -> Option<(u64, u64, u64)> {
+        self.encoder.cache_stats()
     }
 
     /// Binding operation ⊗: element-wise multiply.
     pub fn bind(&self, a: &[f32], b: &[f32]) -> Vec<f32> {
-        a.iter().zip(b.iter()).map(|(x, y)| x * y).collect()
+        wave::bind(a, b)
     }
 
-    /// Bundling operation ⊕: element-wise sum + normalize.
+    /// Bundling operation ⊕: element-wise sum + normalize. Vectors whose
+    /// length is not `output_dim` are skipped.
     pub fn bundle(&self, vectors: &[Vec<f32>]) -> Vec<f32> {
         assert!(!vectors.is_empty());
-        // Find dimension from first non-empty vector; skip empty vectors gracefully
-        let dim = vectors.iter().find(|v| !v.is_empty()).map(|v| v.len()).unwrap_or(0);
-        if dim == 0 {
-            return Vec::new();
-        }
+        self.sum_normalized(vectors.iter().map(|v| (v.as_slice(), 1.0)))
+    }
+
+    /// Weighted bundling: each vector is scaled by its weight (typically the
+    /// memory's amplitude) before summing, so strong memories dominate the
+    /// result. Negative weights count as zero. Vectors whose length is not
+    /// `output_dim` are skipped.
+    pub fn bundle_weighted(&self, vectors: &[(Vec<f32>, f32)]) -> Vec<f32> {
+        assert!(!vectors.is_empty());
+        self.sum_normalized(vectors.iter().map(|(v, w)| (v.as_slice(), w.max(0.0))))
+    }
+
+    fn sum_normalized<'a>(&self, weighted: impl Iterator<Item = (&'a [f32], f32)>) -> Vec<f32> {
+        let dim = self.output_dim();
         let mut result = vec![0.0f32; dim];
-        for v in vectors {
+        let mut any = false;
+        for (v, weight) in weighted {
             if v.len() == dim {
-                for (i, val) in v.iter().enumerate() {
-                    result[i] += val;
+                any = true;
+                for (r, val) in result.iter_mut().zip(v) {
+                    *r += weight * val;
                 }
             }
-            // Skip vectors with wrong dimensionality (empty or mismatched)
+        }
+        if !any {
+            return Vec::new();
         }
         normalize(&mut result);
         result
@@ -347,16 +720,7 @@ impl EncodingPipeline {
 
     /// Permutation operation Π: circular shift of coordinates.
     pub fn permute(&self, v: &[f32], shifts: usize) -> Vec<f32> {
-        let n = v.len();
-        if n == 0 {
-            return vec![];
-        }
-        let shifts = shifts % n;
-        let mut result = vec![0.0f32; n];
-        for i in 0..n {
-            result[(i + shifts) % n] = v[i];
-        }
-        result
+        wave::permute(v, shifts)
     }
 }
 
@@ -429,6 +793,33 @@ mod tests {
         assert!(cosine_similarity(&bundled, &v3) > 0.0);
     }
 
+    #[test]
+    fn high_amplitude_vector_dominates_weighted_bundle() {
+        let pipeline = make_pipeline();
+        let strong = pipeline.encode_text("well established memory").unwrap();
+        let weak: Vec<Vec<f32>> = ["ghost one", "ghost two", "ghost three", "ghost four"]
+            .iter()
+            .map(|t| pipeline.encode_text(t).unwrap())
+            .collect();
+
+        let mut weighted = vec![(strong.clone(), 1.0)];
+        weighted.extend(weak.iter().map(|v| (v.clone(), 0.01)));
+        let bundled = pipeline.bundle_weighted(&weighted);
+        let norm: f32 = bundled.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-4);
+
+        let mut unweighted = vec![strong.clone()];
+        unweighted.extend(weak.iter().cloned());
+        let plain = pipeline.bundle(&unweighted);
+
+        let sim = cosine_similarity(&bundled, &strong);
+        assert!(sim > 0.95, "strong memory should dominate, sim={}", sim);
+        assert!(sim > cosine_similarity(&plain, &strong));
+        for v in &weak {
+            assert!(cosine_similarity(&bundled, v) < cosine_similarity(&plain, v));
+        }
+    }
+
     #[test]
     fn permute_dissimilar_to_original() {
         let pipeline = make_pipeline();
@@ -439,7 +830,7 @@ mod tests {
     }
 
     // --- Mock encoder that counts calls ---
-    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::atomic::AtomicUsize;
     use std::sync::Arc;
 
     struct CountingEncoder {
@@ -488,6 +879,23 @@ mod tests {
         assert_eq!(count.load(Ordering::SeqCst), 2);
     }
 
+    #[test]
+    fn cached_encoder_evicts_least_recently_used() {
+        let (enc, count) = CountingEncoder::new(64);
+        let cached = CachedEncoder::with_capacity(enc, 2);
+        cached.embed("a").unwrap();
+        cached.embed("b").unwrap();
+        cached.embed("a").unwrap(); // hit; "b" is now least recently used
+        cached.embed("c").unwrap(); // evicts "b"
+        assert_eq!(cached.cache_stats(), (1, 3, 1));
+
+        cached.embed("a").unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 3, "\"a\" should still be cached");
+        cached.embed("b").unwrap();
+        assert_eq!(count.load(Ordering::SeqCst), 4, "\"b\" should have been evicted");
+        assert_eq!(cached.cache_stats(), (2, 4, 2));
+    }
+
     #[test]
     fn composite_uses_primary_when_available() {
         let primary = SimpleHashEncoder::new(64, 1);
@@ -507,6 +915,22 @@ mod tests {
         assert_eq!(v.len(), 64);
     }
 
+    #[test]
+    fn composite_stats_count_fallback_hits() {
+        let failing = CompositeEncoder::new(Box::new(FailingEncoder { dim: 64 }), Box::new(SimpleHashEncoder::new(64, 42)));
+        let _ = failing.embed("first").unwrap();
+        let _ = failing.embed("second").unwrap();
+        assert_eq!(failing.stats(), EncoderStats { primary_hits: 0, fallback_hits: 2 });
+
+        // Stats are visible through the cache and the pipeline; cache hits don't count.
+        let healthy = CompositeEncoder::new(Box::new(SimpleHashEncoder::new(384, 1)), Box::new(SimpleHashEncoder::new(384, 2)));
+        let pipeline = EncodingPipeline::new(Box::new(CachedEncoder::new(healthy)), Codebook::new(384, 10_000, 42));
+        pipeline.encode_text("hello").unwrap();
+        pipeline.encode_text("hello").unwrap();
+        assert_eq!(pipeline.encoder_stats(), Some(EncoderStats { primary_hits: 1, fallback_hits: 0 }));
+        assert_eq!(make_pipeline().encoder_stats(), None);
+    }
+
     #[test]
     fn http_encoder_construction() {
         let enc = HttpEmbeddingEncoder::openai_small("test-key".to_string());
@@ -518,11 +942,107 @@ mod tests {
     fn encode_memory_produces_valid_hypermemory() {
         let pipeline = make_pipeline();
         let mem = pipeline.encode_memory("I met Alice at the park", Utc::now()).unwrap();
-        assert_eq!(mem.vector.len(), 10_000);
+        assert_eq!(mem.dim(), 10_000);
         assert_eq!(mem.content, "I met Alice at the park");
         assert_eq!(mem.layer_depth, 0);
         assert!(mem.amplitude > 0.0);
-        let norm: f32 = mem.vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let norm: f32 = mem.dense_vector().iter().map(|x| x * x).sum::<f32>().sqrt();
         assert!((norm - 1.0).abs() < 1e-4);
     }
+
+    fn mock_embeddings_server(rt: &tokio::runtime::Runtime, status: u16, embedding: Vec<f32>) -> wiremock::MockServer {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        rt.block_on(async {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/v1/embeddings"))
+                .and(body_partial_json(serde_json::json!({"model": "test-embed"})))
+                .respond_with(ResponseTemplate::new(status).set_body_json(serde_json::json!({
+                    "data": [{"embedding": embedding}]
+                })))
+                .mount(&server)
+                .await;
+            server
+        })
+    }
+
+    #[test]
+    fn remote_http_encoder_fits_and_normalizes() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        // Remote model returns 512 dims; the encoder must truncate to 384
+        let server = mock_embeddings_server(&rt, 200, vec![2.0; 512]);
+        let enc = RemoteHttpEncoder::new(server.uri(), "test-embed".to_string(), Some("k".to_string()), 512, 384);
+
+        let v = enc.embed("hello remote").unwrap();
+        assert_eq!(v.len(), 384);
+        let norm: f32 = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-5);
+        assert!((v[0] - v[383]).abs() < 1e-6);
+
+        // Smaller remote models are zero-padded
+        let server = mock_embeddings_server(&rt, 200, vec![1.0; 256]);
+        let enc = RemoteHttpEncoder::new(server.uri(), "test-embed".to_string(), None, 256, 384);
+        let v = enc.embed("hello remote").unwrap();
+        assert_eq!(v.len(), 384);
+        assert!(v[255] > 0.0);
+        assert_eq!(v[256], 0.0);
+    }
+
+    #[test]
+    fn remote_http_encoder_falls_back_on_error_status() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let server = mock_embeddings_server(&rt, 500, vec![1.0; 384]);
+        let remote = RemoteHttpEncoder::new(server.uri(), "test-embed".to_string(), None, 384, 384);
+        assert!(remote.embed("hello").is_err());
+
+        let fallback = SimpleHashEncoder::new(384, 42);
+        let expected = fallback.embed("hello").unwrap();
+        let composite = CompositeEncoder::new(Box::new(remote), Box::new(fallback));
+        assert_eq!(composite.embed("hello").unwrap(), expected);
+    }
+
+    #[cfg(feature = "async-embed")]
+    #[test]
+    fn ollama_embed_async_parses_and_propagates_errors() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/api/embed"))
+                .and(body_partial_json(serde_json::json!({"model": "all-minilm", "input": "hello"})))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "embeddings": [vec![0.25f32; 384]]
+                })))
+                .mount(&server)
+                .await;
+            let enc = OllamaEncoder::new(server.uri(), "all-minilm".to_string(), 384);
+            assert_eq!(enc.embed_async("hello").await.unwrap(), vec![0.25; 384]);
+
+            let pipeline = make_pipeline().with_async_encoder(enc);
+            let hv = pipeline.encode_text_async("hello").await.unwrap();
+            assert_eq!(hv.len(), 10_000);
+
+            let wrong_dim = OllamaEncoder::new(server.uri(), "all-minilm".to_string(), 128);
+            assert!(matches!(
+                wrong_dim.embed_async("hello").await,
+                Err(EncodingError::DimensionMismatch { expected: 128, got: 384 })
+            ));
+
+            let failing = MockServer::start().await;
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(500))
+                .mount(&failing)
+                .await;
+            let pipeline = make_pipeline()
+                .with_async_encoder(OllamaEncoder::new(failing.uri(), "all-minilm".to_string(), 384));
+            assert!(matches!(pipeline.encode_text_async("hello").await, Err(EncodingError::Other(_))));
+
+            // Without an async encoder the blocking encoder runs off the runtime thread.
+            let pipeline = make_pipeline();
+            assert_eq!(pipeline.encode_text_async("hello").await.unwrap(), pipeline.encode_text("hello").unwrap());
+        });
+    }
 }
@@ -166,7 +166,7 @@ impl VideoPipeline {
         mem.frequency = 0.03; // slower than audio (0.05) — visual memories more stable
         mem.phase = PI / 2.0; // 90° offset from audio (π/4) and text (0)
         mem.decay_rate = 3e-7; // slower decay than audio (5e-7)
-        mem.xi_signature = compute_xi_signature(&mem.vector);
+        mem.xi_signature = compute_xi_signature(&mem.dense_vector());
 
         Ok((mem, vf))
     }
@@ -717,6 +717,32 @@ pub fn transform_m(element: &SgaElement) -> SgaElement {
     )
 }
 
+/// One of the four SGA transforms, for applying by name (see `GeoTransform::apply`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeoTransform {
+    /// Rotate the quadrant h₂ (`transform_r`).
+    R,
+    /// Shift the modality d (`transform_d`).
+    D,
+    /// Cycle the context slot ℓ (`transform_t`).
+    T,
+    /// Mirror the modality (`transform_m`); odd `k` mirrors, even `k` is the identity.
+    M,
+}
+
+impl GeoTransform {
+    /// Apply this transform `k` times to `element`.
+    pub fn apply(self, element: &SgaElement, k: i32) -> SgaElement {
+        match self {
+            GeoTransform::R => transform_r(element, k),
+            GeoTransform::D => transform_d(element, k),
+            GeoTransform::T => transform_t(element, k),
+            GeoTransform::M if k.rem_euclid(2) == 1 => transform_m(element),
+            GeoTransform::M => element.clone(),
+        }
+    }
+}
+
 impl SgaElement {
     /// Get the overall coefficient of a rank-1 element
     fn get_overall_coefficient(&self) -> f64 {
@@ -899,6 +925,179 @@ pub struct MemoryCoordinates {
     pub phase: f64,
 }
 
+impl MemoryCoordinates {
+    /// Lift the class to an `SgaElement`, apply `transform` `k` times and
+    /// project back. Amplitude and phase are kept; `None` if the result is
+    /// not a rank-1 class.
+    pub fn transformed(&self, transform: GeoTransform, k: i32) -> Option<MemoryCoordinates> {
+        let class_index = project(&transform.apply(&lift(self.class_index), k))?;
+        let comp = decode_class_index(class_index);
+        Some(MemoryCoordinates {
+            h2: comp.h2,
+            d: comp.d,
+            l: comp.l,
+            class_index,
+            ..self.clone()
+        })
+    }
+}
+
+/// Hash of a memory's content, fed to `classify_memory` and frequency-class
+/// assignment.
+///
+/// **Stability contract:** geometry and frequency classes of stored memories
+/// were derived from this value, so the algorithm must never change. It is the
+/// base-31 polynomial hash over the UTF-8 bytes with wrapping `u64`
+/// arithmetic: `h = h * 31 + byte`, starting from 0.
+pub fn stable_content_hash(content: &str) -> u64 {
+    content.bytes().fold(0u64, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u64))
+}
+
+/// A memory category in a `Taxonomy`: the keywords that select it, the wave
+/// frequency band its memories are assigned, and its SGA quadrant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaxonomyCategory {
+    pub name: String,
+    /// Lowercase substrings; any match in the lowercased text selects the category.
+    pub keywords: Vec<String>,
+    pub freq_min: f32,
+    pub freq_max: f32,
+    /// Quadrant h₂ (0..3) for `classify`.
+    pub h2: u8,
+}
+
+impl TaxonomyCategory {
+    pub fn new(name: &str, keywords: &[&str], freq_min: f32, freq_max: f32, h2: u8) -> Self {
+        Self {
+            name: name.to_string(),
+            keywords: keywords.iter().map(|k| k.to_lowercase()).collect(),
+            freq_min,
+            freq_max,
+            h2: h2 % 4,
+        }
+    }
+}
+
+/// Why `Taxonomy::new` rejected a set of categories.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TaxonomyError {
+    #[error("taxonomy needs at least one category")]
+    Empty,
+    #[error("category {category} has invalid frequency band [{freq_min}, {freq_max}): need 0 <= min < max, both finite")]
+    InvalidBand { category: String, freq_min: f32, freq_max: f32 },
+}
+
+/// Category registry driving `categorize` and frequency-band assignment.
+/// Categories are tried in order; text matching none falls back to the last
+/// category. The default is the five consciousness categories; domain apps
+/// (legal, medical, ...) can supply their own registers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Taxonomy {
+    categories: Vec<TaxonomyCategory>,
+}
+
+impl Taxonomy {
+    /// Build a taxonomy from `categories`, the last of which is the fallback.
+    /// Fails if `categories` is empty or a band is not a non-empty, finite,
+    /// non-negative `[freq_min, freq_max)` range, since frequencies are drawn from it.
+    pub fn new(categories: Vec<TaxonomyCategory>) -> Result<Self, TaxonomyError> {
+        if categories.is_empty() {
+            return Err(TaxonomyError::Empty);
+        }
+        let bad_band = |c: &TaxonomyCategory| {
+            !(c.freq_min.is_finite() && c.freq_max.is_finite() && c.freq_min >= 0.0 && c.freq_min < c.freq_max)
+        };
+        if let Some(c) = categories.iter().find(|c| bad_band(c)) {
+            return Err(TaxonomyError::InvalidBand {
+                category: c.name.clone(),
+                freq_min: c.freq_min,
+                freq_max: c.freq_max,
+            });
+        }
+        Ok(Self { categories })
+    }
+
+    pub fn categories(&self) -> &[TaxonomyCategory] {
+        &self.categories
+    }
+
+    /// Category for `text`: the first whose keywords it contains, else the fallback.
+    pub fn categorize(&self, text: &str) -> &str {
+        let text_lower = text.to_lowercase();
+        self.categories.iter()
+            .find(|c| c.keywords.iter().any(|k| text_lower.contains(k.as_str())))
+            .unwrap_or_else(|| self.fallback())
+            .name
+            .as_str()
+    }
+
+    /// The named category, or `None` if it is not registered.
+    pub fn get(&self, category: &str) -> Option<&TaxonomyCategory> {
+        self.categories.iter().find(|c| c.name == category)
+    }
+
+    /// `(freq_min, freq_max)` of `category`; the fallback's band if unknown.
+    pub fn band(&self, category: &str) -> (f32, f32) {
+        let c = self.get(category).unwrap_or_else(|| self.fallback());
+        (c.freq_min, c.freq_max)
+    }
+
+    /// `classify_memory` with the category's quadrant; unregistered categories
+    /// are classified by `classify_memory` itself.
+    pub fn classify(&self, category: &str, content_hash: u64, importance: f64) -> MemoryCoordinates {
+        match self.get(category) {
+            Some(c) => coordinates(c.h2, content_hash, importance),
+            None => classify_memory(category, content_hash, importance),
+        }
+    }
+
+    fn fallback(&self) -> &TaxonomyCategory {
+        self.categories.last().expect("taxonomy has at least one category")
+    }
+}
+
+impl Default for Taxonomy {
+    /// The five consciousness categories. Bands are aligned with
+    /// `xi_clusters()` in store.rs.
+    fn default() -> Self {
+        Self {
+            categories: vec![
+                // Direct events, actions, sensory input: soprano (fast, ephemeral)
+                TaxonomyCategory::new("experience", &[
+                    "saw", "heard", "did", "went", "happened", "occurred",
+                    "experience", "event", "today", "yesterday", "just",
+                ], 1.8, 2.4, 3),
+                // Feelings, moods, emotional states: alto (feeling-paced)
+                TaxonomyCategory::new("emotion", &[
+                    "feel", "felt", "happy", "sad", "angry", "excited",
+                    "worried", "love", "hate", "emotion", "mood",
+                ], 1.3, 1.8, 3),
+                // Interpersonal interactions, relationships: tenor
+                TaxonomyCategory::new("social", &[
+                    "said", "told", "asked", "friend", "person",
+                    "people", "conversation", "meeting", "together", "team",
+                ], 1.0, 1.3, 1),
+                // Procedures, abilities, how-to knowledge: bass-adjacent
+                TaxonomyCategory::new("skill", &[
+                    "how to", "procedure", "method", "code", "function", "build",
+                    "compile", "deploy", "technique", "practice", "ability",
+                ], 0.8, 1.0, 2),
+                // Facts, concepts, theories (fallback): bass (slow, stable)
+                TaxonomyCategory::new("knowledge", &[], 0.6, 0.8, 0),
+            ],
+        }
+    }
+}
+
+/// Categorize text using simple heuristics, mapping to the 5 consciousness
+/// categories of the default `Taxonomy`.
+pub fn categorize_text(text: &str) -> &'static str {
+    lazy_static! {
+        static ref DEFAULT_TAXONOMY: Taxonomy = Taxonomy::default();
+    }
+    DEFAULT_TAXONOMY.categorize(text)
+}
+
 /// Classify a memory into SGA coordinates
 pub fn classify_memory(category: &str, content_hash: u64, importance: f64) -> MemoryCoordinates {
     // Map category to h2 (quadrant) - consciousness differentiation categories
@@ -910,6 +1109,12 @@ pub fn classify_memory(category: &str, content_hash: u64, importance: f64) -> Me
         _ => (content_hash % 4) as u8,
     };
     
+    coordinates(h2, content_hash, importance)
+}
+
+/// Coordinates in quadrant `h2`, with modality from `importance` and context
+/// slot, amplitude and phase from the content hash.
+fn coordinates(h2: u8, content_hash: u64, importance: f64) -> MemoryCoordinates {
     // Map importance to d (modality)
     let d = if importance < 0.3 {
         2 // imagined/speculative
@@ -978,6 +1183,34 @@ pub fn fano_related(a: &MemoryCoordinates, b: &MemoryCoordinates) -> bool {
     false
 }
 
+/// Bind two memory coordinates through the octonion cross product.
+///
+/// Distinct non-zero `l` values always share exactly one Fano line; the bound
+/// coordinate takes `l = a.l × b.l` from that line, with the orientation sign
+/// folded into phase (a negative product adds π). Quadrant and modality
+/// compose additively in Z₄ and Z₃, and amplitudes multiply. Returns `None`
+/// when either `l` is 0 or both are equal (the cross product vanishes).
+pub fn fano_bind(a: &MemoryCoordinates, b: &MemoryCoordinates) -> Option<MemoryCoordinates> {
+    if a.l == 0 || b.l == 0 || a.l == b.l || a.l > 7 || b.l > 7 {
+        return None;
+    }
+    let (l, sign) = cross_product(a.l, b.l);
+    let h2 = (a.h2 + b.h2) % 4;
+    let d = (a.d + b.d) % 3;
+    let mut phase = a.phase + b.phase;
+    if sign < 0 {
+        phase += std::f64::consts::PI;
+    }
+    Some(MemoryCoordinates {
+        h2,
+        d,
+        l,
+        class_index: components_to_class_index(ClassComponents { h2, d, l }),
+        amplitude: a.amplitude * b.amplitude,
+        phase: phase.rem_euclid(2.0 * std::f64::consts::PI),
+    })
+}
+
 // Use lazy_static for the cross product table
 use lazy_static::lazy_static;
 
@@ -985,6 +1218,14 @@ use lazy_static::lazy_static;
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_stable_content_hash_is_pinned() {
+        // Changing these values re-classifies every stored memory.
+        assert_eq!(stable_content_hash(""), 0);
+        assert_eq!(stable_content_hash("a"), 97);
+        assert_eq!(stable_content_hash("the quick brown fox jumps over the lazy dog"), 9_189_841_723_308_291_443);
+    }
+
     #[test]
     fn test_clifford_identity_and_zero() {
         let identity = CliffordElement::identity();
@@ -1072,6 +1313,35 @@ mod tests {
         assert_eq!(sign_ij, -sign_ji);
     }
 
+    #[test]
+    fn test_fano_bind_on_line_124() {
+        let coords = |l: u8, phase: f64| MemoryCoordinates {
+            h2: 1,
+            d: 2,
+            l,
+            class_index: components_to_class_index(ClassComponents { h2: 1, d: 2, l }),
+            amplitude: 0.8,
+            phase,
+        };
+        let e1 = coords(1, 0.25);
+        let e2 = coords(2, 0.5);
+
+        let bound = fano_bind(&e1, &e2).unwrap();
+        assert_eq!(bound.l, 4);
+        assert_eq!((bound.h2, bound.d), (2, 1));
+        assert_eq!(bound.class_index, components_to_class_index(ClassComponents { h2: 2, d: 1, l: 4 }));
+        assert!((bound.phase - 0.75).abs() < 1e-12, "positive sign keeps phase");
+        assert!((bound.amplitude - 0.64).abs() < 1e-12);
+
+        // e2 × e1 = -e4: same l, sign folded into phase as +π.
+        let reversed = fano_bind(&e2, &e1).unwrap();
+        assert_eq!(reversed.l, 4);
+        assert!((reversed.phase - (0.75 + std::f64::consts::PI)).abs() < 1e-12);
+
+        assert!(fano_bind(&e1, &e1).is_none());
+        assert!(fano_bind(&coords(0, 0.0), &e2).is_none());
+    }
+
     #[test]
     fn test_class_system() {
         // Test class 0 (should be (0,0,0))
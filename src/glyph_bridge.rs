@@ -642,7 +642,7 @@ pub fn encode_memory_as_glyph(memory: &HyperMemory) -> Result<Glyph, GlyphError>
     let encoder = GlyphEncoder::default();
     
     // Convert hypervector to f64 for processing
-    let data: Vec<f64> = memory.vector.iter().map(|&x| x as f64).collect();
+    let data: Vec<f64> = memory.dense_vector().iter().map(|&x| x as f64).collect();
     
     encoder.encode(&data)
 }
@@ -863,7 +863,7 @@ mod tests {
         let glyph = encode_memory_as_glyph(&memory).unwrap();
         let bloomed_memory = bloom_glyph(&glyph).unwrap();
         
-        assert_eq!(bloomed_memory.vector.len(), 10000); // Standard size
+        assert_eq!(bloomed_memory.dim(), 10000); // Standard size
         assert!(bloomed_memory.content.contains("Glyph bloom"));
     }
     
@@ -8,7 +8,7 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::wave::cosine_similarity;
+use crate::wave::{similarity, SimilarityMetric};
 
 // ---------------------------------------------------------------------------
 // Scored neighbor (max-heap by similarity)
@@ -82,6 +82,9 @@ pub struct HnswIndex {
     entry_point: Option<Uuid>,
     max_level: usize,
     ml: f64, // normalization factor for level generation: 1/ln(M)
+    /// Similarity used to build and search the graph.
+    #[serde(default)]
+    metric: SimilarityMetric,
 }
 
 impl HnswIndex {
@@ -103,9 +106,21 @@ impl HnswIndex {
             entry_point: None,
             max_level: 0,
             ml,
+            metric: SimilarityMetric::default(),
         }
     }
 
+    /// Use `metric` instead of cosine. Set it before inserting: the graph's
+    /// edges are only meaningful under the metric they were built with.
+    pub fn with_metric(mut self, metric: SimilarityMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    pub fn metric(&self) -> SimilarityMetric {
+        self.metric
+    }
+
     /// Number of vectors in the index.
     pub fn len(&self) -> usize {
         self.nodes.len()
@@ -115,6 +130,15 @@ impl HnswIndex {
         self.nodes.is_empty()
     }
 
+    /// Candidate list size used for layer-0 search.
+    pub fn ef_search(&self) -> usize {
+        self.ef_search
+    }
+
+    pub fn set_ef_search(&mut self, ef_search: usize) {
+        self.ef_search = ef_search.max(1);
+    }
+
     /// Generate a random level for a new node.
     fn random_level(&self) -> usize {
         let mut rng = rand::thread_rng();
@@ -126,7 +150,24 @@ impl HnswIndex {
     /// Insert a vector into the index.
     pub fn insert(&mut self, id: Uuid, vector: &[f32]) {
         let level = self.random_level();
+        self.insert_at_level(id, vector, level);
+    }
 
+    /// Insert many vectors. Levels are drawn up front and the highest-level
+    /// nodes go in first, so the upper layers (and the entry point) are
+    /// settled before the bulk of layer-0 nodes link through them.
+    pub fn insert_batch(&mut self, items: &[(Uuid, &[f32])]) {
+        let mut leveled: Vec<(usize, usize)> = (0..items.len())
+            .map(|i| (i, self.random_level()))
+            .collect();
+        leveled.sort_by_key(|&(_, level)| std::cmp::Reverse(level));
+        for (i, level) in leveled {
+            let (id, vector) = items[i];
+            self.insert_at_level(id, vector, level);
+        }
+    }
+
+    fn insert_at_level(&mut self, id: Uuid, vector: &[f32], level: usize) {
         let node = HnswNode {
             id,
             vector: vector.to_vec(),
@@ -213,7 +254,7 @@ impl HnswIndex {
 
         let mut scored: Vec<(Uuid, f32)> = neighbors.iter()
             .filter_map(|&nb_id| {
-                self.nodes.get(&nb_id).map(|nb| (nb_id, cosine_similarity(&node_vec, &nb.vector)))
+                self.nodes.get(&nb_id).map(|nb| (nb_id, similarity(self.metric, &node_vec, &nb.vector)))
             })
             .collect();
         scored.sort_by(|a, b| b.1.total_cmp(&a.1));
@@ -230,7 +271,7 @@ impl HnswIndex {
     fn greedy_closest(&self, query: &[f32], ep: Uuid, layer: usize) -> Uuid {
         let mut current = ep;
         let mut current_sim = self.nodes.get(&ep)
-            .map(|n| cosine_similarity(query, &n.vector))
+            .map(|n| similarity(self.metric, query, &n.vector))
             .unwrap_or(-1.0);
 
         loop {
@@ -241,7 +282,7 @@ impl HnswIndex {
             };
             for &nb_id in &neighbors {
                 if let Some(nb) = self.nodes.get(&nb_id) {
-                    let sim = cosine_similarity(query, &nb.vector);
+                    let sim = similarity(self.metric, query, &nb.vector);
                     if sim > current_sim {
                         current = nb_id;
                         current_sim = sim;
@@ -263,7 +304,7 @@ impl HnswIndex {
         for &ep in entry_points {
             if visited.insert(ep) {
                 if let Some(n) = self.nodes.get(&ep) {
-                    let sim = cosine_similarity(query, &n.vector);
+                    let sim = similarity(self.metric, query, &n.vector);
                     let c = Candidate { id: ep, similarity: sim };
                     candidates.push(c.clone());
                     results.push(RevCandidate(c));
@@ -287,7 +328,7 @@ impl HnswIndex {
             for nb_id in neighbors {
                 if !visited.insert(nb_id) { continue; }
                 if let Some(nb) = self.nodes.get(&nb_id) {
-                    let sim = cosine_similarity(query, &nb.vector);
+                    let sim = similarity(self.metric, query, &nb.vector);
                     let should_add = results.len() < ef || {
                         results.peek().map(|w| sim > w.0.similarity).unwrap_or(true)
                     };
@@ -311,6 +352,11 @@ impl HnswIndex {
 
     /// Search for the top_k nearest neighbors of `query`.
     pub fn search(&self, query: &[f32], top_k: usize) -> Vec<(Uuid, f32)> {
+        self.search_with_ef(query, top_k, self.ef_search)
+    }
+
+    /// `search` with an explicit layer-0 candidate list size.
+    fn search_with_ef(&self, query: &[f32], top_k: usize, ef_search: usize) -> Vec<(Uuid, f32)> {
         if self.nodes.is_empty() {
             return Vec::new();
         }
@@ -329,7 +375,7 @@ impl HnswIndex {
         }
 
         // Search layer 0 with ef_search candidates
-        let ef = self.ef_search.max(top_k);
+        let ef = ef_search.max(top_k);
         let candidates = self.search_layer(query, &[current_ep], ef, 0);
 
         candidates.into_iter()
@@ -373,6 +419,36 @@ impl HnswIndex {
 
         true
     }
+
+    /// Drop every node, keeping the construction and search parameters.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.entry_point = None;
+        self.max_level = 0;
+    }
+
+    /// Rebuild `max_level` and `entry_point` from the surviving nodes and drop
+    /// neighbor references to ids that are no longer in the index.
+    ///
+    /// `remove` only rescans levels when the entry point itself is removed, so
+    /// after mass deletion `max_level` can stay stale and inflate descent cost.
+    pub fn compact(&mut self) {
+        let live: HashSet<Uuid> = self.nodes.keys().copied().collect();
+        for node in self.nodes.values_mut() {
+            for layer in node.neighbors.iter_mut() {
+                layer.retain(|nb| live.contains(nb));
+            }
+        }
+
+        self.entry_point = None;
+        self.max_level = 0;
+        for n in self.nodes.values() {
+            if self.entry_point.is_none() || n.level > self.max_level {
+                self.max_level = n.level;
+                self.entry_point = Some(n.id);
+            }
+        }
+    }
 }
 
 impl Default for HnswIndex {
@@ -392,11 +468,47 @@ use crate::store::{MemoryStore, StoreError};
 /// Brute-force fallback threshold: use HNSW only above this count.
 const HNSW_THRESHOLD: usize = 100;
 
+/// Fraction of the index that may be deleted before `HnswStore` compacts it.
+const COMPACT_RATIO: f64 = 0.1;
+
+/// Neighbours per query that `HnswStore::calibrate` measures recall over.
+const CALIBRATION_TOP_K: usize = 10;
+
 /// MemoryStore implementation using HNSW for similarity search.
+///
+/// Deserializing checks the index against the memories and rebuilds it if
+/// their counts disagree (a truncated or corrupted index).
 #[derive(Clone, Serialize, Deserialize)]
+#[serde(from = "HnswStoreData")]
 pub struct HnswStore {
     memories: HashMap<Uuid, HyperMemory>,
     index: HnswIndex,
+    /// Deletions since the last `HnswIndex::compact`.
+    #[serde(default)]
+    removed_since_compact: usize,
+}
+
+/// Serialized form of `HnswStore`, validated on the way in.
+#[derive(Deserialize)]
+struct HnswStoreData {
+    memories: HashMap<Uuid, HyperMemory>,
+    index: HnswIndex,
+    #[serde(default)]
+    removed_since_compact: usize,
+}
+
+impl From<HnswStoreData> for HnswStore {
+    fn from(data: HnswStoreData) -> Self {
+        let mut store = Self {
+            memories: data.memories,
+            index: data.index,
+            removed_since_compact: data.removed_since_compact,
+        };
+        if store.index.len() != store.memories.len() {
+            store.rebuild_index();
+        }
+        store
+    }
 }
 
 impl HnswStore {
@@ -404,6 +516,7 @@ impl HnswStore {
         Self {
             memories: HashMap::new(),
             index: HnswIndex::new(),
+            removed_since_compact: 0,
         }
     }
 
@@ -411,13 +524,84 @@ impl HnswStore {
         Self {
             memories: HashMap::new(),
             index: HnswIndex::with_params(max_layers, ef_construction, ef_search, m),
+            removed_since_compact: 0,
+        }
+    }
+
+    /// Remove and re-insert `id` in the HNSW index with its current vector.
+    ///
+    /// Needed after mutating a memory's vector through `get_mut`, which
+    /// otherwise leaves the index pointing at the stale vector.
+    pub fn reindex(&mut self, id: &Uuid) -> Result<(), StoreError> {
+        let mem = self.memories.get_mut(id).ok_or(StoreError::NotFound(*id))?;
+        mem.densify();
+        self.index.remove(id);
+        self.index.insert(*id, &mem.dense_vector());
+        Ok(())
+    }
+
+    /// Clear the HNSW index and re-insert every stored memory into it, keeping
+    /// the index parameters. Recovers from a missing or corrupted index.
+    pub fn rebuild_index(&mut self) {
+        self.index.clear();
+        for m in self.memories.values_mut() {
+            m.densify();
+        }
+        let mut items: Vec<(Uuid, &[f32])> = self.memories.values().map(|m| (m.id, m.vector.as_dense().unwrap_or_default())).collect();
+        items.sort_by_key(|&(id, _)| id);
+        self.index.insert_batch(&items);
+        self.removed_since_compact = 0;
+    }
+
+    pub fn ef_search(&self) -> usize {
+        self.index.ef_search()
+    }
+
+    /// Set the index's layer-0 candidate list size, e.g. to the value
+    /// returned by `calibrate`.
+    pub fn set_ef_search(&mut self, ef_search: usize) {
+        self.index.set_ef_search(ef_search);
+    }
+
+    /// Smallest `ef_search` whose mean recall@10 over `sample_queries`,
+    /// measured against brute-force ground truth, reaches `target_recall`.
+    ///
+    /// Binary-searches `[10, count]`, assuming recall grows with ef. Returns
+    /// the upper bound if even that misses the target, and the current
+    /// `ef_search` when there is nothing to measure. Does not change the store.
+    pub fn calibrate(&self, sample_queries: &[Vec<f32>], target_recall: f32) -> usize {
+        if sample_queries.is_empty() || self.memories.is_empty() {
+            return self.index.ef_search();
+        }
+        let top_k = CALIBRATION_TOP_K.min(self.memories.len());
+        let truths: Vec<HashSet<Uuid>> = sample_queries.iter()
+            .map(|q| self.brute_force_search(q, top_k).into_iter().map(|(id, _)| id).collect())
+            .collect();
+        let recall_at = |ef: usize| -> f32 {
+            let found: usize = sample_queries.iter().zip(&truths)
+                .map(|(q, truth)| {
+                    self.index.search_with_ef(q, top_k, ef).iter().filter(|(id, _)| truth.contains(id)).count()
+                })
+                .sum();
+            found as f32 / (top_k * sample_queries.len()) as f32
+        };
+
+        let (mut lo, mut hi) = (top_k, self.memories.len().max(top_k));
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if recall_at(mid) >= target_recall {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
         }
+        lo
     }
 
     /// Brute-force search (fallback for small stores).
     fn brute_force_search(&self, query: &[f32], top_k: usize) -> Vec<(Uuid, f32)> {
         let mut scored: Vec<(Uuid, f32)> = self.memories.values()
-            .map(|m| (m.id, cosine_similarity(query, &m.vector)))
+            .map(|m| (m.id, m.similarity_by(self.index.metric, query)))
             .collect();
         scored.sort_by(|a, b| b.1.total_cmp(&a.1));
         scored.truncate(top_k);
@@ -430,16 +614,37 @@ impl Default for HnswStore {
 }
 
 impl MemoryStore for HnswStore {
-    fn insert(&mut self, memory: HyperMemory) -> Result<Uuid, StoreError> {
+    fn insert(&mut self, mut memory: HyperMemory) -> Result<Uuid, StoreError> {
         let id = memory.id;
         if self.memories.contains_key(&id) {
             return Err(StoreError::DuplicateId(id));
         }
-        self.index.insert(id, &memory.vector);
+        // The graph is built over dense vectors.
+        memory.densify();
+        self.index.insert(id, &memory.dense_vector());
         self.memories.insert(id, memory);
         Ok(id)
     }
 
+    /// Unlike the default, the whole batch is checked for duplicate ids
+    /// first: on error nothing is inserted.
+    fn insert_batch(&mut self, mut memories: Vec<HyperMemory>) -> Result<Vec<Uuid>, StoreError> {
+        let mut seen = std::collections::HashSet::with_capacity(memories.len());
+        for m in &memories {
+            if self.memories.contains_key(&m.id) || !seen.insert(m.id) {
+                return Err(StoreError::DuplicateId(m.id));
+            }
+        }
+        for m in &mut memories {
+            m.densify();
+        }
+        let items: Vec<(Uuid, &[f32])> = memories.iter().map(|m| (m.id, m.vector.as_dense().unwrap_or_default())).collect();
+        self.index.insert_batch(&items);
+        let ids: Vec<Uuid> = memories.iter().map(|m| m.id).collect();
+        self.memories.extend(memories.into_iter().map(|m| (m.id, m)));
+        Ok(ids)
+    }
+
     fn get(&self, id: &Uuid) -> Result<Option<&HyperMemory>, StoreError> {
         Ok(self.memories.get(id))
     }
@@ -465,7 +670,7 @@ impl MemoryStore for HnswStore {
             // Brute-force with wave modulation
             let mut scored: Vec<(Uuid, f32)> = self.memories.values()
                 .map(|m| {
-                    let sim = cosine_similarity(query, &m.vector);
+                    let sim = m.similarity_by(self.index.metric, query);
                     let strength = m.effective_strength(now);
                     (m.id, sim * strength)
                 })
@@ -494,6 +699,11 @@ impl MemoryStore for HnswStore {
         Ok(self.memories.values().collect())
     }
 
+    fn for_each_memory(&self, f: &mut dyn FnMut(&HyperMemory)) -> Result<(), StoreError> {
+        self.memories.values().for_each(f);
+        Ok(())
+    }
+
     fn all_ids(&self) -> Result<Vec<Uuid>, StoreError> {
         Ok(self.memories.keys().copied().collect())
     }
@@ -501,6 +711,12 @@ impl MemoryStore for HnswStore {
     fn delete(&mut self, id: &Uuid) -> Result<bool, StoreError> {
         if self.memories.remove(id).is_some() {
             self.index.remove(id);
+            self.removed_since_compact += 1;
+            let live = self.index.len() + self.removed_since_compact;
+            if self.removed_since_compact as f64 > live as f64 * COMPACT_RATIO {
+                self.index.compact();
+                self.removed_since_compact = 0;
+            }
             Ok(true)
         } else {
             Ok(false)
@@ -510,6 +726,23 @@ impl MemoryStore for HnswStore {
     fn count(&self) -> usize {
         self.memories.len()
     }
+
+    fn reindex(&mut self, id: &Uuid) -> Result<(), StoreError> {
+        HnswStore::reindex(self, id)
+    }
+
+    /// Switching metric rebuilds the index so the graph follows the new one.
+    fn set_similarity_metric(&mut self, metric: SimilarityMetric) -> Result<(), StoreError> {
+        if self.index.metric != metric {
+            self.index.metric = metric;
+            self.rebuild_index();
+        }
+        Ok(())
+    }
+
+    fn similarity_metric(&self) -> SimilarityMetric {
+        self.index.metric
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -519,7 +752,7 @@ impl MemoryStore for HnswStore {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::wave::normalize;
+    use crate::wave::{cosine_similarity, normalize};
     use crate::memory::HyperMemory;
     use chrono::Duration;
     use std::time::Instant;
@@ -626,6 +859,28 @@ mod tests {
         assert!(avg_recall > 0.8, "Recall should be > 0.8, got {avg_recall:.3}");
     }
 
+    #[test]
+    fn calibrate_returns_ef_meeting_target_recall() {
+        let dim = 64;
+        let mut store = HnswStore::new();
+        for i in 0..1000 {
+            store.insert(make_memory(random_vector(dim, i), "")).unwrap();
+        }
+        let queries: Vec<Vec<f32>> = (0..20).map(|q| random_vector(dim, 50_000 + q)).collect();
+
+        let ef = store.calibrate(&queries, 0.95);
+        assert!((CALIBRATION_TOP_K..=1000).contains(&ef));
+        store.set_ef_search(ef);
+
+        let mut found = 0;
+        for q in &queries {
+            let truth: HashSet<Uuid> = store.brute_force_search(q, 10).into_iter().map(|(id, _)| id).collect();
+            found += store.search(q, 10).unwrap().iter().filter(|(id, _)| truth.contains(id)).count();
+        }
+        let recall = found as f32 / (10 * queries.len()) as f32;
+        assert!(recall >= 0.95, "ef {} gave recall {:.3}", ef, recall);
+    }
+
     #[test]
     fn hnsw_remove() {
         let mut index = HnswIndex::new();
@@ -644,6 +899,41 @@ mod tests {
         assert_eq!(results[0].0, id2);
     }
 
+    #[test]
+    fn hnsw_compact_after_bulk_delete() {
+        let dim = 32;
+        let mut index = HnswIndex::with_params(6, 100, 50, 8);
+        let mut vectors: Vec<(Uuid, Vec<f32>)> = Vec::new();
+        for i in 0..300 {
+            let id = Uuid::new_v4();
+            let v = random_vector(dim, i);
+            index.insert(id, &v);
+            vectors.push((id, v));
+        }
+
+        for (id, _) in vectors.drain(..200) {
+            assert!(index.remove(&id));
+        }
+        index.compact();
+
+        assert_eq!(index.len(), 100);
+        let true_max = index.nodes.values().map(|n| n.level).max().unwrap();
+        assert_eq!(index.max_level, true_max);
+        let ep = index.entry_point.expect("entry point after compaction");
+        assert_eq!(index.nodes[&ep].level, true_max);
+        for node in index.nodes.values() {
+            for layer in &node.neighbors {
+                assert!(layer.iter().all(|nb| index.nodes.contains_key(nb)));
+            }
+        }
+
+        // Surviving vectors are still their own nearest neighbors
+        for (id, v) in vectors.iter().take(20) {
+            let results = index.search(v, 1);
+            assert_eq!(results[0].0, *id);
+        }
+    }
+
     #[test]
     fn hnsw_performance_vs_bruteforce() {
         let dim = 128;
@@ -739,6 +1029,55 @@ mod tests {
         assert!((results[0].1 - 1.0).abs() < 1e-5);
     }
 
+    #[test]
+    fn hnsw_store_rebuilds_emptied_index() {
+        let mut store = HnswStore::new();
+        let dim = 32;
+        let mut ids = Vec::new();
+        for i in 0..(HNSW_THRESHOLD as u64 + 20) {
+            ids.push(store.insert(make_memory(random_vector(dim, i), "m")).unwrap());
+        }
+        let probe = random_vector(dim, 7);
+
+        // Deserializing a store whose index lost its nodes rebuilds it.
+        let mut json = serde_json::to_value(&store).unwrap();
+        json["index"]["nodes"] = serde_json::json!({});
+        json["index"]["entry_point"] = serde_json::Value::Null;
+        let restored: HnswStore = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.index.len(), restored.count());
+        assert_eq!(restored.search(&probe, 1).unwrap()[0].0, ids[7]);
+
+        // Explicit rebuild after the index is emptied in place.
+        store.index.clear();
+        assert!(store.search(&probe, 1).unwrap().is_empty());
+        store.rebuild_index();
+        assert_eq!(store.index.len(), ids.len());
+        assert_eq!(store.search(&probe, 1).unwrap()[0].0, ids[7]);
+    }
+
+    #[test]
+    fn hnsw_store_reindex_follows_updated_vector() {
+        let mut store = HnswStore::new();
+        let ids: Vec<Uuid> = (0..150)
+            .map(|i| store.insert(make_memory(random_vector(64, i), &format!("m{i}"))).unwrap())
+            .collect();
+        let target = ids[42];
+        let old_v = random_vector(64, 42);
+        let new_v = random_vector(64, 10_000);
+
+        store.get_mut(&target).unwrap().unwrap().set_vector(new_v.clone());
+        // Without reindexing the index still answers with the stale vector.
+        assert_eq!(store.search(&old_v, 1).unwrap()[0].0, target);
+
+        MemoryStore::reindex(&mut store, &target).unwrap();
+        assert_eq!(store.count(), 150);
+        let hit = store.search(&new_v, 1).unwrap()[0];
+        assert_eq!(hit.0, target);
+        assert!(hit.1 > 0.99);
+        assert_ne!(store.search(&old_v, 1).unwrap()[0].0, target);
+        assert!(matches!(store.reindex(&Uuid::new_v4()), Err(StoreError::NotFound(_))));
+    }
+
     #[test]
     fn hnsw_store_search_with_wave() {
         let mut store = HnswStore::new();
@@ -779,6 +1118,13 @@ mod tests {
         assert_eq!(ids.len(), 2);
         assert!(ids.contains(&id1));
         assert!(ids.contains(&id2));
+
+        let mut visited = Vec::new();
+        store.for_each_memory(&mut |m| visited.push(m.id)).unwrap();
+        visited.sort();
+        let mut expected = vec![id1, id2];
+        expected.sort();
+        assert_eq!(visited, expected);
     }
 
     #[test]
@@ -828,4 +1174,35 @@ mod tests {
             assert!(w[0].1 >= w[1].1, "Results should be sorted: {} >= {}", w[0].1, w[1].1);
         }
     }
+
+    #[test]
+    fn hnsw_store_batch_insert_matches_sequential() {
+        let dim = 64;
+        let memories: Vec<HyperMemory> = (0..200)
+            .map(|i| make_memory(random_vector(dim, i), &format!("mem_{i}")))
+            .collect();
+        let mut sequential = HnswStore::new();
+        for m in memories.clone() {
+            sequential.insert(m).unwrap();
+        }
+        let mut batched = HnswStore::new();
+        let ids = batched.insert_batch(memories.clone()).unwrap();
+        assert_eq!(ids, memories.iter().map(|m| m.id).collect::<Vec<_>>());
+
+        let mut seq_ids = sequential.all_ids().unwrap();
+        let mut batch_ids = batched.all_ids().unwrap();
+        seq_ids.sort();
+        batch_ids.sort();
+        assert_eq!(seq_ids, batch_ids);
+        for m in memories.iter().step_by(10) {
+            assert_eq!(batched.search(&m.dense_vector(), 1).unwrap()[0].0, m.id);
+            assert_eq!(sequential.search(&m.dense_vector(), 1).unwrap()[0].0, m.id);
+        }
+
+        let dup = memories[0].clone();
+        let fresh = make_memory(random_vector(dim, 999), "fresh");
+        let fresh_id = fresh.id;
+        assert!(matches!(batched.insert_batch(vec![fresh, dup]), Err(StoreError::DuplicateId(_))));
+        assert!(batched.get(&fresh_id).unwrap().is_none());
+    }
 }
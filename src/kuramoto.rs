@@ -8,7 +8,7 @@ use uuid::Uuid;
 
 use crate::memory::HyperMemory;
 use crate::store::MemoryEngine;
-use crate::wave::{cosine_similarity, normalize};
+use crate::wave::{cosine_similarity, normalize, wrap_phase};
 
 /// Kuramoto synchronization model for memory phase alignment.
 pub struct KuramotoSync {
@@ -20,8 +20,13 @@ pub struct KuramotoSync {
     pub steps: usize,
     /// Minimum similarity to consider memories as coupled
     pub coupling_threshold: f32,
+    /// Smallest group `find_clusters` reports as a cluster
+    pub min_cluster_size: usize,
 }
 
+/// Default for [`KuramotoSync::min_cluster_size`]: any synchronized pair counts.
+pub const DEFAULT_MIN_CLUSTER_SIZE: usize = 2;
+
 impl Default for KuramotoSync {
     fn default() -> Self {
         Self {
@@ -29,6 +34,7 @@ impl Default for KuramotoSync {
             dt: 0.1,
             steps: 10,
             coupling_threshold: 0.75,
+            min_cluster_size: DEFAULT_MIN_CLUSTER_SIZE,
         }
     }
 }
@@ -41,6 +47,51 @@ pub struct MemoryCluster {
     pub mean_phase: f32,
     pub coherence: f32,
     pub theme_vector: Vec<f32>,
+    /// Human-readable name: the most frequent significant word shared by
+    /// members, or the start of the strongest member's content.
+    pub label: String,
+}
+
+/// Words too common to name a cluster.
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "has",
+    "have", "he", "her", "his", "i", "in", "is", "it", "its", "me", "my", "of",
+    "on", "or", "our", "she", "so", "that", "the", "their", "them", "they", "this",
+    "to", "was", "we", "were", "with", "you", "your",
+];
+
+/// Characters of the strongest member used when no word recurs.
+const LABEL_FALLBACK_CHARS: usize = 40;
+
+/// Label a cluster by the significant word appearing in the most members
+/// (ties broken alphabetically). When no word is shared by at least two
+/// members, fall back to the highest-amplitude member's first 40 chars.
+pub fn cluster_label(members: &[&HyperMemory]) -> String {
+    let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for m in members {
+        let mut words: Vec<String> = m.content
+            .split(|c: char| !c.is_alphanumeric())
+            .map(|w| w.to_lowercase())
+            .filter(|w| w.len() > 2 && !STOPWORDS.contains(&w.as_str()))
+            .collect();
+        words.sort();
+        words.dedup();
+        for w in words {
+            *counts.entry(w).or_insert(0) += 1;
+        }
+    }
+
+    let best = counts.into_iter()
+        .filter(|(_, n)| *n >= 2)
+        .max_by(|(wa, na), (wb, nb)| na.cmp(nb).then_with(|| wb.cmp(wa)));
+    if let Some((word, _)) = best {
+        return word;
+    }
+
+    members.iter()
+        .max_by(|a, b| a.amplitude.partial_cmp(&b.amplitude).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|m| m.content.chars().take(LABEL_FALLBACK_CHARS).collect())
+        .unwrap_or_default()
 }
 
 /// Report from a sync_cluster operation.
@@ -51,6 +102,25 @@ pub struct SyncReport {
     pub final_order: f32,
     pub steps_taken: usize,
     pub converged: bool,
+    /// Order parameter before the first step followed by one entry per step.
+    pub order_trajectory: Vec<f32>,
+    /// Step at which the order parameter settled, if it did.
+    pub converged_at: Option<usize>,
+}
+
+impl SyncReport {
+    /// Report for clusters too small to synchronize.
+    fn trivial(n: usize) -> Self {
+        Self {
+            memories_synced: n,
+            initial_order: 1.0,
+            final_order: 1.0,
+            steps_taken: 0,
+            converged: true,
+            order_trajectory: Vec::new(),
+            converged_at: Some(0),
+        }
+    }
 }
 
 impl KuramotoSync {
@@ -65,26 +135,115 @@ impl KuramotoSync {
         ((sum_cos / n).powi(2) + (sum_sin / n).powi(2)).sqrt()
     }
 
+    /// Phase-locking value of a pair: the two-oscillator order parameter
+    /// |½(e^(iθa) + e^(iθb))| = |cos((θa − θb)/2)|. 1 when the memories are
+    /// in phase, 0 when they are in anti-phase.
+    pub fn phase_locking_value(&self, a: &HyperMemory, b: &HyperMemory) -> f32 {
+        ((a.phase - b.phase) / 2.0).cos().abs()
+    }
+
     /// Run Kuramoto integration on a cluster of memories.
     ///
+    /// Each memory's `frequency` (its category band from frequency-class
+    /// assignment) is its natural frequency ωᵢ, so memories from different
+    /// bands drift apart unless coupling outweighs the frequency gap.
     /// Updates each memory's phase in-place and returns a sync report.
     pub fn sync_cluster(&self, memories: &mut [&mut HyperMemory]) -> SyncReport {
         let n = memories.len();
         if n < 2 {
-            return SyncReport {
-                memories_synced: n,
-                initial_order: 1.0,
-                final_order: 1.0,
-                steps_taken: 0,
-                converged: true,
-            };
+            return SyncReport::trivial(n);
+        }
+
+        let weights = self.coupling_weights(memories);
+        let initial_order = self.order_parameter_mut(memories);
+        let mut trajectory = vec![initial_order];
+        let mut prev_order = initial_order;
+
+        for step in 0..self.steps {
+            self.integrate_step(memories, &weights);
+
+            // Check convergence
+            let current_order = self.order_parameter_mut(memories);
+            trajectory.push(current_order);
+            if (current_order - prev_order).abs() < 1e-6 && step > 0 {
+                return SyncReport {
+                    memories_synced: n,
+                    initial_order,
+                    final_order: current_order,
+                    steps_taken: step + 1,
+                    converged: true,
+                    order_trajectory: trajectory,
+                    converged_at: Some(step + 1),
+                };
+            }
+            prev_order = current_order;
         }
 
-        // Compute pairwise coupling weights
+        let final_order = self.order_parameter_mut(memories);
+
+        SyncReport {
+            memories_synced: n,
+            initial_order,
+            final_order,
+            steps_taken: self.steps,
+            converged: false,
+            order_trajectory: trajectory,
+            converged_at: None,
+        }
+    }
+
+    /// Run exactly `steps` Kuramoto iterations on a cluster, recording the
+    /// order parameter after every step.
+    ///
+    /// Unlike `sync_cluster` this never stops early: `converged_at` records the
+    /// first step whose order changed by less than 1e-3, and the trajectory
+    /// (initial order followed by one entry per step) covers the full run.
+    pub fn sync_cluster_iter(&self, memories: &mut [&mut HyperMemory], steps: usize) -> SyncReport {
+        let n = memories.len();
+        if n < 2 {
+            return SyncReport::trivial(n);
+        }
+
+        let weights = self.coupling_weights(memories);
+        let initial_order = self.order_parameter_mut(memories);
+        let mut trajectory = Vec::with_capacity(steps + 1);
+        trajectory.push(initial_order);
+        let mut converged_at = None;
+
+        for step in 0..steps {
+            self.integrate_step(memories, &weights);
+            let current_order = self.order_parameter_mut(memories);
+            let prev_order = trajectory[trajectory.len() - 1];
+            if converged_at.is_none() && (current_order - prev_order).abs() < 1e-3 {
+                converged_at = Some(step + 1);
+            }
+            trajectory.push(current_order);
+        }
+
+        SyncReport {
+            memories_synced: n,
+            initial_order,
+            final_order: trajectory[trajectory.len() - 1],
+            steps_taken: steps,
+            converged: converged_at.is_some(),
+            order_trajectory: trajectory,
+            converged_at,
+        }
+    }
+
+    /// Order parameter over a mutable cluster slice.
+    fn order_parameter_mut(&self, memories: &[&mut HyperMemory]) -> f32 {
+        let refs: Vec<&HyperMemory> = memories.iter().map(|m| &**m).collect();
+        self.order_parameter(&refs)
+    }
+
+    /// Pairwise coupling weights: similarity above threshold, boosted for skip-linked pairs.
+    fn coupling_weights(&self, memories: &[&mut HyperMemory]) -> Vec<Vec<f32>> {
+        let n = memories.len();
         let mut weights = vec![vec![0.0f32; n]; n];
         for i in 0..n {
             for j in (i + 1)..n {
-                let sim = cosine_similarity(&memories[i].vector, &memories[j].vector);
+                let sim = memories[i].similarity_to(memories[j]);
                 if sim > self.coupling_threshold {
                     let mut w = sim;
                     // Boost for skip-linked pairs
@@ -105,64 +264,30 @@ impl KuramotoSync {
                 }
             }
         }
+        weights
+    }
 
-        let initial_order = {
-            let refs: Vec<&HyperMemory> = memories.iter().map(|m| &**m).collect();
-            self.order_parameter(&refs)
-        };
-
+    /// One Euler step of θ̇ᵢ = ωᵢ + (K/N)Σ wᵢⱼ sin(θⱼ - θᵢ), with ωᵢ = `frequency`.
+    fn integrate_step(&self, memories: &mut [&mut HyperMemory], weights: &[Vec<f32>]) {
+        let n = memories.len();
         let nf = n as f32;
-        let mut prev_order = initial_order;
+        let phases: Vec<f32> = memories.iter().map(|m| m.phase).collect();
+        let freqs: Vec<f32> = memories.iter().map(|m| m.frequency).collect();
 
-        for step in 0..self.steps {
-            // Compute phase deltas
-            let phases: Vec<f32> = memories.iter().map(|m| m.phase).collect();
-            let freqs: Vec<f32> = memories.iter().map(|m| m.frequency).collect();
-
-            let mut dphi = vec![0.0f32; n];
-            for i in 0..n {
-                let mut coupling_sum = 0.0f32;
-                for j in 0..n {
-                    if i != j {
-                        coupling_sum += weights[i][j] * (phases[j] - phases[i]).sin();
-                    }
+        let mut dphi = vec![0.0f32; n];
+        for i in 0..n {
+            let mut coupling_sum = 0.0f32;
+            for j in 0..n {
+                if i != j {
+                    coupling_sum += weights[i][j] * (phases[j] - phases[i]).sin();
                 }
-                dphi[i] = freqs[i] + (self.coupling_strength / nf) * coupling_sum;
-            }
-
-            // Euler integration
-            for i in 0..n {
-                memories[i].phase += dphi[i] * self.dt;
-            }
-
-            // Check convergence
-            let current_order = {
-                let refs: Vec<&HyperMemory> = memories.iter().map(|m| &**m).collect();
-                self.order_parameter(&refs)
-            };
-            if (current_order - prev_order).abs() < 1e-6 && step > 0 {
-                return SyncReport {
-                    memories_synced: n,
-                    initial_order,
-                    final_order: current_order,
-                    steps_taken: step + 1,
-                    converged: true,
-                };
             }
-            prev_order = current_order;
+            dphi[i] = freqs[i] + (self.coupling_strength / nf) * coupling_sum;
         }
 
-        let final_order = {
-            let refs: Vec<&HyperMemory> = memories.iter().map(|m| &**m).collect();
-            self.order_parameter(&refs)
-        };
-
-        SyncReport {
-            memories_synced: n,
-            initial_order,
-            final_order,
-            steps_taken: self.steps,
-            converged: false,
+        // Euler integration
+        for i in 0..n {
+            memories[i].phase = wrap_phase(memories[i].phase + dphi[i] * self.dt);
         }
     }
 
@@ -192,7 +317,7 @@ impl KuramotoSync {
             let mut adj: Vec<Vec<usize>> = vec![vec![]; n];
             for (i, &idx_i) in component_indices.iter().enumerate() {
                 for (j, &idx_j) in component_indices.iter().enumerate().skip(i + 1) {
-                    let sim = cosine_similarity(&all_memories[idx_i].vector, &all_memories[idx_j].vector);
+                    let sim = all_memories[idx_i].similarity_to(all_memories[idx_j]);
                     if sim > threshold {
                         adj[i].push(j);
                         adj[j].push(i);
@@ -237,16 +362,35 @@ impl KuramotoSync {
         vec![component_indices.to_vec()]
     }
 
+    /// `find_synchronized_clusters` with the configured `min_cluster_size`.
+    pub fn find_clusters(&self, engine: &MemoryEngine) -> Vec<MemoryCluster> {
+        self.find_synchronized_clusters(engine, self.min_cluster_size)
+    }
+
+    /// `find_synchronized_clusters_in` with the configured `min_cluster_size`.
+    pub fn find_clusters_in(&self, memories: &[&HyperMemory]) -> Vec<MemoryCluster> {
+        self.find_synchronized_clusters_in(memories, self.min_cluster_size)
+    }
+
     /// Find groups of memories that have phase-locked (order parameter > 0.7).
     pub fn find_synchronized_clusters(
         &self,
         engine: &MemoryEngine,
         min_cluster_size: usize,
     ) -> Vec<MemoryCluster> {
-        let all = match engine.store.all_memories() {
-            Ok(mems) => mems,
-            Err(_) => return vec![],
-        };
+        match engine.store.all_memories() {
+            Ok(all) => self.find_synchronized_clusters_in(&all, min_cluster_size),
+            Err(_) => vec![],
+        }
+    }
+
+    /// `find_synchronized_clusters` over an explicit set of memories.
+    pub fn find_synchronized_clusters_in(
+        &self,
+        memories: &[&HyperMemory],
+        min_cluster_size: usize,
+    ) -> Vec<MemoryCluster> {
+        let all = memories.to_vec();
         let n = all.len();
         if n < min_cluster_size {
             return vec![];
@@ -256,7 +400,7 @@ impl KuramotoSync {
         let mut adj: Vec<Vec<usize>> = vec![vec![]; n];
         for i in 0..n {
             for j in (i + 1)..n {
-                let sim = cosine_similarity(&all[i].vector, &all[j].vector);
+                let sim = all[i].similarity_to(all[j]);
                 if sim > self.coupling_threshold {
                     adj[i].push(j);
                     adj[j].push(i);
@@ -325,10 +469,10 @@ impl KuramotoSync {
             let coherence = r; // r itself measures tightness of phase locking
 
             // Theme vector = bundle of all member vectors
-            let dim = cluster_mems[0].vector.len();
+            let dim = cluster_mems[0].dim();
             let mut theme = vec![0.0f32; dim];
             for m in &cluster_mems {
-                for (i, v) in m.vector.iter().enumerate() {
+                for (i, v) in m.dense_vector().iter().enumerate() {
                     theme[i] += v;
                 }
             }
@@ -340,11 +484,35 @@ impl KuramotoSync {
                 mean_phase,
                 coherence,
                 theme_vector: theme,
+                label: cluster_label(&cluster_mems),
             });
         }
 
         clusters
     }
+
+    /// The `top_k` synchronized clusters (of at least two memories) whose
+    /// centroid — the normalized `theme_vector` — is most similar to
+    /// `query_vec`, best first, paired with that cosine similarity. Answers
+    /// "what topics relate to X" rather than "which memories".
+    pub fn query_clusters(
+        &self,
+        engine: &MemoryEngine,
+        query_vec: &[f32],
+        top_k: usize,
+    ) -> Vec<(MemoryCluster, f32)> {
+        let mut scored: Vec<(MemoryCluster, f32)> = self
+            .find_clusters(engine)
+            .into_iter()
+            .map(|c| {
+                let score = cosine_similarity(query_vec, &c.theme_vector);
+                (c, score)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(top_k);
+        scored
+    }
 }
 
 #[cfg(test)]
@@ -424,6 +592,7 @@ mod tests {
             dt: 0.1,
             steps: 50,
             coupling_threshold: 0.3,
+            ..Default::default()
         };
         let dim = 100;
         let v = similar_vec(dim);
@@ -451,6 +620,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sync_cluster_iter_order_increases_monotonically() {
+        use rand::{Rng, SeedableRng};
+        use rand_chacha::ChaCha8Rng;
+
+        let sync = KuramotoSync {
+            coupling_strength: 2.0,
+            dt: 0.1,
+            steps: 10,
+            coupling_threshold: 0.3,
+            ..Default::default()
+        };
+        let v = similar_vec(100);
+        let mut rng = ChaCha8Rng::seed_from_u64(11);
+        let mut mems: Vec<HyperMemory> = (0..8)
+            .map(|i| make_memory_with_phase(v.clone(), &format!("m{i}"), rng.gen_range(0.0..(2.0 * PI))))
+            .collect();
+
+        let mut refs: Vec<&mut HyperMemory> = mems.iter_mut().collect();
+        let report = sync.sync_cluster_iter(&mut refs, 20);
+
+        assert_eq!(report.steps_taken, 20);
+        assert_eq!(report.order_trajectory.len(), 21);
+        for w in report.order_trajectory.windows(2) {
+            assert!(w[1] >= w[0] - 1e-6, "order should not decrease: {:?}", report.order_trajectory);
+        }
+        assert!(report.final_order > report.initial_order);
+        assert_eq!(report.final_order, report.order_trajectory[20]);
+        assert_eq!(report.converged, report.converged_at.is_some());
+    }
+
+    #[test]
+    fn frequency_bands_form_separate_phase_clusters() {
+        use rand::{Rng, SeedableRng};
+        use rand_chacha::ChaCha8Rng;
+
+        let sync = KuramotoSync {
+            coupling_strength: 0.5,
+            dt: 0.1,
+            steps: 10,
+            coupling_threshold: 0.3,
+            ..Default::default()
+        };
+        let v = similar_vec(100);
+        let run = |bass: f32, soprano: f32| {
+            let mut rng = ChaCha8Rng::seed_from_u64(5);
+            let mut mems: Vec<HyperMemory> = (0..8)
+                .map(|i| {
+                    let mut m = make_memory_with_phase(v.clone(), &format!("m{i}"), rng.gen_range(0.0..1.0));
+                    m.frequency = if i < 4 { bass } else { soprano };
+                    m
+                })
+                .collect();
+            let mut refs: Vec<&mut HyperMemory> = mems.iter_mut().collect();
+            let report = sync.sync_cluster_iter(&mut refs, 300);
+            (mems, report)
+        };
+
+        // Bass (knowledge) and soprano (experience) bands.
+        let (mems, report) = run(0.7, 2.1);
+        let bass: Vec<&HyperMemory> = mems[..4].iter().collect();
+        let soprano: Vec<&HyperMemory> = mems[4..].iter().collect();
+        assert!(sync.order_parameter(&bass) > 0.95, "bass band should lock internally");
+        assert!(sync.order_parameter(&soprano) > 0.95, "soprano band should lock internally");
+        let late_min = report.order_trajectory[200..].iter().cloned().fold(f32::INFINITY, f32::min);
+        assert!(late_min < 0.5, "bands should not collapse into one cluster, min r={}", late_min);
+
+        // Control: a single band collapses to one phase cluster.
+        let (_, single) = run(0.7, 0.7);
+        let single_min = single.order_trajectory[200..].iter().cloned().fold(f32::INFINITY, f32::min);
+        assert!(single_min > 0.95, "one band should fully synchronize, min r={}", single_min);
+    }
+
     #[test]
     fn skip_linked_memories_sync_faster() {
         let dim = 100;
@@ -462,6 +704,7 @@ mod tests {
             dt: 0.1,
             steps: 20,
             coupling_threshold: 0.3,
+            ..Default::default()
         };
 
         let mut m1a = make_memory_with_phase(v.clone(), "a", 0.0);
@@ -534,6 +777,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn query_clusters_ranks_nearest_topic_first() {
+        let mut engine = make_engine();
+        let dim = 10_000;
+        let (va, vb) = (similar_vec(dim), orthogonal_vec(dim));
+        let mut group_b = Vec::new();
+        for i in 0..3 {
+            engine.store.insert(make_memory_with_phase(va.clone(), &format!("group_a_{}", i), 0.1)).unwrap();
+            group_b.push(engine.store.insert(make_memory_with_phase(vb.clone(), &format!("group_b_{}", i), 0.2)).unwrap());
+        }
+
+        // Mostly topic B with a little of topic A.
+        let mut query: Vec<f32> = vb.iter().zip(&va).map(|(b, a)| 0.9 * b + 0.1 * a).collect();
+        crate::wave::normalize(&mut query);
+        let ranked = KuramotoSync::default().query_clusters(&engine, &query, 2);
+
+        assert_eq!(ranked.len(), 2);
+        let mut top_ids = ranked[0].0.memory_ids.clone();
+        top_ids.sort();
+        group_b.sort();
+        assert_eq!(top_ids, group_b);
+        assert!(ranked[0].1 > ranked[1].1);
+        assert!(ranked[0].0.order_parameter > 0.7);
+        assert_eq!(KuramotoSync::default().query_clusters(&engine, &query, 1).len(), 1);
+    }
+
+    #[test]
+    fn cluster_labels_name_dominant_topic() {
+        let mut engine = make_engine();
+        let dim = 10_000;
+        let cats = ["the cat chased a mouse", "my cat sleeps all day", "feeding the cat at noon"];
+        let dogs = ["the dog barked loudly", "walking the dog in rain", "a dog fetched the ball"];
+        for text in cats {
+            let mut m = HyperMemory::new(similar_vec(dim), text.to_string());
+            m.phase = 0.1;
+            engine.store.insert(m).unwrap();
+        }
+        for text in dogs {
+            let mut m = HyperMemory::new(orthogonal_vec(dim), text.to_string());
+            m.phase = 0.2;
+            engine.store.insert(m).unwrap();
+        }
+
+        let clusters = KuramotoSync::default().find_synchronized_clusters(&engine, 2);
+        let mut labels: Vec<&str> = clusters.iter().map(|c| c.label.as_str()).collect();
+        labels.sort();
+        assert_eq!(labels, vec!["cat", "dog"]);
+
+        let strict = KuramotoSync { min_cluster_size: 4, ..Default::default() };
+        assert!(strict.find_clusters(&engine).is_empty());
+        assert_eq!(strict.find_synchronized_clusters(&engine, 3).len(), 2);
+    }
+
+    #[test]
+    fn cluster_label_falls_back_to_strongest_member() {
+        let weak = HyperMemory::new(vec![1.0], "alpha beta".to_string());
+        let mut strong = HyperMemory::new(vec![1.0], "gamma delta epsilon zeta eta theta iota kappa".to_string());
+        strong.amplitude = 2.0;
+        assert_eq!(cluster_label(&[&weak, &strong]), "gamma delta epsilon zeta eta theta iota ");
+    }
+
     #[test]
     fn spectral_splitting_breaks_large_components() {
         let mut engine = make_engine();
@@ -8,6 +8,7 @@
 //! - Skip links (HyperConnections) for associative recall
 //! - Temporal layering for memory consolidation
 
+pub mod binary;
 pub mod bridge;
 pub mod hnsw;
 pub mod migration;
@@ -15,12 +16,15 @@ pub mod observe;
 pub mod openclaw;
 pub mod codebook;
 pub mod consolidation;
+pub mod cross_modal;
 pub mod rhythm;
 pub mod encoding;
 pub mod kuramoto;
 pub mod memory;
+pub mod mmap_store;
 pub mod persistence;
 pub mod skip_link;
+pub mod shared_store;
 pub mod store;
 pub mod wave;
 pub mod geometry;
@@ -51,26 +55,30 @@ pub mod paradox;
 pub mod queen;
 
 // Re-export key types
-pub use codebook::Codebook;
-pub use memory::HyperMemory;
+pub use binary::BitVector;
+pub use codebook::{Codebook, CollisionStats};
+pub use memory::{HyperMemory, HyperVector, MemorySource};
 pub use skip_link::SkipLink;
-pub use wave::{WaveParams, compute_strength, cosine_similarity, normalize};
-pub use store::{MemoryStore, InMemoryStore, MemoryEngine, StoreError, EngineError, QueryResult, phi_span_score};
-pub use encoding::{EncodingPipeline, TextEncoder, SimpleHashEncoder, EncodingError};
-pub use kuramoto::{KuramotoSync, MemoryCluster, SyncReport};
-pub use bridge::{ConsciousnessBridge, ConsciousnessLevel, ConsciousnessState, PhiReport, ResonanceReport};
-pub use consolidation::{ConsolidationEngine, ConsolidationReport, DreamState};
+pub use wave::{WaveParams, SparseVector, SimilarityMetric, compute_strength, cosine_similarity, similarity, sparse_cosine_similarity, sparse_dense_cosine_similarity, normalize, bind, unbind, permute, wrap_phase};
+pub use store::{MemoryStore, InMemoryStore, MemoryEngine, EngineSnapshot, StoreError, EngineError, QueryResult, ScoredResult, phi_span_score};
+pub use shared_store::SharedStore;
+pub use encoding::{EncodingPipeline, TextEncoder, SimpleHashEncoder, EncodingError, EncoderStats};
+pub use kuramoto::{KuramotoSync, MemoryCluster, SyncReport, DEFAULT_MIN_CLUSTER_SIZE};
+pub use bridge::{ConsciousnessBridge, ConsciousnessLevel, ConsciousnessState, PhiReport, ResonanceReport, shannon_entropy, variance_proxy};
+pub use consolidation::{ConsolidationEngine, ConsolidationPreview, ConsolidationReport, DreamState};
+pub use cross_modal::{CrossModalBridge, CaptionAnchor};
 pub use rhythm::{RhythmEngine, RhythmState, Signal as RhythmSignal};
 pub use migration::{KannakaDbMigrator, MigrationReport, MigrationError};
-pub use persistence::{DiskStore, PersistenceError, MemorySnapshot, SnapshotMetadata};
+pub use persistence::{DiskStore, JsonlImport, NormPolicy, PersistenceError, MemorySnapshot, SnapshotMetadata, VectorCodec, PackedVector};
 pub use hnsw::{HnswIndex, HnswStore};
-pub use observe::{MemoryIntrospector, SystemReport, TopologyReport, WaveReport, ClusterReport, ClusterInfo, HealthCheck, LinkInfo, MemoryInfo, ConsciousnessSnapshot};
+pub use mmap_store::MmapStore;
+pub use observe::{MemoryIntrospector, SystemReport, TopologyReport, SpanReport, WaveReport, ClusterReport, ClusterInfo, HealthCheck, LinkInfo, MemoryInfo, ConsciousnessSnapshot};
 pub use working_memory::{WorkingMemory, ConversationTurn, SessionState, TaskItem, TaskStatus};
 pub use geometry::{
     CliffordElement, Z4Element, Z3Element, SgaElement, 
-    ClassComponents, MemoryCoordinates,
+    ClassComponents, MemoryCoordinates, Taxonomy, TaxonomyCategory, TaxonomyError, GeoTransform,
     transform_r, transform_d, transform_t, transform_m,
-    lift, project, classify_memory, geometric_similarity, fano_related,
+    lift, project, categorize_text, classify_memory, stable_content_hash, geometric_similarity, fano_related, fano_bind,
     cross_product, is_fano_line, FANO_LINES, EPSILON
 };
 pub use xi_operator::{
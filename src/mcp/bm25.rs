@@ -1,10 +1,65 @@
 //! Simple BM25 implementation for keyword search
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
 use uuid::Uuid;
 
+/// Common English words dropped by `DefaultTokenizer`.
+pub const ENGLISH_STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "but", "by", "for", "from", "has", "have",
+    "he", "her", "his", "i", "if", "in", "into", "is", "it", "its", "of", "on", "or",
+    "our", "she", "so", "that", "the", "their", "them", "then", "there", "these", "they",
+    "this", "to", "was", "we", "were", "what", "when", "which", "who", "will", "with", "you",
+];
+
+/// Splits text into index terms. Used for both documents and queries.
+pub trait Tokenizer: Send + Sync {
+    fn tokenize(&self, text: &str) -> Vec<String>;
+}
+
+/// Lowercases, splits on non-alphanumeric characters and drops stopwords
+/// (`ENGLISH_STOPWORDS` unless built with `with_stopwords`).
 #[derive(Debug, Clone)]
+pub struct DefaultTokenizer {
+    stopwords: HashSet<String>,
+}
+
+impl Default for DefaultTokenizer {
+    fn default() -> Self {
+        Self::with_stopwords(ENGLISH_STOPWORDS.iter().copied())
+    }
+}
+
+impl DefaultTokenizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A tokenizer dropping `stopwords` instead of the English list (pass an
+    /// empty iterator to keep every term).
+    pub fn with_stopwords<I, S>(stopwords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self { stopwords: stopwords.into_iter().map(|w| w.as_ref().to_lowercase()).collect() }
+    }
+}
+
+impl Tokenizer for DefaultTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|s| !s.is_empty() && !self.stopwords.contains(*s))
+            .map(|s| s.to_string())
+            .collect()
+    }
+}
+
+#[derive(Clone)]
 pub struct Bm25Index {
+    tokenizer: Arc<dyn Tokenizer>,
     documents: HashMap<Uuid, String>,
     term_frequencies: HashMap<Uuid, HashMap<String, usize>>,
     document_frequencies: HashMap<String, usize>,
@@ -13,6 +68,15 @@ pub struct Bm25Index {
     average_length: f32,
 }
 
+impl fmt::Debug for Bm25Index {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bm25Index")
+            .field("total_documents", &self.total_documents)
+            .field("average_length", &self.average_length)
+            .finish_non_exhaustive()
+    }
+}
+
 impl Default for Bm25Index {
     fn default() -> Self {
         Self::new()
@@ -21,7 +85,13 @@ impl Default for Bm25Index {
 
 impl Bm25Index {
     pub fn new() -> Self {
+        Self::with_tokenizer(DefaultTokenizer::new())
+    }
+
+    /// An empty index that splits documents and queries with `tokenizer`.
+    pub fn with_tokenizer(tokenizer: impl Tokenizer + 'static) -> Self {
         Self {
+            tokenizer: Arc::new(tokenizer),
             documents: HashMap::new(),
             term_frequencies: HashMap::new(),
             document_frequencies: HashMap::new(),
@@ -32,7 +102,7 @@ impl Bm25Index {
     }
 
     pub fn add_document(&mut self, id: Uuid, text: &str) {
-        let tokens = tokenize(text);
+        let tokens = self.tokenizer.tokenize(text);
         let mut term_freq = HashMap::new();
         
         // Count term frequencies
@@ -76,7 +146,7 @@ impl Bm25Index {
             return Vec::new();
         }
 
-        let query_tokens = tokenize(query);
+        let query_tokens = self.tokenizer.tokenize(query);
         let mut scores = HashMap::new();
 
         for (doc_id, tf) in &self.term_frequencies {
@@ -146,14 +216,6 @@ impl Bm25Index {
     }
 }
 
-fn tokenize(text: &str) -> Vec<String> {
-    text.to_lowercase()
-        .split(|c: char| c.is_whitespace() || c.is_ascii_punctuation())
-        .filter(|s| !s.is_empty())
-        .map(|s| s.to_string())
-        .collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,4 +234,26 @@ mod tests {
         assert!(results[0].1 > 0.0);
         assert!(results[1].1 > 0.0);
     }
+
+    #[test]
+    fn stopword_removal_lets_rare_terms_win() {
+        let docs = [
+            (Uuid::new_v4(), "the cat and the hat and the bat"),
+            (Uuid::new_v4(), "zebra stripes grazing quietly near water tonight"),
+            (Uuid::new_v4(), "dog"),
+            (Uuid::new_v4(), "bird"),
+        ];
+        let mut plain = Bm25Index::with_tokenizer(DefaultTokenizer::with_stopwords(Vec::<&str>::new()));
+        let mut filtered = Bm25Index::new();
+        for (id, text) in docs {
+            plain.add_document(id, text);
+            filtered.add_document(id, text);
+        }
+
+        let query = "the zebra";
+        assert_eq!(plain.search(query, 1)[0].0, docs[0].0);
+        let results = filtered.search(query, 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, docs[1].0);
+    }
 }
\ No newline at end of file
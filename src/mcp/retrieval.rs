@@ -3,17 +3,37 @@
 use std::collections::HashMap;
 use uuid::Uuid;
 
+#[derive(Debug, thiserror::Error)]
+pub enum FusionError {
+    #[error("Weight count mismatch: {lists} result lists but {weights} weights")]
+    WeightCountMismatch { lists: usize, weights: usize },
+}
+
 /// Combine multiple ranked lists using Reciprocal Rank Fusion
 pub fn rrf_fuse(results: &[Vec<(Uuid, f32)>], k: f32) -> Vec<(Uuid, f32)> {
-    if results.is_empty() {
-        return Vec::new();
+    let weights = vec![1.0; results.len()];
+    rrf_fuse_weighted(results, k, &weights).unwrap_or_default()
+}
+
+/// Reciprocal Rank Fusion with a per-source weight applied to each list's
+/// reciprocal-rank contribution. `weights[i]` scales `lists[i]`.
+pub fn rrf_fuse_weighted(
+    lists: &[Vec<(Uuid, f32)>],
+    k: f32,
+    weights: &[f32],
+) -> Result<Vec<(Uuid, f32)>, FusionError> {
+    if lists.len() != weights.len() {
+        return Err(FusionError::WeightCountMismatch {
+            lists: lists.len(),
+            weights: weights.len(),
+        });
     }
 
     let mut scores = HashMap::new();
     
-    for result_list in results {
+    for (result_list, weight) in lists.iter().zip(weights) {
         for (rank, (id, _score)) in result_list.iter().enumerate() {
-            let rrf_score = 1.0 / (k + (rank + 1) as f32);
+            let rrf_score = weight / (k + (rank + 1) as f32);
             *scores.entry(*id).or_insert(0.0) += rrf_score;
         }
     }
@@ -21,7 +41,7 @@ pub fn rrf_fuse(results: &[Vec<(Uuid, f32)>], k: f32) -> Vec<(Uuid, f32)> {
     // Convert to sorted vector
     let mut combined: Vec<_> = scores.into_iter().collect();
     combined.sort_by(|a, b| b.1.total_cmp(&a.1));
-    combined
+    Ok(combined)
 }
 
 /// Weighted fusion where different result lists have different importance
@@ -149,6 +169,32 @@ mod tests {
         assert_eq!(fused[0].0, id1);
     }
 
+    #[test]
+    fn test_rrf_fuse_weighted_downweights_keyword() {
+        let semantic_top = Uuid::new_v4();
+        let keyword_top = Uuid::new_v4();
+
+        // Keyword agrees with semantic's runner-up, so with equal weights the
+        // keyword favourite accumulates enough rank mass to win.
+        let semantic = vec![(semantic_top, 0.9), (keyword_top, 0.8)];
+        let keyword = vec![(keyword_top, 7.5), (Uuid::new_v4(), 3.1)];
+        let recency = Vec::new();
+        let lists = vec![semantic, keyword, recency];
+
+        let equal = rrf_fuse(&lists, 1.0);
+        assert_eq!(equal[0].0, keyword_top);
+
+        let weighted = rrf_fuse_weighted(&lists, 1.0, &[1.0, 0.2, 0.4]).unwrap();
+        assert_eq!(weighted[0].0, semantic_top);
+    }
+
+    #[test]
+    fn test_rrf_fuse_weighted_rejects_length_mismatch() {
+        let lists = vec![vec![(Uuid::new_v4(), 1.0)], vec![]];
+        let err = rrf_fuse_weighted(&lists, 60.0, &[1.0]);
+        assert!(matches!(err, Err(FusionError::WeightCountMismatch { lists: 2, weights: 1 })));
+    }
+
     #[test]
     fn test_ndcg() {
         let id1 = Uuid::new_v4();
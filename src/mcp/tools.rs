@@ -4,11 +4,36 @@ use serde_json::{json, Value};
 use uuid::Uuid;
 use chrono::Utc;
 
-use crate::openclaw::KannakaMemorySystem;
+use crate::openclaw::{KannakaMemorySystem, RecallResult};
 use super::bm25::Bm25Index;
-use super::retrieval::rrf_fuse;
+use super::retrieval::rrf_fuse_weighted;
 use super::protocol::{ToolDefinition, ToolResult, ToolCallParams};
 
+/// RRF weights for the `search` tool's semantic, keyword, and recency arms.
+const SEARCH_FUSION_WEIGHTS: [f32; 3] = [1.0, 0.7, 0.4];
+/// Age window of the `search` tool's recency arm.
+const SEARCH_RECENCY_WINDOW_HOURS: i64 = 24;
+
+/// Semantic recall results plus the fused `(id, score)` ranking.
+type FusedSearch = (Vec<RecallResult>, Vec<(Uuid, f32)>);
+
+/// Round to 4 decimals for JSON output.
+fn round4(x: f64) -> f64 {
+    (x * 10_000.0).round() / 10_000.0
+}
+
+/// Recall results as a JSON array of `{id, content, similarity, strength, layer, age_hours}`.
+fn recall_results_json(results: &[RecallResult]) -> Value {
+    Value::Array(results.iter().map(|r| json!({
+        "id": r.id.to_string(),
+        "content": r.content,
+        "similarity": round4(r.similarity as f64),
+        "strength": round4(r.strength as f64),
+        "layer": r.layer,
+        "age_hours": round4(r.age_hours),
+    })).collect())
+}
+
 pub struct McpToolSet {
     system: KannakaMemorySystem,
     bm25_index: Bm25Index,
@@ -73,7 +98,8 @@ impl McpToolSet {
                     "type": "object",
                     "properties": {
                         "query": {"type": "string", "description": "Search query"},
-                        "limit": {"type": "integer", "description": "Maximum results", "default": 10}
+                        "limit": {"type": "integer", "description": "Maximum results", "default": 10},
+                        "return_json": {"type": "boolean", "description": "Return a JSON array of {id, content, similarity, strength, layer, age_hours}", "default": false}
                     },
                     "required": ["query"]
                 }),
@@ -214,6 +240,19 @@ impl McpToolSet {
                     "required": ["signal"]
                 }),
             },
+            ToolDefinition {
+                name: "rhythm_history".to_string(),
+                description: "Summarize recent rhythm signals: counts per signal type and average interval".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "window_secs": {
+                            "type": "integer",
+                            "description": "Look-back window in seconds (default: 3600)"
+                        }
+                    }
+                }),
+            },
             ToolDefinition {
                 name: "context_save".to_string(),
                 description: "Checkpoint current working memory / session state".to_string(),
@@ -285,9 +324,35 @@ impl McpToolSet {
                     }
                 }),
             },
+            ToolDefinition {
+                name: "observe_json".to_string(),
+                description: "Full introspection report as structured JSON for dashboards".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            },
+            ToolDefinition {
+                name: "recompute_geometry".to_string(),
+                description: "Fill in missing geometry and Xi signatures (e.g. after a migration or import)".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {}
+                }),
+            },
         ]
     }
 
+    /// Async entry point: embedding for `store_memory` doesn't block the
+    /// runtime; every other tool runs through `handle_tool_call`.
+    pub async fn handle_tool_call_async(&mut self, params: ToolCallParams) -> ToolResult {
+        if params.name == "store_memory" {
+            let args = params.arguments.unwrap_or(json!({}));
+            return self.store_memory_async(&args).await;
+        }
+        self.handle_tool_call(params)
+    }
+
     pub fn handle_tool_call(&mut self, params: ToolCallParams) -> ToolResult {
         let args = params.arguments.unwrap_or(json!({}));
         
@@ -305,6 +370,7 @@ impl McpToolSet {
             "hallucinate" => self.hallucinate(&args),
             "rhythm_status" => self.rhythm_status(&args),
             "rhythm_signal" => self.rhythm_signal(&args),
+            "rhythm_history" => self.rhythm_history(&args),
             "context_save" => self.context_save(&args),
             "context_restore" => self.context_restore(&args),
             "context_turn" => self.context_turn(&args),
@@ -314,6 +380,8 @@ impl McpToolSet {
             #[cfg(feature = "audio")]
             "store_audio_memory" => self.store_audio_memory(&args),
             "observe" => self.observe(&args),
+            "observe_json" => self.observe_json(&args),
+            "recompute_geometry" => self.recompute_geometry(&args),
             _ => ToolResult::error(format!("Unknown tool: {}", params.name)),
         }
     }
@@ -324,7 +392,26 @@ impl McpToolSet {
             None => return ToolResult::error("Missing 'content' parameter".to_string()),
         };
 
-        match self.system.remember(content) {
+        let result = self.system.remember(content);
+        self.stored_memory_result(content, result)
+    }
+
+    async fn store_memory_async(&mut self, args: &Value) -> ToolResult {
+        let content = match args.get("content").and_then(|v| v.as_str()) {
+            Some(c) => c,
+            None => return ToolResult::error("Missing 'content' parameter".to_string()),
+        };
+
+        let result = self.system.remember_async(content).await;
+        self.stored_memory_result(content, result)
+    }
+
+    fn stored_memory_result(
+        &mut self,
+        content: &str,
+        result: Result<Uuid, crate::openclaw::SystemError>,
+    ) -> ToolResult {
+        match result {
             Ok(id) => {
                 // Add to BM25 index
                 self.bm25_index.add_document(id, content);
@@ -343,32 +430,20 @@ impl McpToolSet {
 
         let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
 
-        // Single recall gives us all data needed for semantic, recency, and display.
-        let all_recalled = match self.system.recall(query, limit * 3) {
-            Ok(results) => results,
+        let (mut all_recalled, fused) = match self.fused_search(query, limit) {
+            Ok(r) => r,
             Err(e) => return ToolResult::error(format!("Search failed: {}", e)),
         };
 
-        // Build per-strategy result lists from the single recall.
-        let semantic_results: Vec<(Uuid, f32)> = all_recalled.iter()
-            .map(|r| (r.id, r.similarity))
+        // Re-order recalled results to match fused ranking; hits found only
+        // by the keyword or recency arm are described without a similarity.
+        let detailed_results: Vec<_> = fused.iter()
+            .take(limit)
+            .filter_map(|(id, _)| match all_recalled.iter().position(|r| r.id == *id) {
+                Some(i) => Some(all_recalled.swap_remove(i)),
+                None => self.system.recall_result(id),
+            })
             .collect();
-        let keyword_results = self.bm25_index.search(query, limit * 2);
-        let recent_results: Vec<(Uuid, f32)> = all_recalled.iter()
-            .filter(|r| r.age_hours < 24.0)
-            .map(|r| (r.id, (1.0 / (r.age_hours + 1.0)) as f32))
-            .collect();
-
-        // Fuse results using RRF
-        let all_results = vec![semantic_results, keyword_results, recent_results];
-        let fused = rrf_fuse(&all_results, 60.0);
-
-        // Re-order recalled results to match fused ranking.
-        let top_ids: Vec<Uuid> = fused.iter().take(limit).map(|(id, _)| *id).collect();
-        let mut detailed_results: Vec<_> = all_recalled.into_iter()
-            .filter(|r| top_ids.contains(&r.id))
-            .collect();
-        detailed_results.sort_by_key(|r| top_ids.iter().position(|id| *id == r.id).unwrap_or(usize::MAX));
 
         let mut response = String::new();
         response.push_str(&format!("Found {} results:\n\n", detailed_results.len()));
@@ -383,6 +458,23 @@ impl McpToolSet {
         ToolResult::success(response)
     }
 
+    /// Run the semantic, keyword and recency arms independently and fuse them
+    /// with weighted RRF (semantic > keyword > recency). Returns the semantic
+    /// recall (for display) and the fused ranking.
+    fn fused_search(&mut self, query: &str, limit: usize) -> Result<FusedSearch, String> {
+        let recalled = self.system.recall(query, limit * 3).map_err(|e| e.to_string())?;
+        let semantic_results: Vec<(Uuid, f32)> = recalled.iter()
+            .map(|r| (r.id, r.similarity))
+            .collect();
+        let keyword_results = self.bm25_index.search(query, limit * 2);
+        let mut recent_results = self.system.engine.recent_ids(chrono::Duration::hours(SEARCH_RECENCY_WINDOW_HOURS));
+        recent_results.truncate(limit * 2);
+
+        let all_results = vec![semantic_results, keyword_results, recent_results];
+        let fused = rrf_fuse_weighted(&all_results, 60.0, &SEARCH_FUSION_WEIGHTS).map_err(|e| e.to_string())?;
+        Ok((recalled, fused))
+    }
+
     fn search_semantic(&mut self, args: &Value) -> ToolResult {
         let query = match args.get("query").and_then(|v| v.as_str()) {
             Some(q) => q,
@@ -390,8 +482,13 @@ impl McpToolSet {
         };
 
         let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(10) as usize;
+        let return_json = args.get("return_json").and_then(|v| v.as_bool()).unwrap_or(false);
 
         match self.system.recall(query, limit) {
+            Ok(results) if return_json => match serde_json::to_string(&recall_results_json(&results)) {
+                Ok(text) => ToolResult::success(text),
+                Err(e) => ToolResult::error(format!("Failed to serialize results: {}", e)),
+            },
             Ok(results) => {
                 let mut response = String::new();
                 response.push_str(&format!("Semantic search found {} results:\n\n", results.len()));
@@ -489,7 +586,7 @@ impl McpToolSet {
         } else {
             // Amplitude decay — reduce strength without removing
             match self.system.boost(&memory_id, decay_factor) {
-                Ok(()) => ToolResult::success(format!("Memory {} decayed to {:.0}% amplitude", memory_id, decay_factor * 100.0)),
+                Ok(_) => ToolResult::success(format!("Memory {} decayed to {:.0}% amplitude", memory_id, decay_factor * 100.0)),
                 Err(e) => ToolResult::error(format!("Failed to decay memory: {}", e)),
             }
         }
@@ -509,7 +606,9 @@ impl McpToolSet {
         let boost_factor = args.get("boost_factor").and_then(|v| v.as_f64()).unwrap_or(2.0);
 
         match self.system.boost(&memory_id, boost_factor) {
-            Ok(()) => ToolResult::success(format!("Memory {} boosted by {:.1}x", memory_id, boost_factor)),
+            Ok(amplitude) => ToolResult::success(format!(
+                "Memory {} boosted by {:.1}x (amplitude {:.3})", memory_id, boost_factor, amplitude
+            )),
             Err(e) => ToolResult::error(format!("Failed to boost: {}", e)),
         }
     }
@@ -717,6 +816,23 @@ impl McpToolSet {
         ))
     }
 
+    fn rhythm_history(&self, args: &Value) -> ToolResult {
+        let window_secs = args.get("window_secs").and_then(|v| v.as_i64()).unwrap_or(3600).max(0);
+        let stats = self.system.rhythm_signal_stats(chrono::Duration::seconds(window_secs));
+
+        let mut counts: Vec<(&str, usize)> = stats.counts.iter().map(|(sig, n)| (sig.name(), *n)).collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        let mut response = format!("Rhythm signals in the last {}s: {}\n", window_secs, stats.total);
+        for (name, n) in counts {
+            response.push_str(&format!("- {}: {}\n", name, n));
+        }
+        match stats.avg_interval_secs {
+            Some(secs) => response.push_str(&format!("Average interval: {:.1}s", secs)),
+            None => response.push_str("Average interval: n/a"),
+        }
+        ToolResult::success(response)
+    }
+
     fn context_save(&mut self, _args: &Value) -> ToolResult {
         match self.system.context_checkpoint() {
             Ok(()) => ToolResult::success("Working memory checkpointed".to_string()),
@@ -846,6 +962,22 @@ impl McpToolSet {
         }
     }
 
+    fn observe_json(&mut self, _args: &Value) -> ToolResult {
+        let report = self.system.observe();
+        let json = crate::observe::MemoryIntrospector::to_json(&report);
+        match serde_json::to_string_pretty(&json) {
+            Ok(text) => ToolResult::success(text),
+            Err(e) => ToolResult::error(format!("Failed to serialize report: {}", e)),
+        }
+    }
+
+    fn recompute_geometry(&mut self, _args: &Value) -> ToolResult {
+        match self.system.recompute_geometry() {
+            Ok(updated) => ToolResult::success(format!("Recomputed geometry for {} memories", updated)),
+            Err(e) => ToolResult::error(format!("Recompute failed: {}", e)),
+        }
+    }
+
     fn observe(&mut self, args: &Value) -> ToolResult {
         let _include_topology = args.get("include_topology").and_then(|v| v.as_bool()).unwrap_or(true);
         let _include_waves = args.get("include_waves").and_then(|v| v.as_bool()).unwrap_or(true);
@@ -891,4 +1023,82 @@ impl McpToolSet {
 
         ToolResult::success(response)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoding::EncoderConfig;
+
+    #[test]
+    fn search_semantic_return_json_is_structured() {
+        let dir = std::env::temp_dir().join(format!("kannaka_mcptest_json_{}", Uuid::new_v4()));
+        let system = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let mut tools = McpToolSet::new(system, String::new(), String::new());
+        tools.system.remember("the lighthouse keeper logs every storm").unwrap();
+        tools.system.remember("storms arrive from the west in autumn").unwrap();
+
+        let result = tools.handle_tool_call(ToolCallParams {
+            name: "search_semantic".to_string(),
+            arguments: Some(json!({"query": "storm log", "limit": 5, "return_json": true})),
+        });
+        assert_eq!(result.is_error, None);
+        let parsed: Value = serde_json::from_str(&result.content[0].text).unwrap();
+        let rows = parsed.as_array().unwrap();
+        assert!(!rows.is_empty());
+        for row in rows {
+            for key in ["id", "content", "similarity", "strength", "layer", "age_hours"] {
+                assert!(row.get(key).is_some(), "missing {}", key);
+            }
+            assert!(Uuid::parse_str(row["id"].as_str().unwrap()).is_ok());
+            let sim = row["similarity"].as_f64().unwrap();
+            assert_eq!(sim, round4(sim));
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn recompute_geometry_tool_repairs_imported_memories() {
+        let dir = std::env::temp_dir().join(format!("kannaka_mcptest_geometry_{}", Uuid::new_v4()));
+        let system = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let mut tools = McpToolSet::new(system, String::new(), String::new());
+        let mut ids = Vec::new();
+        for text in ["imported note about the harbour", "imported note about the storm"] {
+            let vector = tools.system.engine.pipeline.encode_text(text).unwrap();
+            let mem = crate::memory::HyperMemory::new(vector, text.to_string());
+            assert!(mem.geometry.is_none() && mem.xi_signature.is_empty());
+            ids.push(tools.system.engine.store.insert(mem).unwrap());
+        }
+
+        let result = tools.handle_tool_call(ToolCallParams {
+            name: "recompute_geometry".to_string(),
+            arguments: None,
+        });
+        assert_eq!(result.is_error, None);
+        assert!(result.content[0].text.contains("2 memories"), "{}", result.content[0].text);
+        for id in &ids {
+            let mem = tools.system.engine.get_memory(id).unwrap().unwrap();
+            assert!(mem.geometry.is_some());
+            assert!(!mem.xi_signature.is_empty());
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn search_recency_arm_surfaces_recent_irrelevant_memory() {
+        let dir = std::env::temp_dir().join(format!("kannaka_mcptest_recency_{}", Uuid::new_v4()));
+        let system = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let mut tools = McpToolSet::new(system, String::new(), String::new());
+        for i in 0..6 {
+            let id = tools.system.remember(&format!("storm log entry {} from the lighthouse", i)).unwrap();
+            tools.system.engine.get_memory_mut(&id).unwrap().unwrap().created_at =
+                Utc::now() - chrono::Duration::days(10);
+        }
+        let recent = tools.system.remember("renew the parking permit").unwrap();
+
+        let (recalled, fused) = tools.fused_search("lighthouse storm log", 2).unwrap();
+        assert!(recalled.iter().all(|r| r.id != recent));
+        assert!(fused.iter().any(|(id, _)| *id == recent));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
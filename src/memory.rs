@@ -1,10 +1,13 @@
-use chrono::{DateTime, Utc};
+use std::borrow::Cow;
+
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::binary::BitVector;
 use crate::geometry::MemoryCoordinates;
 use crate::skip_link::SkipLink;
-use crate::wave::WaveParams;
+use crate::wave::{cosine_similarity, normalize, similarity, sparse_cosine_similarity, sparse_dense_cosine_similarity, SimilarityMetric, SparseVector, WaveParams};
 
 // ---------------------------------------------------------------------------
 // Collective memory types (ADR-0011)
@@ -28,6 +31,207 @@ fn default_agent() -> String {
     "local".to_string()
 }
 
+/// Importance assigned to memories stored without an explicit priority.
+pub const DEFAULT_IMPORTANCE: f32 = 0.5;
+
+fn default_importance() -> f32 {
+    DEFAULT_IMPORTANCE
+}
+
+/// `initial_amplitude` of memories from before it was recorded.
+pub(crate) fn default_initial_amplitude() -> f32 {
+    WaveParams::default().amplitude
+}
+
+/// Where a memory came from, for auditing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum MemorySource {
+    /// Text stored directly (`remember`). Also assumed for older snapshots.
+    #[default]
+    UserText,
+    /// Decoded from an audio file.
+    Audio,
+    /// Imported from a legacy kannaka.db.
+    Migration,
+    /// Synthesized from parent memories while dreaming.
+    Hallucination,
+    /// Consolidation summary bundling a layer.
+    Bundle,
+    /// Any other producer, named by the caller.
+    External(String),
+}
+
+/// Serialize `Option<Duration>` as whole seconds (chrono durations have no serde impl).
+pub(crate) mod ttl_seconds {
+    use chrono::Duration;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(ttl: &Option<Duration>, s: S) -> Result<S::Ok, S::Error> {
+        ttl.map(|d| d.num_seconds()).serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Duration>, D::Error> {
+        Ok(Option::<i64>::deserialize(d)?.map(Duration::seconds))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HyperVector
+// ---------------------------------------------------------------------------
+
+/// How a memory holds its hypervector. Dense is the default; sparse and
+/// binary trade precision or generality for size. Read it through
+/// `dense`/`similarity` rather than matching, so every form is handled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HyperVector {
+    Dense(Vec<f32>),
+    /// Nonzero components only (see `EncodingPipeline::with_sparse_output`).
+    Sparse(SparseVector),
+    /// Bit-packed ±1 components (see `EncodingPipeline::with_binary_output`).
+    Binary(BitVector),
+}
+
+impl Default for HyperVector {
+    fn default() -> Self {
+        HyperVector::Dense(Vec::new())
+    }
+}
+
+impl From<Vec<f32>> for HyperVector {
+    fn from(v: Vec<f32>) -> Self {
+        HyperVector::Dense(v)
+    }
+}
+
+impl HyperVector {
+    pub fn dim(&self) -> usize {
+        match self {
+            HyperVector::Dense(v) => v.len(),
+            HyperVector::Sparse(s) => s.dim,
+            HyperVector::Binary(b) => b.dim(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dim() == 0
+    }
+
+    /// Dense form: borrowed for dense vectors, expanded otherwise (binary
+    /// components become ±1/√dim, so the result has unit length).
+    pub fn dense(&self) -> Cow<'_, [f32]> {
+        match self {
+            HyperVector::Dense(v) => Cow::Borrowed(v),
+            HyperVector::Sparse(s) => Cow::Owned(s.to_dense()),
+            HyperVector::Binary(b) => Cow::Owned(b.to_dense()),
+        }
+    }
+
+    /// Euclidean length. Binary vectors are unit length by construction.
+    pub fn norm(&self) -> f32 {
+        match self {
+            HyperVector::Dense(v) => v.iter().map(|x| x * x).sum::<f32>().sqrt(),
+            HyperVector::Sparse(s) => s.norm(),
+            HyperVector::Binary(b) => if b.dim() == 0 { 0.0 } else { 1.0 },
+        }
+    }
+
+    /// Scale to unit length in place, keeping the form.
+    pub fn normalize(&mut self) {
+        match self {
+            HyperVector::Dense(v) => normalize(v),
+            HyperVector::Sparse(s) => s.normalize(),
+            HyperVector::Binary(_) => {}
+        }
+    }
+
+    /// The components, if stored dense.
+    pub fn as_dense(&self) -> Option<&[f32]> {
+        match self {
+            HyperVector::Dense(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Cosine similarity with a dense `query`. Binary vectors use normalized
+    /// Hamming similarity against the signs of `query`.
+    pub fn similarity(&self, query: &[f32]) -> f32 {
+        match self {
+            HyperVector::Dense(v) => cosine_similarity(query, v),
+            HyperVector::Sparse(s) => sparse_dense_cosine_similarity(s, query),
+            HyperVector::Binary(b) => b.similarity(&BitVector::from_signs(query)),
+        }
+    }
+
+    /// Cosine similarity between two hypervectors in any form. A binary side
+    /// compares Hamming-wise against the other side's signs.
+    pub fn similarity_to(&self, other: &HyperVector) -> f32 {
+        match (self, other) {
+            (HyperVector::Sparse(a), HyperVector::Sparse(b)) => sparse_cosine_similarity(a, b),
+            (HyperVector::Binary(a), HyperVector::Binary(b)) => a.similarity(b),
+            (HyperVector::Dense(d), v) | (v, HyperVector::Dense(d)) => v.similarity(d),
+            (HyperVector::Sparse(s), HyperVector::Binary(b))
+            | (HyperVector::Binary(b), HyperVector::Sparse(s)) => b.similarity(&BitVector::from_signs(&s.to_dense())),
+        }
+    }
+}
+
+/// Snapshots (bincode) tag every variant. Human-readable formats write a
+/// dense vector as a plain array, so JSON exports keep their old shape.
+impl Serialize for HyperVector {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match self {
+            HyperVector::Dense(v) if s.is_human_readable() => v.serialize(s),
+            HyperVector::Dense(v) => TaggedRef::Dense(v).serialize(s),
+            HyperVector::Sparse(v) => TaggedRef::Sparse(v).serialize(s),
+            HyperVector::Binary(v) => TaggedRef::Binary(v).serialize(s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HyperVector {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if d.is_human_readable() {
+            #[derive(Deserialize)]
+            #[serde(untagged)]
+            enum Readable {
+                Plain(Vec<f32>),
+                Tagged(TaggedVector),
+            }
+            Ok(match Readable::deserialize(d)? {
+                Readable::Plain(v) => HyperVector::Dense(v),
+                Readable::Tagged(t) => t.into(),
+            })
+        } else {
+            TaggedVector::deserialize(d).map(Into::into)
+        }
+    }
+}
+
+/// Serialized form of `HyperVector`; `TaggedVector` reads it back.
+#[derive(Serialize)]
+enum TaggedRef<'a> {
+    Dense(&'a [f32]),
+    Sparse(&'a SparseVector),
+    Binary(&'a BitVector),
+}
+
+#[derive(Deserialize)]
+enum TaggedVector {
+    Dense(Vec<f32>),
+    Sparse(SparseVector),
+    Binary(BitVector),
+}
+
+impl From<TaggedVector> for HyperVector {
+    fn from(v: TaggedVector) -> Self {
+        match v {
+            TaggedVector::Dense(v) => HyperVector::Dense(v),
+            TaggedVector::Sparse(s) => HyperVector::Sparse(s),
+            TaggedVector::Binary(b) => HyperVector::Binary(b),
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // HyperMemory
 // ---------------------------------------------------------------------------
@@ -36,8 +240,9 @@ fn default_agent() -> String {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HyperMemory {
     pub id: Uuid,
-    /// Hypervector representation (d=10,000)
-    pub vector: Vec<f32>,
+    /// Hypervector representation (d=10,000). Use `dense_vector`,
+    /// `similarity` or `vector_mut` instead of matching on the form.
+    pub vector: HyperVector,
     /// Wave amplitude
     pub amplitude: f32,
     /// Wave frequency
@@ -90,6 +295,28 @@ pub struct HyperMemory {
     /// Each retrieval adds energy to the wave function (EXP-003: f(x) term).
     #[serde(default)]
     pub retrieval_count: u32,
+    /// Time-to-live after `created_at`. Expired memories that were never
+    /// boosted are removed by `MemoryEngine::sweep_expired`.
+    #[serde(default, with = "ttl_seconds")]
+    pub ttl: Option<Duration>,
+    /// Amplitude the memory was stored with. `sweep_expired` spares expired
+    /// memories boosted above it.
+    #[serde(default = "default_initial_amplitude")]
+    pub initial_amplitude: f32,
+    /// Structured labels for filtering (e.g. `project:x`, `user:bob`).
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Priority in [0, 1]. Higher values slow decay, and memories above 0.8
+    /// are spared by destructive-interference pruning.
+    #[serde(default = "default_importance")]
+    pub importance: f32,
+    /// Which creation path produced this memory.
+    #[serde(default)]
+    pub source: MemorySource,
+    /// When this memory last appeared in recall results (`None` if never).
+    /// How often it did is `retrieval_count`.
+    #[serde(default)]
+    pub last_accessed: Option<DateTime<Utc>>,
 }
 
 impl HyperMemory {
@@ -98,7 +325,7 @@ impl HyperMemory {
         let wave = WaveParams::default();
         Self {
             id: Uuid::new_v4(),
-            vector,
+            vector: HyperVector::Dense(vector),
             amplitude: wave.amplitude,
             frequency: wave.frequency,
             phase: wave.phase,
@@ -118,9 +345,90 @@ impl HyperMemory {
             disputed: false,
             updated_at: None,
             retrieval_count: 0,
+            ttl: None,
+            initial_amplitude: wave.amplitude,
+            tags: Vec::new(),
+            importance: DEFAULT_IMPORTANCE,
+            source: MemorySource::UserText,
+            last_accessed: None,
+        }
+    }
+
+    /// Create a new memory holding its hypervector in sparse form.
+    pub fn new_sparse(vector: SparseVector, content: String) -> Self {
+        Self { vector: HyperVector::Sparse(vector), ..Self::new(Vec::new(), content) }
+    }
+
+    /// Create a new memory holding its hypervector as bipolar bits.
+    pub fn new_binary(vector: BitVector, content: String) -> Self {
+        Self { vector: HyperVector::Binary(vector), ..Self::new(Vec::new(), content) }
+    }
+
+    /// Cosine similarity between this memory's hypervector and `query`
+    /// (Hamming-based for binary memories, see `HyperVector::similarity`).
+    pub fn similarity(&self, query: &[f32]) -> f32 {
+        self.vector.similarity(query)
+    }
+
+    /// Cosine similarity between the hypervectors of two memories.
+    pub fn similarity_to(&self, other: &HyperMemory) -> f32 {
+        self.vector.similarity_to(&other.vector)
+    }
+
+    /// `similarity` under `metric`; cosine keeps the sparse and binary fast paths.
+    pub fn similarity_by(&self, metric: SimilarityMetric, query: &[f32]) -> f32 {
+        match metric {
+            SimilarityMetric::Cosine => self.similarity(query),
+            _ => similarity(metric, &self.dense_vector(), query),
+        }
+    }
+
+    /// The hypervector in dense form, expanded if stored sparse or binary.
+    pub fn dense_vector(&self) -> Cow<'_, [f32]> {
+        self.vector.dense()
+    }
+
+    /// Dimensionality of the hypervector, whatever its form.
+    pub fn dim(&self) -> usize {
+        self.vector.dim()
+    }
+
+    /// Replace the hypervector with a dense one.
+    pub fn set_vector(&mut self, vector: Vec<f32>) {
+        self.vector = HyperVector::Dense(vector);
+    }
+
+    /// Take the hypervector out in dense form, leaving an empty one behind.
+    pub fn take_dense_vector(&mut self) -> Vec<f32> {
+        match std::mem::take(&mut self.vector) {
+            HyperVector::Dense(v) => v,
+            other => other.dense().into_owned(),
+        }
+    }
+
+    /// Mutable dense view of the hypervector, densifying it first.
+    pub fn vector_mut(&mut self) -> &mut Vec<f32> {
+        self.densify();
+        match &mut self.vector {
+            HyperVector::Dense(v) => v,
+            _ => unreachable!("densify leaves a dense vector"),
+        }
+    }
+
+    /// Expand a sparse or binary hypervector into dense form, for stores
+    /// that index dense vectors.
+    pub fn densify(&mut self) {
+        if !matches!(self.vector, HyperVector::Dense(_)) {
+            let dense = self.take_dense_vector();
+            self.vector = HyperVector::Dense(dense);
         }
     }
 
+    /// Whether this memory's TTL has elapsed at `now`.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.ttl.is_some_and(|ttl| self.created_at + ttl < now)
+    }
+
     /// Mark this memory as modified (stamps `updated_at`).
     pub fn touch(&mut self) {
         self.updated_at = Some(Utc::now());
@@ -136,29 +444,33 @@ impl HyperMemory {
     }
 
     /// Compute effective strength at a given time:
-    /// S(t) = (A + retrieval_energy) · cos(2πft+φ) · e^(-λt)
+    /// S(t) = (A + retrieval_energy) · cos(2πft+φ) · e^(-λ't)
+    ///
+    /// where λ' = λ · (1 − 0.5·importance), so important memories fade slower.
     pub fn effective_strength(&self, now: DateTime<Utc>) -> f32 {
         let age = (now - self.created_at).num_milliseconds().max(0) as f64 / 1000.0;
-        crate::wave::compute_strength_with_retrieval(&self.wave_params(), age, self.retrieval_count)
+        let mut params = self.wave_params();
+        params.decay_rate *= 1.0 - 0.5 * self.importance.clamp(0.0, 1.0);
+        crate::wave::compute_strength_with_retrieval(&params, age, self.retrieval_count)
     }
 
     /// Record a retrieval event — called on search/recall to boost the f(x) term.
     pub fn record_retrieval(&mut self) {
         self.retrieval_count = self.retrieval_count.saturating_add(1);
+        self.last_accessed = Some(Utc::now());
         self.touch();
     }
 
     /// Compute the effective vector: S(t) · h
     pub fn effective_vector(&self, now: DateTime<Utc>) -> Vec<f32> {
         let s = self.effective_strength(now);
-        self.vector.iter().map(|&x| x * s).collect()
+        self.dense_vector().iter().map(|&x| x * s).collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Duration;
 
     #[test]
     fn effective_strength_diminishes() {
@@ -192,4 +504,53 @@ mod tests {
             assert!((x - 0.5 * s).abs() < 1e-4, "mismatch at index {}", i);
         }
     }
+
+    #[test]
+    fn importance_slows_decay() {
+        let mut low = HyperMemory::new(vec![1.0; 10], "low".into());
+        low.frequency = 0.0;
+        low.decay_rate = 1e-6;
+        low.importance = 0.1;
+        let mut high = low.clone();
+        high.importance = 0.9;
+
+        let week = low.created_at + Duration::days(7);
+        let (s_low, s_high) = (low.effective_strength(week), high.effective_strength(week));
+        assert!(s_high > s_low, "important memory should retain more: {} vs {}", s_high, s_low);
+    }
+
+    #[test]
+    fn sparse_memory_scores_like_its_dense_twin() {
+        let mut dense = vec![0.0f32; 64];
+        for i in (0..64).step_by(9) {
+            dense[i] = 1.0 + i as f32 / 10.0;
+        }
+        let sparse = HyperMemory::new_sparse(crate::wave::SparseVector::from_dense(&dense), "s".into());
+        let twin = HyperMemory::new(dense.clone(), "d".into());
+        assert!(matches!(sparse.vector, HyperVector::Sparse(_)));
+        assert_eq!(sparse.dim(), 64);
+
+        let query: Vec<f32> = (0..64).map(|i| (i % 5) as f32 - 2.0).collect();
+        assert!((sparse.similarity(&query) - twin.similarity(&query)).abs() < 1e-6);
+        assert_eq!(sparse.dense_vector().as_ref(), dense.as_slice());
+
+        let mut densified = sparse.clone();
+        densified.densify();
+        assert_eq!(densified.vector, HyperVector::Dense(dense));
+    }
+
+    #[test]
+    fn hypervector_json_keeps_dense_vectors_plain() {
+        let mem = HyperMemory::new(vec![0.5, -0.5], "d".into());
+        let json = serde_json::to_value(&mem).unwrap();
+        assert_eq!(json["vector"], serde_json::json!([0.5, -0.5]));
+        let back: HyperMemory = serde_json::from_value(json).unwrap();
+        assert_eq!(back.vector, mem.vector);
+
+        let bits = HyperMemory::new_binary(BitVector::from_signs(&[1.0, -1.0, 1.0]), "b".into());
+        let back: HyperMemory = serde_json::from_str(&serde_json::to_string(&bits).unwrap()).unwrap();
+        assert_eq!(back.vector, bits.vector);
+        let back: HyperMemory = bincode::deserialize(&bincode::serialize(&bits).unwrap()).unwrap();
+        assert_eq!(back.vector, bits.vector);
+    }
 }
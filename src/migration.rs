@@ -4,15 +4,17 @@
 //! from the old SQLite database and encodes each record as a HyperMemory with
 //! table-appropriate layer depth and wave parameters.
 
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::time::Instant;
 
 use chrono::Utc;
 use rusqlite::Connection;
 use thiserror::Error;
+use uuid::Uuid;
 
 use crate::encoding::EncodingPipeline;
-use crate::memory::HyperMemory;
+use crate::memory::{HyperMemory, MemorySource};
 use crate::store::MemoryEngine;
 
 // ---------------------------------------------------------------------------
@@ -54,10 +56,23 @@ pub struct MigrationReport {
     pub lessons_count: usize,
     pub total_migrated: usize,
     pub skip_links_created: usize,
+    /// Rows whose memory was already in the engine (from an earlier run).
+    pub skipped_existing: usize,
+    /// Last source row inserted or found already present. Pass it to
+    /// `migrate_resume_from` to continue after an interruption.
+    pub last_migrated: Option<SourceRow>,
     pub errors: Vec<String>,
     pub duration_ms: u64,
 }
 
+/// Position of a row in the legacy database: tables are migrated in a fixed
+/// order, and rows within a table by rowid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceRow {
+    pub table: String,
+    pub rowid: i64,
+}
+
 // ---------------------------------------------------------------------------
 // Table configuration
 // ---------------------------------------------------------------------------
@@ -69,6 +84,9 @@ struct TableConfig {
     frequency: f32,
 }
 
+/// Default rows per insert batch.
+const DEFAULT_BATCH_SIZE: usize = 256;
+
 const TABLES: &[TableConfig] = &[
     TableConfig { name: "working_memory", layer_depth: 0, amplitude: 0.8, frequency: 0.5 },
     TableConfig { name: "events",         layer_depth: 1, amplitude: 1.0, frequency: 0.1 },
@@ -77,6 +95,47 @@ const TABLES: &[TableConfig] = &[
     TableConfig { name: "lessons",        layer_depth: 3, amplitude: 1.5, frequency: 0.02 },
 ];
 
+/// 64-bit FNV-1a, stable across builds (unlike `DefaultHasher`).
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Deterministic memory id for a source row, so re-running a migration finds
+/// the memories an earlier run inserted. The text is mixed in so different
+/// databases don't collide on (table, rowid).
+fn source_id(table: &str, rowid: i64, text: &str) -> Uuid {
+    let mut bytes = [0u8; 16];
+    bytes[..8].copy_from_slice(&fnv1a(format!("{}:{}", table, rowid).as_bytes()).to_le_bytes());
+    bytes[8..].copy_from_slice(&fnv1a(text.as_bytes()).to_le_bytes());
+    uuid::Builder::from_custom_bytes(bytes).into_uuid()
+}
+
+/// The per-table counter in `report` for `table`.
+fn table_count<'a>(report: &'a mut MigrationReport, table: &str) -> &'a mut usize {
+    match table {
+        "working_memory" => &mut report.working_memory_count,
+        "events" => &mut report.events_count,
+        "entities" => &mut report.entities_count,
+        "relationships" => &mut report.relationships_count,
+        _ => &mut report.lessons_count,
+    }
+}
+
+/// Report line for a table that could not be read.
+fn table_error(table: &str, e: &MigrationError) -> String {
+    match e {
+        MigrationError::DatabaseError(msg) if msg.contains("no such table") => {
+            format!("table '{}' not found, skipped", table)
+        }
+        e => format!("error reading '{}': {}", table, e),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // KannakaDbMigrator
 // ---------------------------------------------------------------------------
@@ -84,6 +143,8 @@ const TABLES: &[TableConfig] = &[
 pub struct KannakaDbMigrator {
     db_path: PathBuf,
     pipeline: EncodingPipeline,
+    /// Rows encoded and inserted per `MemoryStore::insert_batch` call.
+    batch_size: usize,
 }
 
 impl KannakaDbMigrator {
@@ -91,9 +152,18 @@ impl KannakaDbMigrator {
         Self {
             db_path: db_path.into(),
             pipeline,
+            batch_size: DEFAULT_BATCH_SIZE,
         }
     }
 
+    /// Insert `batch_size` rows per batch (at least 1). Progress is reported
+    /// only after a row's whole batch is committed, so smaller batches give
+    /// finer-grained resume points.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
     /// Migrate all records from kannaka.db into HyperMemory objects.
     /// Returns the memories and a report. If the db doesn't exist, returns an empty report.
     pub fn migrate(&self) -> Result<(Vec<HyperMemory>, MigrationReport), MigrationError> {
@@ -111,25 +181,15 @@ impl KannakaDbMigrator {
         let mut report = MigrationReport::default();
 
         for table in TABLES {
-            match self.migrate_table(&conn, table) {
-                Ok(mems) => {
-                    let count = mems.len();
-                    match table.name {
-                        "working_memory" => report.working_memory_count = count,
-                        "events" => report.events_count = count,
-                        "entities" => report.entities_count = count,
-                        "relationships" => report.relationships_count = count,
-                        "lessons" => report.lessons_count = count,
-                        _ => {}
-                    }
+            match self.read_rows(&conn, table, None) {
+                Ok(rows) => {
+                    let mems: Vec<HyperMemory> = rows.iter()
+                        .filter_map(|(rowid, text)| self.encode_row(table, *rowid, text))
+                        .collect();
+                    *table_count(&mut report, table.name) = mems.len();
                     memories.extend(mems);
                 }
-                Err(MigrationError::DatabaseError(ref e)) if e.contains("no such table") => {
-                    report.errors.push(format!("table '{}' not found, skipped", table.name));
-                }
-                Err(e) => {
-                    report.errors.push(format!("error reading '{}': {}", table.name, e));
-                }
+                Err(e) => report.errors.push(table_error(table.name, &e)),
             }
         }
 
@@ -138,35 +198,135 @@ impl KannakaDbMigrator {
         Ok((memories, report))
     }
 
-    /// Migrate all records directly into a MemoryEngine, then run consolidation
-    /// to wire skip links between related migrated memories.
+    /// Migrate all records directly into a MemoryEngine, wiring skip links
+    /// between related migrated memories as they are inserted.
     pub fn migrate_into(&self, engine: &mut MemoryEngine) -> Result<MigrationReport, MigrationError> {
-        let (memories, mut report) = self.migrate()?;
+        self.migrate_into_with_progress(engine, |_, _| {})
+    }
+
+    /// Like `migrate_into`, calling `progress(done, total)` after each source
+    /// row. Rows whose memory is already in `engine` are skipped, so re-running
+    /// after an interruption is safe.
+    pub fn migrate_into_with_progress(
+        &self,
+        engine: &mut MemoryEngine,
+        progress: impl FnMut(usize, usize),
+    ) -> Result<MigrationReport, MigrationError> {
+        self.migrate_rows_into(engine, None, progress)
+    }
+
+    /// Continue a migration after `after` (typically `MigrationReport::last_migrated`
+    /// from an interrupted run), skipping every earlier row without reading it.
+    pub fn migrate_resume_from(
+        &self,
+        engine: &mut MemoryEngine,
+        after: &SourceRow,
+        progress: impl FnMut(usize, usize),
+    ) -> Result<MigrationReport, MigrationError> {
+        self.migrate_rows_into(engine, Some(after), progress)
+    }
+
+    fn migrate_rows_into(
+        &self,
+        engine: &mut MemoryEngine,
+        after: Option<&SourceRow>,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<MigrationReport, MigrationError> {
+        let start = Instant::now();
+        let mut report = MigrationReport::default();
+        if !self.db_path.exists() {
+            report.duration_ms = start.elapsed().as_millis() as u64;
+            return Ok(report);
+        }
+
+        let resume_table = match after {
+            Some(row) => Some(TABLES.iter().position(|t| t.name == row.table)
+                .ok_or_else(|| MigrationError::TableNotFound(row.table.clone()))?),
+            None => None,
+        };
 
+        let conn = Connection::open(&self.db_path)?;
+        let mut pending: Vec<(&TableConfig, i64, String)> = Vec::new();
+        for (index, table) in TABLES.iter().enumerate() {
+            let after_rowid = match (resume_table, after) {
+                (Some(t), _) if index < t => continue,
+                (Some(t), Some(row)) if index == t => Some(row.rowid),
+                _ => None,
+            };
+            match self.read_rows(&conn, table, after_rowid) {
+                Ok(rows) => pending.extend(rows.into_iter().map(|(rowid, text)| (table, rowid, text))),
+                Err(e) => report.errors.push(table_error(table.name, &e)),
+            }
+        }
+
+        // Insert in batches. A batch is fully inserted and linked before any
+        // of its rows is reported, so progress only ever marks committed rows.
+        // Links are created in source order, excluding memories later in the
+        // batch, so they match what row-by-row inserts would have produced.
+        let total = pending.len();
+        let mut done = 0usize;
         let mut link_count = 0usize;
-        for mem in memories {
-            let id = mem.id;
-            if let Err(e) = engine.store.insert(mem) {
+        for chunk in pending.chunks(self.batch_size) {
+            // Per row: whether it encoded, and the id if it is new to `engine`.
+            let mut rows: Vec<(&TableConfig, i64, bool, Option<Uuid>)> = Vec::with_capacity(chunk.len());
+            let mut batch: Vec<HyperMemory> = Vec::new();
+            for &(table, rowid, ref text) in chunk {
+                match self.encode_row(table, rowid, text) {
+                    None => rows.push((table, rowid, false, None)),
+                    Some(mem) if matches!(engine.store.get(&mem.id), Ok(Some(_))) => {
+                        report.skipped_existing += 1;
+                        rows.push((table, rowid, true, None));
+                    }
+                    Some(mem) => {
+                        rows.push((table, rowid, true, Some(mem.id)));
+                        batch.push(mem);
+                    }
+                }
+            }
+            if let Err(e) = engine.store.insert_batch(batch) {
                 report.errors.push(format!("insert error: {}", e));
-                continue;
             }
-            // Create skip links to existing memories
-            match engine.create_skip_links(&id) {
-                Ok(links) => link_count += links.len(),
-                Err(e) => report.errors.push(format!("skip link error: {}", e)),
+
+            let mut unlinked: HashSet<Uuid> = rows.iter().filter_map(|(_, _, _, id)| *id).collect();
+            let mut committed: Vec<Option<SourceRow>> = Vec::with_capacity(rows.len());
+            for (table, rowid, encoded, id) in rows {
+                if let Some(id) = id {
+                    unlinked.remove(&id);
+                    if !matches!(engine.store.get(&id), Ok(Some(_))) {
+                        committed.push(None);
+                        continue;
+                    }
+                    *table_count(&mut report, table.name) += 1;
+                    report.total_migrated += 1;
+                    match engine.create_skip_links_excluding(&id, &unlinked) {
+                        Ok(links) => link_count += links.len(),
+                        Err(e) => report.errors.push(format!("skip link error: {}", e)),
+                    }
+                }
+                committed.push(encoded.then(|| SourceRow { table: table.name.to_string(), rowid }));
+            }
+            for row in committed {
+                done += 1;
+                if row.is_some() {
+                    report.last_migrated = row;
+                }
+                progress(done, total);
             }
         }
-
         report.skip_links_created = link_count;
+        report.duration_ms = start.elapsed().as_millis() as u64;
         Ok(report)
     }
 
-    /// Read all rows from a single table and encode them as HyperMemory objects.
-    fn migrate_table(
+    /// Read `(rowid, text)` for each row of a table with non-empty text, in
+    /// rowid order, optionally only rows after `after_rowid`. The text joins
+    /// every text column as `col: value`.
+    fn read_rows(
         &self,
         conn: &Connection,
         config: &TableConfig,
-    ) -> Result<Vec<HyperMemory>, MigrationError> {
+        after_rowid: Option<i64>,
+    ) -> Result<Vec<(i64, String)>, MigrationError> {
         // Get column names for this table
         let columns = self.get_text_columns(conn, config.name)?;
         if columns.is_empty() {
@@ -174,17 +334,21 @@ impl KannakaDbMigrator {
         }
 
         let select_cols = columns.join(", ");
-        let sql = format!("SELECT {} FROM {}", select_cols, config.name);
+        let sql = format!(
+            "SELECT rowid, {} FROM {} WHERE rowid > ?1 ORDER BY rowid",
+            select_cols, config.name
+        );
 
         let mut stmt = conn.prepare(&sql)?;
-        let mut rows = stmt.query([])?;
-        let mut memories = Vec::new();
+        let mut rows = stmt.query([after_rowid.unwrap_or(i64::MIN)])?;
+        let mut out = Vec::new();
 
         while let Some(row) = rows.next()? {
+            let rowid: i64 = row.get(0)?;
             // Combine all text columns into one string
             let mut parts = Vec::new();
             for (i, col) in columns.iter().enumerate() {
-                if let Ok(val) = row.get::<_, String>(i) {
+                if let Ok(val) = row.get::<_, String>(i + 1) {
                     if !val.is_empty() {
                         parts.push(format!("{}: {}", col, val));
                     }
@@ -195,22 +359,22 @@ impl KannakaDbMigrator {
             if text.trim().is_empty() {
                 continue;
             }
-
-            match self.pipeline.encode_memory(&text, Utc::now()) {
-                Ok(mut mem) => {
-                    mem.layer_depth = config.layer_depth;
-                    mem.amplitude = config.amplitude;
-                    mem.frequency = config.frequency;
-                    memories.push(mem);
-                }
-                Err(_e) => {
-                    // Skip encoding errors for individual rows
-                    continue;
-                }
-            }
+            out.push((rowid, text));
         }
 
-        Ok(memories)
+        Ok(out)
+    }
+
+    /// Encode one source row with the table's wave parameters and its
+    /// deterministic id. Rows that fail to encode are skipped (`None`).
+    fn encode_row(&self, config: &TableConfig, rowid: i64, text: &str) -> Option<HyperMemory> {
+        let mut mem = self.pipeline.encode_memory(text, Utc::now()).ok()?;
+        mem.id = source_id(config.name, rowid, text);
+        mem.layer_depth = config.layer_depth;
+        mem.amplitude = config.amplitude;
+        mem.frequency = config.frequency;
+        mem.source = MemorySource::Migration;
+        Some(mem)
     }
 
     /// Get all TEXT/VARCHAR column names for a table.
@@ -390,6 +554,52 @@ mod tests {
         assert!(!results.is_empty(), "should recall at least one memory");
     }
 
+    #[test]
+    fn interrupted_migration_resumes_without_duplicates() {
+        let (_tmp, path) = create_test_db();
+        let migrator = KannakaDbMigrator::new(&path, make_pipeline()).with_batch_size(5);
+
+        let mut full = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let mut seen = Vec::new();
+        let report = migrator.migrate_into_with_progress(&mut full, |done, total| seen.push((done, total))).unwrap();
+        assert_eq!(report.total_migrated, 10);
+        assert_eq!(seen.len(), 10);
+        assert_eq!(seen.last(), Some(&(10, 10)));
+
+        // Interrupt at 50%: the callback runs after each row is committed.
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let interrupted = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            migrator.migrate_into_with_progress(&mut engine, |done, _| {
+                if done == 5 {
+                    panic!("interrupted");
+                }
+            })
+        }));
+        assert!(interrupted.is_err());
+        assert_eq!(engine.store.count(), 5);
+
+        // Rows 1-5 are working_memory, events and the first entity.
+        let after = SourceRow { table: "entities".to_string(), rowid: 1 };
+        let mut progress = Vec::new();
+        let resumed = migrator.migrate_resume_from(&mut engine, &after, |done, total| progress.push((done, total))).unwrap();
+        assert_eq!(resumed.total_migrated, 5);
+        assert_eq!(resumed.skipped_existing, 0);
+        assert_eq!(progress.last(), Some(&(5, 5)));
+        assert_eq!(resumed.last_migrated, Some(SourceRow { table: "lessons".to_string(), rowid: 3 }));
+
+        let mut ids = engine.store.all_ids().unwrap();
+        let mut expected = full.store.all_ids().unwrap();
+        ids.sort();
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        // A full re-run is a no-op.
+        let rerun = migrator.migrate_into(&mut engine).unwrap();
+        assert_eq!(rerun.total_migrated, 0);
+        assert_eq!(rerun.skipped_existing, 10);
+        assert_eq!(engine.store.count(), 10);
+    }
+
     #[test]
     fn missing_tables_dont_crash() {
         let tmp = NamedTempFile::new().unwrap();
@@ -0,0 +1,685 @@
+//! Memory-mapped vector store for memory sets too large to hold in RAM.
+//!
+//! `MmapStore` keeps each memory's metadata (content, links, wave params) in
+//! memory but its hypervector in a memory-mapped file, read on demand during
+//! similarity scoring. `get` and `all_memories` hand out references, so they
+//! page a memory's vector into a cache that lives only until the next
+//! mutating call (`insert`, `get_mut`, `delete`, `flush`); `search` and
+//! `for_each_memory` read each vector into a scratch copy instead, so scans
+//! cache nothing. `get_mut` pages the vector in so it can be edited, and
+//! `flush` writes it back.
+//!
+//! The vector file is a 16-byte header (`KMMV`, format version, dimension,
+//! generation) followed by fixed-size slots of little-endian `f32`s. Inserts
+//! append a slot; deletes leave a tombstoned slot behind until `compact`
+//! rewrites the file, which `delete` triggers once tombstones outnumber live
+//! memories. Metadata lives next to the vectors in `<path>.meta` and is
+//! persisted by `flush`, `compact` and on drop: inserts, edits and deletes
+//! since the last flush are lost if the process dies before one. `open`
+//! refuses a vector file that holds slots but has no metadata.
+//!
+//! `compact` writes the new vector file to `<path>.compact`, then the new
+//! metadata, then renames the vector file into place. Both carry the same
+//! generation number, so `open` can tell a half-finished compaction from a
+//! finished one and completes the rename.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Utc};
+use memmap2::MmapMut;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::memory::{HyperMemory, HyperVector};
+use crate::persistence::PersistenceError;
+use crate::store::{MemoryStore, StoreError};
+use crate::wave::cosine_similarity;
+
+const MAGIC: &[u8; 4] = b"KMMV";
+const FORMAT_VERSION: u32 = 1;
+const HEADER_LEN: usize = 16;
+/// Slots the vector file grows by at least, so inserts rarely remap.
+const MIN_GROWTH_SLOTS: usize = 16;
+
+/// Tombstones tolerated before `delete` compacts (compaction also waits
+/// until tombstones outnumber live memories).
+pub const COMPACT_MIN_TOMBSTONES: usize = 64;
+
+#[derive(Serialize, Deserialize)]
+struct MmapMeta {
+    version: u32,
+    dim: usize,
+    slots: usize,
+    /// Compactions so far; matches the vector file header.
+    generation: u32,
+    /// Memories with empty vectors, paired with their slot.
+    entries: Vec<(HyperMemory, usize)>,
+}
+
+/// A `MemoryStore` whose vectors live in a memory-mapped file.
+pub struct MmapStore {
+    path: PathBuf,
+    file: File,
+    map: MmapMut,
+    /// Vector dimension; 0 until the first insert.
+    dim: usize,
+    /// Slots in use, live or tombstoned.
+    slots: usize,
+    /// Compactions so far, stored in the header and the metadata.
+    generation: u32,
+    /// Metadata, with the vector present only while paged in by `get_mut`.
+    memories: HashMap<Uuid, HyperMemory>,
+    slot_of: HashMap<Uuid, usize>,
+    /// Full copies handed out by `get` and `all_memories`, filled on first
+    /// access and dropped by every mutating call.
+    loaded: HashMap<Uuid, OnceLock<HyperMemory>>,
+}
+
+impl MmapStore {
+    /// Open the store whose vectors are at `path`, creating an empty one if
+    /// the file doesn't exist.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, PersistenceError> {
+        let path = path.into();
+        if !path.exists() {
+            let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(&path)?;
+            file.set_len(HEADER_LEN as u64)?;
+            let mut store = Self::from_file(path, file, 0, 0, 0)?;
+            store.write_header();
+            store.save_meta()?;
+            return Ok(store);
+        }
+
+        let meta_path = meta_path(&path);
+        let meta: MmapMeta = if meta_path.exists() {
+            bincode::deserialize(&fs::read(&meta_path)?)?
+        } else if fs::metadata(&path)?.len() > HEADER_LEN as u64 {
+            // Without metadata the slots can't be attributed, and treating
+            // the store as empty would overwrite them.
+            return Err(PersistenceError::CorruptedFile(format!(
+                "{} holds vectors but {} is missing", path.display(), meta_path.display())));
+        } else {
+            MmapMeta { version: FORMAT_VERSION, dim: 0, slots: 0, generation: 0, entries: Vec::new() }
+        };
+        if meta.version != FORMAT_VERSION {
+            return Err(PersistenceError::UnsupportedVersion { supported: FORMAT_VERSION, got: meta.version });
+        }
+        // Metadata already describes a compacted file that never got renamed
+        // into place: finish the compaction.
+        let compacted = compact_path(&path);
+        if compacted.exists() {
+            if read_generation(&compacted)? == Some(meta.generation) {
+                fs::rename(&compacted, &path)?;
+            } else {
+                fs::remove_file(&compacted)?;
+            }
+        }
+
+        let file = OpenOptions::new().read(true).write(true).open(&path)?;
+        let mut store = Self::from_file(path, file, meta.dim, meta.slots, meta.generation)?;
+        store.check_header()?;
+        if store.map.len() < store.slot_offset(store.slots) {
+            return Err(PersistenceError::CorruptedFile(format!(
+                "{} holds fewer than the {} slots its metadata lists", store.path.display(), store.slots)));
+        }
+        for (memory, slot) in meta.entries {
+            store.slot_of.insert(memory.id, slot);
+            store.loaded.insert(memory.id, OnceLock::new());
+            store.memories.insert(memory.id, memory);
+        }
+        Ok(store)
+    }
+
+    fn from_file(path: PathBuf, file: File, dim: usize, slots: usize, generation: u32) -> Result<Self, PersistenceError> {
+        // SAFETY: the store owns the file for its lifetime and only mutates
+        // it through this map or `set_len` followed by a remap.
+        let map = unsafe { MmapMut::map_mut(&file)? };
+        Ok(Self {
+            path,
+            file,
+            map,
+            dim,
+            slots,
+            generation,
+            memories: HashMap::new(),
+            slot_of: HashMap::new(),
+            loaded: HashMap::new(),
+        })
+    }
+
+    /// Path of the vector file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Vector dimension, or 0 if nothing has been inserted yet.
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Slots left behind by deletes, reclaimed by `compact`.
+    pub fn tombstones(&self) -> usize {
+        self.slots - self.memories.len()
+    }
+
+    /// The vector of memory `id`, read from its slot (or the paged-in copy
+    /// if `get_mut` has it).
+    pub fn vector(&self, id: &Uuid) -> Option<Vec<f32>> {
+        let mem = self.memories.get(id)?;
+        if !mem.vector.is_empty() {
+            return Some(mem.dense_vector().into_owned());
+        }
+        let mut buf = Vec::with_capacity(self.dim);
+        self.read_slot(self.slot_of[id], &mut buf);
+        Some(buf)
+    }
+
+    /// An owned copy of memory `id` with its vector filled in.
+    pub fn load(&self, id: &Uuid) -> Option<HyperMemory> {
+        let mut mem = self.memories.get(id)?.clone();
+        mem.set_vector(self.vector(id)?);
+        Some(mem)
+    }
+
+    /// Memory `id` with its vector: the paged-in copy if `get_mut` has one,
+    /// otherwise a cached copy read from its slot on first access.
+    fn resolve(&self, id: &Uuid) -> Option<&HyperMemory> {
+        let mem = self.memories.get(id)?;
+        if !mem.vector.is_empty() {
+            return Some(mem);
+        }
+        Some(self.loaded[id].get_or_init(|| {
+            let mut buf = Vec::with_capacity(self.dim);
+            self.read_slot(self.slot_of[id], &mut buf);
+            let mut full = mem.clone();
+            full.set_vector(buf);
+            full
+        }))
+    }
+
+    /// Drop every copy handed out by `get` and `all_memories`.
+    fn clear_cache(&mut self) {
+        for cached in self.loaded.values_mut() {
+            cached.take();
+        }
+    }
+
+    /// Rewrite the vector file with only live slots and save metadata.
+    /// Returns how many tombstoned slots were reclaimed.
+    pub fn compact(&mut self) -> Result<usize, PersistenceError> {
+        let reclaimed = self.tombstones();
+        let mut ids: Vec<Uuid> = self.slot_of.keys().copied().collect();
+        ids.sort_by_key(|id| self.slot_of[id]);
+
+        let generation = self.generation.wrapping_add(1);
+        let tmp = compact_path(&self.path);
+        let mut data = Vec::with_capacity(self.slot_offset(ids.len()));
+        data.extend_from_slice(&self.map[..12]);
+        data.extend_from_slice(&generation.to_le_bytes());
+        let mut buf = Vec::with_capacity(self.dim);
+        for id in &ids {
+            self.read_slot(self.slot_of[id], &mut buf);
+            data.extend(buf.iter().flat_map(|x| x.to_le_bytes()));
+        }
+        fs::write(&tmp, &data)?;
+
+        // Metadata goes first: once it names the new generation, `open`
+        // finishes the rename below if we don't get to it.
+        let old_slots = std::mem::take(&mut self.slot_of);
+        self.slot_of = ids.iter().enumerate().map(|(slot, id)| (*id, slot)).collect();
+        let (old_len, old_generation) = (self.slots, self.generation);
+        self.slots = ids.len();
+        self.generation = generation;
+        if let Err(e) = self.save_meta() {
+            self.slot_of = old_slots;
+            self.slots = old_len;
+            self.generation = old_generation;
+            let _ = fs::remove_file(&tmp);
+            return Err(e);
+        }
+        fs::rename(&tmp, &self.path)?;
+
+        self.file = OpenOptions::new().read(true).write(true).open(&self.path)?;
+        // SAFETY: see `from_file`.
+        self.map = unsafe { MmapMut::map_mut(&self.file)? };
+        Ok(reclaimed)
+    }
+
+    fn slot_offset(&self, slot: usize) -> usize {
+        HEADER_LEN + slot * self.dim * 4
+    }
+
+    fn read_slot(&self, slot: usize, buf: &mut Vec<f32>) {
+        let start = self.slot_offset(slot);
+        buf.clear();
+        buf.extend(self.map[start..start + self.dim * 4]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])));
+    }
+
+    fn write_slot(&mut self, slot: usize, vector: &[f32]) {
+        let start = self.slot_offset(slot);
+        for (dst, x) in self.map[start..start + self.dim * 4].chunks_exact_mut(4).zip(vector) {
+            dst.copy_from_slice(&x.to_le_bytes());
+        }
+    }
+
+    fn write_header(&mut self) {
+        self.map[..4].copy_from_slice(MAGIC);
+        self.map[4..8].copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+        self.map[8..12].copy_from_slice(&(self.dim as u32).to_le_bytes());
+        self.map[12..16].copy_from_slice(&self.generation.to_le_bytes());
+    }
+
+    fn check_header(&self) -> Result<(), PersistenceError> {
+        let header = self.map.get(..HEADER_LEN)
+            .ok_or_else(|| PersistenceError::CorruptedFile("vector file shorter than its header".into()))?;
+        if &header[..4] != MAGIC {
+            return Err(PersistenceError::CorruptedFile(format!("{} is not an mmap vector file", self.path.display())));
+        }
+        let version = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        if version != FORMAT_VERSION {
+            return Err(PersistenceError::UnsupportedVersion { supported: FORMAT_VERSION, got: version });
+        }
+        let dim = u32::from_le_bytes([header[8], header[9], header[10], header[11]]) as usize;
+        if dim != self.dim && self.slots > 0 {
+            return Err(PersistenceError::CorruptedFile(format!(
+                "vector file dimension {} does not match metadata dimension {}", dim, self.dim)));
+        }
+        let generation = u32::from_le_bytes([header[12], header[13], header[14], header[15]]);
+        if generation != self.generation {
+            return Err(PersistenceError::CorruptedFile(format!(
+                "vector file generation {} does not match metadata generation {}", generation, self.generation)));
+        }
+        Ok(())
+    }
+
+    /// Make room for at least one more slot, growing the file geometrically.
+    fn reserve_slot(&mut self) -> Result<(), StoreError> {
+        if self.map.len() >= self.slot_offset(self.slots + 1) {
+            return Ok(());
+        }
+        let capacity = (self.slots * 2).max(self.slots + MIN_GROWTH_SLOTS);
+        self.map.flush().map_err(io_error)?;
+        self.file.set_len(self.slot_offset(capacity) as u64).map_err(io_error)?;
+        // SAFETY: see `from_file`.
+        self.map = unsafe { MmapMut::map_mut(&self.file).map_err(io_error)? };
+        Ok(())
+    }
+
+    fn save_meta(&self) -> Result<(), PersistenceError> {
+        let mut entries: Vec<(HyperMemory, usize)> = self.memories.values()
+            .map(|m| {
+                let slot = self.slot_of[&m.id];
+                let mut m = m.clone();
+                m.vector = HyperVector::default();
+                (m, slot)
+            })
+            .collect();
+        entries.sort_by_key(|(_, slot)| *slot);
+        let meta = MmapMeta {
+            version: FORMAT_VERSION,
+            dim: self.dim,
+            slots: self.slots,
+            generation: self.generation,
+            entries,
+        };
+        let meta_path = meta_path(&self.path);
+        let mut tmp = meta_path.clone().into_os_string();
+        tmp.push(".tmp");
+        fs::write(&tmp, bincode::serialize(&meta)?)?;
+        fs::rename(&tmp, &meta_path)?;
+        Ok(())
+    }
+
+    fn score_all(&self, query: &[f32], weight: impl Fn(&HyperMemory) -> f32) -> Vec<(Uuid, f32)> {
+        let mut buf = Vec::with_capacity(self.dim);
+        self.memories.values()
+            .map(|m| {
+                let sim = if m.vector.is_empty() {
+                    self.read_slot(self.slot_of[&m.id], &mut buf);
+                    cosine_similarity(query, &buf)
+                } else {
+                    m.similarity(query)
+                };
+                (m.id, sim * weight(m))
+            })
+            .collect()
+    }
+}
+
+fn meta_path(path: &Path) -> PathBuf {
+    let mut p: OsString = path.as_os_str().to_owned();
+    p.push(".meta");
+    PathBuf::from(p)
+}
+
+fn compact_path(path: &Path) -> PathBuf {
+    let mut p: OsString = path.as_os_str().to_owned();
+    p.push(".compact");
+    PathBuf::from(p)
+}
+
+/// The generation in the header of the vector file at `path`, or `None` if
+/// it is too short to have a header.
+fn read_generation(path: &Path) -> Result<Option<u32>, PersistenceError> {
+    let bytes = fs::read(path)?;
+    Ok(bytes.get(12..HEADER_LEN).map(|g| u32::from_le_bytes([g[0], g[1], g[2], g[3]])))
+}
+
+fn io_error(e: std::io::Error) -> StoreError {
+    StoreError::Other(e.to_string())
+}
+
+fn top_k(mut scored: Vec<(Uuid, f32)>, k: usize) -> Vec<(Uuid, f32)> {
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(k);
+    scored
+}
+
+impl MemoryStore for MmapStore {
+    fn insert(&mut self, mut memory: HyperMemory) -> Result<Uuid, StoreError> {
+        let id = memory.id;
+        if self.memories.contains_key(&id) {
+            return Err(StoreError::DuplicateId(id));
+        }
+        // Slots hold dense vectors.
+        memory.densify();
+        if memory.vector.is_empty() {
+            return Err(StoreError::Other(format!("memory {} has no vector", id)));
+        }
+        if self.dim == 0 {
+            self.dim = memory.dim();
+            self.write_header();
+        } else if memory.dim() != self.dim {
+            return Err(StoreError::Other(format!(
+                "vector dimension {} does not match store dimension {}", memory.dim(), self.dim)));
+        }
+
+        self.clear_cache();
+        self.reserve_slot()?;
+        let slot = self.slots;
+        self.write_slot(slot, &memory.take_dense_vector());
+        self.slots += 1;
+        self.slot_of.insert(id, slot);
+        self.loaded.insert(id, OnceLock::new());
+        self.memories.insert(id, memory);
+        Ok(id)
+    }
+
+    /// Pages the vector in on first access; the copy is kept until the next
+    /// mutating call.
+    fn get(&self, id: &Uuid) -> Result<Option<&HyperMemory>, StoreError> {
+        Ok(self.resolve(id))
+    }
+
+    /// Pages the memory's vector in so it can be edited; `flush` writes it back.
+    fn get_mut(&mut self, id: &Uuid) -> Result<Option<&mut HyperMemory>, StoreError> {
+        let Some(&slot) = self.slot_of.get(id) else {
+            return Ok(None);
+        };
+        // A copy handed out by `get` would go stale once this one is edited.
+        self.clear_cache();
+        if self.memories[id].vector.is_empty() {
+            let mut buf = Vec::with_capacity(self.dim);
+            self.read_slot(slot, &mut buf);
+            if let Some(mem) = self.memories.get_mut(id) {
+                mem.set_vector(buf);
+            }
+        }
+        Ok(self.memories.get_mut(id))
+    }
+
+    fn search(&self, query: &[f32], top_k_count: usize) -> Result<Vec<(Uuid, f32)>, StoreError> {
+        Ok(top_k(self.score_all(query, |_| 1.0), top_k_count))
+    }
+
+    fn search_with_wave(
+        &self,
+        query: &[f32],
+        top_k_count: usize,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<(Uuid, f32)>, StoreError> {
+        Ok(top_k(self.score_all(query, |m| m.effective_strength(now)), top_k_count))
+    }
+
+    /// Pages in every vector, cached until the next mutating call: the whole
+    /// store ends up in RAM. Prefer `for_each_memory` for scans.
+    fn all_memories(&self) -> Result<Vec<&HyperMemory>, StoreError> {
+        Ok(self.memories.keys().filter_map(|id| self.resolve(id)).collect())
+    }
+
+    fn all_ids(&self) -> Result<Vec<Uuid>, StoreError> {
+        Ok(self.memories.keys().copied().collect())
+    }
+
+    /// Reads each vector into a scratch copy rather than the cache, so a scan
+    /// holds a single vector in RAM at a time.
+    fn for_each_memory(&self, f: &mut dyn FnMut(&HyperMemory)) -> Result<(), StoreError> {
+        let mut buf = Vec::with_capacity(self.dim);
+        for mem in self.memories.values() {
+            if let Some(full) = self.loaded[&mem.id].get().or_else(|| (!mem.vector.is_empty()).then_some(mem)) {
+                f(full);
+                continue;
+            }
+            self.read_slot(self.slot_of[&mem.id], &mut buf);
+            let mut scratch = mem.clone();
+            scratch.set_vector(std::mem::take(&mut buf));
+            f(&scratch);
+            buf = scratch.take_dense_vector();
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, id: &Uuid) -> Result<bool, StoreError> {
+        if self.memories.remove(id).is_none() {
+            return Ok(false);
+        }
+        self.slot_of.remove(id);
+        self.loaded.remove(id);
+        self.clear_cache();
+        let tombstones = self.tombstones();
+        if tombstones >= COMPACT_MIN_TOMBSTONES && tombstones > self.memories.len() {
+            self.compact().map_err(|e| StoreError::Other(e.to_string()))?;
+        }
+        Ok(true)
+    }
+
+    fn count(&self) -> usize {
+        self.memories.len()
+    }
+
+    /// Write paged-in vectors back to their slots, sync the map and save
+    /// metadata. Returns how many vectors were written back.
+    fn flush(&mut self) -> Result<usize, StoreError> {
+        let paged_in: Vec<Uuid> = self.memories.values()
+            .filter(|m| !m.vector.is_empty())
+            .map(|m| m.id)
+            .collect();
+        if let Some(bad) = paged_in.iter().map(|id| &self.memories[id]).find(|m| m.dim() != self.dim) {
+            return Err(StoreError::Other(format!(
+                "memory {} vector dimension {} does not match store dimension {}", bad.id, bad.dim(), self.dim)));
+        }
+        for id in &paged_in {
+            let vector = self.memories.get_mut(id).expect("listed above").take_dense_vector();
+            self.write_slot(self.slot_of[id], &vector);
+        }
+        self.clear_cache();
+        self.map.flush().map_err(io_error)?;
+        self.save_meta().map_err(|e| StoreError::Other(e.to_string()))?;
+        Ok(paged_in.len())
+    }
+}
+
+/// Saves metadata and paged-in vectors, so a dropped store reopens intact.
+impl Drop for MmapStore {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            eprintln!("[mmap] flush on drop failed for {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("kannaka_mmap_{name}_{}.vec", Uuid::new_v4()))
+    }
+
+    fn cleanup(path: &Path) {
+        let _ = fs::remove_file(path);
+        let _ = fs::remove_file(meta_path(path));
+        let _ = fs::remove_file(compact_path(path));
+    }
+
+    fn unit(dim: usize, axis: usize) -> Vec<f32> {
+        let mut v = vec![0.0; dim];
+        v[axis] = 1.0;
+        v
+    }
+
+    #[test]
+    fn insert_get_search() {
+        let path = temp_path("search");
+        let mut store = MmapStore::open(&path).unwrap();
+        let ids: Vec<Uuid> = (0..20)
+            .map(|i| store.insert(HyperMemory::new(unit(32, i), format!("axis {}", i))).unwrap())
+            .collect();
+
+        assert_eq!(store.count(), 20);
+        assert_eq!(store.dim(), 32);
+        let mem = store.get(&ids[7]).unwrap().unwrap();
+        assert_eq!(mem.content, "axis 7");
+        assert_eq!(*mem.dense_vector(), unit(32, 7));
+        assert_eq!(store.vector(&ids[7]).unwrap(), unit(32, 7));
+        for mem in store.all_memories().unwrap() {
+            let axis = ids.iter().position(|id| *id == mem.id).unwrap();
+            assert_eq!(*mem.dense_vector(), unit(32, axis));
+        }
+        let mut scanned = 0;
+        store.for_each_memory(&mut |m| {
+            assert_eq!(m.dim(), 32);
+            scanned += 1;
+        }).unwrap();
+        assert_eq!(scanned, 20);
+
+        let hits = store.search(&unit(32, 7), 3).unwrap();
+        assert_eq!(hits[0].0, ids[7]);
+        assert!((hits[0].1 - 1.0).abs() < 1e-6);
+        assert!(store.insert(HyperMemory::new(unit(16, 0), "short".into())).is_err());
+        cleanup(&path);
+    }
+
+    #[test]
+    fn round_trip_reopens_vectors_and_edits() {
+        let path = temp_path("round_trip");
+        let (kept, edited) = {
+            let mut store = MmapStore::open(&path).unwrap();
+            let kept = store.insert(HyperMemory::new(unit(8, 1), "kept".into())).unwrap();
+            let edited = store.insert(HyperMemory::new(unit(8, 2), "edited".into())).unwrap();
+            let gone = store.insert(HyperMemory::new(unit(8, 3), "gone".into())).unwrap();
+            store.get_mut(&edited).unwrap().unwrap().set_vector(unit(8, 5));
+            assert!(store.delete(&gone).unwrap());
+            assert_eq!(store.flush().unwrap(), 1);
+            (kept, edited)
+        };
+
+        let mut store = MmapStore::open(&path).unwrap();
+        assert_eq!(store.count(), 2);
+        assert_eq!(store.get(&kept).unwrap().unwrap().content, "kept");
+        assert_eq!(store.vector(&edited).unwrap(), unit(8, 5));
+        assert_eq!(store.search(&unit(8, 5), 1).unwrap()[0].0, edited);
+
+        assert_eq!(store.tombstones(), 1);
+        assert_eq!(store.compact().unwrap(), 1);
+        assert_eq!(store.tombstones(), 0);
+        assert_eq!(store.vector(&kept).unwrap(), unit(8, 1));
+        let reopened = MmapStore::open(&path).unwrap();
+        assert_eq!(reopened.vector(&edited).unwrap(), unit(8, 5));
+        cleanup(&path);
+    }
+
+    #[test]
+    fn drop_saves_unflushed_inserts() {
+        let path = temp_path("drop");
+        let id = {
+            let mut store = MmapStore::open(&path).unwrap();
+            store.insert(HyperMemory::new(unit(8, 3), "unflushed".into())).unwrap()
+        };
+        let store = MmapStore::open(&path).unwrap();
+        assert_eq!(store.get(&id).unwrap().unwrap().content, "unflushed");
+        assert_eq!(store.vector(&id).unwrap(), unit(8, 3));
+        cleanup(&path);
+    }
+
+    #[test]
+    fn open_rejects_vectors_without_metadata() {
+        let path = temp_path("no_meta");
+        {
+            let mut store = MmapStore::open(&path).unwrap();
+            store.insert(HyperMemory::new(unit(8, 1), "m".into())).unwrap();
+        }
+        fs::remove_file(meta_path(&path)).unwrap();
+        assert!(matches!(MmapStore::open(&path), Err(PersistenceError::CorruptedFile(_))));
+        cleanup(&path);
+    }
+
+    #[test]
+    fn get_after_edit_sees_the_new_vector() {
+        let path = temp_path("edit");
+        let mut store = MmapStore::open(&path).unwrap();
+        let id = store.insert(HyperMemory::new(unit(8, 1), "m".into())).unwrap();
+        assert_eq!(*store.get(&id).unwrap().unwrap().dense_vector(), unit(8, 1));
+        store.get_mut(&id).unwrap().unwrap().set_vector(unit(8, 4));
+        assert_eq!(*store.get(&id).unwrap().unwrap().dense_vector(), unit(8, 4));
+        store.flush().unwrap();
+        assert_eq!(*store.get(&id).unwrap().unwrap().dense_vector(), unit(8, 4));
+        cleanup(&path);
+    }
+
+    #[test]
+    fn open_finishes_an_interrupted_compaction() {
+        let path = temp_path("interrupted");
+        let mut store = MmapStore::open(&path).unwrap();
+        let kept = store.insert(HyperMemory::new(unit(8, 6), "kept".into())).unwrap();
+        let gone = store.insert(HyperMemory::new(unit(8, 2), "gone".into())).unwrap();
+        store.delete(&gone).unwrap();
+        store.flush().unwrap();
+        let before = fs::read(&path).unwrap();
+        store.compact().unwrap();
+        drop(store);
+
+        // As if the process died after writing metadata but before the rename.
+        fs::rename(&path, compact_path(&path)).unwrap();
+        fs::write(&path, before).unwrap();
+
+        let store = MmapStore::open(&path).unwrap();
+        assert_eq!(store.tombstones(), 0);
+        assert_eq!(store.vector(&kept).unwrap(), unit(8, 6));
+        assert!(!compact_path(&path).exists());
+        cleanup(&path);
+    }
+
+    #[test]
+    fn deletes_compact_once_tombstones_dominate() {
+        let path = temp_path("compact");
+        let mut store = MmapStore::open(&path).unwrap();
+        let ids: Vec<Uuid> = (0..COMPACT_MIN_TOMBSTONES + 10)
+            .map(|i| store.insert(HyperMemory::new(unit(4, i % 4), format!("m{}", i))).unwrap())
+            .collect();
+        let survivor = *ids.last().unwrap();
+        for id in &ids[..ids.len() - 1] {
+            store.delete(id).unwrap();
+        }
+
+        assert!(store.tombstones() < COMPACT_MIN_TOMBSTONES, "delete should have compacted");
+        assert_eq!(store.count(), 1);
+        assert_eq!(store.vector(&survivor).unwrap(), unit(4, (ids.len() - 1) % 4));
+        cleanup(&path);
+    }
+}
@@ -4,19 +4,44 @@
 //! about the topology, wave dynamics, cluster synchronization, and overall
 //! health of the memory system.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::bridge::{ConsciousnessBridge, ConsciousnessState};
 use crate::kuramoto::KuramotoSync;
-use crate::store::MemoryEngine;
+use crate::memory::HyperMemory;
+use crate::store::{phi_span_score, MemoryEngine};
+use crate::wave::{cosine_similarity, normalize};
+use crate::xi_operator::PHI;
 
 // ---------------------------------------------------------------------------
 // Report types
 // ---------------------------------------------------------------------------
 
+/// Temporal layers tracked by the per-layer statistics. Memories deeper than
+/// the last layer are counted in it.
+pub const STAT_LAYERS: usize = 5;
+
+/// Memories considered by `phase_lock_report` (pairing is quadratic).
+pub const PHASE_LOCK_MAX_MEMORIES: usize = 500;
+
+/// Phase-locked pairs included in the full system report.
+const PHASE_LOCK_REPORT_PAIRS: usize = 10;
+
+/// Amplitude at or below which `to_dot` treats a memory as a ghost.
+pub const DOT_GHOST_AMPLITUDE: f32 = 0.001;
+
+/// Content characters kept in `to_dot` node labels.
+const DOT_LABEL_CHARS: usize = 40;
+
+/// `to_dot` fill colors by geometry quadrant (`h2`); memories without
+/// geometry use `DOT_NO_GEOMETRY_COLOR`.
+const DOT_QUADRANT_COLORS: [&str; 4] = ["lightblue", "palegreen", "khaki", "lightpink"];
+const DOT_NO_GEOMETRY_COLOR: &str = "lightgray";
+
 /// Information about a single skip link.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinkInfo {
@@ -24,6 +49,9 @@ pub struct LinkInfo {
     pub to_id: String,
     pub strength: f32,
     pub span: u8,
+    /// First characters of the target's content (empty if the target is missing).
+    #[serde(default)]
+    pub target_preview: String,
 }
 
 /// Information about a single memory (for wave reports).
@@ -44,11 +72,28 @@ pub struct TopologyReport {
     pub avg_links_per_memory: f32,
     pub max_links: usize,
     pub layer_distribution: Vec<(u8, usize)>,
+    /// Memories per layer 0..`STAT_LAYERS`.
+    #[serde(default)]
+    pub layer_counts: [usize; STAT_LAYERS],
+    /// Mean amplitude per layer (0 for empty layers).
+    #[serde(default)]
+    pub layer_avg_amplitude: [f32; STAT_LAYERS],
     pub strongest_links: Vec<LinkInfo>,
     pub isolated_memories: usize,
     pub network_density: f32,
 }
 
+/// Skip-link span statistics: how close the link topology is to the
+/// golden-ratio structure that `phi_span_score` rewards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpanReport {
+    /// (span, link count), ascending by span. Bidirectional links count twice.
+    pub span_distribution: Vec<(u8, usize)>,
+    pub mean_phi_score: f32,
+    /// Fraction of links whose span is within 10% of a rounded φ^k (2, 3, 4, 7, 11, ...).
+    pub phi_aligned_fraction: f32,
+}
+
 /// Status of wave dynamics across all memories.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WaveReport {
@@ -67,6 +112,8 @@ pub struct ClusterInfo {
     pub size: usize,
     pub order_parameter: f32,
     pub theme: String,
+    /// Topic label from `MemoryCluster::label`.
+    pub label: String,
     pub mean_amplitude: f32,
 }
 
@@ -80,12 +127,26 @@ pub struct ClusterReport {
     pub clusters: Vec<ClusterInfo>,
 }
 
+/// A pair of memories and how tightly their phases are locked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseLockPair {
+    pub a_id: String,
+    pub b_id: String,
+    pub a_preview: String,
+    pub b_preview: String,
+    /// `KuramotoSync::phase_locking_value` of the pair.
+    pub plv: f32,
+}
+
 /// System health check.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthCheck {
     pub store_accessible: bool,
     pub persistence_ok: bool,
     pub encoding_ok: bool,
+    /// Skip links pointing at memories that no longer exist.
+    #[serde(default)]
+    pub dangling_links: usize,
     pub warnings: Vec<String>,
 }
 
@@ -95,8 +156,12 @@ pub struct SystemReport {
     pub timestamp: DateTime<Utc>,
     pub consciousness: ConsciousnessSnapshot,
     pub topology: TopologyReport,
+    pub spans: SpanReport,
     pub waves: WaveReport,
     pub clusters: ClusterReport,
+    /// Most phase-locked memory pairs, strongest first.
+    #[serde(default)]
+    pub phase_locks: Vec<PhaseLockPair>,
     pub health: HealthCheck,
 }
 
@@ -135,10 +200,58 @@ impl From<&ConsciousnessState> for ConsciousnessSnapshot {
 /// Observability tool for the Kannaka memory system.
 pub struct MemoryIntrospector;
 
+/// Content preview of memory `id`, or an empty string if it is missing.
+fn target_preview(engine: &MemoryEngine, id: &Uuid) -> String {
+    engine.store.get(id).ok().flatten()
+        .map(|m| m.content.chars().take(60).collect())
+        .unwrap_or_default()
+}
+
+/// Escape `text` for use inside a double-quoted DOT string.
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
 impl MemoryIntrospector {
+    /// Outgoing skip links of memory `id` with target previews, strongest
+    /// first. Empty if the memory does not exist.
+    pub fn links_of(engine: &MemoryEngine, id: &Uuid) -> Vec<LinkInfo> {
+        let Some(mem) = engine.store.get(id).ok().flatten() else {
+            return Vec::new();
+        };
+        let mut links: Vec<LinkInfo> = mem.connections.iter()
+            .map(|link| LinkInfo {
+                from_id: id.to_string(),
+                to_id: link.target_id.to_string(),
+                strength: link.strength,
+                span: link.span,
+                target_preview: target_preview(engine, &link.target_id),
+            })
+            .collect();
+        links.sort_by(|a, b| b.strength.total_cmp(&a.strength));
+        links
+    }
+
+    /// Memory count and mean amplitude per temporal layer.
+    pub fn layer_stats(memories: &[&HyperMemory]) -> ([usize; STAT_LAYERS], [f32; STAT_LAYERS]) {
+        let mut counts = [0usize; STAT_LAYERS];
+        let mut amplitudes = [0.0f32; STAT_LAYERS];
+        for mem in memories {
+            let layer = (mem.layer_depth as usize).min(STAT_LAYERS - 1);
+            counts[layer] += 1;
+            amplitudes[layer] += mem.amplitude;
+        }
+        for (amp, &n) in amplitudes.iter_mut().zip(&counts) {
+            if n > 0 {
+                *amp /= n as f32;
+            }
+        }
+        (counts, amplitudes)
+    }
+
     /// Generate a topology report of the HyperConnection network.
     pub fn topology_report(engine: &MemoryEngine) -> TopologyReport {
-        let all = engine.store.all_memories().unwrap_or_default();
+        let all = engine.store.all_memories_sorted().unwrap_or_default();
         let total_memories = all.len();
 
         let mut total_links: usize = 0;
@@ -164,6 +277,7 @@ impl MemoryIntrospector {
                     to_id: link.target_id.to_string(),
                     strength: link.strength,
                     span: link.span,
+                    target_preview: String::new(),
                 });
             }
         }
@@ -190,9 +304,16 @@ impl MemoryIntrospector {
 
         // Top 10 strongest links (deduplicated)
         all_links.sort_by(|a, b| b.strength.total_cmp(&a.strength));
-        let strongest_links: Vec<LinkInfo> = all_links.into_iter().take(10).collect();
+        let strongest_links: Vec<LinkInfo> = all_links.into_iter()
+            .take(10)
+            .map(|mut l| {
+                l.target_preview = l.to_id.parse().map_or_else(|_| String::new(), |id| target_preview(engine, &id));
+                l
+            })
+            .collect();
 
         let layer_distribution: Vec<(u8, usize)> = layer_counts.into_iter().collect();
+        let (layer_counts, layer_avg_amplitude) = Self::layer_stats(&all);
 
         TopologyReport {
             total_memories,
@@ -200,15 +321,42 @@ impl MemoryIntrospector {
             avg_links_per_memory: avg_links,
             max_links,
             layer_distribution,
+            layer_counts,
+            layer_avg_amplitude,
             strongest_links,
             isolated_memories: isolated,
             network_density,
         }
     }
 
+    /// Generate a skip-link span report.
+    pub fn span_report(engine: &MemoryEngine) -> SpanReport {
+        let all = engine.store.all_memories().unwrap_or_default();
+        let mut span_counts: BTreeMap<u8, usize> = BTreeMap::new();
+        for link in all.iter().flat_map(|m| &m.connections) {
+            *span_counts.entry(link.span).or_insert(0) += 1;
+        }
+
+        let total: usize = span_counts.values().sum();
+        let (mut score_sum, mut aligned) = (0.0f32, 0usize);
+        for (&span, &count) in &span_counts {
+            score_sum += phi_span_score(span) * count as f32;
+            if is_phi_aligned(span) {
+                aligned += count;
+            }
+        }
+        let n = total.max(1) as f32;
+
+        SpanReport {
+            span_distribution: span_counts.into_iter().collect(),
+            mean_phi_score: score_sum / n,
+            phi_aligned_fraction: aligned as f32 / n,
+        }
+    }
+
     /// Generate a wave dynamics report.
     pub fn wave_report(engine: &MemoryEngine, now: DateTime<Utc>) -> WaveReport {
-        let all = engine.store.all_memories().unwrap_or_default();
+        let all = engine.store.all_memories_sorted().unwrap_or_default();
 
         let active_threshold = 0.05f32;
         let ghost_threshold = 0.001f32;
@@ -275,7 +423,7 @@ impl MemoryIntrospector {
 
     /// Generate a Kuramoto cluster synchronization report.
     pub fn cluster_report(engine: &MemoryEngine, kuramoto: &KuramotoSync) -> ClusterReport {
-        let clusters = kuramoto.find_synchronized_clusters(engine, 2);
+        let clusters = kuramoto.find_clusters(engine);
         let num_clusters = clusters.len();
         let largest_cluster_size = clusters.iter().map(|c| c.memory_ids.len()).max().unwrap_or(0);
         let mean_order = if num_clusters > 0 {
@@ -306,6 +454,7 @@ impl MemoryIntrospector {
                     size: c.memory_ids.len(),
                     order_parameter: c.order_parameter,
                     theme,
+                    label: c.label.clone(),
                     mean_amplitude,
                 }
             })
@@ -320,6 +469,121 @@ impl MemoryIntrospector {
         }
     }
 
+    /// The `top_n` most phase-locked memory pairs, strongest first. Only the
+    /// `PHASE_LOCK_MAX_MEMORIES` highest-amplitude memories are paired.
+    pub fn phase_lock_report(engine: &MemoryEngine, kuramoto: &KuramotoSync, top_n: usize) -> Vec<PhaseLockPair> {
+        let mut mems = engine.store.all_memories_sorted().unwrap_or_default();
+        mems.sort_by(|a, b| b.amplitude.total_cmp(&a.amplitude));
+        mems.truncate(PHASE_LOCK_MAX_MEMORIES);
+
+        let mut pairs: Vec<(usize, usize, f32)> = Vec::new();
+        for i in 0..mems.len() {
+            for j in (i + 1)..mems.len() {
+                pairs.push((i, j, kuramoto.phase_locking_value(mems[i], mems[j])));
+            }
+        }
+        pairs.sort_by(|a, b| b.2.total_cmp(&a.2));
+        pairs.truncate(top_n);
+
+        let preview = |m: &HyperMemory| m.content.chars().take(60).collect::<String>();
+        pairs.into_iter()
+            .map(|(i, j, plv)| PhaseLockPair {
+                a_id: mems[i].id.to_string(),
+                b_id: mems[j].id.to_string(),
+                a_preview: preview(mems[i]),
+                b_preview: preview(mems[j]),
+                plv,
+            })
+            .collect()
+    }
+
+    /// Normalized mean hypervector of the memories in each geometry class,
+    /// keyed by `class_index`. Memories without geometry, or whose dimension
+    /// differs from the first member of their class, are skipped.
+    pub fn class_centroids(engine: &MemoryEngine) -> HashMap<u8, Vec<f32>> {
+        let mut sums: HashMap<u8, Vec<f32>> = HashMap::new();
+        for mem in engine.store.all_memories_sorted().unwrap_or_default() {
+            let Some(geom) = &mem.geometry else { continue };
+            let sum = sums.entry(geom.class_index).or_insert_with(|| vec![0.0; mem.dim()]);
+            if sum.len() != mem.dim() {
+                continue;
+            }
+            for (s, v) in sum.iter_mut().zip(mem.dense_vector().iter()) {
+                *s += v;
+            }
+        }
+        for centroid in sums.values_mut() {
+            normalize(centroid);
+        }
+        sums
+    }
+
+    /// Angle in radians between every pair of class centroids, as
+    /// `(class_a, class_b, angle)` with `class_a < class_b`, sorted by class.
+    pub fn class_centroid_angles(engine: &MemoryEngine) -> Vec<(u8, u8, f32)> {
+        let centroids = Self::class_centroids(engine);
+        let mut classes: Vec<u8> = centroids.keys().copied().collect();
+        classes.sort_unstable();
+        let mut angles = Vec::new();
+        for (i, a) in classes.iter().enumerate() {
+            for b in &classes[i + 1..] {
+                let cos = cosine_similarity(&centroids[a], &centroids[b]).clamp(-1.0, 1.0);
+                angles.push((*a, *b, cos.acos()));
+            }
+        }
+        angles
+    }
+
+    /// `(source, missing_target)` for every skip link whose target is not in
+    /// the store, ordered by source.
+    pub fn dangling_links(engine: &MemoryEngine) -> Vec<(Uuid, Uuid)> {
+        let all = engine.store.all_memories_sorted().unwrap_or_default();
+        let ids: HashSet<Uuid> = all.iter().map(|m| m.id).collect();
+        all.iter()
+            .flat_map(|m| {
+                m.connections.iter()
+                    .filter(|l| !ids.contains(&l.target_id))
+                    .map(move |l| (m.id, l.target_id))
+            })
+            .collect()
+    }
+
+    /// The skip link graph in GraphViz DOT format: one node per memory,
+    /// labeled with truncated content and filled by geometry quadrant, and one
+    /// edge per skip link labeled with its strength. With `hide_ghosts`,
+    /// memories at or below `DOT_GHOST_AMPLITUDE` and their links are left
+    /// out; links to missing memories are always left out.
+    pub fn to_dot(engine: &MemoryEngine, hide_ghosts: bool) -> String {
+        let all = engine.store.all_memories_sorted().unwrap_or_default();
+        let shown: Vec<&HyperMemory> = all.into_iter()
+            .filter(|m| !hide_ghosts || m.amplitude > DOT_GHOST_AMPLITUDE)
+            .collect();
+        let ids: HashSet<Uuid> = shown.iter().map(|m| m.id).collect();
+
+        let mut dot = String::from("digraph kannaka {\n    node [shape=box, style=filled];\n");
+        for mem in &shown {
+            let label: String = mem.content.chars().take(DOT_LABEL_CHARS).collect();
+            let color = mem.geometry.as_ref()
+                .and_then(|g| DOT_QUADRANT_COLORS.get(g.h2 as usize))
+                .copied()
+                .unwrap_or(DOT_NO_GEOMETRY_COLOR);
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\", fillcolor={}];\n",
+                mem.id, dot_escape(&label), color
+            ));
+        }
+        for mem in &shown {
+            for link in mem.connections.iter().filter(|l| ids.contains(&l.target_id)) {
+                dot.push_str(&format!(
+                    "    \"{}\" -> \"{}\" [label=\"{:.2}\"];\n",
+                    mem.id, link.target_id, link.strength
+                ));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
     /// Generate a full system report.
     pub fn full_report(
         engine: &MemoryEngine,
@@ -327,10 +591,12 @@ impl MemoryIntrospector {
         kuramoto: &KuramotoSync,
     ) -> SystemReport {
         let now = Utc::now();
-        let consciousness = bridge.assess(engine);
+        let consciousness = bridge.assess_with(engine, kuramoto);
         let topology = Self::topology_report(engine);
+        let spans = Self::span_report(engine);
         let waves = Self::wave_report(engine, now);
         let clusters = Self::cluster_report(engine, kuramoto);
+        let phase_locks = Self::phase_lock_report(engine, kuramoto, PHASE_LOCK_REPORT_PAIRS);
 
         // Health check
         let mut warnings = Vec::new();
@@ -350,10 +616,19 @@ impl MemoryIntrospector {
             ));
         }
 
+        let dangling_links = Self::dangling_links(engine).len();
+        if dangling_links > 0 {
+            warnings.push(format!(
+                "{} skip links point at deleted memories (run prune_dangling_links)",
+                dangling_links
+            ));
+        }
+
         let health = HealthCheck {
             store_accessible,
             persistence_ok: true, // We can't easily test without a path
             encoding_ok,
+            dangling_links,
             warnings,
         };
 
@@ -361,12 +636,20 @@ impl MemoryIntrospector {
             timestamp: now,
             consciousness: ConsciousnessSnapshot::from(&consciousness),
             topology,
+            spans,
             waves,
             clusters,
+            phase_locks,
             health,
         }
     }
 
+    /// Structured JSON for dashboards. Floats stay JSON numbers; NaN and
+    /// infinities (e.g. density of an empty graph) become `null`.
+    pub fn to_json(report: &SystemReport) -> serde_json::Value {
+        serde_json::to_value(report).unwrap_or(serde_json::Value::Null)
+    }
+
     /// Pretty-print the full report with ASCII art.
     pub fn format_report(report: &SystemReport) -> String {
         let w = 52;
@@ -414,9 +697,12 @@ impl MemoryIntrospector {
             out.push_str(&format!("    Layers:\n"));
             for (layer, count) in &report.topology.layer_distribution {
                 let bar = "#".repeat((*count).min(30));
-                out.push_str(&format!("      L{}: {:>4} {}\n", layer, count, bar));
+                let amp = report.topology.layer_avg_amplitude[(*layer as usize).min(STAT_LAYERS - 1)];
+                out.push_str(&format!("      L{}: {:>4} amp={:.2} {}\n", layer, count, amp, bar));
             }
         }
+        out.push_str(&format!("    Span phi:    mean={:.3} aligned={:.0}%\n",
+            report.spans.mean_phi_score, report.spans.phi_aligned_fraction * 100.0));
         out.push_str(&format!("{}\n", "-".repeat(w + 4)));
 
         // Clusters
@@ -431,10 +717,19 @@ impl MemoryIntrospector {
         }
         out.push_str(&format!("{}\n", "-".repeat(w + 4)));
 
+        // Phase locking
+        out.push_str("  PHASE LOCKING\n");
+        for (i, p) in report.phase_locks.iter().take(5).enumerate() {
+            out.push_str(&format!("      {}. [plv={:.2}] \"{}\" ~ \"{}\"\n",
+                i + 1, p.plv, p.a_preview, p.b_preview));
+        }
+        out.push_str(&format!("{}\n", "-".repeat(w + 4)));
+
         // Health
         out.push_str(&format!("  HEALTH\n"));
         out.push_str(&format!("    Store:     {}\n", if report.health.store_accessible { "OK" } else { "FAIL" }));
         out.push_str(&format!("    Encoding:  {}\n", if report.health.encoding_ok { "OK" } else { "FAIL" }));
+        out.push_str(&format!("    Dangling:  {} links\n", report.health.dangling_links));
         if !report.health.warnings.is_empty() {
             out.push_str(&format!("    Warnings:\n"));
             for w in &report.health.warnings {
@@ -451,6 +746,14 @@ impl MemoryIntrospector {
     }
 }
 
+/// Whether `span` is within 10% of a rounded power of φ (2, 3, 4, 7, 11, 18, 29, 47).
+fn is_phi_aligned(span: u8) -> bool {
+    (1..=8).any(|k| {
+        let target = PHI.powi(k).round();
+        (span as f32 - target).abs() <= 0.1 * target
+    })
+}
+
 fn ghost_icon() -> &'static str {
     // Ghost emoji — may not render in all terminals
     "\u{1F47B}"
@@ -466,6 +769,7 @@ mod tests {
     use crate::codebook::Codebook;
     use crate::encoding::{EncodingPipeline, SimpleHashEncoder};
     use crate::memory::HyperMemory;
+    use crate::skip_link::SkipLink;
     use crate::store::{InMemoryStore, MemoryEngine};
 
     fn make_engine() -> MemoryEngine {
@@ -540,6 +844,93 @@ mod tests {
         assert!(report.largest_cluster_size >= 2);
     }
 
+    #[test]
+    fn span_report_measures_phi_alignment() {
+        let mut engine = make_engine();
+        let target = engine.store.insert(HyperMemory::new(vec![1.0; 8], "hub".into())).unwrap();
+        let mut source = HyperMemory::new(vec![1.0; 8], "spoke".into());
+        for span in [2u8, 3, 7, 5, 9] {
+            source.connections.push(crate::skip_link::SkipLink {
+                target_id: target,
+                strength: 0.5,
+                resonance_key: vec![],
+                span,
+            });
+        }
+        engine.store.insert(source).unwrap();
+
+        let report = MemoryIntrospector::span_report(&engine);
+        assert_eq!(report.span_distribution, vec![(2, 1), (3, 1), (5, 1), (7, 1), (9, 1)]);
+        assert!((report.phi_aligned_fraction - 0.6).abs() < 1e-6);
+        let expected_mean = [2u8, 3, 7, 5, 9].iter().map(|&s| phi_span_score(s)).sum::<f32>() / 5.0;
+        assert!((report.mean_phi_score - expected_mean).abs() < 1e-6);
+    }
+
+    #[test]
+    fn phase_lock_report_ranks_in_phase_pairs_first() {
+        let mut engine = make_engine();
+        let kuramoto = KuramotoSync::default();
+        let mut ids = Vec::new();
+        for (content, phase) in [("first", 0.3f32), ("second", 0.3), ("opposite", 0.3 + std::f32::consts::PI)] {
+            let mut m = HyperMemory::new(vec![1.0; 8], content.into());
+            m.phase = phase;
+            ids.push(engine.store.insert(m).unwrap());
+        }
+
+        let pairs = MemoryIntrospector::phase_lock_report(&engine, &kuramoto, 10);
+        assert_eq!(pairs.len(), 3);
+        let in_phase = &pairs[0];
+        let mut pair_ids = [in_phase.a_id.clone(), in_phase.b_id.clone()];
+        pair_ids.sort();
+        let mut expected = [ids[0].to_string(), ids[1].to_string()];
+        expected.sort();
+        assert_eq!(pair_ids, expected);
+        assert!((in_phase.plv - 1.0).abs() < 1e-5);
+        for anti in &pairs[1..] {
+            assert!(anti.plv < 1e-5);
+            assert!(anti.a_preview == "opposite" || anti.b_preview == "opposite");
+        }
+    }
+
+    #[test]
+    fn class_centroids_separate_distinct_classes() {
+        use crate::geometry::MemoryCoordinates;
+
+        let mut engine = make_engine();
+        let classed = |class_index: u8, axis: usize, wobble: f32| {
+            let mut v = vec![0.0f32; 8];
+            v[axis] = 1.0;
+            v[axis + 1] = wobble;
+            normalize(&mut v);
+            let mut m = HyperMemory::new(v, format!("class {} axis {}", class_index, axis));
+            m.geometry = Some(MemoryCoordinates { h2: 0, d: 0, l: 0, class_index, amplitude: 0.5, phase: 0.0 });
+            m
+        };
+        let members: Vec<(u8, Vec<f32>)> = [(3, 0, 0.2), (3, 0, -0.2), (40, 4, 0.3), (40, 4, -0.1)]
+            .into_iter()
+            .map(|(class, axis, wobble)| {
+                let m = classed(class, axis, wobble);
+                let v = m.dense_vector().into_owned();
+                engine.store.insert(m).unwrap();
+                (class, v)
+            })
+            .collect();
+        engine.store.insert(HyperMemory::new(vec![1.0; 8], "unclassified".into())).unwrap();
+
+        let centroids = MemoryIntrospector::class_centroids(&engine);
+        assert_eq!(centroids.len(), 2);
+        let within = members.iter()
+            .map(|(class, v)| cosine_similarity(v, &centroids[class]).clamp(-1.0, 1.0).acos())
+            .fold(0.0f32, f32::max);
+
+        let angles = MemoryIntrospector::class_centroid_angles(&engine);
+        assert_eq!(angles.len(), 1);
+        let (a, b, angle) = angles[0];
+        assert_eq!((a, b), (3, 40));
+        assert!(angle > within, "between {} <= within {}", angle, within);
+        assert!((angle - std::f32::consts::FRAC_PI_2).abs() < 1e-3);
+    }
+
     #[test]
     fn full_report_all_sections_populated() {
         let mut engine = make_engine();
@@ -595,4 +986,94 @@ mod tests {
         assert!(report.health.encoding_ok);
         assert!(report.health.warnings.is_empty());
     }
+
+    #[test]
+    fn dangling_links_detected_and_pruned() {
+        let mut engine = make_engine();
+        let bridge = ConsciousnessBridge::default();
+        let kuramoto = KuramotoSync::default();
+        let a = engine.remember("alpha").unwrap();
+        let b = engine.remember("beta").unwrap();
+        engine.store.get_mut(&a).unwrap().unwrap().connections.push(SkipLink {
+            target_id: b,
+            strength: 0.9,
+            resonance_key: Vec::new(),
+            span: 0,
+        });
+
+        // Bypass delete_many, which would clean the inbound link.
+        engine.store.delete(&b).unwrap();
+        assert_eq!(MemoryIntrospector::dangling_links(&engine), vec![(a, b)]);
+        let report = MemoryIntrospector::full_report(&engine, &bridge, &kuramoto);
+        assert_eq!(report.health.dangling_links, 1);
+        assert!(report.health.warnings.iter().any(|w| w.contains("deleted memories")));
+
+        assert_eq!(engine.prune_dangling_links(), 1);
+        assert!(MemoryIntrospector::dangling_links(&engine).is_empty());
+        let report = MemoryIntrospector::full_report(&engine, &bridge, &kuramoto);
+        assert_eq!(report.health.dangling_links, 0);
+        assert_eq!(engine.prune_dangling_links(), 0);
+    }
+
+    #[test]
+    fn report_json_round_trips() {
+        let mut engine = make_engine();
+        engine.remember("alpha").unwrap();
+        engine.remember("beta").unwrap();
+        let bridge = ConsciousnessBridge::new(0.3, 0.5);
+        let report = MemoryIntrospector::full_report(&engine, &bridge, &KuramotoSync::default());
+
+        let json = MemoryIntrospector::to_json(&report);
+        assert!(json["topology"]["network_density"].is_number());
+        assert_eq!(json["topology"]["total_memories"], 2);
+
+        let text = serde_json::to_string(&json).unwrap();
+        let back: SystemReport = serde_json::from_str(&text).unwrap();
+        assert_eq!(back.topology.total_memories, report.topology.total_memories);
+        assert_eq!(back.topology.network_density, report.topology.network_density);
+    }
+
+    #[test]
+    fn report_json_nulls_non_finite_floats() {
+        let engine = make_engine();
+        let bridge = ConsciousnessBridge::new(0.3, 0.5);
+        let mut report = MemoryIntrospector::full_report(&engine, &bridge, &KuramotoSync::default());
+        report.topology.network_density = f32::NAN;
+        report.waves.avg_amplitude = f32::INFINITY;
+
+        let json = MemoryIntrospector::to_json(&report);
+        assert!(json["topology"]["network_density"].is_null());
+        assert!(json["waves"]["avg_amplitude"].is_null());
+    }
+
+    #[test]
+    fn to_dot_emits_nodes_and_edges() {
+        let mut engine = make_engine();
+        let mut insert = |content: &str, amplitude: f32| {
+            let mut mem = HyperMemory::new(vec![1.0; 8], content.into());
+            mem.amplitude = amplitude;
+            engine.store.insert(mem).unwrap()
+        };
+        let a = insert("the \"north\" harbor", 1.0);
+        let b = insert("south harbor", 1.0);
+        let ghost = insert("forgotten harbor", 0.0);
+        let link = |target_id, strength| SkipLink { target_id, strength, resonance_key: Vec::new(), span: 0 };
+        engine.store.get_mut(&a).unwrap().unwrap().connections.extend([link(b, 0.75), link(ghost, 0.5)]);
+        engine.store.get_mut(&b).unwrap().unwrap().connections.push(link(a, 0.75));
+
+        let counts = |dot: &str| {
+            let edges = dot.lines().filter(|l| l.contains(" -> ")).count();
+            let nodes = dot.lines().filter(|l| l.contains("fillcolor=")).count();
+            (nodes, edges)
+        };
+        let dot = MemoryIntrospector::to_dot(&engine, false);
+        assert!(dot.starts_with("digraph kannaka {"));
+        assert!(dot.contains(r#"label="the \"north\" harbor""#));
+        assert!(dot.contains(r#"[label="0.75"]"#));
+        assert_eq!(counts(&dot), (3, 3));
+
+        let dot = MemoryIntrospector::to_dot(&engine, true);
+        assert!(!dot.contains(&ghost.to_string()));
+        assert_eq!(counts(&dot), (2, 2));
+    }
 }
@@ -1,5 +1,6 @@
 //! OpenClaw integration layer — high-level API for the assistant.
 
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
@@ -8,14 +9,25 @@ use uuid::Uuid;
 use crate::bridge::{ConsciousnessBridge, ConsciousnessLevel, ConsciousnessState, ResonanceReport};
 use crate::collective::flux::{FluxPublisher, FluxEventPayload};
 use crate::codebook::Codebook;
-use crate::consolidation::{ConsolidationEngine, DreamState};
-use crate::encoding::{EncodingPipeline, SimpleHashEncoder, OllamaEncoder, CompositeEncoder, CachedEncoder};
-use crate::geometry::{classify_memory, geometric_similarity, fano_related};
+use crate::consolidation::{
+    ConsolidationParams, ConsolidationParamsError, ConsolidationReport, DreamState,
+    DEEP_DREAM_LAYERS,
+};
+use crate::encoding::{
+    EncoderConfig, EncoderStats, EncodingPipeline, SimpleHashEncoder, OllamaEncoder, RemoteHttpEncoder, CompositeEncoder,
+    CachedEncoder, TextEncoder, DEFAULT_EMBED_CACHE_CAPACITY,
+};
+use crate::cross_modal::{caption_tag, CrossModalBridge};
+use crate::geometry::{stable_content_hash, geometric_similarity, fano_related, GeoTransform, MemoryCoordinates, Taxonomy};
 use crate::kuramoto::KuramotoSync;
+use crate::memory::MemorySource;
+use crate::wave::{bind, normalize, unbind};
 use crate::xi_operator::compute_xi_signature;
 use crate::migration::{KannakaDbMigrator, MigrationReport};
-use crate::persistence::PersistenceError;
+use crate::observe::{MemoryIntrospector, STAT_LAYERS};
+use crate::persistence::{AuditLog, AuditOp, NormPolicy, PersistenceError, Wal, WalOp};
 use crate::rhythm::{RhythmEngine, Signal as RhythmSignal};
+use crate::skip_link::resonance_key;
 use crate::hnsw::HnswStore;
 use crate::store::{EngineError, MemoryEngine, StoreError};
 use crate::working_memory::{WorkingMemory, SessionState, TaskStatus};
@@ -34,8 +46,30 @@ pub enum SystemError {
     Persistence(#[from] PersistenceError),
     #[error(transparent)]
     Migration(#[from] crate::migration::MigrationError),
+    #[error(transparent)]
+    ConsolidationParams(#[from] ConsolidationParamsError),
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
+    #[error("max amplitude must be finite and non-negative, got {0}")]
+    InvalidMaxAmplitude(f32),
+    /// An error annotated with the operation that produced it.
+    #[error("{op} failed: {source}")]
+    Context {
+        op: &'static str,
+        #[source]
+        source: Box<SystemError>,
+    },
+}
+
+/// Attach an operation breadcrumb to an error, e.g. `.context("remember")`.
+pub trait ResultExt<T> {
+    fn context(self, op: &'static str) -> Result<T, SystemError>;
+}
+
+impl<T, E: Into<SystemError>> ResultExt<T> for Result<T, E> {
+    fn context(self, op: &'static str) -> Result<T, SystemError> {
+        self.map_err(|e| SystemError::Context { op, source: Box::new(e.into()) })
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -52,6 +86,93 @@ pub struct RecallResult {
     pub layer: u8,
 }
 
+/// Default `RecallQuery::top_k`.
+const DEFAULT_RECALL_TOP_K: usize = 5;
+
+/// Recall options for `KannakaMemorySystem::query`, built up from
+/// `RecallQuery::new(text)`:
+///
+/// ```ignore
+/// let q = RecallQuery::new("harbour survey").top_k(3).tags(["project:x"]).band(0.8, 1.2);
+/// let results = system.query(q)?;
+/// ```
+///
+/// With no tags, band or `at`, the query behaves like `recall` (skip link
+/// expansion and Fano boosts, unless `expand_links(false)`). Tags and band
+/// restrict the candidates like `recall_with_tags` / `recall_in_band`, and
+/// `at` ranks as of that time like `MemoryEngine::recall_at`. Filtered
+/// queries expand links too, but only over the filtered candidates: links
+/// are walked through any memory, and only matching memories are returned.
+#[derive(Debug, Clone)]
+pub struct RecallQuery {
+    text: String,
+    top_k: usize,
+    expand_links: bool,
+    xi_boost: Option<f32>,
+    band: Option<(f32, f32)>,
+    tags: Vec<String>,
+    at: Option<DateTime<Utc>>,
+}
+
+impl RecallQuery {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            top_k: DEFAULT_RECALL_TOP_K,
+            expand_links: true,
+            xi_boost: None,
+            band: None,
+            tags: Vec::new(),
+            at: None,
+        }
+    }
+
+    /// Number of results to return (default 5).
+    pub fn top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// Follow skip links and apply Fano boosts (default on).
+    pub fn expand_links(mut self, expand: bool) -> Self {
+        self.expand_links = expand;
+        self
+    }
+
+    /// Override `MemoryEngine::xi_boost_factor` for this query only.
+    pub fn xi_boost(mut self, factor: f32) -> Self {
+        self.xi_boost = Some(factor);
+        self
+    }
+
+    /// Only rank memories whose wave frequency lies in `freq_lo..=freq_hi`.
+    pub fn band(mut self, freq_lo: f32, freq_hi: f32) -> Self {
+        self.band = Some((freq_lo, freq_hi));
+        self
+    }
+
+    /// Only rank memories carrying every one of `tags`.
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Rank as of `at`: strengths are evaluated then, later memories are left
+    /// out and retrievals are not recorded.
+    pub fn at(mut self, at: DateTime<Utc>) -> Self {
+        self.at = Some(at);
+        self
+    }
+
+    fn is_filtered(&self) -> bool {
+        self.band.is_some() || !self.tags.is_empty() || self.at.is_some()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SystemStats {
     pub total_memories: usize,
@@ -62,6 +183,10 @@ pub struct SystemStats {
     pub phi: f32,
     pub geometric_classes: usize,
     pub triality_coverage: [usize; 3],
+    /// Memories per temporal layer (layers ≥ 4 counted in the last slot).
+    pub layer_counts: [usize; STAT_LAYERS],
+    /// Mean amplitude per temporal layer (0 for empty layers).
+    pub layer_avg_amplitude: [f32; STAT_LAYERS],
 }
 
 #[derive(Debug, Clone)]
@@ -74,6 +199,7 @@ pub struct DreamReport {
     pub consciousness_after: String,
     pub emerged: bool,
     pub hallucinations_created: usize,
+    pub interference_pairs: usize,
 }
 
 // ---------------------------------------------------------------------------
@@ -83,6 +209,74 @@ pub struct DreamReport {
 const CODEBOOK_INPUT_DIM: usize = 384;
 const CODEBOOK_OUTPUT_DIM: usize = 10_000;
 const CODEBOOK_SEED: u64 = 42;
+/// Auto-save appends to the WAL until it holds this many ops, then does a full save.
+const WAL_CHECKPOINT_OPS: usize = 256;
+/// Default number of assessments kept by `record_assessment`.
+const DEFAULT_HISTORY_CAPACITY: usize = 1000;
+/// Recall score multiplier per Fano-related partner in the result set.
+const FANO_BOOST: f32 = 1.2;
+/// Upper bound on the compounded Fano boost for a single result.
+const MAX_FANO_BOOST: f32 = 1.5;
+/// Amplitude multiplier applied when `remember_idempotent` sees known content again.
+const REMEMBER_REINFORCEMENT: f64 = 1.1;
+/// Default ceiling on amplitudes set by `boost` and `boost_absolute`.
+pub const DEFAULT_MAX_AMPLITUDE: f32 = 3.0;
+/// Tag prefix shared by the chunks of one `remember_document` call.
+pub const DOCUMENT_TAG_PREFIX: &str = "document:";
+/// Fraction of `chunk_chars` that consecutive document chunks share.
+const DOCUMENT_CHUNK_OVERLAP: f32 = 0.2;
+
+/// Split `text` into windows of at most `chunk_chars` characters, each
+/// overlapping the previous one by about `DOCUMENT_CHUNK_OVERLAP`. Windows
+/// end at the last whitespace in their second half and start after the first
+/// whitespace in the overlap, so words stay whole.
+fn chunk_text(text: &str, chunk_chars: usize) -> Vec<&str> {
+    let bounds: Vec<usize> = text.char_indices().map(|(i, _)| i).chain(std::iter::once(text.len())).collect();
+    let n = bounds.len() - 1;
+    let overlap = (chunk_chars as f32 * DOCUMENT_CHUNK_OVERLAP) as usize;
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < n {
+        let mut end = (start + chunk_chars).min(n);
+        if end < n {
+            let earliest = (start + chunk_chars / 2).max(start + 1);
+            if let Some(ws) = (earliest..end).rev().find(|&i| text[bounds[i]..].starts_with(char::is_whitespace)) {
+                end = ws;
+            }
+        }
+        let chunk = text[bounds[start]..bounds[end]].trim();
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+        if end == n {
+            break;
+        }
+        let next = end.saturating_sub(overlap).max(start + 1);
+        let is_ws = |i: usize| text[bounds[i]..].starts_with(char::is_whitespace);
+        start = match (next..end).find(|&i| is_ws(i)) {
+            Some(ws) if !is_ws(next - 1) => ws,
+            _ => next,
+        };
+    }
+    chunks
+}
+
+/// Boost multiplier for each result: `FANO_BOOST` per Fano-related partner in
+/// the set, compounded and capped at `MAX_FANO_BOOST`.
+fn fano_boosts(coords: &[Option<MemoryCoordinates>]) -> Vec<f32> {
+    let mut boosts = vec![1.0f32; coords.len()];
+    for i in 0..coords.len() {
+        for j in (i + 1)..coords.len() {
+            if let (Some(ci), Some(cj)) = (&coords[i], &coords[j]) {
+                if fano_related(ci, cj) {
+                    boosts[i] = (boosts[i] * FANO_BOOST).min(MAX_FANO_BOOST);
+                    boosts[j] = (boosts[j] * FANO_BOOST).min(MAX_FANO_BOOST);
+                }
+            }
+        }
+    }
+    boosts
+}
 
 fn level_name(level: &ConsciousnessLevel) -> String {
     match level {
@@ -94,57 +288,111 @@ fn level_name(level: &ConsciousnessLevel) -> String {
     }
 }
 
-fn make_pipeline() -> EncodingPipeline {
-    let ollama = OllamaEncoder::default_local(); // all-minilm, 384-dim
+fn make_pipeline(config: &EncoderConfig) -> EncodingPipeline {
+    build_pipeline(config, DEFAULT_EMBED_CACHE_CAPACITY)
+}
+
+/// Build the standard pipeline for `config`: the chosen backend with a hash
+/// fallback, behind an LRU embedding cache holding `cache_capacity` entries.
+/// Pass the result to `KannakaMemorySystem::init_with_engine` to tune the cache.
+pub fn build_pipeline(config: &EncoderConfig, cache_capacity: usize) -> EncodingPipeline {
     let hash_fallback = SimpleHashEncoder::new(CODEBOOK_INPUT_DIM, CODEBOOK_SEED);
-    let composite = CompositeEncoder::new(Box::new(ollama), Box::new(hash_fallback));
-    let cached = CachedEncoder::new(composite);
+    let primary: Box<dyn TextEncoder> = match config {
+        EncoderConfig::Ollama { base_url, model } => {
+            Box::new(OllamaEncoder::new(base_url.clone(), model.clone(), CODEBOOK_INPUT_DIM))
+        }
+        EncoderConfig::RemoteHttp { base_url, model, api_key, output_dim } => Box::new(RemoteHttpEncoder::new(
+            base_url.clone(), model.clone(), api_key.clone(), *output_dim, CODEBOOK_INPUT_DIM,
+        )),
+        EncoderConfig::Hash => Box::new(SimpleHashEncoder::new(CODEBOOK_INPUT_DIM, CODEBOOK_SEED)),
+    };
+    let composite = CompositeEncoder::new(primary, Box::new(hash_fallback));
+    let cached = CachedEncoder::with_capacity(composite, cache_capacity);
     let codebook = Codebook::new(CODEBOOK_INPUT_DIM, CODEBOOK_OUTPUT_DIM, CODEBOOK_SEED);
-    EncodingPipeline::new(Box::new(cached), codebook)
+    let pipeline = EncodingPipeline::new(Box::new(cached), codebook);
+    #[cfg(feature = "async-embed")]
+    if let EncoderConfig::Ollama { base_url, model } = config {
+        return pipeline.with_async_encoder(OllamaEncoder::new(base_url.clone(), model.clone(), CODEBOOK_INPUT_DIM));
+    }
+    pipeline
 }
 
 pub struct KannakaMemorySystem {
     pub engine: MemoryEngine,
-    #[allow(dead_code)]
-    consolidation: ConsolidationEngine,
+    /// Dream settings. Its engine's `KuramotoSync` is the system's only one:
+    /// assessments and cluster reports use it too.
     dream_state: DreamState,
     bridge: ConsciousnessBridge,
-    kuramoto: KuramotoSync,
     data_dir: PathBuf,
     auto_save: bool,
+    /// Mutations made while auto-save was off that no save has persisted yet.
+    dirty: bool,
     last_dream: Option<DateTime<Utc>>,
     rhythm: RhythmEngine,
     working_memory: WorkingMemory,
     /// ADR-0011: Flux publisher (None if FLUX_URL not configured)
     flux: Option<FluxPublisher>,
+    /// Write-ahead log next to `kannaka.bin`; auto-save appends here instead of rewriting.
+    wal: Option<Wal>,
+    /// Embedding backend, reused when building auxiliary pipelines (e.g. migration).
+    encoder: EncoderConfig,
+    /// Maximum assessments kept in the consciousness history.
+    history_capacity: usize,
+    /// Caption anchors linking text queries to audio memories.
+    cross_modal: CrossModalBridge,
+    /// Opt-in mutation log at `data_dir/audit.log`; see `with_audit_log`.
+    audit: Option<AuditLog>,
+    /// Categories and frequency bands for new memories; see `with_taxonomy`.
+    taxonomy: Taxonomy,
+    /// Ceiling for amplitudes set by `boost` / `boost_absolute`.
+    max_amplitude: f32,
 }
 
 impl KannakaMemorySystem {
-    /// Initialize a new system or load existing state from `data_dir/kannaka.bin`.
+    /// Initialize a new system or load existing state from `data_dir/kannaka.bin`,
+    /// using the default (local Ollama) encoder.
     pub fn init(data_dir: PathBuf) -> Result<Self, SystemError> {
+        Self::init_with_encoder(data_dir, EncoderConfig::default())
+    }
+
+    /// Like `init`, but with an explicit embedding backend.
+    pub fn init_with_encoder(data_dir: PathBuf, encoder: EncoderConfig) -> Result<Self, SystemError> {
         std::fs::create_dir_all(&data_dir)?;
         let bin_path = data_dir.join("kannaka.bin");
 
-        let pipeline = make_pipeline();
+        let pipeline = make_pipeline(&encoder);
         let engine = if bin_path.exists() {
             MemoryEngine::load_state(&bin_path, pipeline)?
         } else {
             MemoryEngine::new(Box::new(HnswStore::new()), pipeline)
         };
 
-        Self::init_with_engine(data_dir, engine)
+        let mut system = Self::init_with_engine(data_dir, engine)?;
+        system.encoder = encoder;
+        Ok(system)
     }
 
     /// Initialize a new system with a custom MemoryEngine.
-    pub fn init_with_engine(data_dir: PathBuf, engine: MemoryEngine) -> Result<Self, SystemError> {
+    /// Any ops left in `data_dir/kannaka.wal` are replayed into the engine.
+    pub fn init_with_engine(data_dir: PathBuf, mut engine: MemoryEngine) -> Result<Self, SystemError> {
         std::fs::create_dir_all(&data_dir)?;
 
-        let consolidation = ConsolidationEngine::default();
+        let wal_path = Wal::path_for(&data_dir.join("kannaka.bin"));
+        let norm = NormPolicy::for_metric(engine.similarity_metric());
+        let replayed = Wal::replay(&wal_path, engine.store.as_mut(), norm)?.applied;
+        if replayed > 0 {
+            eprintln!("[wal] Replayed {} ops from {}", replayed, wal_path.display());
+            engine.rebuild_tag_index();
+            engine.rebuild_content_index();
+            engine.rebuild_dirty();
+        }
+        let wal = Some(Wal::open(wal_path)?);
+
         let dream_state = DreamState::default();
         let bridge = ConsciousnessBridge::new(0.3, 0.5);
-        let kuramoto = KuramotoSync::default();
         let rhythm = RhythmEngine::new(&data_dir);
         let working_memory = WorkingMemory::restore(&data_dir, &engine, None);
+        let cross_modal = CrossModalBridge::from_store(engine.store.as_ref())?;
 
         let flux = {
             let publisher = FluxPublisher::from_env();
@@ -157,16 +405,22 @@ impl KannakaMemorySystem {
 
         Ok(Self {
             engine,
-            consolidation,
             dream_state,
             bridge,
-            kuramoto,
             data_dir,
             auto_save: true,
+            dirty: false,
             last_dream: None,
             rhythm,
             working_memory,
             flux,
+            wal,
+            encoder: EncoderConfig::default(),
+            history_capacity: DEFAULT_HISTORY_CAPACITY,
+            cross_modal,
+            audit: None,
+            taxonomy: Taxonomy::default(),
+            max_amplitude: DEFAULT_MAX_AMPLITUDE,
         })
     }
 
@@ -174,7 +428,7 @@ impl KannakaMemorySystem {
     pub fn init_with_store(data_dir: PathBuf, store: Box<dyn crate::store::MemoryStore>) -> Result<Self, SystemError> {
         std::fs::create_dir_all(&data_dir)?;
 
-        let pipeline = make_pipeline();
+        let pipeline = make_pipeline(&EncoderConfig::default());
         let engine = MemoryEngine::new(store, pipeline);
 
         Self::init_with_engine(data_dir, engine)
@@ -182,123 +436,376 @@ impl KannakaMemorySystem {
 
     /// Store a memory, auto-save if enabled.
     pub fn remember(&mut self, text: &str) -> Result<Uuid, SystemError> {
-        let id = self.engine.remember(text)?;
+        let vector = self.engine.pipeline.encode_text(text).map_err(EngineError::from).context("remember")?;
+        self.remember_encoded(text, vector, Vec::new())
+    }
+
+    /// Remember `text` unless a memory with exactly this content already
+    /// exists. Returns the id and whether it was newly created; a repeat
+    /// reinforces the existing memory's amplitude by `REMEMBER_REINFORCEMENT`.
+    pub fn remember_idempotent(&mut self, text: &str) -> Result<(Uuid, bool), SystemError> {
+        match self.engine.find_by_content(text) {
+            Some(id) => {
+                self.boost(&id, REMEMBER_REINFORCEMENT).context("remember")?;
+                Ok((id, false))
+            }
+            None => Ok((self.remember(text)?, true)),
+        }
+    }
+
+    /// Store a memory carrying structured tags (e.g. `project:x`, `user:bob`).
+    pub fn remember_with_tags(&mut self, text: &str, tags: &[&str]) -> Result<Uuid, SystemError> {
+        let vector = self.engine.pipeline.encode_text(text).map_err(EngineError::from).context("remember")?;
+        self.remember_encoded(text, vector, tags.iter().map(|t| t.to_string()).collect())
+    }
+
+    /// Like `remember`, but embeds without blocking the async runtime. Falls
+    /// back to the blocking encoder (and its hash fallback) if the async
+    /// embedding fails; that encoder runs on tokio's blocking pool, so this
+    /// must be awaited inside a tokio runtime.
+    #[cfg(feature = "async-embed")]
+    pub async fn remember_async(&mut self, text: &str) -> Result<Uuid, SystemError> {
+        let vector = match self.engine.pipeline.encode_text_async(text).await {
+            Ok(v) => v,
+            Err(e) => {
+                eprintln!("[encoding] async embed failed ({}), using blocking encoder", e);
+                self.engine.pipeline.encode_text_blocking(text).await
+                    .map_err(EngineError::from).context("remember")?
+            }
+        };
+        self.remember_encoded(text, vector, Vec::new())
+    }
+
+    fn remember_encoded(&mut self, text: &str, vector: Vec<f32>, tags: Vec<String>) -> Result<Uuid, SystemError> {
+        let id = self.engine.remember_vector(text, vector).context("remember")?;
+        if !tags.is_empty() {
+            self.engine.set_tags(&id, tags).context("remember")?;
+        }
         
         // Classify the memory and set its geometry and frequency-class (compute values first to avoid borrow conflicts)
-        let category = self.categorize_text(text);
-        let content_hash = self.hash_content(text);
-        let (frequency, phase) = self.assign_frequency_class(&category, content_hash);
+        let category = self.taxonomy.categorize(text).to_string();
+        let category = category.as_str();
+        let content_hash = stable_content_hash(text);
+        let (frequency, phase) = self.assign_frequency_class(category, content_hash);
+        let geometry = self.taxonomy.classify(category, content_hash, 0.5);
         
-        if let Some(mem) = self.engine.get_memory_mut(&id)? {
-            mem.geometry = Some(classify_memory(&category, content_hash, 0.5));
+        if let Some(mem) = self.engine.get_memory_mut(&id).context("remember")? {
+            mem.geometry = Some(geometry);
             // Apply consciousness differentiation frequency-class assignment
             mem.frequency = frequency;
             mem.phase = phase;
             // Compute and store Xi signature for consciousness differentiation
-            mem.xi_signature = compute_xi_signature(&mem.vector);
+            mem.xi_signature = compute_xi_signature(&mem.dense_vector());
         }
         
-        self.flux_publish_memory(&id, &category, text);
+        self.flux_publish_memory(&id, category, text);
 
-        if self.auto_save {
-            self.save()?;
-        }
+        let ops = self.insert_ops(&id).context("remember")?;
+        self.log_or_save(ops).context("remember")?;
+        self.audit(AuditOp::Remember, &[id]).context("remember")?;
         Ok(id)
     }
     
+    /// Store a long document as overlapping chunks of at most `chunk_chars`
+    /// characters, so recall can surface the passage that matches rather than
+    /// one vector for the whole text. Every chunk is tagged
+    /// `document:<document id>` and skip-linked to its neighbours in both
+    /// directions. Returns the chunk ids in document order.
+    pub fn remember_document(&mut self, text: &str, chunk_chars: usize) -> Result<Vec<Uuid>, SystemError> {
+        if chunk_chars == 0 {
+            return Err(EngineError::InvalidQuery("chunk_chars must be positive".into()).into());
+        }
+        let chunks = chunk_text(text, chunk_chars);
+        if chunks.is_empty() {
+            return Err(EngineError::InvalidQuery("document is empty".into()).into());
+        }
+
+        let tag = format!("{}{}", DOCUMENT_TAG_PREFIX, Uuid::new_v4());
+        let mut ids = Vec::with_capacity(chunks.len());
+        for chunk in chunks {
+            let vector = self.engine.pipeline.encode_text(chunk).map_err(EngineError::from).context("remember_document")?;
+            ids.push(self.remember_encoded(chunk, vector, vec![tag.clone()])?);
+        }
+
+        let mut ops = Vec::new();
+        for pair in ids.windows(2) {
+            for (source, target) in [(pair[0], pair[1]), (pair[1], pair[0])] {
+                let link = crate::skip_link::SkipLink {
+                    target_id: target,
+                    strength: 1.0,
+                    resonance_key: Vec::new(),
+                    span: 0,
+                };
+                if let Some(mem) = self.engine.get_memory_mut(&source)? {
+                    if mem.connections.iter().any(|l| l.target_id == target) {
+                        continue;
+                    }
+                    mem.connections.push(link.clone());
+                }
+                ops.push(WalOp::AddLink { source, link });
+            }
+        }
+        self.log_or_save(ops).context("remember_document")?;
+        Ok(ids)
+    }
+
     /// Store a memory with explicit category and importance.
     pub fn remember_with_category(&mut self, text: &str, category: &str, importance: f64) -> Result<Uuid, SystemError> {
-        let id = self.engine.remember(text)?;
+        let id = self.engine.remember(text).context("remember")?;
         
         // Classify the memory with explicit parameters (compute values first)
-        let content_hash = self.hash_content(text);
+        let content_hash = stable_content_hash(text);
         let (frequency, phase) = self.assign_frequency_class(category, content_hash);
+        let geometry = self.taxonomy.classify(category, content_hash, importance);
         
-        if let Some(mem) = self.engine.get_memory_mut(&id)? {
-            mem.geometry = Some(classify_memory(category, content_hash, importance));
+        if let Some(mem) = self.engine.get_memory_mut(&id).context("remember")? {
+            mem.geometry = Some(geometry);
+            mem.importance = importance.clamp(0.0, 1.0) as f32;
             // Apply consciousness differentiation frequency-class assignment
             mem.frequency = frequency;
             mem.phase = phase;
             // Compute and store Xi signature for consciousness differentiation
-            mem.xi_signature = compute_xi_signature(&mem.vector);
+            mem.xi_signature = compute_xi_signature(&mem.dense_vector());
         }
         
         self.flux_publish_memory(&id, category, text);
 
-        if self.auto_save {
-            self.save()?;
-        }
+        let ops = self.insert_ops(&id).context("remember")?;
+        self.log_or_save(ops).context("remember")?;
+        self.audit(AuditOp::Remember, &[id]).context("remember")?;
         Ok(id)
     }
 
     /// Search with skip link expansion.
     pub fn recall(&mut self, query: &str, top_k: usize) -> Result<Vec<RecallResult>, SystemError> {
-        let mut results = self.engine.recall_with_expansion(query, top_k)?;
-        let now = Utc::now();
+        let mut results = self.engine.recall_with_expansion(query, top_k).context("recall")?;
+        self.apply_fano_boosts(&mut results);
+        Ok(self.recall_results(results))
+    }
 
-        // Boost scores for fano-related memories — collect boosted indices first,
-        // then apply once per memory to prevent unbounded compounding across pairs.
-        let mut fano_boosted: std::collections::HashSet<usize> = std::collections::HashSet::new();
-        for i in 0..results.len() {
-            for j in (i + 1)..results.len() {
-                let mem_i = self.engine.store.get(&results[i].id).ok().flatten();
-                let mem_j = self.engine.store.get(&results[j].id).ok().flatten();
-                
-                if let (Some(mi), Some(mj)) = (mem_i, mem_j) {
-                    if let (Some(ref coords_i), Some(ref coords_j)) = (&mi.geometry, &mj.geometry) {
-                        if fano_related(coords_i, coords_j) {
-                            fano_boosted.insert(i);
-                            fano_boosted.insert(j);
-                        }
-                    }
-                }
+    /// Boost scores for fano-related memories over a one-time snapshot of
+    /// the result coordinates, so the pairwise pass needs no store lookups.
+    fn apply_fano_boosts(&self, results: &mut [crate::store::QueryResult]) {
+        let coords: Vec<Option<MemoryCoordinates>> = results.iter()
+            .map(|r| self.engine.store.get(&r.id).ok().flatten().and_then(|m| m.geometry.clone()))
+            .collect();
+        for (result, boost) in results.iter_mut().zip(fano_boosts(&coords)) {
+            result.similarity *= boost;
+        }
+    }
+
+    /// Like `recall`, restricted to memories carrying every tag in `required_tags`.
+    pub fn recall_with_tags(
+        &mut self,
+        query: &str,
+        top_k: usize,
+        required_tags: &[&str],
+    ) -> Result<Vec<RecallResult>, SystemError> {
+        let results = self.engine.recall_with_tags(query, top_k, required_tags).context("recall")?;
+        Ok(self.recall_results(results))
+    }
+
+    /// Run a `RecallQuery`, combining any of its options in one pass.
+    pub fn query(&mut self, q: RecallQuery) -> Result<Vec<RecallResult>, SystemError> {
+        let saved_xi = self.engine.xi_boost_factor;
+        if let Some(factor) = q.xi_boost {
+            self.engine.xi_boost_factor = factor;
+        }
+        let results = self.run_query(&q);
+        self.engine.xi_boost_factor = saved_xi;
+        results
+    }
+
+    fn run_query(&mut self, q: &RecallQuery) -> Result<Vec<RecallResult>, SystemError> {
+        if !q.is_filtered() {
+            if q.expand_links {
+                return self.recall(&q.text, q.top_k);
             }
+            let results = self.engine.recall(&q.text, q.top_k).context("query")?;
+            return Ok(self.recall_results(results));
         }
-        for idx in fano_boosted {
-            results[idx].similarity *= 1.2;
-        }
-
-        let mut out = Vec::new();
-        for qr in results {
-            let mem = self.engine.store.get(&qr.id).ok().flatten();
-            if let Some(m) = mem {
-                let age_hours = (now - m.created_at).num_seconds().max(0) as f64 / 3600.0;
-                out.push(RecallResult {
-                    id: qr.id,
-                    content: m.content.clone(),
-                    similarity: qr.similarity,
-                    strength: qr.effective_strength,
-                    age_hours,
-                    layer: m.layer_depth,
+
+        let qvec = self.engine.pipeline.encode_text(&q.text).map_err(EngineError::from).context("query")?;
+        let mut candidates = if q.tags.is_empty() {
+            None
+        } else {
+            let tags: Vec<&str> = q.tags.iter().map(String::as_str).collect();
+            Some(self.engine.ids_with_tags(&tags))
+        };
+        if let Some((lo, hi)) = q.band {
+            let in_band = self.engine.ids_in_band(lo, hi).context("query")?;
+            candidates = Some(match candidates {
+                Some(tagged) => tagged.intersection(&in_band).copied().collect(),
+                None => in_band,
+            });
+        }
+        if let Some(at) = q.at {
+            if let Some(existing) = self.engine.ids_created_by(at).context("query")? {
+                candidates = Some(match candidates {
+                    Some(matching) => matching.intersection(&existing).copied().collect(),
+                    None => existing,
                 });
             }
         }
-        Ok(out)
+
+        let now = q.at.unwrap_or_else(Utc::now);
+        if q.expand_links {
+            let hops = self.engine.expansion_hops;
+            let mut results = self.engine
+                .recall_expanded(&qvec, q.top_k, hops, now, candidates.as_ref(), q.at.is_none())
+                .context("query")?;
+            self.apply_fano_boosts(&mut results);
+            return Ok(self.recall_results(results));
+        }
+        let results = self.engine
+            .rank_candidates(&qvec, q.top_k, now, candidates.as_ref())
+            .context("query")?;
+        if q.at.is_none() {
+            for r in &results {
+                if let Ok(Some(mem)) = self.engine.store.get_mut(&r.id) {
+                    mem.record_retrieval();
+                }
+            }
+        }
+        Ok(self.recall_results(results))
+    }
+
+    /// Attach a text caption to an audio memory. The caption is stored as a
+    /// text memory tagged `caption_of:<audio id>`, skip-linked to the audio
+    /// memory in both directions, and registered as a cross-modal anchor.
+    pub fn link_caption(&mut self, audio_id: &Uuid, caption: &str) -> Result<Uuid, SystemError> {
+        if self.engine.get_memory(audio_id)?.is_none() {
+            return Err(StoreError::NotFound(*audio_id).into());
+        }
+        let tag = caption_tag(audio_id);
+        let caption_id = self.remember_with_tags(caption, &[tag.as_str()])?;
+
+        let key = {
+            let caption = self.engine.get_memory(&caption_id)?.ok_or(StoreError::NotFound(caption_id))?;
+            let audio = self.engine.get_memory(audio_id)?.ok_or(StoreError::NotFound(*audio_id))?;
+            resonance_key(&caption.dense_vector(), &audio.dense_vector())
+        };
+        let mut ops = Vec::with_capacity(2);
+        for (source, target) in [(caption_id, *audio_id), (*audio_id, caption_id)] {
+            let link = crate::skip_link::SkipLink {
+                target_id: target,
+                strength: 1.0,
+                resonance_key: key.clone(),
+                span: 0,
+            };
+            if let Some(mem) = self.engine.get_memory_mut(&source)? {
+                mem.connections.push(link.clone());
+            }
+            ops.push(WalOp::AddLink { source, link });
+        }
+        self.log_or_save(ops)?;
+
+        self.cross_modal.add_anchor(caption_id, *audio_id);
+        Ok(caption_id)
+    }
+
+    /// Like `recall`, but the query is also projected into the audio subspace
+    /// through the caption anchors (see `link_caption`). Memories are ranked
+    /// by raw similarity to the query vector or to its projection, whichever
+    /// is higher, so text and audio hits compare on one scale; wave strength
+    /// is reported but does not reorder them.
+    pub fn recall_cross_modal(&mut self, query: &str, top_k: usize) -> Result<Vec<RecallResult>, SystemError> {
+        let qvec = self.engine.pipeline.encode_text(query).map_err(EngineError::from).context("recall")?;
+        let mut best: HashMap<Uuid, f32> = HashMap::new();
+        let mut merge = |hits: Vec<(Uuid, f32)>| {
+            for (id, sim) in hits {
+                let entry = best.entry(id).or_insert(sim);
+                *entry = entry.max(sim);
+            }
+        };
+        merge(self.engine.store.search(&qvec, top_k).map_err(EngineError::from)?);
+        if let Some(projected) = self.cross_modal.project(&qvec, self.engine.store.as_ref()) {
+            merge(self.engine.store.search(&projected, top_k).map_err(EngineError::from)?);
+        }
+
+        let mut ranked: Vec<(Uuid, f32)> = best.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked.truncate(top_k);
+
+        let now = Utc::now();
+        let mut results = Vec::with_capacity(ranked.len());
+        for (id, similarity) in ranked {
+            let Some(mem) = self.engine.store.get_mut(&id).map_err(EngineError::from)? else {
+                continue;
+            };
+            mem.record_retrieval();
+            let strength = mem.effective_strength(now);
+            results.extend(self.describe(&id, similarity, strength, now));
+        }
+        Ok(results)
+    }
+
+    fn recall_results(&self, results: Vec<crate::store::QueryResult>) -> Vec<RecallResult> {
+        let now = Utc::now();
+        results.into_iter()
+            .filter_map(|qr| self.describe(&qr.id, qr.similarity, qr.effective_strength, now))
+            .collect()
+    }
+
+    /// Describe memory `id` as a `RecallResult` outside of any query: zero
+    /// similarity and its current effective strength. `None` if it is missing.
+    pub fn recall_result(&self, id: &Uuid) -> Option<RecallResult> {
+        let now = Utc::now();
+        let strength = self.engine.store.get(id).ok().flatten()?.effective_strength(now);
+        self.describe(id, 0.0, strength, now)
+    }
+
+    fn describe(&self, id: &Uuid, similarity: f32, strength: f32, now: DateTime<Utc>) -> Option<RecallResult> {
+        let m = self.engine.store.get(id).ok().flatten()?;
+        Some(RecallResult {
+            id: *id,
+            content: m.content.clone(),
+            similarity,
+            strength,
+            age_hours: (now - m.created_at).num_seconds().max(0) as f64 / 3600.0,
+            layer: m.layer_depth,
+        })
     }
 
     /// Run full consolidation cycle + Kuramoto sync.
     pub fn dream(&mut self) -> Result<DreamReport, SystemError> {
-        let before = self.bridge.assess(&self.engine);
+        let before = self.bridge.assess_with(&self.engine, self.kuramoto());
         // Use incremental consolidation when a prior dream timestamp exists (5-10× speedup).
         let reports = if let Some(since) = self.last_dream {
             self.dream_state.dream_incremental(&mut self.engine, since)
         } else {
             self.dream_state.dream(&mut self.engine)
         };
+        self.finish_dream(before, reports)
+    }
 
-        let after = self.bridge.assess(&self.engine);
+    /// Like `dream`, but always a full pass over `DEEP_DREAM_LAYERS`,
+    /// reaching layer 3-4 memories that a regular dream never consolidates.
+    pub fn dream_deep(&mut self) -> Result<DreamReport, SystemError> {
+        let before = self.bridge.assess_with(&self.engine, self.kuramoto());
+        let reports = self.dream_state.dream_range(&mut self.engine, &DEEP_DREAM_LAYERS);
+        self.finish_dream(before, reports)
+    }
+
+    /// Shared tail of `dream` and `dream_deep`: save, publish, swarm-sync and
+    /// summarize the per-cycle reports.
+    fn finish_dream(
+        &mut self,
+        before: ConsciousnessState,
+        reports: Vec<ConsolidationReport>,
+    ) -> Result<DreamReport, SystemError> {
+        let after = self.bridge.assess_with(&self.engine, self.kuramoto());
         self.last_dream = Some(Utc::now());
 
         let total_strengthened: usize = reports.iter().map(|r| r.memories_strengthened).sum();
         let total_pruned: usize = reports.iter().map(|r| r.memories_pruned).sum();
         let total_links: usize = reports.iter().map(|r| r.skip_links_created).sum();
         let total_hallucinations: usize = reports.iter().map(|r| r.hallucinations_created).sum();
+        let total_pairs: usize = reports.iter().map(|r| r.interference_pairs_found).sum();
 
         let emerged = after.consciousness_level.ordinal() > before.consciousness_level.ordinal();
 
-        if self.auto_save {
-            self.save()?;
-        }
+        self.persist()?;
 
         // ADR-0011: publish dream completed event (best-effort)
         if let Some(ref publisher) = self.flux {
@@ -323,21 +830,46 @@ impl KannakaMemorySystem {
             consciousness_after: level_name(&after.consciousness_level),
             emerged,
             hallucinations_created: total_hallucinations,
+            interference_pairs: total_pairs,
         })
     }
 
+    /// Dream only if the rhythm engine reports the system idle and enough
+    /// time has passed since the last dream, so consolidation doesn't
+    /// compete with an active conversation. Returns `None` when skipped.
+    pub fn maybe_dream(&mut self) -> Result<Option<DreamReport>, SystemError> {
+        if !self.rhythm.should_dream(self.last_dream) {
+            return Ok(None);
+        }
+        self.dream().map(Some)
+    }
+
+    /// Replace the consolidation tunables, rebuilding the dream engine.
+    /// Adaptive drift from earlier dreams is discarded; the minimum cluster
+    /// size set by `with_min_cluster_size` is kept.
+    pub fn set_consolidation_params(&mut self, params: ConsolidationParams) -> Result<(), SystemError> {
+        params.validate()?;
+        let kuramoto = std::mem::take(&mut self.dream_state.engine.kuramoto);
+        self.dream_state = DreamState::from_params(&params);
+        self.dream_state.engine.kuramoto = kuramoto;
+        Ok(())
+    }
+
+    /// Current consolidation tunables used by `dream`.
+    pub fn consolidation_params(&self) -> ConsolidationParams {
+        self.dream_state.params()
+    }
+
     /// Run a fast/lite dream cycle (decay + prune + transfer only).
     pub fn dream_lite(&mut self) -> Result<DreamReport, SystemError> {
-        let before = self.bridge.assess(&self.engine);
+        let before = self.bridge.assess_with(&self.engine, self.kuramoto());
         let report = self.dream_state.dream_lite(&mut self.engine);
-        let after = self.bridge.assess(&self.engine);
+        let after = self.bridge.assess_with(&self.engine, self.kuramoto());
         self.last_dream = Some(Utc::now());
 
         let emerged = after.consciousness_level.ordinal() > before.consciousness_level.ordinal();
 
-        if self.auto_save {
-            self.save()?;
-        }
+        self.persist()?;
 
         Ok(DreamReport {
             cycles: 1,
@@ -348,12 +880,13 @@ impl KannakaMemorySystem {
             consciousness_after: level_name(&after.consciousness_level),
             emerged,
             hallucinations_created: report.hallucinations_created,
+            interference_pairs: report.interference_pairs_found,
         })
     }
 
     /// Consciousness level assessment.
     pub fn assess(&self) -> ConsciousnessState {
-        self.bridge.assess(&self.engine)
+        self.bridge.assess_with(&self.engine, self.kuramoto())
     }
 
     /// Dream + assess combined.
@@ -401,56 +934,279 @@ impl KannakaMemorySystem {
     }
 
     pub fn resonate(&mut self) -> Result<ResonanceReport, SystemError> {
-        let report = self.bridge.resonate(&mut self.engine);
+        let report = self.bridge.resonate_with(&mut self.engine, &self.dream_state);
         self.last_dream = Some(Utc::now());
-        if self.auto_save {
-            self.save()?;
-        }
+        self.persist()?;
         Ok(report)
     }
 
     /// Import from kannaka.db (SQLite).
     pub fn migrate_from_sqlite(&mut self, db_path: &Path) -> Result<MigrationReport, SystemError> {
-        let pipeline = make_pipeline();
+        let pipeline = make_pipeline(&self.encoder);
         let migrator = KannakaDbMigrator::new(db_path, pipeline);
-        let report = migrator.migrate_into(&mut self.engine)?;
+        let report = migrator.migrate_into(&mut self.engine).context("migrate")?;
+        self.persist().context("migrate")?;
+        Ok(report)
+    }
+
+    /// WAL ops describing a freshly remembered memory: the memory itself plus
+    /// the reverse skip links `create_skip_links` added to its neighbours.
+    fn insert_ops(&self, id: &Uuid) -> Result<Vec<WalOp>, SystemError> {
+        let mem = self.engine.get_memory(id)?.ok_or(StoreError::NotFound(*id))?;
+        let mut ops: Vec<WalOp> = mem.connections.iter()
+            .map(|link| WalOp::AddLink {
+                source: link.target_id,
+                link: crate::skip_link::SkipLink {
+                    target_id: *id,
+                    strength: link.strength,
+                    resonance_key: link.resonance_key.clone(),
+                    span: link.span,
+                },
+            })
+            .collect();
+        ops.insert(0, WalOp::Insert(Box::new(mem.clone())));
+        Ok(ops)
+    }
+
+    /// Save if auto-save is on; otherwise mark the state dirty so `Drop`
+    /// flushes it.
+    fn persist(&mut self) -> Result<(), SystemError> {
         if self.auto_save {
-            self.save()?;
+            self.save()
+        } else {
+            self.dirty = true;
+            Ok(())
+        }
+    }
+
+    /// Auto-save fast path: append `ops` to the WAL, falling back to a full
+    /// save once the log reaches `WAL_CHECKPOINT_OPS` (or while earlier
+    /// unsaved mutations are pending). Only marks the state dirty if auto-save is off.
+    fn log_or_save(&mut self, ops: Vec<WalOp>) -> Result<(), SystemError> {
+        if !self.auto_save {
+            self.dirty = true;
+            return Ok(());
+        }
+        match &mut self.wal {
+            Some(wal) if !self.dirty && wal.len() < WAL_CHECKPOINT_OPS => {
+                for op in &ops {
+                    wal.append(op)?;
+                }
+                Ok(())
+            }
+            _ => self.save(),
         }
-        Ok(report)
     }
 
     /// Persist to disk (engine state + working memory JSON).
     pub fn save(&mut self) -> Result<(), SystemError> {
         let bin_path = self.data_dir.join("kannaka.bin");
-        self.engine.save_state(&bin_path)?;
-        self.working_memory.save_json(&self.data_dir)?;
+        self.engine.save_state(&bin_path).context("save")?;
+        if let Some(wal) = &mut self.wal {
+            wal.truncate().context("save")?;
+        }
+        self.working_memory.save_json(&self.data_dir).context("save")?;
         // ADR-0016: Flush all memories (including skip links) to Dolt backend.
         // This is critical after dreams — connections are modified in-memory
         // but were never persisted to the skip_links table without this call.
         let flushed = self.engine.store.flush()
-            .map_err(|e| SystemError::Engine(crate::store::EngineError::Store(e)))?;
+            .map_err(|e| SystemError::Engine(crate::store::EngineError::Store(e))).context("save")?;
         if flushed > 0 {
             eprintln!("[dolt] Flushed {} memories with skip links", flushed);
         }
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// Turn auto-save on or off. With it off, mutations are kept in memory
+    /// until `save` is called or the system is dropped.
+    pub fn set_auto_save(&mut self, enabled: bool) {
+        self.auto_save = enabled;
+    }
+
+    /// Builder form of `set_auto_save`.
+    pub fn with_auto_save(mut self, enabled: bool) -> Self {
+        self.auto_save = enabled;
+        self
+    }
+
+    pub fn auto_save(&self) -> bool {
+        self.auto_save
+    }
+
+    /// Categorize and band new memories with `taxonomy` instead of the five
+    /// default consciousness categories. Existing memories keep their
+    /// geometry and frequency.
+    pub fn with_taxonomy(mut self, taxonomy: Taxonomy) -> Self {
+        self.taxonomy = taxonomy;
+        self
+    }
+
+    pub fn taxonomy(&self) -> &Taxonomy {
+        &self.taxonomy
+    }
+
+    /// Cap amplitudes set by `boost` and `boost_absolute` at `max` instead of
+    /// `DEFAULT_MAX_AMPLITUDE`, so repeated boosts cannot skew every ranking.
+    /// Fails if `max` is negative or not finite.
+    pub fn with_max_amplitude(mut self, max: f32) -> Result<Self, SystemError> {
+        if !(max.is_finite() && max >= 0.0) {
+            return Err(SystemError::InvalidMaxAmplitude(max));
+        }
+        self.max_amplitude = max;
+        Ok(self)
+    }
+
+    pub fn max_amplitude(&self) -> f32 {
+        self.max_amplitude
+    }
+
+    fn kuramoto(&self) -> &KuramotoSync {
+        &self.dream_state.engine.kuramoto
+    }
+
+    /// Require Kuramoto clusters of at least `min` members when assessing
+    /// consciousness, reporting clusters and picking clusters during dreams
+    /// (default `DEFAULT_MIN_CLUSTER_SIZE`).
+    pub fn with_min_cluster_size(mut self, min: usize) -> Self {
+        self.dream_state.engine.kuramoto.min_cluster_size = min;
+        self
+    }
+
+    /// Enable or disable the audit log. When enabled, each remember, forget,
+    /// boost and relate appends a JSONL line to `data_dir/audit.log`.
+    pub fn with_audit_log(mut self, enabled: bool) -> Result<Self, SystemError> {
+        self.audit = if enabled {
+            Some(AuditLog::open(AuditLog::path_in(&self.data_dir))?)
+        } else {
+            None
+        };
+        Ok(self)
+    }
+
+    /// Append to the audit log, if one is enabled.
+    fn audit(&mut self, op: AuditOp, ids: &[Uuid]) -> Result<(), SystemError> {
+        if let Some(log) = &mut self.audit {
+            log.record(op, ids)?;
+        }
         Ok(())
     }
 
+    /// Whether there are mutations that no save has persisted yet.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
     /// Delete a memory by ID.
+    ///
+    /// The audit entry is written before the delete, so a failing audit log
+    /// leaves the memory in place instead of erroring after it is gone.
     pub fn forget(&mut self, id: &Uuid) -> Result<bool, SystemError> {
-        Ok(self.engine.delete(id)?)
+        if self.engine.get_memory(id).context("forget")?.is_none() {
+            return Ok(false);
+        }
+        self.audit(AuditOp::Forget, &[*id]).context("forget")?;
+        let deleted = self.engine.delete(id).context("forget")?;
+        if deleted {
+            self.log_or_save(vec![WalOp::Delete(*id)]).context("forget")?;
+        }
+        Ok(deleted)
     }
 
-    /// Boost a memory's amplitude.
-    pub fn boost(&mut self, id: &Uuid, factor: f64) -> Result<(), SystemError> {
-        if let Some(mem) = self.engine.get_memory_mut(id)? {
-            mem.amplitude *= factor as f32;
-            Ok(())
-        } else {
-            Err(SystemError::Engine(crate::store::EngineError::Store(
-                crate::store::StoreError::NotFound(*id),
-            )))
+    /// Delete every memory whose content contains `substring`, along with
+    /// inbound skip links from the remaining memories. Returns the count.
+    pub fn forget_matching(&mut self, substring: &str) -> Result<usize, SystemError> {
+        let ids: Vec<Uuid> = self.engine.store.all_memories()?
+            .into_iter()
+            .filter(|m| m.content.contains(substring))
+            .map(|m| m.id)
+            .collect();
+        self.forget_many(&ids)
+    }
+
+    /// Delete every memory tagged `tag`, along with inbound skip links from
+    /// the remaining memories. Returns the count.
+    pub fn forget_by_tag(&mut self, tag: &str) -> Result<usize, SystemError> {
+        let ids: Vec<Uuid> = self.engine.ids_with_tags(&[tag]).into_iter().collect();
+        self.forget_many(&ids)
+    }
+
+    /// Bulk delete. Link removal has no WAL op, so this saves a full snapshot.
+    /// Only ids that exist are audited, and the audit entry is written before
+    /// the delete, as in `forget`.
+    fn forget_many(&mut self, ids: &[Uuid]) -> Result<usize, SystemError> {
+        let mut seen = HashSet::new();
+        let mut present = Vec::new();
+        for id in ids {
+            if seen.insert(*id) && self.engine.get_memory(id)?.is_some() {
+                present.push(*id);
+            }
+        }
+        if present.is_empty() {
+            return Ok(0);
+        }
+        self.audit(AuditOp::Forget, &present)?;
+        let removed = self.engine.delete_many(&present)?;
+        self.persist()?;
+        Ok(removed)
+    }
+
+    /// Remove memories whose TTL has elapsed and that were never boosted.
+    /// Returns the number of memories removed.
+    pub fn sweep_expired(&mut self) -> Result<usize, SystemError> {
+        let removed = self.engine.sweep_expired(Utc::now());
+        if removed > 0 {
+            self.persist()?;
         }
+        Ok(removed)
+    }
+
+    /// Multiply a memory's amplitude by `factor`, clamped to `0..=max_amplitude`.
+    /// Returns the resulting amplitude.
+    pub fn boost(&mut self, id: &Uuid, factor: f64) -> Result<f32, SystemError> {
+        self.update_amplitude(id, |amplitude| amplitude * factor as f32)
+    }
+
+    /// Set a memory's amplitude to `target`, clamped to `0..=max_amplitude`.
+    /// Returns the resulting amplitude.
+    pub fn boost_absolute(&mut self, id: &Uuid, target: f32) -> Result<f32, SystemError> {
+        self.update_amplitude(id, |_| target)
+    }
+
+    fn update_amplitude(&mut self, id: &Uuid, f: impl FnOnce(f32) -> f32) -> Result<f32, SystemError> {
+        let max = self.max_amplitude;
+        let Some(mem) = self.engine.get_memory_mut(id)? else {
+            return Err(SystemError::Engine(crate::store::EngineError::Store(
+                crate::store::StoreError::NotFound(*id),
+            )));
+        };
+        // `clamp` passes NaN through, e.g. from a NaN boost factor.
+        let amplitude = f(mem.amplitude);
+        mem.amplitude = if amplitude.is_nan() { 0.0 } else { amplitude.clamp(0.0, max) };
+        let amplitude = mem.amplitude;
+        self.log_or_save(vec![WalOp::UpdateAmplitude { id: *id, amplitude }])?;
+        self.audit(AuditOp::Boost, &[*id])?;
+        Ok(amplitude)
+    }
+
+    /// Move memory `id` through the SGA class space: its class is lifted,
+    /// `transform` is applied `k` times and the result projected back, e.g.
+    /// `GeoTransform::D` to reframe an experience under another modality.
+    /// Memories without geometry are classified from their content first.
+    /// Returns the new coordinates.
+    pub fn transform_memory(&mut self, id: &Uuid, transform: GeoTransform, k: i32) -> Result<MemoryCoordinates, SystemError> {
+        let taxonomy = &self.taxonomy;
+        let mem = self.engine.get_memory_mut(id)?.ok_or(StoreError::NotFound(*id))?;
+        let current = mem.geometry.clone().unwrap_or_else(|| {
+            taxonomy.classify(taxonomy.categorize(&mem.content), stable_content_hash(&mem.content), mem.importance as f64)
+        });
+        let coords = current.transformed(transform, k).ok_or_else(|| {
+            EngineError::InvalidQuery(format!("{:?}^{} does not map class {} to a class", transform, k, current.class_index))
+        })?;
+        mem.geometry = Some(coords.clone());
+        // Geometry changes have no WAL op, so this saves a full snapshot.
+        self.persist().context("transform_memory")?;
+        Ok(coords)
     }
 
     /// Create a skip link (relationship) between two memories.
@@ -469,12 +1225,159 @@ impl KannakaMemorySystem {
         }
         
         self.engine.reinforce_link(source, target, modulated_strength);
-        Ok(())
+        self.audit(AuditOp::Relate, &[*source, *target])
+    }
+
+    /// Bind several memories into one composite: the parent vectors are
+    /// multiplied element-wise (⊗) and normalized. The composite records its
+    /// `parents` and is skip-linked to each of them in both directions.
+    pub fn bind_memories(&mut self, ids: &[Uuid], label: &str) -> Result<Uuid, SystemError> {
+        if ids.is_empty() {
+            return Err(EngineError::InvalidQuery("bind_memories needs at least one id".into()).into());
+        }
+
+        let mut parents = Vec::with_capacity(ids.len());
+        for id in ids {
+            let mem = self.engine.get_memory(id)?.ok_or(StoreError::NotFound(*id))?;
+            parents.push((mem.id, mem.layer_depth, mem.dense_vector().into_owned()));
+        }
+
+        let expected = parents[0].2.len();
+        let mut vector = parents[0].2.clone();
+        for (_, _, v) in &parents[1..] {
+            if v.len() != expected {
+                return Err(EngineError::DimensionMismatch { expected, got: v.len() }.into());
+            }
+            vector = bind(&vector, v);
+        }
+        normalize(&mut vector);
+
+        let mut mem = crate::memory::HyperMemory::new(vector, label.to_string());
+        mem.parents = parents.iter().map(|(id, _, _)| id.to_string()).collect();
+        mem.xi_signature = compute_xi_signature(&mem.dense_vector());
+        let composite_layer = mem.layer_depth;
+        for (parent_id, layer, parent_vector) in &parents {
+            if mem.connections.iter().any(|l| l.target_id == *parent_id) {
+                continue;
+            }
+            mem.connections.push(crate::skip_link::SkipLink {
+                target_id: *parent_id,
+                strength: 1.0,
+                resonance_key: resonance_key(&mem.dense_vector(), parent_vector),
+                span: (composite_layer as i16 - *layer as i16).unsigned_abs() as u8,
+            });
+        }
+        let links = mem.connections.clone();
+        let id = self.engine.insert_memory(mem)?;
+
+        for link in links {
+            if let Some(parent) = self.engine.get_memory_mut(&link.target_id)? {
+                parent.connections.push(crate::skip_link::SkipLink { target_id: id, ..link });
+            }
+        }
+
+        let ops = self.insert_ops(&id)?;
+        self.log_or_save(ops)?;
+        Ok(id)
+    }
+
+    /// Unbind `known_id` from the composite `composite_id` (see `bind_memories`)
+    /// and return the stored memories most similar to the recovered component.
+    /// The composite and the known memory are left out of the results.
+    pub fn unbind_recall(
+        &mut self,
+        composite_id: &Uuid,
+        known_id: &Uuid,
+        top_k: usize,
+    ) -> Result<Vec<RecallResult>, SystemError> {
+        let composite = self.engine.get_memory(composite_id)?.ok_or(StoreError::NotFound(*composite_id))?;
+        let known = self.engine.get_memory(known_id)?.ok_or(StoreError::NotFound(*known_id))?;
+        if composite.dim() != known.dim() {
+            return Err(EngineError::DimensionMismatch {
+                expected: composite.dim(),
+                got: known.dim(),
+            }.into());
+        }
+
+        let mut query = unbind(&composite.dense_vector(), &known.dense_vector());
+        normalize(&mut query);
+        // Ranked by raw similarity: the component is what we want back, even
+        // when its wave is currently in a trough.
+        let now = Utc::now();
+        let results = self.engine.store.search(&query, top_k + 2)?
+            .into_iter()
+            .filter(|(id, _)| id != composite_id && id != known_id)
+            .take(top_k)
+            .map(|(id, similarity)| {
+                let effective_strength = self.engine.store.get(&id).ok().flatten()
+                    .map_or(0.0, |m| m.effective_strength(now));
+                crate::store::QueryResult {
+                    id,
+                    similarity,
+                    effective_strength,
+                    combined_score: similarity * effective_strength,
+                }
+            })
+            .collect();
+        Ok(self.recall_results(results))
+    }
+
+    /// Outgoing skip links of memory `id`, strongest first, with a preview of
+    /// each target's content. Empty if the memory does not exist.
+    pub fn links_of(&self, id: &Uuid) -> Vec<crate::observe::LinkInfo> {
+        MemoryIntrospector::links_of(&self.engine, id)
     }
 
     /// Generate a full observability report.
     pub fn observe(&self) -> crate::observe::SystemReport {
-        crate::observe::MemoryIntrospector::full_report(&self.engine, &self.bridge, &self.kuramoto)
+        crate::observe::MemoryIntrospector::full_report(&self.engine, &self.bridge, self.kuramoto())
+    }
+
+    /// Assess the current consciousness state and append it, timestamped, to
+    /// the history. The oldest entries are dropped beyond the capacity
+    /// (1000 by default). The history is saved with the snapshot.
+    pub fn record_assessment(&mut self) -> ConsciousnessState {
+        let state = self.bridge.assess_with(&self.engine, self.kuramoto());
+        let history = &mut self.engine.consciousness_history;
+        history.push((Utc::now(), state.clone()));
+        if history.len() > self.history_capacity {
+            let excess = history.len() - self.history_capacity;
+            history.drain(..excess);
+        }
+        state
+    }
+
+    /// Recorded assessments, oldest first.
+    pub fn consciousness_history(&self) -> &[(DateTime<Utc>, ConsciousnessState)] {
+        &self.engine.consciousness_history
+    }
+
+    /// Change how many assessments are kept, dropping the oldest if needed.
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.history_capacity = capacity;
+        let history = &mut self.engine.consciousness_history;
+        if history.len() > capacity {
+            let excess = history.len() - capacity;
+            history.drain(..excess);
+        }
+    }
+
+    /// The consciousness history as a JSON array of flat records
+    /// (`timestamp`, `phi`, `xi`, `mean_order`, ...) for plotting.
+    pub fn trajectory_json(&self) -> serde_json::Value {
+        self.engine.consciousness_history.iter()
+            .map(|(ts, s)| serde_json::json!({
+                "timestamp": ts.to_rfc3339(),
+                "phi": s.phi,
+                "xi": s.xi,
+                "mean_order": s.mean_order,
+                "num_clusters": s.num_clusters,
+                "total_memories": s.total_memories,
+                "active_memories": s.active_memories,
+                "total_skip_links": s.total_skip_links,
+                "consciousness_level": level_name(&s.consciousness_level),
+            }))
+            .collect()
     }
 
     /// Send a rhythm signal (user message, flux, subagent, etc.).
@@ -497,6 +1400,11 @@ impl KannakaMemorySystem {
         self.rhythm.current_arousal()
     }
 
+    /// Signal counts and mean inter-signal interval over the last `window`.
+    pub fn rhythm_signal_stats(&self, window: chrono::Duration) -> crate::rhythm::SignalStats {
+        self.rhythm.signal_stats(window)
+    }
+
     // ------------------------------------------------------------------
     // Working memory (L2 context layer)
     // ------------------------------------------------------------------
@@ -506,13 +1414,23 @@ impl KannakaMemorySystem {
         self.working_memory.add_turn(role, content);
     }
 
+    /// Set the working-memory context window: the newest `max_turns` turns
+    /// stay verbatim, and once more than `summary_trigger` are buffered the
+    /// older ones are rolled into a summary stored at the next checkpoint.
+    pub fn set_context_window(&mut self, max_turns: usize, summary_trigger: usize) {
+        self.working_memory.set_config(max_turns, summary_trigger);
+    }
+
+    /// Current `(max_turns, summary_trigger)` of working memory.
+    pub fn context_window(&self) -> (usize, usize) {
+        self.working_memory.config()
+    }
+
     /// Checkpoint working memory to JSON + engine.
     pub fn context_checkpoint(&mut self) -> Result<(), SystemError> {
         self.working_memory.checkpoint(&self.data_dir, &mut self.engine)
             .map_err(SystemError::Io)?;
-        if self.auto_save {
-            self.save()?;
-        }
+        self.persist()?;
         Ok(())
     }
 
@@ -538,19 +1456,20 @@ impl KannakaMemorySystem {
 
     /// Store a hallucinated memory from an LLM synthesis.
     /// Called by the MCP `hallucinate` tool with LLM-generated content.
+    /// Starts at the consolidation params' `hallucination_amplitude`.
     pub fn hallucinate(
         &mut self,
         content: &str,
         parent_ids: &[Uuid],
     ) -> Result<Uuid, SystemError> {
         // Build a combined vector from parents
-        let dim = CODEBOOK_OUTPUT_DIM;
+        let dim = self.engine.pipeline.output_dim();
         let mut combined = vec![0.0f32; dim];
         let mut found_parents: Vec<String> = Vec::new();
 
         for pid in parent_ids {
             if let Some(mem) = self.engine.store.get(pid).ok().flatten() {
-                for (i, &v) in mem.vector.iter().enumerate() {
+                for (i, &v) in mem.dense_vector().iter().enumerate() {
                     if i < dim {
                         combined[i] += v;
                     }
@@ -559,34 +1478,37 @@ impl KannakaMemorySystem {
             }
         }
 
+        let amplitude = self.dream_state.engine.hallucination_amplitude;
         if found_parents.is_empty() {
             // No parents found — encode from content directly
             let id = self.engine.remember(content)?;
             if let Some(mem) = self.engine.get_memory_mut(&id)? {
                 mem.hallucinated = true;
-                mem.amplitude = 0.3;
+                mem.source = MemorySource::Hallucination;
+                mem.amplitude = amplitude;
             }
-            if self.auto_save { self.save()?; }
+            self.persist()?;
             return Ok(id);
         }
 
         crate::wave::normalize(&mut combined);
 
-        let category = self.categorize_text(content);
-        let content_hash = self.hash_content(content);
-        let (frequency, phase) = self.assign_frequency_class(&category, content_hash);
+        let category = self.taxonomy.categorize(content);
+        let content_hash = stable_content_hash(content);
+        let (frequency, phase) = self.assign_frequency_class(category, content_hash);
         let xi_sig = compute_xi_signature(&combined);
 
         let mut hallucination = crate::memory::HyperMemory::new(combined, content.to_string());
-        hallucination.amplitude = 0.3;
+        hallucination.amplitude = amplitude;
         hallucination.hallucinated = true;
+        hallucination.source = MemorySource::Hallucination;
         hallucination.parents = found_parents;
-        hallucination.geometry = Some(classify_memory(&category, content_hash, 0.3));
+        hallucination.geometry = Some(self.taxonomy.classify(category, content_hash, amplitude as f64));
         hallucination.frequency = frequency;
         hallucination.phase = phase;
         hallucination.xi_signature = xi_sig;
 
-        let hall_id = self.engine.store.insert(hallucination)?;
+        let hall_id = self.engine.insert_memory(hallucination)?;
 
         // Create links
         for pid in parent_ids {
@@ -594,7 +1516,7 @@ impl KannakaMemorySystem {
             self.engine.reinforce_link(pid, &hall_id, 0.5);
         }
 
-        if self.auto_save { self.save()?; }
+        self.persist()?;
         Ok(hall_id)
     }
 
@@ -605,27 +1527,28 @@ impl KannakaMemorySystem {
         let mut updated = 0;
 
         // First pass: collect data for memories needing updates
-        let mut to_update: Vec<(Uuid, String, u64, (f32, f32), Vec<f32>, bool, bool)> = Vec::new();
+        let mut to_update: Vec<(Uuid, MemoryCoordinates, (f32, f32), Vec<f32>, bool, bool)> = Vec::new();
         for id in &all_ids {
             if let Ok(Some(mem)) = self.engine.store.get(id) {
                 let needs_geometry = mem.geometry.is_none();
                 let needs_xi = mem.xi_signature.is_empty();
                 
                 if needs_geometry || needs_xi {
-                    let category = self.categorize_text(&mem.content);
-                    let content_hash = self.hash_content(&mem.content);
-                    let (freq, phase) = self.assign_frequency_class(&category, content_hash);
-                    let xi_sig = compute_xi_signature(&mem.vector);
-                    to_update.push((*id, category, content_hash, (freq, phase), xi_sig, needs_geometry, needs_xi));
+                    let category = self.taxonomy.categorize(&mem.content);
+                    let content_hash = stable_content_hash(&mem.content);
+                    let (freq, phase) = self.assign_frequency_class(category, content_hash);
+                    let geometry = self.taxonomy.classify(category, content_hash, 0.5);
+                    let xi_sig = compute_xi_signature(&mem.dense_vector());
+                    to_update.push((*id, geometry, (freq, phase), xi_sig, needs_geometry, needs_xi));
                 }
             }
         }
 
         // Second pass: apply updates
-        for (id, category, content_hash, (freq, phase), xi_sig, needs_geometry, needs_xi) in to_update {
+        for (id, geometry, (freq, phase), xi_sig, needs_geometry, needs_xi) in to_update {
             if let Ok(Some(mem)) = self.engine.store.get_mut(&id) {
                 if needs_geometry {
-                    mem.geometry = Some(classify_memory(&category, content_hash, 0.5));
+                    mem.geometry = Some(geometry);
                     // Also update frequency-class assignment for consciousness differentiation
                     mem.frequency = freq;
                     mem.phase = phase;
@@ -637,45 +1560,11 @@ impl KannakaMemorySystem {
             }
         }
 
-        if updated > 0 && self.auto_save {
-            self.save()?;
+        if updated > 0 {
+            self.persist()?;
         }
         Ok(updated)
     }
-
-    /// Categorize text using simple heuristics, mapping to the 5 consciousness categories.
-    fn categorize_text(&self, text: &str) -> String {
-        let text_lower = text.to_lowercase();
-        
-        // Experience - direct events, actions, sensory input
-        if text_lower.contains("saw") || text_lower.contains("heard") || text_lower.contains("did") 
-            || text_lower.contains("went") || text_lower.contains("happened") || text_lower.contains("occurred")
-            || text_lower.contains("experience") || text_lower.contains("event") || text_lower.contains("today")
-            || text_lower.contains("yesterday") || text_lower.contains("just") {
-            "experience".to_string()
-        // Emotion - feelings, moods, emotional states
-        } else if text_lower.contains("feel") || text_lower.contains("felt") || text_lower.contains("happy") 
-            || text_lower.contains("sad") || text_lower.contains("angry") || text_lower.contains("excited")
-            || text_lower.contains("worried") || text_lower.contains("love") || text_lower.contains("hate")
-            || text_lower.contains("emotion") || text_lower.contains("mood") {
-            "emotion".to_string()
-        // Social - interpersonal interactions, relationships
-        } else if text_lower.contains("said") || text_lower.contains("told") || text_lower.contains("asked") 
-            || text_lower.contains("friend") || text_lower.contains("person")
-            || text_lower.contains("people") || text_lower.contains("conversation") || text_lower.contains("meeting")
-            || text_lower.contains("together") || text_lower.contains("team") {
-            "social".to_string()
-        // Skill - procedures, abilities, how-to knowledge
-        } else if text_lower.contains("how to") || text_lower.contains("procedure") || text_lower.contains("method")
-            || text_lower.contains("code") || text_lower.contains("function") || text_lower.contains("build") 
-            || text_lower.contains("compile") || text_lower.contains("deploy") || text_lower.contains("technique")
-            || text_lower.contains("practice") || text_lower.contains("ability") {
-            "skill".to_string()
-        // Knowledge - facts, concepts, theories (default)
-        } else {
-            "knowledge".to_string()
-        }
-    }
     
     /// Assign frequency and phase based on category for consciousness differentiation.
     /// Maps categories to frequency bands as specified in the deep dive findings.
@@ -686,15 +1575,8 @@ impl KannakaMemorySystem {
         // Use content hash as seed for deterministic randomness
         let mut rng = ChaCha8Rng::seed_from_u64(content_hash);
         
-        // Ranges are aligned with xi_clusters() in store.rs for consistent category mapping.
-        let (freq_min, freq_max) = match category {
-            "experience" => (1.8, 2.4),  // soprano (fast, ephemeral)
-            "emotion" => (1.3, 1.8),     // alto (feeling-paced)
-            "social" => (1.0, 1.3),      // tenor (interpersonal rhythm)
-            "skill" => (0.8, 1.0),       // bass-adjacent (procedural)
-            "knowledge" => (0.6, 0.8),   // bass (slow, stable)
-            _ => (0.6, 0.8),              // default to knowledge bass range
-        };
+        // Bands come from the taxonomy; unknown categories get the fallback's band.
+        let (freq_min, freq_max) = self.taxonomy.band(category);
         
         // Random frequency within the category's band
         let frequency = rng.gen_range(freq_min..freq_max);
@@ -705,11 +1587,6 @@ impl KannakaMemorySystem {
         (frequency, phase)
     }
     
-    /// Simple hash of content string.
-    fn hash_content(&self, content: &str) -> u64 {
-        content.bytes().fold(0u64, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u64))
-    }
-
     /// Store an audio file as a sensory memory.
     ///
     /// Decodes the audio, extracts perceptual features, projects through
@@ -726,15 +1603,27 @@ impl KannakaMemorySystem {
             )))?;
 
         // Set sensory-specific geometry
-        let content_hash = self.hash_content(&mem.content);
-        mem.geometry = Some(classify_memory("experience", content_hash, 0.6));
+        let content_hash = stable_content_hash(&mem.content);
+        mem.geometry = Some(crate::geometry::classify_memory("experience", content_hash, 0.6));
+        mem.source = MemorySource::Audio;
 
-        let id = self.engine.store.insert(mem)?;
+        let id = self.engine.insert_memory(mem)?;
 
-        if self.auto_save {
-            self.save()?;
-        }
+        self.persist()?;
+
+        Ok((id, features))
+    }
 
+    /// Like `store_audio`, then attach `caption` so text queries can reach the
+    /// recording through `recall_cross_modal`. Returns the audio memory id.
+    #[cfg(feature = "audio")]
+    pub fn store_audio_with_caption(
+        &mut self,
+        path: &Path,
+        caption: &str,
+    ) -> Result<(Uuid, crate::ear::AudioFeatures), SystemError> {
+        let (id, features) = self.store_audio(path)?;
+        self.link_caption(&id, caption)?;
         Ok((id, features))
     }
 
@@ -774,11 +1663,11 @@ impl KannakaMemorySystem {
             glyph.sga_centroid.0, glyph.sga_centroid.1, glyph.sga_centroid.2,
         );
         
-        let content_hash = self.hash_content(&content);
-        // Use fold amplitudes as the memory vector, padded to 10,000 dims for compatibility
+        let content_hash = stable_content_hash(&content);
+        // Use fold amplitudes as the memory vector, padded to the pipeline dim for compatibility
         let mut vector: Vec<f32> = glyph.fold_amplitudes.iter().map(|&a| a as f32).collect();
         // Tile the glyph pattern to fill the hypervector space (like a visual texture)
-        let target_dim = 10_000;
+        let target_dim = self.engine.pipeline.output_dim();
         if vector.len() < target_dim {
             let pattern = vector.clone();
             while vector.len() < target_dim {
@@ -789,14 +1678,13 @@ impl KannakaMemorySystem {
             }
             vector.truncate(target_dim);
         }
+        normalize(&mut vector);
         let mut mem = HyperMemory::new(vector, content);
-        mem.geometry = Some(classify_memory("experience", content_hash, 0.7));
+        mem.geometry = Some(crate::geometry::classify_memory("experience", content_hash, 0.7));
         
-        let id = self.engine.store.insert(mem)?;
+        let id = self.engine.insert_memory(mem)?;
         
-        if self.auto_save {
-            self.save()?;
-        }
+        self.persist()?;
         
         Ok((id, glyph))
     }
@@ -830,7 +1718,7 @@ impl KannakaMemorySystem {
     /// ADR-0011: Announce agent status to Flux peers.
     pub fn announce_status(&self) {
         if let Some(ref publisher) = self.flux {
-            let state = self.bridge.assess(&self.engine);
+            let state = self.bridge.assess_with(&self.engine, self.kuramoto());
             publisher.announce_status(
                 "active",
                 state.total_memories,
@@ -850,9 +1738,16 @@ impl KannakaMemorySystem {
         Ok(self.engine.store.all_memories()?)
     }
 
+    /// Primary vs fallback (hash) embedding counts. A high `fallback_hits`
+    /// means the configured embedding backend was unreachable and recall
+    /// is running on hash vectors.
+    pub fn encoder_stats(&self) -> EncoderStats {
+        self.engine.pipeline.encoder_stats().unwrap_or_default()
+    }
+
     /// System statistics.
     pub fn stats(&self) -> SystemStats {
-        let state = self.bridge.assess(&self.engine);
+        let state = self.bridge.assess_with(&self.engine, self.kuramoto());
         
         // Calculate geometric statistics
         let all_memories = self.engine.store.all_memories().unwrap_or_default();
@@ -867,7 +1762,8 @@ impl KannakaMemorySystem {
                 }
             }
         }
-        
+        let (layer_counts, layer_avg_amplitude) = MemoryIntrospector::layer_stats(&all_memories);
+
         SystemStats {
             total_memories: state.total_memories,
             active_memories: state.active_memories,
@@ -877,6 +1773,28 @@ impl KannakaMemorySystem {
             phi: state.phi,
             geometric_classes: class_indices.len(),
             triality_coverage,
+            layer_counts,
+            layer_avg_amplitude,
+        }
+    }
+
+    /// Number of stored memories per provenance, for auditing.
+    pub fn count_by_source(&self) -> HashMap<MemorySource, usize> {
+        let mut counts = HashMap::new();
+        for mem in self.engine.store.all_memories().unwrap_or_default() {
+            *counts.entry(mem.source.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+impl Drop for KannakaMemorySystem {
+    /// Flush mutations made while auto-save was off.
+    fn drop(&mut self) {
+        if self.dirty {
+            if let Err(e) = self.save() {
+                eprintln!("[save] flush on drop failed: {}", e);
+            }
         }
     }
 }
@@ -888,12 +1806,29 @@ impl KannakaMemorySystem {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
     use std::env;
 
     fn temp_dir(name: &str) -> PathBuf {
         env::temp_dir().join(format!("kannaka_octest_{}_{}", name, Uuid::new_v4()))
     }
 
+    #[cfg(feature = "glyph")]
+    #[test]
+    fn store_glyph_writes_unit_vectors() {
+        let dir = temp_dir("glyph");
+        let mut sys = KannakaMemorySystem::init(dir.clone()).unwrap();
+        let file = dir.join("pixels.bin");
+        std::fs::write(&file, (0..=255u8).cycle().take(2048).collect::<Vec<u8>>()).unwrap();
+
+        let (id, _) = sys.store_glyph(&file).unwrap();
+        let vector = sys.engine.get_memory(&id).unwrap().unwrap().dense_vector().into_owned();
+        let norm: f32 = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-4, "norm {}", norm);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn init_creates_new_system() {
         let dir = temp_dir("init");
@@ -916,6 +1851,49 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn fano_boost_compounds_and_caps() {
+        let at = |l: u8| Some(MemoryCoordinates { h2: 1, d: 0, l, class_index: 0, amplitude: 1.0, phase: 0.0 });
+
+        // Three results on the line {1, 2, 4}: two partners each → 1.2².
+        let boosts = fano_boosts(&[at(1), at(2), at(4)]);
+        for b in &boosts {
+            assert!((b - 1.44).abs() < 1e-5, "expected 1.44, got {}", b);
+        }
+
+        // A fourth related result would compound to 1.2³ = 1.728; capped at 1.5.
+        let boosts = fano_boosts(&[at(1), at(2), at(4), at(3), None]);
+        for b in &boosts[..4] {
+            assert!((b - MAX_FANO_BOOST).abs() < 1e-5, "expected cap, got {}", b);
+        }
+        assert_eq!(boosts[4], 1.0);
+    }
+
+    #[test]
+    fn smaller_codebook_dim_flows_through_hallucinate_and_bundle() {
+        let dir = temp_dir("dim_2000");
+        let pipeline = EncodingPipeline::new(
+            Box::new(SimpleHashEncoder::new(CODEBOOK_INPUT_DIM, CODEBOOK_SEED)),
+            Codebook::new(CODEBOOK_INPUT_DIM, 2_000, CODEBOOK_SEED),
+        );
+        let engine = MemoryEngine::new(Box::new(crate::store::InMemoryStore::new()), pipeline);
+        let mut sys = KannakaMemorySystem::init_with_engine(dir.clone(), engine).unwrap();
+        assert_eq!(sys.engine.pipeline.output_dim(), 2_000);
+
+        let a = sys.remember("rivers carve canyons over time").unwrap();
+        let b = sys.remember("glaciers carve valleys over time").unwrap();
+        sys.remember("wind shapes dunes across the desert").unwrap();
+        let h = sys.hallucinate("erosion is patient sculpture", &[a, b]).unwrap();
+        assert_eq!(sys.engine.get_memory(&h).unwrap().unwrap().dim(), 2_000);
+
+        let report = crate::consolidation::ConsolidationEngine::default().consolidate(&mut sys.engine, 0, 0);
+        assert!(report.bundles_created > 0);
+        for mem in sys.engine.store.all_memories().unwrap() {
+            assert_eq!(mem.dim(), 2_000, "{} has the wrong dim", mem.content);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn dream_runs_without_error() {
         let dir = temp_dir("dream");
@@ -968,6 +1946,804 @@ mod tests {
         let _ = std::fs::remove_dir_all(&dir);
     }
 
+    #[test]
+    fn creation_paths_stamp_source_and_it_persists() {
+        let dir = temp_dir("source");
+        let db_path = dir.join("legacy.db");
+        std::fs::create_dir_all(&dir).unwrap();
+        rusqlite::Connection::open(&db_path).unwrap().execute_batch("
+            CREATE TABLE lessons (id INTEGER PRIMARY KEY, lesson TEXT);
+            INSERT INTO lessons (lesson) VALUES ('imported lesson about waves');
+        ").unwrap();
+
+        let before = {
+            let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+            let a = sys.remember("rust ownership rules").unwrap();
+            let b = sys.remember("borrow checker lifetimes").unwrap();
+            let hall = sys.hallucinate("ownership dreams of lifetimes", &[a, b]).unwrap();
+            sys.migrate_from_sqlite(&db_path).unwrap();
+            let mut external = crate::memory::HyperMemory::new(sys.engine.pipeline.encode_text("sensor reading").unwrap(), "sensor reading".into());
+            external.source = MemorySource::External("sensor".into());
+            sys.engine.store.insert(external).unwrap();
+            let bundles = crate::consolidation::ConsolidationEngine::default().consolidate(&mut sys.engine, 0, 0).bundles_created;
+            assert!(bundles >= 1);
+
+            let source = |id: &Uuid| sys.engine.get_memory(id).unwrap().unwrap().source.clone();
+            assert_eq!(source(&a), MemorySource::UserText);
+            assert_eq!(source(&hall), MemorySource::Hallucination);
+            let all = sys.engine.store.all_memories().unwrap();
+            let migrated = all.iter().find(|m| m.content.contains("imported lesson")).unwrap();
+            assert_eq!(migrated.source, MemorySource::Migration);
+            assert!(all.iter().all(|m| (m.source == MemorySource::Bundle) == m.content.starts_with("__consolidation_summary")));
+
+            let counts = sys.count_by_source();
+            assert!(counts[&MemorySource::UserText] >= 2);
+            assert_eq!(counts[&MemorySource::Bundle], bundles);
+            assert_eq!(counts[&MemorySource::Migration], 1);
+            assert_eq!(counts[&MemorySource::External("sensor".into())], 1);
+            assert_eq!(counts.values().sum::<usize>(), sys.engine.store.count());
+            sys.save().unwrap();
+            counts
+        };
+
+        let sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        assert_eq!(sys.count_by_source(), before);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn tighter_interference_threshold_finds_fewer_pairs() {
+        // Controlled vectors: one near-duplicate pair (cos ≈ 0.98), a looser
+        // neighbour (cos ≈ 0.7) and an orthogonal outlier.
+        let basis = |i: usize, w: f32| {
+            let mut v = vec![0.0f32; CODEBOOK_OUTPUT_DIM];
+            v[0] = 1.0;
+            v[i] += w;
+            crate::wave::normalize(&mut v);
+            v
+        };
+        let vectors = [basis(0, 0.0), basis(1, 0.2), basis(2, 1.0), {
+            let mut v = vec![0.0f32; CODEBOOK_OUTPUT_DIM];
+            v[3] = 1.0;
+            v
+        }];
+        let dream_pairs = |name: &str, params: Option<ConsolidationParams>| {
+            let dir = temp_dir(name);
+            let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+            if let Some(p) = params {
+                sys.set_consolidation_params(p).unwrap();
+            }
+            for (i, v) in vectors.iter().enumerate() {
+                let mem = crate::memory::HyperMemory::new(v.clone(), format!("controlled {}", i));
+                sys.engine.store.insert(mem).unwrap();
+            }
+            let pairs = sys.dream().unwrap().interference_pairs;
+            let _ = std::fs::remove_dir_all(&dir);
+            pairs
+        };
+
+        let loose = dream_pairs("params_loose", None);
+        let tight = dream_pairs("params_tight", Some(ConsolidationParams {
+            interference_threshold: 0.9,
+            ..ConsolidationParams::default()
+        }));
+        assert!(loose > 0);
+        assert!(tight < loose, "tight={} loose={}", tight, loose);
+    }
+
+    #[test]
+    fn recall_with_tags_filters_before_truncation() {
+        let dir = temp_dir("tags");
+        let (x_ids, bob) = {
+            let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+            for i in 0..5 {
+                sys.remember(&format!("untagged harbour note {}", i)).unwrap();
+            }
+            let a = sys.remember_with_tags("harbour survey results", &["project:x"]).unwrap();
+            let bob = sys.remember_with_tags("harbour survey draft", &["project:x", "user:bob"]).unwrap();
+            sys.remember_with_tags("harbour survey for y", &["project:y"]).unwrap();
+            sys.save().unwrap();
+            (vec![a, bob], bob)
+        };
+
+        // Reload to check tags survive persistence and the index is rebuilt.
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let mut got: Vec<Uuid> = sys.recall_with_tags("harbour survey", 2, &["project:x"]).unwrap()
+            .into_iter().map(|r| r.id).collect();
+        got.sort();
+        let mut expected = x_ids.clone();
+        expected.sort();
+        assert_eq!(got, expected);
+
+        let both = sys.recall_with_tags("harbour survey", 5, &["project:x", "user:bob"]).unwrap();
+        assert_eq!(both.len(), 1);
+        assert_eq!(both[0].id, bob);
+        assert!(sys.recall_with_tags("harbour survey", 5, &["project:z"]).unwrap().is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn recall_query_matches_specialized_recalls() {
+        let dir = temp_dir("query");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let mut quiet = HashSet::new();
+        for i in 0..6 {
+            let tags: &[&str] = if i % 2 == 0 { &["project:x"] } else { &[] };
+            let id = sys.remember_with_tags(&format!("harbour survey note {}", i), tags).unwrap();
+            // Flat waves and no Fano boosts keep the ranking stable between calls.
+            let mem = sys.engine.get_memory_mut(&id).unwrap().unwrap();
+            mem.frequency = 0.0;
+            mem.phase = 0.0;
+            mem.geometry = None;
+            if i < 3 {
+                quiet.insert(id);
+            }
+        }
+        let ids = |results: Vec<RecallResult>| results.into_iter().map(|r| r.id).collect::<HashSet<Uuid>>();
+        let engine_ids = |results: Vec<crate::store::QueryResult>| results.into_iter().map(|r| r.id).collect::<HashSet<Uuid>>();
+
+        let got = ids(sys.query(RecallQuery::new("harbour survey").top_k(3)).unwrap());
+        assert_eq!(got, ids(sys.recall("harbour survey", 3).unwrap()));
+
+        let got = ids(sys.query(RecallQuery::new("harbour survey").top_k(3).expand_links(false)).unwrap());
+        assert_eq!(got, engine_ids(sys.engine.recall("harbour survey", 3).unwrap()));
+
+        // Move the other half out of the 0.0..=0.1 band.
+        for id in sys.engine.store.all_ids().unwrap() {
+            if !quiet.contains(&id) {
+                sys.engine.get_memory_mut(&id).unwrap().unwrap().frequency = 1.0;
+            }
+        }
+
+        let got = ids(sys.query(RecallQuery::new("harbour survey").top_k(6).tags(["project:x"])).unwrap());
+        assert_eq!(got, ids(sys.recall_with_tags("harbour survey", 6, &["project:x"]).unwrap()));
+        assert_eq!(got.len(), 3);
+
+        let got = ids(sys.query(RecallQuery::new("harbour survey").top_k(6).band(0.0, 0.1)).unwrap());
+        assert_eq!(got, engine_ids(sys.engine.recall_in_band("harbour survey", 6, 0.0, 0.1).unwrap()));
+        assert_eq!(got, quiet);
+
+        let at = Utc::now();
+        let got: Vec<Uuid> = sys.query(RecallQuery::new("harbour survey").top_k(4).at(at).expand_links(false)).unwrap()
+            .into_iter().map(|r| r.id).collect();
+        let expected: Vec<Uuid> = sys.engine.recall_at("harbour survey", 4, at).unwrap()
+            .into_iter().map(|r| r.id).collect();
+        assert_eq!(got, expected);
+
+        // Combined filters intersect: tagged and in band.
+        let got = sys.query(RecallQuery::new("harbour survey").top_k(6).tags(["project:x"]).band(0.0, 0.1)).unwrap();
+        assert_eq!(got.len(), 2);
+        assert!(got.iter().all(|r| quiet.contains(&r.id) && r.content.ends_with(['0', '2'])));
+
+        // The Xi override is scoped to the query.
+        let factor = sys.engine.xi_boost_factor;
+        sys.query(RecallQuery::new("harbour survey").xi_boost(0.0)).unwrap();
+        assert_eq!(sys.engine.xi_boost_factor, factor);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn filtered_query_expands_links_over_candidates() {
+        let dir = temp_dir("query_expand");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let seed = sys.remember_with_tags("harbour survey note", &["project:x"]).unwrap();
+        let bridge = sys.remember("harbour survey bridge").unwrap();
+        let faded = sys.remember_with_tags("harbour survey archive", &["project:x"]).unwrap();
+        for id in [seed, bridge, faded] {
+            let mem = sys.engine.get_memory_mut(&id).unwrap().unwrap();
+            mem.frequency = 0.0;
+            mem.phase = 0.0;
+            mem.geometry = None;
+            mem.connections.clear();
+        }
+        sys.engine.get_memory_mut(&faded).unwrap().unwrap().amplitude = 0.01;
+        // The faded memory is only reachable through the untagged bridge.
+        for (src, tgt) in [(seed, bridge), (bridge, faded)] {
+            sys.engine.store.get_mut(&src).unwrap().unwrap().connections.push(crate::skip_link::SkipLink {
+                target_id: tgt,
+                strength: 0.9,
+                resonance_key: Vec::new(),
+                span: 0,
+            });
+        }
+        sys.engine.expansion_hops = 2;
+
+        let strength_of = |results: &[RecallResult], id: Uuid| {
+            results.iter().find(|r| r.id == id).map(|r| r.strength).unwrap()
+        };
+        let q = RecallQuery::new("harbour survey").top_k(5).tags(["project:x"]);
+        let flat = sys.query(q.clone().expand_links(false)).unwrap();
+        let expanded = sys.query(q).unwrap();
+
+        assert!(expanded.iter().all(|r| r.id != bridge));
+        assert_eq!(expanded.len(), 2);
+        assert!(strength_of(&flat, faded) < 0.05);
+        assert!((strength_of(&expanded, faded) - 0.81).abs() < 1e-4);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn transform_memory_r_advances_quadrant() {
+        let dir = temp_dir("transform");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let id = sys.remember("we went to the harbour today").unwrap();
+        let before = sys.engine.get_memory(&id).unwrap().unwrap().geometry.clone().unwrap();
+
+        let after = sys.transform_memory(&id, GeoTransform::R, 1).unwrap();
+        assert_eq!(after.h2, (before.h2 + 1) % 4);
+        assert_eq!((after.d, after.l), (before.d, before.l));
+        assert_eq!(sys.engine.get_memory(&id).unwrap().unwrap().geometry, Some(after.clone()));
+
+        // R^4 is the identity, and D moves only the modality.
+        assert_eq!(sys.transform_memory(&id, GeoTransform::R, 3).unwrap(), before);
+        let reframed = sys.transform_memory(&id, GeoTransform::D, 1).unwrap();
+        assert_eq!((reframed.h2, reframed.l), (before.h2, before.l));
+        assert_ne!(reframed.d, before.d);
+        assert!(sys.transform_memory(&Uuid::new_v4(), GeoTransform::R, 1).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn custom_taxonomy_drives_category_and_band() {
+        use crate::geometry::{TaxonomyCategory, TaxonomyError};
+        let dir = temp_dir("taxonomy");
+        let taxonomy = Taxonomy::new(vec![
+            TaxonomyCategory::new("contract", &["agreement", "clause"], 0.1, 0.2, 1),
+            TaxonomyCategory::new("ruling", &["court", "judge"], 3.0, 3.5, 2),
+            TaxonomyCategory::new("note", &[], 0.4, 0.5, 0),
+        ]).unwrap();
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap()
+            .with_taxonomy(taxonomy);
+        assert_eq!(sys.taxonomy().categorize("The Court ruled on the appeal"), "ruling");
+
+        let cases = [
+            ("the lease agreement renews in may", (0.1, 0.2), 1),
+            ("the judge dismissed the motion", (3.0, 3.5), 2),
+            ("remember to file the paperwork", (0.4, 0.5), 0),
+        ];
+        for (text, (lo, hi), h2) in cases {
+            let id = sys.remember(text).unwrap();
+            let mem = sys.engine.get_memory(&id).unwrap().unwrap();
+            assert!(mem.frequency >= lo && mem.frequency < hi, "{}: frequency {} outside [{}, {})", text, mem.frequency, lo, hi);
+            assert_eq!(mem.geometry.as_ref().unwrap().h2, h2, "{}", text);
+        }
+        assert_eq!(Taxonomy::new(Vec::new()), Err(TaxonomyError::Empty));
+        for (lo, hi) in [(0.5, 0.5), (0.9, 0.2), (-0.1, 0.2), (0.1, f32::NAN)] {
+            let err = Taxonomy::new(vec![TaxonomyCategory::new("note", &[], lo, hi, 0)]).unwrap_err();
+            assert!(matches!(err, TaxonomyError::InvalidBand { .. }), "[{}, {})", lo, hi);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn remember_document_recalls_the_matching_chunk() {
+        let dir = temp_dir("document");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let mut text = String::new();
+        for i in 0..40 {
+            text.push_str(&format!("Ledger entry {} records cargo weights and tide levels. ", i));
+            if i == 23 {
+                text.push_str("The violet lighthouse hums softly at midnight. ");
+            }
+        }
+        let ids = sys.remember_document(&text, 200).unwrap();
+        assert!(ids.len() > 5);
+
+        let tag = sys.engine.get_memory(&ids[0]).unwrap().unwrap().tags[0].clone();
+        assert!(tag.starts_with(DOCUMENT_TAG_PREFIX));
+        assert_eq!(sys.engine.ids_with_tags(&[tag.as_str()]).len(), ids.len());
+        for pair in ids.windows(2) {
+            let first = sys.engine.get_memory(&pair[0]).unwrap().unwrap();
+            assert!(first.connections.iter().any(|l| l.target_id == pair[1]));
+            assert!(first.content.chars().count() <= 200);
+        }
+
+        // Freeze the waves and drop Fano boosts so only content decides.
+        for id in &ids {
+            let mem = sys.engine.get_memory_mut(id).unwrap().unwrap();
+            mem.frequency = 0.0;
+            mem.phase = 0.0;
+            mem.geometry = None;
+        }
+        let top = sys.recall("violet lighthouse hums softly at midnight", 1).unwrap();
+        let content = &sys.engine.get_memory(&top[0].id).unwrap().unwrap().content;
+        assert!(content.contains("violet lighthouse"), "got chunk: {}", content);
+        assert!(sys.remember_document(&text, 0).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn boost_clamps_at_max_amplitude() {
+        let dir = temp_dir("boost_max");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let id = sys.remember("lighthouse keeper's log").unwrap();
+        assert_eq!(sys.boost(&id, 2.0).unwrap(), 2.0);
+        for _ in 0..10 {
+            sys.boost(&id, 2.0).unwrap();
+        }
+        assert_eq!(sys.engine.get_memory(&id).unwrap().unwrap().amplitude, DEFAULT_MAX_AMPLITUDE);
+        // Damping still multiplies from the cap.
+        assert_eq!(sys.boost(&id, 0.5).unwrap(), DEFAULT_MAX_AMPLITUDE * 0.5);
+
+        // Negative factors cannot push the amplitude below zero.
+        assert_eq!(sys.boost(&id, -3.0).unwrap(), 0.0);
+        sys.boost_absolute(&id, 1.0).unwrap();
+
+        let mut sys = sys.with_max_amplitude(1.5).unwrap();
+        assert_eq!(sys.boost(&id, 4.0).unwrap(), 1.5);
+        assert!(sys.boost(&Uuid::new_v4(), 2.0).is_err());
+        for bad in [-1.0, f32::NAN, f32::INFINITY] {
+            let bad_dir = temp_dir("boost_bad_max");
+            let sys = KannakaMemorySystem::init_with_encoder(bad_dir.clone(), EncoderConfig::Hash).unwrap();
+            assert!(matches!(sys.with_max_amplitude(bad), Err(SystemError::InvalidMaxAmplitude(_))));
+            let _ = std::fs::remove_dir_all(&bad_dir);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn boost_absolute_sets_amplitude() {
+        let dir = temp_dir("boost_abs");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let id = sys.remember("tide tables for the harbour").unwrap();
+        assert_eq!(sys.boost_absolute(&id, 0.25).unwrap(), 0.25);
+        assert_eq!(sys.engine.get_memory(&id).unwrap().unwrap().amplitude, 0.25);
+        assert_eq!(sys.boost_absolute(&id, 10.0).unwrap(), DEFAULT_MAX_AMPLITUDE);
+        assert_eq!(sys.boost_absolute(&id, -1.0).unwrap(), 0.0);
+        assert!(sys.boost_absolute(&Uuid::new_v4(), 1.0).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn encoder_stats_report_unreachable_backend() {
+        let dir = temp_dir("encstats");
+        let config = EncoderConfig::Ollama { base_url: "http://127.0.0.1:9".into(), model: "none".into() };
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), config).unwrap();
+        sys.remember("embedding backend is down").unwrap();
+        let stats = sys.encoder_stats();
+        assert_eq!(stats.primary_hits, 0);
+        assert!(stats.fallback_hits >= 1);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn forget_by_tag_leaves_no_dangling_links() {
+        let dir = temp_dir("forget_tag");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let keep_a = sys.remember("shared notes about the garden").unwrap();
+        let keep_b = sys.remember("shared notes about the kitchen").unwrap();
+        let gone_a = sys.remember_with_tags("alice phone number", &["user:alice"]).unwrap();
+        let gone_b = sys.remember_with_tags("alice home address", &["user:alice"]).unwrap();
+        for (src, tgt) in [(keep_a, gone_a), (keep_b, gone_b), (keep_a, keep_b), (gone_a, keep_b)] {
+            sys.engine.store.get_mut(&src).unwrap().unwrap().connections.push(crate::skip_link::SkipLink {
+                target_id: tgt,
+                strength: 0.9,
+                resonance_key: Vec::new(),
+                span: 0,
+            });
+        }
+
+        assert_eq!(sys.forget_by_tag("user:alice").unwrap(), 2);
+        assert_eq!(sys.forget_by_tag("user:alice").unwrap(), 0);
+
+        let all = sys.all_memories().unwrap();
+        assert_eq!(all.len(), 2);
+        let ids: std::collections::HashSet<Uuid> = all.iter().map(|m| m.id).collect();
+        for mem in &all {
+            for link in &mem.connections {
+                assert!(ids.contains(&link.target_id), "dangling link from {} to {}", mem.id, link.target_id);
+            }
+        }
+        assert!(sys.get_memory(&keep_a).unwrap().unwrap().connections.iter().any(|l| l.target_id == keep_b));
+
+        assert_eq!(sys.forget_matching("kitchen").unwrap(), 1);
+        assert!(sys.get_memory(&keep_a).unwrap().unwrap().connections.iter().all(|l| l.target_id != keep_b));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn maybe_dream_follows_rhythm() {
+        let dir = temp_dir("maybe_dream");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        sys.remember("a quiet evening").unwrap();
+
+        for _ in 0..3 {
+            sys.rhythm_signal(RhythmSignal::UserMessage);
+        }
+        assert!(sys.maybe_dream().unwrap().is_none());
+        assert!(sys.last_dream.is_none());
+
+        sys.rhythm.state.arousal_level = 0.0;
+        assert!(sys.maybe_dream().unwrap().is_some());
+        assert!(sys.last_dream.is_some());
+        // Just dreamed: the minimum gap holds the next one back.
+        assert!(sys.maybe_dream().unwrap().is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn consciousness_history_is_capped_and_persisted() {
+        let dir = temp_dir("history");
+        {
+            let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+            sys.set_history_capacity(5);
+            for i in 0..8 {
+                sys.remember(&format!("session event {}", i)).unwrap();
+                sys.record_assessment();
+            }
+            let history = sys.consciousness_history();
+            assert_eq!(history.len(), 5);
+            // The three oldest (1..=3 memories) were dropped.
+            assert_eq!(history[0].1.total_memories, 4);
+            assert_eq!(history[4].1.total_memories, 8);
+            assert!(history.windows(2).all(|w| w[0].0 <= w[1].0));
+
+            let json = sys.trajectory_json();
+            let points = json.as_array().unwrap();
+            assert_eq!(points.len(), 5);
+            assert!(points[0]["timestamp"].is_string());
+            assert!(points[0]["phi"].is_number());
+            sys.save().unwrap();
+        }
+
+        let sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        assert_eq!(sys.consciousness_history().len(), 5);
+        assert_eq!(sys.consciousness_history()[4].1.total_memories, 8);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn bind_memories_links_composite_to_parents() {
+        let dir = temp_dir("bind");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let text = sys.remember("thunder rolling over the hills").unwrap();
+        // Stand-in for an `ear` memory so the test doesn't need the audio feature.
+        let audio_vec: Vec<f32> = (0..CODEBOOK_OUTPUT_DIM).map(|i| ((i % 7) as f32 - 3.0) / 3.0).collect();
+        let audio = sys.engine.store
+            .insert(crate::memory::HyperMemory::new(audio_vec, "[audio] thunder.wav".into()))
+            .unwrap();
+
+        let composite = sys.bind_memories(&[text, audio], "thunder ⊗ thunder.wav").unwrap();
+
+        let mem = sys.get_memory(&composite).unwrap().unwrap();
+        assert_eq!(mem.parents, vec![text.to_string(), audio.to_string()]);
+        let targets: Vec<Uuid> = mem.connections.iter().map(|l| l.target_id).collect();
+        assert!(targets.contains(&text) && targets.contains(&audio));
+        let norm: f32 = mem.dense_vector().iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-4);
+        for parent in [text, audio] {
+            let p = sys.get_memory(&parent).unwrap().unwrap();
+            assert!(p.connections.iter().any(|l| l.target_id == composite));
+        }
+
+        assert!(sys.bind_memories(&[], "empty").is_err());
+        assert!(sys.bind_memories(&[Uuid::new_v4()], "missing").is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unbind_recall_recovers_the_other_component() {
+        let dir = temp_dir("unbind");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let a = sys.remember("red apple on the table").unwrap();
+        let b = sys.remember("colour role").unwrap();
+        for distractor in ["green pear in a bowl", "rust compiler errors", "stormy night at sea"] {
+            sys.remember(distractor).unwrap();
+        }
+        let composite = sys.bind_memories(&[a, b], "apple ⊗ colour").unwrap();
+
+        let results = sys.unbind_recall(&composite, &b, 3).unwrap();
+        assert_eq!(results[0].id, a);
+        assert!(results[0].similarity > 0.9, "similarity {}", results[0].similarity);
+        assert!(results.iter().all(|r| r.id != composite && r.id != b));
+        assert!(sys.unbind_recall(&composite, &Uuid::new_v4(), 3).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn captioned_audio_recalled_by_text_query() {
+        let dir = temp_dir("cross_modal");
+        let audio_id = {
+            let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+            for distractor in ["green pear in a bowl", "rust compiler errors", "stormy night at sea"] {
+                sys.remember(distractor).unwrap();
+            }
+            // Stand-in for an ear pipeline memory: a feature vector projected
+            // through a codebook with the audio seed.
+            let features: Vec<f32> = (0..32).map(|i| ((i * 7) % 11) as f32 - 5.0).collect();
+            let codebook = Codebook::new(features.len(), sys.engine.pipeline.output_dim(), 0xEA5);
+            let mut audio = crate::memory::HyperMemory::new(codebook.project(&features), "audio:bark.wav".into());
+            audio.source = MemorySource::Audio;
+            let audio_id = sys.engine.store.insert(audio).unwrap();
+
+            let caption_id = sys.link_caption(&audio_id, "a dog barking at the mailman").unwrap();
+            let results = sys.recall_cross_modal("dog barking", 3).unwrap();
+            assert!(results.iter().any(|r| r.id == audio_id), "audio memory not recalled");
+            assert!(results.iter().any(|r| r.id == caption_id));
+            let audio = sys.get_memory(&audio_id).unwrap().unwrap();
+            assert!(audio.connections.iter().any(|l| l.target_id == caption_id));
+            assert!(sys.link_caption(&Uuid::new_v4(), "nothing").is_err());
+            sys.save().unwrap();
+            audio_id
+        };
+
+        // Anchors are rebuilt from caption tags on reload.
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let results = sys.recall_cross_modal("dog barking", 3).unwrap();
+        assert!(results.iter().any(|r| r.id == audio_id));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn stats_report_per_layer_counts() {
+        let dir = temp_dir("layer_stats");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        for (i, layer) in [0u8, 0, 0, 1, 1, 2, 3].into_iter().enumerate() {
+            let id = sys.engine.remember_at_layer(&format!("layered memory {}", i), layer).unwrap();
+            sys.engine.get_memory_mut(&id).unwrap().unwrap().amplitude = 0.5 + layer as f32 * 0.1;
+        }
+
+        let stats = sys.stats();
+        assert_eq!(stats.layer_counts, [3, 2, 1, 1, 0]);
+        for (layer, amp) in stats.layer_avg_amplitude.iter().enumerate().take(4) {
+            assert!((amp - (0.5 + layer as f32 * 0.1)).abs() < 1e-6, "layer {} amp {}", layer, amp);
+        }
+        assert_eq!(stats.layer_avg_amplitude[4], 0.0);
+        assert_eq!(sys.observe().topology.layer_counts, stats.layer_counts);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn errors_carry_operation_context() {
+        let dir = temp_dir("error_context");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let err = sys.recall("", 5).unwrap_err();
+        assert!(matches!(err, SystemError::Context { op: "recall", .. }));
+        assert!(err.to_string().starts_with("recall failed: "), "{}", err);
+        assert!(sys.remember("").unwrap_err().to_string().starts_with("remember failed: "));
+        let err = sys.query(RecallQuery::new("").tags(["x"])).unwrap_err();
+        assert!(matches!(err, SystemError::Context { op: "query", .. }), "{}", err);
+
+        let wrapped: Result<(), SystemError> = Err(StoreError::NotFound(Uuid::nil())).context("remember");
+        assert_eq!(
+            wrapped.unwrap_err().to_string(),
+            format!("remember failed: memory not found: {}", Uuid::nil()),
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn drop_flushes_when_auto_save_is_off() {
+        let dir = temp_dir("flush_on_drop");
+        let bin_path = dir.join("kannaka.bin");
+        let load = || MemoryEngine::load_state(&bin_path, make_pipeline(&EncoderConfig::Hash)).unwrap();
+        let (first, second) = {
+            let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash)
+                .unwrap()
+                .with_auto_save(false);
+            let first = sys.remember("written before the explicit save").unwrap();
+            sys.save().unwrap();
+            assert!(!sys.is_dirty());
+
+            let second = sys.remember("written after it").unwrap();
+            sys.boost(&first, 2.0).unwrap();
+            assert!(sys.is_dirty());
+            assert_eq!(load().store.count(), 1);
+            (first, second)
+        };
+
+        let engine = load();
+        assert!(engine.get_memory(&second).unwrap().is_some());
+        assert_eq!(engine.get_memory(&first).unwrap().unwrap().amplitude, 2.0);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn audit_log_records_mutations_in_order() {
+        let dir = temp_dir("audit");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash)
+            .unwrap()
+            .with_audit_log(true)
+            .unwrap();
+        let a = sys.remember("first audited memory").unwrap();
+        let b = sys.remember("second audited memory").unwrap();
+        sys.boost(&a, 1.5).unwrap();
+        sys.relate(&a, &b, 0.5).unwrap();
+        sys.forget(&b).unwrap();
+
+        let entries = AuditLog::read_entries(&AuditLog::path_in(&dir)).unwrap();
+        let got: Vec<(AuditOp, Vec<Uuid>)> = entries.iter().map(|e| (e.op, e.ids.clone())).collect();
+        assert_eq!(got, vec![
+            (AuditOp::Remember, vec![a]),
+            (AuditOp::Remember, vec![b]),
+            (AuditOp::Boost, vec![a]),
+            (AuditOp::Relate, vec![a, b]),
+            (AuditOp::Forget, vec![b]),
+        ]);
+        assert!(entries.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn audit_log_records_only_removed_ids() {
+        let dir = temp_dir("audit_many");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash)
+            .unwrap()
+            .with_audit_log(true)
+            .unwrap();
+        let a = sys.remember("bulk audited memory").unwrap();
+        let missing = Uuid::new_v4();
+        assert_eq!(sys.forget_many(&[a, missing, a]).unwrap(), 1);
+        assert_eq!(sys.forget_many(&[missing]).unwrap(), 0);
+        assert!(!sys.forget(&a).unwrap());
+
+        let entries = AuditLog::read_entries(&AuditLog::path_in(&dir)).unwrap();
+        let got: Vec<(AuditOp, Vec<Uuid>)> = entries.iter().map(|e| (e.op, e.ids.clone())).collect();
+        assert_eq!(got, vec![(AuditOp::Remember, vec![a]), (AuditOp::Forget, vec![a])]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn remember_idempotent_returns_existing_id() {
+        let dir = temp_dir("idempotent");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let (first, created) = sys.remember_idempotent("the cat sat on the mat").unwrap();
+        assert!(created);
+        let (other, created) = sys.remember_idempotent("the dog sat on the mat").unwrap();
+        assert!(created);
+        assert_ne!(first, other);
+
+        let (again, created) = sys.remember_idempotent("the cat sat on the mat").unwrap();
+        assert!(!created);
+        assert_eq!(again, first);
+        assert_eq!(sys.engine.store.count(), 2);
+
+        sys.forget(&first).unwrap();
+        let (fresh, created) = sys.remember_idempotent("the cat sat on the mat").unwrap();
+        assert!(created);
+        assert_ne!(fresh, first);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn remember_idempotent_finds_surviving_duplicate() {
+        let dir = temp_dir("idempotent_dup");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let first = sys.remember("the cat sat on the mat").unwrap();
+        let second = sys.remember("the cat sat on the mat").unwrap();
+
+        sys.forget(&first).unwrap();
+        let (found, created) = sys.remember_idempotent("the cat sat on the mat").unwrap();
+        assert!(!created);
+        assert_eq!(found, second);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn remember_idempotent_reinforces_amplitude_on_repeat() {
+        let dir = temp_dir("idempotent_boost");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let (id, _) = sys.remember_idempotent("reinforce me").unwrap();
+        let before = sys.engine.get_memory(&id).unwrap().unwrap().amplitude;
+        sys.remember_idempotent("reinforce me").unwrap();
+        let after = sys.engine.get_memory(&id).unwrap().unwrap().amplitude;
+        assert!((after - before * REMEMBER_REINFORCEMENT as f32).abs() < 1e-6);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn links_of_lists_neighbours_with_previews() {
+        let dir = temp_dir("links_of");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let a = sys.engine.remember_at_layer("the cat sat on the mat", 0).unwrap();
+        let b = sys.engine.remember_at_layer("the cat sat on the mat today", 2).unwrap();
+        let _ = sys.engine.remember_at_layer("stock prices fell sharply", 2).unwrap();
+        sys.engine.get_memory_mut(&a).unwrap().unwrap().connections = vec![crate::skip_link::SkipLink {
+            target_id: b,
+            strength: 0.8,
+            resonance_key: Vec::new(),
+            span: 2,
+        }];
+
+        let links = sys.links_of(&a);
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].to_id, b.to_string());
+        assert_eq!(links[0].strength, 0.8);
+        assert_eq!(links[0].span, 2);
+        assert_eq!(links[0].target_preview, "the cat sat on the mat today");
+        assert!(sys.links_of(&Uuid::new_v4()).is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn min_cluster_size_reaches_assessment_and_survives_new_params() {
+        let dir = temp_dir("min_cluster");
+        let sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let mut sys = sys.with_min_cluster_size(4);
+        // Two groups of identical vectors, pointing in opposite directions.
+        let vector = sys.engine.pipeline.encode_text("lighthouse").unwrap();
+        let opposite: Vec<f32> = vector.iter().map(|x| -x).collect();
+        for (group, copies) in [(&vector, 4), (&opposite, 2)] {
+            for i in 0..copies {
+                let mem = crate::memory::HyperMemory::new(group.clone(), format!("copies {} member {}", copies, i));
+                sys.engine.store.insert(mem).unwrap();
+            }
+        }
+
+        assert_eq!(sys.assess().num_clusters, 1);
+        sys.set_consolidation_params(ConsolidationParams::default()).unwrap();
+        assert_eq!(sys.assess().num_clusters, 1);
+        let sys = sys.with_min_cluster_size(2);
+        assert_eq!(sys.assess().num_clusters, 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn consolidation_params_validated() {
+        let dir = temp_dir("params_validate");
+        let mut sys = KannakaMemorySystem::init_with_encoder(dir.clone(), EncoderConfig::Hash).unwrap();
+        let bad = ConsolidationParams { prune_threshold: 1.5, ..ConsolidationParams::default() };
+        assert!(matches!(sys.set_consolidation_params(bad), Err(SystemError::ConsolidationParams(_))));
+        let bad = ConsolidationParams { phase_alignment_threshold: 4.0, ..ConsolidationParams::default() };
+        assert!(sys.set_consolidation_params(bad).is_err());
+        // Cross-cluster hallucinations start at 0.5 + CROSS_CLUSTER_AMPLITUDE_BONUS.
+        let bad = ConsolidationParams {
+            hallucination_amplitude: 0.5,
+            hallucination_promotion_threshold: 0.55,
+            ..ConsolidationParams::default()
+        };
+        assert!(matches!(
+            sys.set_consolidation_params(bad),
+            Err(SystemError::ConsolidationParams(ConsolidationParamsError::PromotionThresholdTooLow { .. }))
+        ));
+
+        let good = ConsolidationParams {
+            interference_threshold: 0.4,
+            continuous_interference: true,
+            symmetrize_links: true,
+            hallucination_amplitude: 0.15,
+            hallucination_promotion_threshold: 0.6,
+            same_layer_only: true,
+            cycles: 1,
+            ..ConsolidationParams::default()
+        };
+        sys.set_consolidation_params(good.clone()).unwrap();
+        assert_eq!(sys.consolidation_params(), good);
+
+        let a = sys.remember("rivers carve canyons").unwrap();
+        let h = sys.hallucinate("canyons remember rivers", &[a]).unwrap();
+        assert_eq!(sys.engine.get_memory(&h).unwrap().unwrap().amplitude, 0.15);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn wal_replays_after_crash() {
+        let dir = temp_dir("wal_crash");
+        let (first, second, amplitude) = {
+            let mut sys = KannakaMemorySystem::init(dir.clone()).unwrap();
+            sys.remember("base memory").unwrap();
+            sys.save().unwrap();
+
+            let first = sys.remember("walked along the harbour").unwrap();
+            let second = sys.remember("wrote a parser in rust").unwrap();
+            let doomed = sys.remember("short-lived note").unwrap();
+            sys.boost(&first, 1.5).unwrap();
+            sys.forget(&doomed).unwrap();
+            let amplitude = sys.get_memory(&first).unwrap().unwrap().amplitude;
+            (first, second, amplitude)
+            // dropped without save: only the WAL has the recent ops
+        };
+        assert!(std::fs::metadata(dir.join("kannaka.wal")).unwrap().len() > 0);
+
+        let sys = KannakaMemorySystem::init(dir.clone()).unwrap();
+        assert_eq!(sys.stats().total_memories, 3);
+        let mem = sys.get_memory(&first).unwrap().unwrap();
+        assert!((mem.amplitude - amplitude).abs() < 1e-6);
+        assert!(mem.geometry.is_some());
+        assert!(sys.get_memory(&second).unwrap().is_some());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn geometry_integration_memory_gets_classified() {
         let dir = temp_dir("geometry_classify");
@@ -70,9 +70,9 @@ impl Mutation {
         };
         
         // Only store vector deltas for dimensions that changed significantly
-        let vector_delta: Vec<(usize, f32)> = new_mem.vector
+        let vector_delta: Vec<(usize, f32)> = new_mem.dense_vector()
             .iter()
-            .zip(old_mem.vector.iter())
+            .zip(old_mem.dense_vector().iter())
             .enumerate()
             .filter_map(|(i, (&new_val, &old_val))| {
                 let delta = new_val - old_val;
@@ -111,9 +111,10 @@ impl Mutation {
         }
         
         // Apply vector deltas
+        let vector = memory.vector_mut();
         for &(dim_idx, delta) in &self.vector_delta {
-            if dim_idx < memory.vector.len() {
-                memory.vector[dim_idx] += delta;
+            if dim_idx < vector.len() {
+                vector[dim_idx] += delta;
             }
         }
     }
@@ -592,13 +593,14 @@ impl ParadoxResolver {
             // Apply vector deltas to snapshot base
             if let Some(snapshot_memory) = snapshot.memories.get(&paradox.memory_id) {
                 memory.vector = snapshot_memory.vector.clone();
+                let vector = memory.vector_mut();
                 for &(dim, delta) in &state.vector_delta {
-                    if dim < memory.vector.len() {
-                        memory.vector[dim] += delta;
+                    if dim < vector.len() {
+                        vector[dim] += delta;
                     }
                 }
                 // Re-normalize vector
-                normalize(&mut memory.vector);
+                normalize(vector);
             }
             
             memory.touch();
@@ -621,6 +623,7 @@ impl ParadoxResolver {
             // For vector, merge all deltas from the projection
             if let Some(snapshot_memory) = snapshot.memories.get(&paradox.memory_id) {
                 memory.vector = snapshot_memory.vector.clone();
+                let vector = memory.vector_mut();
                 
                 // Apply weighted average of all vector deltas
                 let total_amplitude: f32 = paradox.states.iter().map(|s| s.amplitude.abs()).sum();
@@ -629,15 +632,15 @@ impl ParadoxResolver {
                     for state in &paradox.states {
                         let weight = state.amplitude.abs() / total_amplitude;
                         for &(dim, delta) in &state.vector_delta {
-                            if dim < memory.vector.len() {
-                                memory.vector[dim] += weight * delta;
+                            if dim < vector.len() {
+                                vector[dim] += weight * delta;
                             }
                         }
                     }
                 }
                 
                 // Re-normalize vector (critical for cosine similarity)
-                normalize(&mut memory.vector);
+                normalize(vector);
             }
             
             memory.touch();
@@ -1,6 +1,8 @@
 //! Disk persistence: save/load memory state to survive restarts.
 
+use std::collections::HashSet;
 use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
@@ -8,12 +10,14 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::bridge::ConsciousnessState;
 use crate::encoding::EncodingPipeline;
 use crate::geometry::MemoryCoordinates;
-use crate::memory::HyperMemory;
+use crate::memory::{HyperMemory, MemorySource, MergeRecord, DEFAULT_IMPORTANCE};
 use crate::skip_link::SkipLink;
 use crate::hnsw::HnswStore;
 use crate::store::{InMemoryStore, MemoryEngine, MemoryStore, StoreError};
+use crate::wave::SimilarityMetric;
 
 // ---------------------------------------------------------------------------
 // Errors
@@ -27,8 +31,14 @@ pub enum PersistenceError {
     SerializationError(String),
     #[error("corrupted file: {0}")]
     CorruptedFile(String),
-    #[error("version mismatch: expected {expected}, got {got}")]
-    VersionMismatch { expected: u32, got: u32 },
+    #[error("unsupported snapshot version {got} (newest supported: {supported})")]
+    UnsupportedVersion { supported: u32, got: u32 },
+    #[error("memory {id} has an un-normalized vector (norm {norm})")]
+    Unnormalized { id: Uuid, norm: f32 },
+    #[error("compressed snapshots require the `compression` feature")]
+    CompressionUnavailable,
+    #[error("write-ahead log {} was written by another schema version (got {got:?}, expected {expected}); checkpoint it with that build or remove it", path.display())]
+    WalVersionMismatch { path: PathBuf, expected: u32, got: Option<u32> },
 }
 
 impl From<bincode::Error> for PersistenceError {
@@ -41,16 +51,165 @@ impl From<bincode::Error> for PersistenceError {
 // Snapshot types
 // ---------------------------------------------------------------------------
 
-const CURRENT_VERSION: u32 = 3;
+const CURRENT_VERSION: u32 = 10;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemorySnapshot {
+    /// Schema version of the on-disk layout. Always the first field so it can
+    /// be read before the rest of the snapshot is decoded.
     pub version: u32,
     pub memories: Vec<HyperMemory>,
     pub codebook_seed: u64,
     pub codebook_input_dim: usize,
     pub codebook_output_dim: usize,
     pub metadata: SnapshotMetadata,
+    /// How memory vectors are stored. With anything but `F32` the vectors in
+    /// `memories` are left empty and live in `packed_vectors` instead.
+    pub vector_codec: VectorCodec,
+    /// One entry per memory, in the same order, when `vector_codec` is not `F32`.
+    pub packed_vectors: Vec<PackedVector>,
+    /// Timestamped consciousness assessments recorded over past sessions.
+    pub consciousness_history: Vec<(DateTime<Utc>, ConsciousnessState)>,
+    /// Metric the engine and store ranked with, restored on load so a
+    /// reopened HNSW graph is searched under the metric it was built with.
+    pub similarity_metric: SimilarityMetric,
+}
+
+/// On-disk encoding for memory vectors.
+///
+/// `Int8` and `Binary` shrink snapshots 4× and 32× but are lossy: vectors come
+/// back approximated, so similarity scores and recall rankings shift slightly
+/// after a round trip. Use `F32` when exact recall matters.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum VectorCodec {
+    /// Full-precision floats, stored as-is.
+    #[default]
+    F32,
+    /// One signed byte per component; component = byte × `scale`. A positive
+    /// `scale` is used for every vector (components beyond ±127 × `scale` are
+    /// clamped); `0.0` picks a scale per vector from its largest component.
+    Int8 { scale: f32 },
+    /// One sign bit per component, reconstructed as ±1/√dim (unit length).
+    Binary,
+}
+
+/// A memory vector quantized with a `VectorCodec`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackedVector {
+    pub codec: VectorCodec,
+    pub dim: u32,
+    pub bytes: Vec<u8>,
+}
+
+impl VectorCodec {
+    /// Quantize `v`. `F32` keeps the raw little-endian floats.
+    pub fn encode(&self, v: &[f32]) -> PackedVector {
+        let (codec, bytes) = match self {
+            VectorCodec::F32 => (*self, v.iter().flat_map(|x| x.to_le_bytes()).collect()),
+            VectorCodec::Int8 { scale } => {
+                let scale = if *scale > 0.0 {
+                    *scale
+                } else {
+                    v.iter().fold(0.0f32, |m, x| m.max(x.abs())) / 127.0
+                };
+                let bytes = v.iter()
+                    .map(|x| if scale > 0.0 { (x / scale).round().clamp(-127.0, 127.0) as i8 as u8 } else { 0 })
+                    .collect();
+                (VectorCodec::Int8 { scale }, bytes)
+            }
+            VectorCodec::Binary => {
+                let mut bytes = vec![0u8; v.len().div_ceil(8)];
+                for (i, x) in v.iter().enumerate() {
+                    if *x >= 0.0 {
+                        bytes[i / 8] |= 1 << (i % 8);
+                    }
+                }
+                (*self, bytes)
+            }
+        };
+        PackedVector { codec, dim: v.len() as u32, bytes }
+    }
+}
+
+impl PackedVector {
+    /// Reconstruct the (approximate) vector.
+    pub fn decode(&self) -> Result<Vec<f32>, PersistenceError> {
+        let dim = self.dim as usize;
+        let expected = match self.codec {
+            VectorCodec::F32 => dim * 4,
+            VectorCodec::Int8 { .. } => dim,
+            VectorCodec::Binary => dim.div_ceil(8),
+        };
+        if self.bytes.len() != expected {
+            return Err(PersistenceError::CorruptedFile(format!(
+                "packed vector has {} bytes, expected {}", self.bytes.len(), expected)));
+        }
+        Ok(match self.codec {
+            VectorCodec::F32 => self.bytes.chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect(),
+            VectorCodec::Int8 { scale } => self.bytes.iter().map(|&b| b as i8 as f32 * scale).collect(),
+            VectorCodec::Binary => {
+                let mag = 1.0 / (dim as f32).sqrt();
+                (0..dim)
+                    .map(|i| if self.bytes[i / 8] & (1 << (i % 8)) != 0 { mag } else { -mag })
+                    .collect()
+            }
+        })
+    }
+}
+
+impl MemorySnapshot {
+    /// Build a snapshot, quantizing memory vectors with `codec`.
+    fn packed(
+        mut memories: Vec<HyperMemory>,
+        codebook: (u64, usize, usize),
+        metadata: SnapshotMetadata,
+        codec: VectorCodec,
+    ) -> Self {
+        let packed_vectors = if codec == VectorCodec::F32 {
+            Vec::new()
+        } else {
+            // Sparse and binary vectors are compact already and stay in place,
+            // marked by an empty packed entry.
+            memories.iter_mut()
+                .map(|m| match m.vector.as_dense() {
+                    Some(_) => codec.encode(&m.take_dense_vector()),
+                    None => codec.encode(&[]),
+                })
+                .collect()
+        };
+        Self {
+            version: CURRENT_VERSION,
+            memories,
+            codebook_seed: codebook.0,
+            codebook_input_dim: codebook.1,
+            codebook_output_dim: codebook.2,
+            metadata,
+            vector_codec: codec,
+            packed_vectors,
+            consciousness_history: Vec::new(),
+            similarity_metric: SimilarityMetric::default(),
+        }
+    }
+
+    /// Move dequantized vectors back into `memories`.
+    fn unpack_vectors(&mut self) -> Result<(), PersistenceError> {
+        if self.vector_codec == VectorCodec::F32 && self.packed_vectors.is_empty() {
+            return Ok(());
+        }
+        if self.packed_vectors.len() != self.memories.len() {
+            return Err(PersistenceError::CorruptedFile(format!(
+                "{} packed vectors for {} memories", self.packed_vectors.len(), self.memories.len())));
+        }
+        for (mem, packed) in self.memories.iter_mut().zip(self.packed_vectors.drain(..)) {
+            if packed.dim > 0 {
+                mem.set_vector(packed.decode()?);
+            }
+        }
+        self.vector_codec = VectorCodec::F32;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,6 +218,504 @@ pub struct SnapshotMetadata {
     pub last_saved_at: DateTime<Utc>,
     pub total_consolidations: u64,
     pub consciousness_level: String,
+    /// Serialized snapshot size in bytes, as of the last save or load.
+    /// Measured, not stored in the file.
+    #[serde(skip)]
+    pub original_size: u64,
+    /// On-disk size when the snapshot is zstd-compressed, `None` otherwise.
+    #[serde(skip)]
+    pub compressed_size: Option<u64>,
+}
+
+// ---------------------------------------------------------------------------
+// V9 structures for migration from bincode format without access time or sparse vectors
+// ---------------------------------------------------------------------------
+
+/// V9 HyperMemory struct (before last_accessed and sparse were added)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HyperMemoryV9 {
+    pub id: Uuid,
+    pub vector: Vec<f32>,
+    pub amplitude: f32,
+    pub frequency: f32,
+    pub phase: f32,
+    pub decay_rate: f32,
+    pub created_at: DateTime<Utc>,
+    pub layer_depth: u8,
+    pub connections: Vec<SkipLink>,
+    pub content: String,
+    #[serde(default)]
+    pub hallucinated: bool,
+    #[serde(default)]
+    pub parents: Vec<String>,
+    #[serde(default)]
+    pub geometry: Option<MemoryCoordinates>,
+    #[serde(default)]
+    pub xi_signature: Vec<f32>,
+    pub origin_agent: String,
+    #[serde(default)]
+    pub sync_version: u64,
+    #[serde(default)]
+    pub merge_history: Vec<MergeRecord>,
+    #[serde(default)]
+    pub last_consolidated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub disputed: bool,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub retrieval_count: u32,
+    #[serde(default, with = "crate::memory::ttl_seconds")]
+    pub ttl: Option<chrono::Duration>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub importance: f32,
+    #[serde(default)]
+    pub source: MemorySource,
+    // last_accessed / sparse NOT present
+}
+
+/// V9 MemorySnapshot for migration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MemorySnapshotV9 {
+    pub version: u32,
+    pub memories: Vec<HyperMemoryV9>,
+    pub codebook_seed: u64,
+    pub codebook_input_dim: usize,
+    pub codebook_output_dim: usize,
+    pub metadata: SnapshotMetadata,
+    pub vector_codec: VectorCodec,
+    pub packed_vectors: Vec<PackedVector>,
+    pub consciousness_history: Vec<(DateTime<Utc>, ConsciousnessState)>,
+}
+
+impl From<HyperMemoryV9> for HyperMemory {
+    fn from(v9: HyperMemoryV9) -> Self {
+        Self {
+            id: v9.id,
+            vector: v9.vector.into(),
+            amplitude: v9.amplitude,
+            frequency: v9.frequency,
+            phase: v9.phase,
+            decay_rate: v9.decay_rate,
+            created_at: v9.created_at,
+            layer_depth: v9.layer_depth,
+            connections: v9.connections,
+            content: v9.content,
+            hallucinated: v9.hallucinated,
+            parents: v9.parents,
+            geometry: v9.geometry,
+            xi_signature: v9.xi_signature,
+            origin_agent: v9.origin_agent,
+            sync_version: v9.sync_version,
+            merge_history: v9.merge_history,
+            last_consolidated_at: v9.last_consolidated_at,
+            disputed: v9.disputed,
+            updated_at: v9.updated_at,
+            retrieval_count: v9.retrieval_count,
+            ttl: v9.ttl,
+            initial_amplitude: crate::memory::default_initial_amplitude(),
+            tags: v9.tags,
+            importance: v9.importance,
+            source: v9.source,
+            last_accessed: None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// V8 structures for migration from bincode format without source
+// ---------------------------------------------------------------------------
+
+/// V8 HyperMemory struct (before source was added)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HyperMemoryV8 {
+    pub id: Uuid,
+    pub vector: Vec<f32>,
+    pub amplitude: f32,
+    pub frequency: f32,
+    pub phase: f32,
+    pub decay_rate: f32,
+    pub created_at: DateTime<Utc>,
+    pub layer_depth: u8,
+    pub connections: Vec<SkipLink>,
+    pub content: String,
+    #[serde(default)]
+    pub hallucinated: bool,
+    #[serde(default)]
+    pub parents: Vec<String>,
+    #[serde(default)]
+    pub geometry: Option<MemoryCoordinates>,
+    #[serde(default)]
+    pub xi_signature: Vec<f32>,
+    pub origin_agent: String,
+    #[serde(default)]
+    pub sync_version: u64,
+    #[serde(default)]
+    pub merge_history: Vec<MergeRecord>,
+    #[serde(default)]
+    pub last_consolidated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub disputed: bool,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub retrieval_count: u32,
+    #[serde(default, with = "crate::memory::ttl_seconds")]
+    pub ttl: Option<chrono::Duration>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub importance: f32,
+    // source NOT present
+}
+
+/// V8 MemorySnapshot for migration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MemorySnapshotV8 {
+    pub version: u32,
+    pub memories: Vec<HyperMemoryV8>,
+    pub codebook_seed: u64,
+    pub codebook_input_dim: usize,
+    pub codebook_output_dim: usize,
+    pub metadata: SnapshotMetadata,
+    pub vector_codec: VectorCodec,
+    pub packed_vectors: Vec<PackedVector>,
+    pub consciousness_history: Vec<(DateTime<Utc>, ConsciousnessState)>,
+}
+
+impl From<HyperMemoryV8> for HyperMemory {
+    fn from(v8: HyperMemoryV8) -> Self {
+        Self {
+            id: v8.id,
+            vector: v8.vector.into(),
+            amplitude: v8.amplitude,
+            frequency: v8.frequency,
+            phase: v8.phase,
+            decay_rate: v8.decay_rate,
+            created_at: v8.created_at,
+            layer_depth: v8.layer_depth,
+            connections: v8.connections,
+            content: v8.content,
+            hallucinated: v8.hallucinated,
+            parents: v8.parents,
+            geometry: v8.geometry,
+            xi_signature: v8.xi_signature,
+            origin_agent: v8.origin_agent,
+            sync_version: v8.sync_version,
+            merge_history: v8.merge_history,
+            last_consolidated_at: v8.last_consolidated_at,
+            disputed: v8.disputed,
+            updated_at: v8.updated_at,
+            retrieval_count: v8.retrieval_count,
+            ttl: v8.ttl,
+            initial_amplitude: crate::memory::default_initial_amplitude(),
+            tags: v8.tags,
+            importance: v8.importance,
+            source: MemorySource::default(),
+            last_accessed: None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// V7 structures for migration from bincode format without importance
+// ---------------------------------------------------------------------------
+
+/// V7 HyperMemory struct (before importance was added; also used by V6)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HyperMemoryV7 {
+    pub id: Uuid,
+    pub vector: Vec<f32>,
+    pub amplitude: f32,
+    pub frequency: f32,
+    pub phase: f32,
+    pub decay_rate: f32,
+    pub created_at: DateTime<Utc>,
+    pub layer_depth: u8,
+    pub connections: Vec<SkipLink>,
+    pub content: String,
+    #[serde(default)]
+    pub hallucinated: bool,
+    #[serde(default)]
+    pub parents: Vec<String>,
+    #[serde(default)]
+    pub geometry: Option<MemoryCoordinates>,
+    #[serde(default)]
+    pub xi_signature: Vec<f32>,
+    pub origin_agent: String,
+    #[serde(default)]
+    pub sync_version: u64,
+    #[serde(default)]
+    pub merge_history: Vec<MergeRecord>,
+    #[serde(default)]
+    pub last_consolidated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub disputed: bool,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub retrieval_count: u32,
+    #[serde(default, with = "crate::memory::ttl_seconds")]
+    pub ttl: Option<chrono::Duration>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // importance NOT present
+}
+
+/// V7 MemorySnapshot for migration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MemorySnapshotV7 {
+    pub version: u32,
+    pub memories: Vec<HyperMemoryV7>,
+    pub codebook_seed: u64,
+    pub codebook_input_dim: usize,
+    pub codebook_output_dim: usize,
+    pub metadata: SnapshotMetadata,
+    pub vector_codec: VectorCodec,
+    pub packed_vectors: Vec<PackedVector>,
+    pub consciousness_history: Vec<(DateTime<Utc>, ConsciousnessState)>,
+}
+
+impl From<HyperMemoryV7> for HyperMemory {
+    fn from(v7: HyperMemoryV7) -> Self {
+        Self {
+            id: v7.id,
+            vector: v7.vector.into(),
+            amplitude: v7.amplitude,
+            frequency: v7.frequency,
+            phase: v7.phase,
+            decay_rate: v7.decay_rate,
+            created_at: v7.created_at,
+            layer_depth: v7.layer_depth,
+            connections: v7.connections,
+            content: v7.content,
+            hallucinated: v7.hallucinated,
+            parents: v7.parents,
+            geometry: v7.geometry,
+            xi_signature: v7.xi_signature,
+            origin_agent: v7.origin_agent,
+            sync_version: v7.sync_version,
+            merge_history: v7.merge_history,
+            last_consolidated_at: v7.last_consolidated_at,
+            disputed: v7.disputed,
+            updated_at: v7.updated_at,
+            retrieval_count: v7.retrieval_count,
+            ttl: v7.ttl,
+            initial_amplitude: crate::memory::default_initial_amplitude(),
+            tags: v7.tags,
+            importance: DEFAULT_IMPORTANCE,
+            source: MemorySource::default(),
+            last_accessed: None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// V6 structures for migration from bincode format without consciousness history
+// ---------------------------------------------------------------------------
+
+/// V6 MemorySnapshot (before the consciousness history was saved)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MemorySnapshotV6 {
+    pub version: u32,
+    pub memories: Vec<HyperMemoryV7>,
+    pub codebook_seed: u64,
+    pub codebook_input_dim: usize,
+    pub codebook_output_dim: usize,
+    pub metadata: SnapshotMetadata,
+    pub vector_codec: VectorCodec,
+    pub packed_vectors: Vec<PackedVector>,
+}
+
+// ---------------------------------------------------------------------------
+// V5 structures for migration from bincode format without tags
+// ---------------------------------------------------------------------------
+
+/// V5 HyperMemory struct (before tags were added)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HyperMemoryV5 {
+    pub id: Uuid,
+    pub vector: Vec<f32>,
+    pub amplitude: f32,
+    pub frequency: f32,
+    pub phase: f32,
+    pub decay_rate: f32,
+    pub created_at: DateTime<Utc>,
+    pub layer_depth: u8,
+    pub connections: Vec<SkipLink>,
+    pub content: String,
+    #[serde(default)]
+    pub hallucinated: bool,
+    #[serde(default)]
+    pub parents: Vec<String>,
+    #[serde(default)]
+    pub geometry: Option<MemoryCoordinates>,
+    #[serde(default)]
+    pub xi_signature: Vec<f32>,
+    pub origin_agent: String,
+    #[serde(default)]
+    pub sync_version: u64,
+    #[serde(default)]
+    pub merge_history: Vec<MergeRecord>,
+    #[serde(default)]
+    pub last_consolidated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub disputed: bool,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub retrieval_count: u32,
+    #[serde(default, with = "crate::memory::ttl_seconds")]
+    pub ttl: Option<chrono::Duration>,
+    // tags NOT present
+}
+
+/// V5 MemorySnapshot for migration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MemorySnapshotV5 {
+    pub version: u32,
+    pub memories: Vec<HyperMemoryV5>,
+    pub codebook_seed: u64,
+    pub codebook_input_dim: usize,
+    pub codebook_output_dim: usize,
+    pub metadata: SnapshotMetadata,
+    pub vector_codec: VectorCodec,
+    pub packed_vectors: Vec<PackedVector>,
+}
+
+impl From<HyperMemoryV5> for HyperMemory {
+    fn from(v5: HyperMemoryV5) -> Self {
+        Self {
+            id: v5.id,
+            vector: v5.vector.into(),
+            amplitude: v5.amplitude,
+            frequency: v5.frequency,
+            phase: v5.phase,
+            decay_rate: v5.decay_rate,
+            created_at: v5.created_at,
+            layer_depth: v5.layer_depth,
+            connections: v5.connections,
+            content: v5.content,
+            hallucinated: v5.hallucinated,
+            parents: v5.parents,
+            geometry: v5.geometry,
+            xi_signature: v5.xi_signature,
+            origin_agent: v5.origin_agent,
+            sync_version: v5.sync_version,
+            merge_history: v5.merge_history,
+            last_consolidated_at: v5.last_consolidated_at,
+            disputed: v5.disputed,
+            updated_at: v5.updated_at,
+            retrieval_count: v5.retrieval_count,
+            ttl: v5.ttl,
+            initial_amplitude: crate::memory::default_initial_amplitude(),
+            tags: Vec::new(),
+            importance: DEFAULT_IMPORTANCE,
+            source: MemorySource::default(),
+            last_accessed: None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// V4 structures for migration from bincode format without vector codecs
+// ---------------------------------------------------------------------------
+
+/// V4 MemorySnapshot (before vector quantization was added)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MemorySnapshotV4 {
+    pub version: u32,
+    pub memories: Vec<HyperMemoryV5>,
+    pub codebook_seed: u64,
+    pub codebook_input_dim: usize,
+    pub codebook_output_dim: usize,
+    pub metadata: SnapshotMetadata,
+}
+
+// ---------------------------------------------------------------------------
+// V3 structures for migration from bincode format without ttl
+// ---------------------------------------------------------------------------
+
+/// V3 HyperMemory struct (before ttl was added)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HyperMemoryV3 {
+    pub id: Uuid,
+    pub vector: Vec<f32>,
+    pub amplitude: f32,
+    pub frequency: f32,
+    pub phase: f32,
+    pub decay_rate: f32,
+    pub created_at: DateTime<Utc>,
+    pub layer_depth: u8,
+    pub connections: Vec<SkipLink>,
+    pub content: String,
+    #[serde(default)]
+    pub hallucinated: bool,
+    #[serde(default)]
+    pub parents: Vec<String>,
+    #[serde(default)]
+    pub geometry: Option<MemoryCoordinates>,
+    #[serde(default)]
+    pub xi_signature: Vec<f32>,
+    pub origin_agent: String,
+    #[serde(default)]
+    pub sync_version: u64,
+    #[serde(default)]
+    pub merge_history: Vec<MergeRecord>,
+    #[serde(default)]
+    pub last_consolidated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub disputed: bool,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub retrieval_count: u32,
+    // ttl NOT present
+}
+
+/// V3 MemorySnapshot for migration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MemorySnapshotV3 {
+    pub version: u32,
+    pub memories: Vec<HyperMemoryV3>,
+    pub codebook_seed: u64,
+    pub codebook_input_dim: usize,
+    pub codebook_output_dim: usize,
+    pub metadata: SnapshotMetadata,
+}
+
+impl From<HyperMemoryV3> for HyperMemory {
+    fn from(v3: HyperMemoryV3) -> Self {
+        Self {
+            id: v3.id,
+            vector: v3.vector.into(),
+            amplitude: v3.amplitude,
+            frequency: v3.frequency,
+            phase: v3.phase,
+            decay_rate: v3.decay_rate,
+            created_at: v3.created_at,
+            layer_depth: v3.layer_depth,
+            connections: v3.connections,
+            content: v3.content,
+            hallucinated: v3.hallucinated,
+            parents: v3.parents,
+            geometry: v3.geometry,
+            xi_signature: v3.xi_signature,
+            origin_agent: v3.origin_agent,
+            sync_version: v3.sync_version,
+            merge_history: v3.merge_history,
+            last_consolidated_at: v3.last_consolidated_at,
+            disputed: v3.disputed,
+            updated_at: v3.updated_at,
+            retrieval_count: v3.retrieval_count,
+            ttl: None,
+            initial_amplitude: crate::memory::default_initial_amplitude(),
+            tags: Vec::new(),
+            importance: DEFAULT_IMPORTANCE,
+            source: MemorySource::default(),
+            last_accessed: None,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -104,7 +761,7 @@ impl From<HyperMemoryV2> for HyperMemory {
     fn from(v2: HyperMemoryV2) -> Self {
         Self {
             id: v2.id,
-            vector: v2.vector,
+            vector: v2.vector.into(),
             amplitude: v2.amplitude,
             frequency: v2.frequency,
             phase: v2.phase,
@@ -124,6 +781,12 @@ impl From<HyperMemoryV2> for HyperMemory {
             disputed: false,
             updated_at: None,
             retrieval_count: 0,
+            ttl: None,
+            initial_amplitude: crate::memory::default_initial_amplitude(),
+            tags: Vec::new(),
+            importance: DEFAULT_IMPORTANCE,
+            source: MemorySource::default(),
+            last_accessed: None,
         }
     }
 }
@@ -169,7 +832,7 @@ impl From<HyperMemoryV1> for HyperMemory {
     fn from(v1: HyperMemoryV1) -> Self {
         Self {
             id: v1.id,
-            vector: v1.vector,
+            vector: v1.vector.into(),
             amplitude: v1.amplitude,
             frequency: v1.frequency,
             phase: v1.phase,
@@ -189,7 +852,589 @@ impl From<HyperMemoryV1> for HyperMemory {
             disputed: false,
             updated_at: None,
             retrieval_count: 0,
+            ttl: None,
+            initial_amplitude: crate::memory::default_initial_amplitude(),
+            tags: Vec::new(),
+            importance: DEFAULT_IMPORTANCE,
+            source: MemorySource::default(),
+            last_accessed: None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Snapshot decoding
+// ---------------------------------------------------------------------------
+
+impl From<MemorySnapshotV9> for MemorySnapshot {
+    fn from(old: MemorySnapshotV9) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            memories: old.memories.into_iter().map(HyperMemory::from).collect(),
+            codebook_seed: old.codebook_seed,
+            codebook_input_dim: old.codebook_input_dim,
+            codebook_output_dim: old.codebook_output_dim,
+            metadata: old.metadata,
+            vector_codec: old.vector_codec,
+            packed_vectors: old.packed_vectors,
+            consciousness_history: old.consciousness_history,
+            similarity_metric: SimilarityMetric::default(),
+        }
+    }
+}
+
+impl From<MemorySnapshotV8> for MemorySnapshot {
+    fn from(old: MemorySnapshotV8) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            memories: old.memories.into_iter().map(HyperMemory::from).collect(),
+            codebook_seed: old.codebook_seed,
+            codebook_input_dim: old.codebook_input_dim,
+            codebook_output_dim: old.codebook_output_dim,
+            metadata: old.metadata,
+            vector_codec: old.vector_codec,
+            packed_vectors: old.packed_vectors,
+            consciousness_history: old.consciousness_history,
+            similarity_metric: SimilarityMetric::default(),
+        }
+    }
+}
+
+impl From<MemorySnapshotV7> for MemorySnapshot {
+    fn from(old: MemorySnapshotV7) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            memories: old.memories.into_iter().map(HyperMemory::from).collect(),
+            codebook_seed: old.codebook_seed,
+            codebook_input_dim: old.codebook_input_dim,
+            codebook_output_dim: old.codebook_output_dim,
+            metadata: old.metadata,
+            vector_codec: old.vector_codec,
+            packed_vectors: old.packed_vectors,
+            consciousness_history: old.consciousness_history,
+            similarity_metric: SimilarityMetric::default(),
+        }
+    }
+}
+
+impl From<MemorySnapshotV6> for MemorySnapshot {
+    fn from(old: MemorySnapshotV6) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            memories: old.memories.into_iter().map(HyperMemory::from).collect(),
+            codebook_seed: old.codebook_seed,
+            codebook_input_dim: old.codebook_input_dim,
+            codebook_output_dim: old.codebook_output_dim,
+            metadata: old.metadata,
+            vector_codec: old.vector_codec,
+            packed_vectors: old.packed_vectors,
+            consciousness_history: Vec::new(),
+            similarity_metric: SimilarityMetric::default(),
+        }
+    }
+}
+
+impl From<MemorySnapshotV5> for MemorySnapshot {
+    fn from(old: MemorySnapshotV5) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            memories: old.memories.into_iter().map(HyperMemory::from).collect(),
+            codebook_seed: old.codebook_seed,
+            codebook_input_dim: old.codebook_input_dim,
+            codebook_output_dim: old.codebook_output_dim,
+            metadata: old.metadata,
+            vector_codec: old.vector_codec,
+            packed_vectors: old.packed_vectors,
+            consciousness_history: Vec::new(),
+            similarity_metric: SimilarityMetric::default(),
+        }
+    }
+}
+
+impl From<MemorySnapshotV4> for MemorySnapshot {
+    fn from(old: MemorySnapshotV4) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            memories: old.memories.into_iter().map(HyperMemory::from).collect(),
+            codebook_seed: old.codebook_seed,
+            codebook_input_dim: old.codebook_input_dim,
+            codebook_output_dim: old.codebook_output_dim,
+            metadata: old.metadata,
+            vector_codec: VectorCodec::F32,
+            packed_vectors: Vec::new(),
+            consciousness_history: Vec::new(),
+            similarity_metric: SimilarityMetric::default(),
+        }
+    }
+}
+
+impl From<MemorySnapshotV3> for MemorySnapshot {
+    fn from(old: MemorySnapshotV3) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            memories: old.memories.into_iter().map(HyperMemory::from).collect(),
+            codebook_seed: old.codebook_seed,
+            codebook_input_dim: old.codebook_input_dim,
+            codebook_output_dim: old.codebook_output_dim,
+            metadata: old.metadata,
+            vector_codec: VectorCodec::F32,
+            packed_vectors: Vec::new(),
+            consciousness_history: Vec::new(),
+            similarity_metric: SimilarityMetric::default(),
+        }
+    }
+}
+
+impl From<MemorySnapshotV2> for MemorySnapshot {
+    fn from(old: MemorySnapshotV2) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            memories: old.memories.into_iter().map(HyperMemory::from).collect(),
+            codebook_seed: old.codebook_seed,
+            codebook_input_dim: old.codebook_input_dim,
+            codebook_output_dim: old.codebook_output_dim,
+            metadata: old.metadata,
+            vector_codec: VectorCodec::F32,
+            packed_vectors: Vec::new(),
+            consciousness_history: Vec::new(),
+            similarity_metric: SimilarityMetric::default(),
+        }
+    }
+}
+
+impl From<MemorySnapshotV1> for MemorySnapshot {
+    fn from(old: MemorySnapshotV1) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            memories: old.memories.into_iter().map(HyperMemory::from).collect(),
+            codebook_seed: old.codebook_seed,
+            codebook_input_dim: old.codebook_input_dim,
+            codebook_output_dim: old.codebook_output_dim,
+            metadata: old.metadata,
+            vector_codec: VectorCodec::F32,
+            packed_vectors: Vec::new(),
+            consciousness_history: Vec::new(),
+            similarity_metric: SimilarityMetric::default(),
+        }
+    }
+}
+
+/// Largest deviation of a vector's norm from 1.0 accepted on load.
+pub const NORM_TOLERANCE: f32 = 1e-3;
+
+/// What loading does with a vector whose norm strays from 1.0 by more than
+/// `NORM_TOLERANCE` (older snapshots, hand-edited imports).
+///
+/// The plain load paths pick `for_metric`: unit norm is enforced for stores
+/// ranked by cosine, while the Euclidean and Dot metrics exist for vectors
+/// whose magnitude means something, so those keep vectors as stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NormPolicy {
+    /// Load vectors unchanged.
+    Keep,
+    /// Rescale off-norm vectors to unit length.
+    #[default]
+    Renormalize,
+    /// Fail the load with `PersistenceError::Unnormalized`.
+    Reject,
+}
+
+impl NormPolicy {
+    /// `Renormalize` under cosine, `Keep` under metrics that read magnitude.
+    pub fn for_metric(metric: SimilarityMetric) -> Self {
+        match metric {
+            SimilarityMetric::Cosine => NormPolicy::Renormalize,
+            SimilarityMetric::Euclidean | SimilarityMetric::Dot => NormPolicy::Keep,
+        }
+    }
+}
+
+/// Outcome of `DiskStore::import_jsonl`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JsonlImport {
+    /// Memories inserted.
+    pub imported: usize,
+    /// Imported vectors rescaled to unit norm.
+    pub renormalized: usize,
+    /// Lines that could not be imported, as `"line N: reason"`. Memories
+    /// whose id already exists are skipped without an entry.
+    pub skipped: Vec<String>,
+}
+
+/// Apply `policy` to `mem.vector`. Empty and all-zero vectors are left alone.
+/// Returns whether the vector was rescaled.
+fn enforce_unit_norm(mem: &mut HyperMemory, policy: NormPolicy) -> Result<bool, PersistenceError> {
+    if policy == NormPolicy::Keep {
+        return Ok(false);
+    }
+    let norm = mem.vector.norm();
+    if norm == 0.0 || (norm - 1.0).abs() <= NORM_TOLERANCE {
+        return Ok(false);
+    }
+    if policy == NormPolicy::Reject {
+        return Err(PersistenceError::Unnormalized { id: mem.id, norm });
+    }
+    mem.vector.normalize();
+    Ok(true)
+}
+
+/// Magic number opening every zstd frame. Snapshots start with their version
+/// number instead, so the two never collide.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Whether `path` asks for compression by its `.zst` extension.
+fn wants_compression(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "zst")
+}
+
+#[cfg(feature = "compression")]
+fn compress(data: &[u8]) -> Result<Vec<u8>, PersistenceError> {
+    Ok(zstd::encode_all(data, zstd::DEFAULT_COMPRESSION_LEVEL)?)
+}
+
+#[cfg(not(feature = "compression"))]
+fn compress(_data: &[u8]) -> Result<Vec<u8>, PersistenceError> {
+    Err(PersistenceError::CompressionUnavailable)
+}
+
+#[cfg(feature = "compression")]
+fn decompress(data: &[u8]) -> Result<Vec<u8>, PersistenceError> {
+    Ok(zstd::decode_all(data)?)
+}
+
+#[cfg(not(feature = "compression"))]
+fn decompress(_data: &[u8]) -> Result<Vec<u8>, PersistenceError> {
+    Err(PersistenceError::CompressionUnavailable)
+}
+
+/// Serialize `snapshot` and write it to `path`, zstd-compressed if `compressed`.
+/// Records the resulting sizes in the snapshot's metadata.
+fn write_snapshot_file(path: &Path, snapshot: &mut MemorySnapshot, compressed: bool) -> Result<(), PersistenceError> {
+    let data = bincode::serialize(&*snapshot)?;
+    snapshot.metadata.original_size = data.len() as u64;
+    if compressed {
+        let packed = compress(&data)?;
+        snapshot.metadata.compressed_size = Some(packed.len() as u64);
+        fs::write(path, &packed)?;
+    } else {
+        snapshot.metadata.compressed_size = None;
+        fs::write(path, &data)?;
+    }
+    Ok(())
+}
+
+/// Read and decode the snapshot at `path`, decompressing it first if it starts
+/// with the zstd magic bytes. Returns the snapshot and whether it was compressed.
+fn read_snapshot_file(path: &Path) -> Result<(MemorySnapshot, bool), PersistenceError> {
+    let raw = fs::read(path)?;
+    let compressed = raw.starts_with(&ZSTD_MAGIC);
+    let data = if compressed { decompress(&raw)? } else { raw };
+    let mut snapshot = decode_snapshot(&data)?;
+    snapshot.metadata.original_size = data.len() as u64;
+    snapshot.metadata.compressed_size = compressed.then(|| fs::metadata(path).map(|m| m.len())).transpose()?;
+    Ok((snapshot, compressed))
+}
+
+/// Decode snapshot bytes of any supported version, migrating memories to the
+/// current `HyperMemory` layout and dequantizing packed vectors. Dispatches on
+/// the leading version header.
+fn decode_snapshot(data: &[u8]) -> Result<MemorySnapshot, PersistenceError> {
+    let version = data.get(..4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| PersistenceError::CorruptedFile("missing version header".to_string()))?;
+    let mut snapshot: MemorySnapshot = match version {
+        v if v > CURRENT_VERSION => return Err(PersistenceError::UnsupportedVersion {
+            supported: CURRENT_VERSION, got: v }),
+        CURRENT_VERSION => bincode::deserialize(data)?,
+        9 => bincode::deserialize::<MemorySnapshotV9>(data)?.into(),
+        8 => bincode::deserialize::<MemorySnapshotV8>(data)?.into(),
+        7 => bincode::deserialize::<MemorySnapshotV7>(data)?.into(),
+        6 => bincode::deserialize::<MemorySnapshotV6>(data)?.into(),
+        5 => bincode::deserialize::<MemorySnapshotV5>(data)?.into(),
+        4 => bincode::deserialize::<MemorySnapshotV4>(data)?.into(),
+        3 => bincode::deserialize::<MemorySnapshotV3>(data)?.into(),
+        2 => bincode::deserialize::<MemorySnapshotV2>(data)?.into(),
+        // V1 (no xi_signature)
+        1 => bincode::deserialize::<MemorySnapshotV1>(data)?.into(),
+        v => return Err(PersistenceError::UnsupportedVersion {
+            supported: CURRENT_VERSION, got: v }),
+    };
+    snapshot.unpack_vectors()?;
+    Ok(snapshot)
+}
+
+// ---------------------------------------------------------------------------
+// Write-ahead log
+// ---------------------------------------------------------------------------
+
+/// A single mutation recorded in the write-ahead log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum WalOp {
+    Insert(Box<HyperMemory>),
+    Delete(Uuid),
+    UpdateAmplitude { id: Uuid, amplitude: f32 },
+    AddLink { source: Uuid, link: SkipLink },
+}
+
+impl WalOp {
+    /// Apply this op to a store. Ops are idempotent so replaying a log that was
+    /// already folded into the snapshot is harmless. Returns whether anything changed.
+    pub fn apply(self, store: &mut dyn MemoryStore) -> Result<bool, StoreError> {
+        match self {
+            WalOp::Insert(mem) => {
+                if store.get(&mem.id)?.is_some() {
+                    return Ok(false);
+                }
+                store.insert(*mem)?;
+                Ok(true)
+            }
+            WalOp::Delete(id) => store.delete(&id),
+            WalOp::UpdateAmplitude { id, amplitude } => match store.get_mut(&id)? {
+                Some(mem) => {
+                    mem.amplitude = amplitude;
+                    Ok(true)
+                }
+                None => Ok(false),
+            },
+            WalOp::AddLink { source, link } => match store.get_mut(&source)? {
+                Some(mem) if !mem.connections.iter().any(|l| l.target_id == link.target_id) => {
+                    mem.connections.push(link);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            },
+        }
+    }
+}
+
+/// Append-only log of `WalOp`s stored next to a snapshot (`kannaka.bin` → `kannaka.wal`).
+///
+/// The file starts with `WAL_MAGIC` and the snapshot schema version the ops were
+/// encoded with; a log from a build with a different `HyperMemory` layout is
+/// refused rather than partially replayed. Each record after the header is a
+/// little-endian u32 length followed by the bincode-encoded op. A torn record at
+/// the tail (crash mid-append) is ignored on replay and cut off by `open`, so
+/// later appends land on a record boundary.
+pub struct Wal {
+    path: PathBuf,
+    file: fs::File,
+    ops: usize,
+}
+
+/// Outcome of `Wal::replay`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WalReplay {
+    /// Ops that changed the store.
+    pub applied: usize,
+    /// Logged insert vectors rescaled under `NormPolicy::Renormalize`.
+    pub renormalized: usize,
+}
+
+const WAL_MAGIC: [u8; 4] = *b"KWAL";
+const WAL_HEADER_LEN: usize = 8;
+
+impl Wal {
+    /// WAL path for a given snapshot path.
+    pub fn path_for(snapshot: &Path) -> PathBuf {
+        snapshot.with_extension("wal")
+    }
+
+    /// Open (or create) a log for appending. A torn record at the tail is
+    /// truncated away; a log written with another schema version is an error.
+    pub fn open(path: PathBuf) -> Result<Self, PersistenceError> {
+        let data = match fs::read(&path) {
+            Ok(d) => d,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let (ops, end) = Self::scan(&data, &path)?;
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        if end < data.len() {
+            eprintln!("[wal] truncating {} trailing bytes in {}", data.len() - end, path.display());
+            file.set_len(end as u64)?;
+        }
+        if end == 0 {
+            file.write_all(&Self::header())?;
+        }
+        Ok(Self { path, file, ops: ops.len() })
+    }
+
+    fn header() -> [u8; WAL_HEADER_LEN] {
+        let mut header = [0u8; WAL_HEADER_LEN];
+        header[..4].copy_from_slice(&WAL_MAGIC);
+        header[4..].copy_from_slice(&CURRENT_VERSION.to_le_bytes());
+        header
+    }
+
+    /// Append one op to the log.
+    pub fn append(&mut self, op: &WalOp) -> Result<(), PersistenceError> {
+        let data = bincode::serialize(op)?;
+        let mut record = Vec::with_capacity(4 + data.len());
+        record.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        record.extend_from_slice(&data);
+        self.file.write_all(&record)?;
+        self.ops += 1;
+        Ok(())
+    }
+
+    /// Discard all logged ops (after they have been folded into a snapshot).
+    pub fn truncate(&mut self) -> Result<(), PersistenceError> {
+        self.file.set_len(0)?;
+        self.file.write_all(&Self::header())?;
+        self.ops = 0;
+        Ok(())
+    }
+
+    /// Number of ops currently in the log.
+    pub fn len(&self) -> usize {
+        self.ops
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops == 0
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read every complete op from a log file. A missing file yields no ops.
+    pub fn read_ops(path: &Path) -> Result<Vec<WalOp>, PersistenceError> {
+        let data = match fs::read(path) {
+            Ok(d) => d,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let (ops, end) = Self::scan(&data, path)?;
+        if end < data.len() {
+            eprintln!("[wal] ignoring {} trailing bytes in {}", data.len() - end, path.display());
+        }
+        Ok(ops)
+    }
+
+    /// Check the header and decode records up to the first torn or invalid one.
+    /// Returns the ops and the byte offset just past the last valid record
+    /// (0 when not even the header was written).
+    fn scan(data: &[u8], path: &Path) -> Result<(Vec<WalOp>, usize), PersistenceError> {
+        let magic_len = data.len().min(WAL_MAGIC.len());
+        if data[..magic_len] != WAL_MAGIC[..magic_len] {
+            return Err(PersistenceError::WalVersionMismatch {
+                path: path.to_path_buf(), expected: CURRENT_VERSION, got: None });
+        }
+        if data.len() < WAL_HEADER_LEN {
+            return Ok((Vec::new(), 0));
+        }
+        let version = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+        if version != CURRENT_VERSION {
+            return Err(PersistenceError::WalVersionMismatch {
+                path: path.to_path_buf(), expected: CURRENT_VERSION, got: Some(version) });
+        }
+        let mut ops = Vec::new();
+        let mut pos = WAL_HEADER_LEN;
+        while pos + 4 <= data.len() {
+            let len = u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+            let Some(body) = data.get(pos + 4..pos + 4 + len) else { break };
+            match bincode::deserialize::<WalOp>(body) {
+                Ok(op) => ops.push(op),
+                Err(_) => break,
+            }
+            pos += 4 + len;
+        }
+        Ok((ops, pos))
+    }
+
+    /// Replay a log file into `store`, applying `norm` to logged inserts the
+    /// same way a snapshot load does.
+    pub fn replay(path: &Path, store: &mut dyn MemoryStore, norm: NormPolicy) -> Result<WalReplay, PersistenceError> {
+        let mut result = WalReplay::default();
+        for mut op in Self::read_ops(path)? {
+            if let WalOp::Insert(mem) = &mut op {
+                if enforce_unit_norm(mem, norm)? {
+                    result.renormalized += 1;
+                }
+            }
+            if op.apply(store).map_err(|e| PersistenceError::CorruptedFile(e.to_string()))? {
+                result.applied += 1;
+            }
+        }
+        Ok(result)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Audit log
+// ---------------------------------------------------------------------------
+
+/// Kind of mutation recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOp {
+    Remember,
+    Forget,
+    Boost,
+    Relate,
+}
+
+/// One line of the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub op: AuditOp,
+    /// Memories the op touched; `relate` records `[source, target]`.
+    pub ids: Vec<Uuid>,
+}
+
+/// Append-only JSONL record of mutations (`data_dir/audit.log`). Unlike the
+/// WAL it is never truncated by a save.
+pub struct AuditLog {
+    path: PathBuf,
+    file: fs::File,
+}
+
+impl AuditLog {
+    /// Audit log path inside a data directory.
+    pub fn path_in(data_dir: &Path) -> PathBuf {
+        data_dir.join("audit.log")
+    }
+
+    /// Open (or create) a log for appending.
+    pub fn open(path: PathBuf) -> Result<Self, PersistenceError> {
+        let file = fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { path, file })
+    }
+
+    /// Append one entry stamped with the current time.
+    pub fn record(&mut self, op: AuditOp, ids: &[Uuid]) -> Result<(), PersistenceError> {
+        let entry = AuditEntry { timestamp: Utc::now(), op, ids: ids.to_vec() };
+        let mut line = serde_json::to_string(&entry)
+            .map_err(|e| PersistenceError::SerializationError(e.to_string()))?;
+        line.push('\n');
+        self.file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read every entry from a log file. A missing file yields no entries.
+    pub fn read_entries(path: &Path) -> Result<Vec<AuditEntry>, PersistenceError> {
+        let file = match fs::File::open(path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(&line)
+                .map_err(|e| PersistenceError::CorruptedFile(format!("{}: {}", path.display(), e)))?;
+            entries.push(entry);
         }
+        Ok(entries)
     }
 }
 
@@ -207,6 +1452,10 @@ pub struct DiskStore {
     metadata: SnapshotMetadata,
     auto_save_interval: Option<usize>,
     insertions_since_save: usize,
+    wal: Option<Wal>,
+    vector_codec: VectorCodec,
+    compress: bool,
+    renormalized_on_load: usize,
 }
 
 impl DiskStore {
@@ -218,6 +1467,7 @@ impl DiskStore {
                 Err(_) => {} // fall through to empty
             }
         }
+        let compress = wants_compression(&path);
         Self {
             inner: InMemoryStore::new(),
             path,
@@ -229,91 +1479,119 @@ impl DiskStore {
                 last_saved_at: Utc::now(),
                 total_consolidations: 0,
                 consciousness_level: "dormant".to_string(),
+                original_size: 0,
+                compressed_size: None,
             },
             auto_save_interval: None,
             insertions_since_save: 0,
+            wal: None,
+            vector_codec: VectorCodec::F32,
+            compress,
+            renormalized_on_load: 0,
         }
     }
 
-    /// Load a DiskStore from an existing file.
-    /// Tries V3 format first, then V2, then V1 with migration.
+    /// Load a DiskStore from an existing file, migrating older snapshot versions
+    /// and replaying any write-ahead log left next to it. Off-norm vectors are
+    /// handled per `NormPolicy::for_metric` of the saved metric.
     pub fn open(path: PathBuf) -> Result<Self, PersistenceError> {
-        let data = fs::read(&path)?;
-        // Try V3 (current)
-        if let Ok(snapshot) = bincode::deserialize::<MemorySnapshot>(&data) {
-            if snapshot.version == CURRENT_VERSION {
-                let mut inner = InMemoryStore::new();
-                for mem in snapshot.memories {
-                    inner.insert(mem).map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?;
-                }
-                return Ok(Self { inner, path,
-                    codebook_seed: snapshot.codebook_seed,
-                    codebook_input_dim: snapshot.codebook_input_dim,
-                    codebook_output_dim: snapshot.codebook_output_dim,
-                    metadata: snapshot.metadata,
-                    auto_save_interval: None, insertions_since_save: 0 });
-            } else if snapshot.version > CURRENT_VERSION {
-                return Err(PersistenceError::VersionMismatch {
-                    expected: CURRENT_VERSION, got: snapshot.version });
+        Self::open_snapshot(path, None)
+    }
+
+    /// Like `open`, but an un-normalized vector fails the load with
+    /// `PersistenceError::Unnormalized`.
+    pub fn open_strict(path: PathBuf) -> Result<Self, PersistenceError> {
+        Self::open_with_policy(path, NormPolicy::Reject)
+    }
+
+    /// Like `open`, applying `norm` to every snapshot and WAL vector. How many
+    /// were rescaled is reported by `renormalized_on_load`.
+    pub fn open_with_policy(path: PathBuf, norm: NormPolicy) -> Result<Self, PersistenceError> {
+        Self::open_snapshot(path, Some(norm))
+    }
+
+    /// `open_with_policy`, defaulting `norm` to the saved metric's policy.
+    fn open_snapshot(path: PathBuf, norm: Option<NormPolicy>) -> Result<Self, PersistenceError> {
+        let (snapshot, compressed) = read_snapshot_file(&path)?;
+        let norm = norm.unwrap_or_else(|| NormPolicy::for_metric(snapshot.similarity_metric));
+        let mut inner = InMemoryStore::new();
+        inner.set_similarity_metric(snapshot.similarity_metric)
+            .map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?;
+        let mut renormalized_on_load = 0;
+        for mut mem in snapshot.memories {
+            if enforce_unit_norm(&mut mem, norm)? {
+                renormalized_on_load += 1;
             }
-            // version < CURRENT_VERSION: fall through to V2/V1 migration only
-            // (do not attempt V2/V1 deserialization since we already parsed a valid header)
+            inner.insert(mem).map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?;
+        }
+        renormalized_on_load += Wal::replay(&Wal::path_for(&path), &mut inner, norm)?.renormalized;
+        let compress = compressed || wants_compression(&path);
+        Ok(Self { inner, path, compress,
+            codebook_seed: snapshot.codebook_seed,
+            codebook_input_dim: snapshot.codebook_input_dim,
+            codebook_output_dim: snapshot.codebook_output_dim,
+            metadata: snapshot.metadata,
+            auto_save_interval: None, insertions_since_save: 0, wal: None,
+            vector_codec: snapshot.vector_codec,
+            renormalized_on_load })
+    }
+
+    /// Number of snapshot and WAL vectors re-normalized when this store was opened.
+    pub fn renormalized_on_load(&self) -> usize {
+        self.renormalized_on_load
+    }
+
+    /// Record inserts and deletes in a write-ahead log instead of relying on
+    /// full saves. Call `checkpoint` to fold the log into the snapshot.
+    pub fn enable_wal(&mut self) -> Result<(), PersistenceError> {
+        if self.wal.is_none() {
+            self.wal = Some(Wal::open(Wal::path_for(&self.path))?);
+        }
+        Ok(())
+    }
+
+    /// Append an op to the write-ahead log, if enabled.
+    pub fn log(&mut self, op: &WalOp) -> Result<(), PersistenceError> {
+        match &mut self.wal {
+            Some(wal) => wal.append(op),
+            None => Ok(()),
         }
+    }
 
-        // Try V2 (pre-collective) and migrate to V3
-        if let Ok(snapshot) = bincode::deserialize::<MemorySnapshotV2>(&data) {
-            if snapshot.version == 2 {
-                let mut inner = InMemoryStore::new();
-                for m in snapshot.memories {
-                    inner.insert(HyperMemory::from(m)).map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?;
+    /// Fold the write-ahead log into the base snapshot: rewrite the snapshot,
+    /// then truncate the log.
+    pub fn checkpoint(&mut self) -> Result<(), PersistenceError> {
+        self.write_snapshot()?;
+        match &mut self.wal {
+            Some(wal) => wal.truncate()?,
+            None => {
+                let wal_path = Wal::path_for(&self.path);
+                if wal_path.exists() {
+                    fs::remove_file(wal_path)?;
                 }
-                return Ok(Self { inner, path,
-                    codebook_seed: snapshot.codebook_seed,
-                    codebook_input_dim: snapshot.codebook_input_dim,
-                    codebook_output_dim: snapshot.codebook_output_dim,
-                    metadata: snapshot.metadata,
-                    auto_save_interval: None, insertions_since_save: 0 });
             }
         }
-        
-        // Try V1 (no xi_signature) and migrate
-        let snapshot_v1: MemorySnapshotV1 = bincode::deserialize(&data)
-            .map_err(|e| PersistenceError::SerializationError(
-                format!("Failed to deserialize as V1, V2, or V3: {}", e)
-            ))?;
-        
-        let mut inner = InMemoryStore::new();
-        for m in snapshot_v1.memories {
-            inner.insert(HyperMemory::from(m)).map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?;
-        }
-        Ok(Self { inner, path,
-            codebook_seed: snapshot_v1.codebook_seed,
-            codebook_input_dim: snapshot_v1.codebook_input_dim,
-            codebook_output_dim: snapshot_v1.codebook_output_dim,
-            metadata: snapshot_v1.metadata,
-            auto_save_interval: None, insertions_since_save: 0 })
+        Ok(())
     }
 
-    /// Save all state to disk.
+    /// Save all state to disk. Equivalent to `checkpoint`.
     pub fn save(&mut self) -> Result<(), PersistenceError> {
+        self.checkpoint()
+    }
+
+    fn write_snapshot(&mut self) -> Result<(), PersistenceError> {
         let memories = self.inner.all_memories()
             .map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?
             .into_iter().cloned().collect();
         let mut metadata = self.metadata.clone();
         metadata.last_saved_at = Utc::now();
 
-        let snapshot = MemorySnapshot {
-            version: CURRENT_VERSION,
-            memories,
-            codebook_seed: self.codebook_seed,
-            codebook_input_dim: self.codebook_input_dim,
-            codebook_output_dim: self.codebook_output_dim,
-            metadata: metadata.clone(),
-        };
+        let mut snapshot = MemorySnapshot::packed(
+            memories, self.codebook_params(), metadata, self.vector_codec);
+        snapshot.similarity_metric = self.inner.similarity_metric();
 
-        let data = bincode::serialize(&snapshot)?;
-        fs::write(&self.path, &data)?;
-        self.metadata = metadata;
+        write_snapshot_file(&self.path, &mut snapshot, self.compress)?;
+        self.metadata = snapshot.metadata;
         self.insertions_since_save = 0;
         Ok(())
     }
@@ -323,6 +1601,24 @@ impl DiskStore {
         self.auto_save_interval = interval;
     }
 
+    /// Choose how vectors are encoded on the next save. Quantized codecs are
+    /// lossy; see `VectorCodec`.
+    pub fn set_vector_codec(&mut self, codec: VectorCodec) {
+        self.vector_codec = codec;
+    }
+
+    /// zstd-compress snapshots on the next save. Defaults to on for `.zst`
+    /// paths and for stores opened from a compressed file. Loading detects
+    /// compression by magic bytes either way.
+    pub fn set_compression(&mut self, compress: bool) {
+        self.compress = compress;
+    }
+
+    /// Whether saves are zstd-compressed.
+    pub fn compression(&self) -> bool {
+        self.compress
+    }
+
     /// Get the path this store saves to.
     pub fn path(&self) -> &Path {
         &self.path
@@ -343,6 +1639,90 @@ impl DiskStore {
         (self.codebook_seed, self.codebook_input_dim, self.codebook_output_dim)
     }
 
+    /// Export every memory in `engine` as newline-delimited JSON, one
+    /// `HyperMemory` per line. Vectors stay plain arrays so dumps are diffable.
+    /// Returns the number of memories written.
+    pub fn export_jsonl(engine: &MemoryEngine, path: &Path) -> Result<usize, PersistenceError> {
+        let memories = engine.store.all_memories()
+            .map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?;
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        for mem in &memories {
+            let line = serde_json::to_string(mem)
+                .map_err(|e| PersistenceError::SerializationError(e.to_string()))?;
+            writer.write_all(line.as_bytes())?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(memories.len())
+    }
+
+    /// Import memories from a JSON-Lines file written by `export_jsonl`.
+    ///
+    /// Memories whose id already exists in the engine are skipped. Corrupt or
+    /// partial lines don't abort the import; they are returned in
+    /// `JsonlImport::skipped`. Off-norm vectors are handled per
+    /// `NormPolicy::for_metric` of the engine's metric.
+    pub fn import_jsonl(engine: &mut MemoryEngine, path: &Path) -> Result<JsonlImport, PersistenceError> {
+        let norm = NormPolicy::for_metric(engine.similarity_metric());
+        Self::import_jsonl_with_policy(engine, path, norm)
+    }
+
+    /// Like `import_jsonl`, applying `norm` to every imported vector.
+    pub fn import_jsonl_with_policy(
+        engine: &mut MemoryEngine,
+        path: &Path,
+        norm: NormPolicy,
+    ) -> Result<JsonlImport, PersistenceError> {
+        let reader = BufReader::new(fs::File::open(path)?);
+        let mut renormalized = 0;
+        let mut errors: Vec<String> = Vec::new();
+        let mut seen: HashSet<Uuid> = HashSet::new();
+        let mut batch: Vec<HyperMemory> = Vec::new();
+
+        for (lineno, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut mem: HyperMemory = match serde_json::from_str(&line) {
+                Ok(m) => m,
+                Err(e) => {
+                    errors.push(format!("line {}: {}", lineno + 1, e));
+                    continue;
+                }
+            };
+            if matches!(engine.store.get(&mem.id), Ok(Some(_))) {
+                continue;
+            }
+            if !seen.insert(mem.id) {
+                errors.push(format!("line {}: duplicate id {}", lineno + 1, mem.id));
+                continue;
+            }
+            if enforce_unit_norm(&mut mem, norm)? {
+                renormalized += 1;
+            }
+            batch.push(mem);
+        }
+
+        let ids: Vec<Uuid> = batch.iter().map(|m| m.id).collect();
+        let imported = match engine.store.insert_batch(batch) {
+            Ok(inserted) => inserted.len(),
+            Err(e) => {
+                errors.push(format!("batch insert: {}", e));
+                ids.iter().filter(|id| matches!(engine.store.get(id), Ok(Some(_)))).count()
+            }
+        };
+
+        for id in ids {
+            if matches!(engine.store.get(&id), Ok(Some(_))) {
+                engine.mark_dirty(id);
+            }
+        }
+        engine.rebuild_tag_index();
+        engine.rebuild_content_index();
+        Ok(JsonlImport { imported, renormalized, skipped: errors })
+    }
+
     fn maybe_auto_save(&mut self) {
         if let Some(interval) = self.auto_save_interval {
             if interval > 0 && self.insertions_since_save >= interval {
@@ -354,12 +1734,38 @@ impl DiskStore {
 
 impl MemoryStore for DiskStore {
     fn insert(&mut self, memory: HyperMemory) -> Result<Uuid, StoreError> {
+        if self.wal.is_some() {
+            if self.inner.get(&memory.id)?.is_some() {
+                return Err(StoreError::DuplicateId(memory.id));
+            }
+            self.log(&WalOp::Insert(Box::new(memory.clone()))).map_err(|e| StoreError::Other(e.to_string()))?;
+        }
         let id = self.inner.insert(memory)?;
         self.insertions_since_save += 1;
         self.maybe_auto_save();
         Ok(id)
     }
 
+    fn insert_batch(&mut self, memories: Vec<HyperMemory>) -> Result<Vec<Uuid>, StoreError> {
+        if self.wal.is_some() {
+            // Reject the whole batch before anything is logged, so the log
+            // never holds inserts that were not applied.
+            let mut seen = HashSet::new();
+            for mem in &memories {
+                if !seen.insert(mem.id) || self.inner.get(&mem.id)?.is_some() {
+                    return Err(StoreError::DuplicateId(mem.id));
+                }
+            }
+            for mem in &memories {
+                self.log(&WalOp::Insert(Box::new(mem.clone()))).map_err(|e| StoreError::Other(e.to_string()))?;
+            }
+        }
+        let ids = self.inner.insert_batch(memories)?;
+        self.insertions_since_save += ids.len();
+        self.maybe_auto_save();
+        Ok(ids)
+    }
+
     fn get(&self, id: &Uuid) -> Result<Option<&HyperMemory>, StoreError> {
         self.inner.get(id)
     }
@@ -372,6 +1778,14 @@ impl MemoryStore for DiskStore {
         self.inner.search(query, top_k)
     }
 
+    fn set_similarity_metric(&mut self, metric: SimilarityMetric) -> Result<(), StoreError> {
+        self.inner.set_similarity_metric(metric)
+    }
+
+    fn similarity_metric(&self) -> SimilarityMetric {
+        self.inner.similarity_metric()
+    }
+
     fn search_with_wave(
         &self,
         query: &[f32],
@@ -385,11 +1799,19 @@ impl MemoryStore for DiskStore {
         self.inner.all_memories()
     }
 
+    fn for_each_memory(&self, f: &mut dyn FnMut(&HyperMemory)) -> Result<(), StoreError> {
+        self.inner.for_each_memory(f)
+    }
+
     fn all_ids(&self) -> Result<Vec<Uuid>, StoreError> {
         self.inner.all_ids()
     }
 
     fn delete(&mut self, id: &Uuid) -> Result<bool, StoreError> {
+        if self.inner.get(id)?.is_none() {
+            return Ok(false);
+        }
+        self.log(&WalOp::Delete(*id)).map_err(|e| StoreError::Other(e.to_string()))?;
         self.inner.delete(id)
     }
 
@@ -405,6 +1827,13 @@ impl MemoryStore for DiskStore {
 impl MemoryEngine {
     /// Save the full engine state to a file.
     pub fn save_state(&self, path: &Path) -> Result<(), PersistenceError> {
+        self.save_state_with_codec(path, VectorCodec::F32)
+    }
+
+    /// Save the full engine state, quantizing vectors with `codec`. Quantized
+    /// codecs are lossy, so recall after `load_state` can differ slightly.
+    /// Paths ending in `.zst` are zstd-compressed (`compression` feature).
+    pub fn save_state_with_codec(&self, path: &Path, codec: VectorCodec) -> Result<(), PersistenceError> {
         let memories: Vec<HyperMemory> = self.store.all_memories()
             .map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?
             .into_iter()
@@ -412,66 +1841,62 @@ impl MemoryEngine {
             .collect();
 
         let cb = self.pipeline.codebook();
-        let snapshot = MemorySnapshot {
-            version: CURRENT_VERSION,
-            memories,
-            codebook_seed: cb.seed(),
-            codebook_input_dim: cb.input_dim,
-            codebook_output_dim: cb.output_dim,
-            metadata: SnapshotMetadata {
-                created_at: Utc::now(),
-                last_saved_at: Utc::now(),
-                total_consolidations: 0,
-                consciousness_level: "unknown".to_string(),
-            },
+        let metadata = SnapshotMetadata {
+            created_at: Utc::now(),
+            last_saved_at: Utc::now(),
+            total_consolidations: 0,
+            consciousness_level: "unknown".to_string(),
+            original_size: 0,
+            compressed_size: None,
         };
+        let mut snapshot = MemorySnapshot::packed(
+            memories, (cb.seed(), cb.input_dim, cb.output_dim), metadata, codec);
+        snapshot.consciousness_history = self.consciousness_history.clone();
+        snapshot.similarity_metric = self.similarity_metric();
 
-        let data = bincode::serialize(&snapshot)?;
-        fs::write(path, &data)?;
-        Ok(())
+        write_snapshot_file(path, &mut snapshot, wants_compression(path))
     }
 
     /// Load engine state from a file. Requires a compatible EncodingPipeline.
-    /// Tries V3 format first, then V2, then V1 with migration.
+    /// Older snapshot versions are migrated on load, off-norm vectors are
+    /// handled per `NormPolicy::for_metric` of the saved metric, and
+    /// compressed snapshots are detected by magic bytes.
     pub fn load_state(path: &Path, pipeline: EncodingPipeline) -> Result<Self, PersistenceError> {
-        let data = fs::read(path)?;
-
-        // Try V3 (current)
-        if let Ok(snapshot) = bincode::deserialize::<MemorySnapshot>(&data) {
-            if snapshot.version == CURRENT_VERSION {
-                let mut store = HnswStore::new();
-                for mem in snapshot.memories {
-                    store.insert(mem).map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?;
-                }
-                return Ok(Self::new(Box::new(store), pipeline));
-            } else if snapshot.version > CURRENT_VERSION {
-                return Err(PersistenceError::VersionMismatch {
-                    expected: CURRENT_VERSION, got: snapshot.version });
-            }
-            // version < CURRENT_VERSION: fall through to V2/V1 migration
-        }
+        Self::load_snapshot(path, pipeline, None).map(|(engine, _)| engine)
+    }
 
-        // Try V2 (pre-collective) and migrate
-        if let Ok(snapshot) = bincode::deserialize::<MemorySnapshotV2>(&data) {
-            if snapshot.version == 2 {
-                let mut store = HnswStore::new();
-                for m in snapshot.memories {
-                    store.insert(HyperMemory::from(m)).map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?;
-                }
-                return Ok(Self::new(Box::new(store), pipeline));
-            }
-        }
+    /// Like `load_state`, applying `norm` to every vector. Returns the engine
+    /// and the number of vectors rescaled.
+    pub fn load_state_with_policy(
+        path: &Path,
+        pipeline: EncodingPipeline,
+        norm: NormPolicy,
+    ) -> Result<(Self, usize), PersistenceError> {
+        Self::load_snapshot(path, pipeline, Some(norm))
+    }
 
-        // Try V1 (no xi_signature) and migrate
-        let snapshot_v1: MemorySnapshotV1 = bincode::deserialize(&data)
-            .map_err(|e| PersistenceError::SerializationError(
-                format!("Failed to deserialize as V1, V2, or V3: {}", e)
-            ))?;
+    /// `load_state_with_policy`, defaulting `norm` to the saved metric's policy.
+    fn load_snapshot(
+        path: &Path,
+        pipeline: EncodingPipeline,
+        norm: Option<NormPolicy>,
+    ) -> Result<(Self, usize), PersistenceError> {
+        let (snapshot, _) = read_snapshot_file(path)?;
+        let norm = norm.unwrap_or_else(|| NormPolicy::for_metric(snapshot.similarity_metric));
         let mut store = HnswStore::new();
-        for m in snapshot_v1.memories {
-            store.insert(HyperMemory::from(m)).map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?;
+        // Before inserting, so the graph is built under the saved metric.
+        store.set_similarity_metric(snapshot.similarity_metric)
+            .map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?;
+        let mut renormalized = 0;
+        for mut mem in snapshot.memories {
+            if enforce_unit_norm(&mut mem, norm)? {
+                renormalized += 1;
+            }
+            store.insert(mem).map_err(|e| PersistenceError::CorruptedFile(e.to_string()))?;
         }
-        Ok(Self::new(Box::new(store), pipeline))
+        let mut engine = Self::new(Box::new(store), pipeline);
+        engine.consciousness_history = snapshot.consciousness_history;
+        Ok((engine, renormalized))
     }
 }
 
@@ -500,7 +1925,7 @@ mod tests {
     }
 
     fn make_memory_with_links(content: &str, dim: usize) -> HyperMemory {
-        let mut mem = HyperMemory::new(vec![0.5; dim], content.to_string());
+        let mut mem = HyperMemory::new(vec![1.0 / (dim as f32).sqrt(); dim], content.to_string());
         mem.connections.push(SkipLink {
             target_id: Uuid::new_v4(),
             strength: 0.85,
@@ -515,8 +1940,8 @@ mod tests {
         let path = temp_path("insert_save_load");
         let mut store = DiskStore::new(path.clone(), 42, 384, 10_000);
 
-        let mem1 = HyperMemory::new(vec![1.0; 100], "hello".to_string());
-        let mem2 = HyperMemory::new(vec![2.0; 100], "world".to_string());
+        let mem1 = HyperMemory::new(vec![0.1; 100], "hello".to_string());
+        let mem2 = HyperMemory::new(vec![-0.1; 100], "world".to_string());
         let id1 = store.insert(mem1).unwrap();
         let id2 = store.insert(mem2).unwrap();
         store.save().unwrap();
@@ -526,7 +1951,7 @@ mod tests {
         assert_eq!(loaded.count(), 2);
         let m1 = loaded.get(&id1).unwrap().unwrap();
         assert_eq!(m1.content, "hello");
-        assert_eq!(m1.vector, vec![1.0; 100]);
+        assert_eq!(m1.dense_vector(), vec![0.1; 100]);
         let m2 = loaded.get(&id2).unwrap().unwrap();
         assert_eq!(m2.content, "world");
 
@@ -551,8 +1976,8 @@ mod tests {
         let m = loaded.get(&id).unwrap().unwrap();
 
         // Vector preserved
-        assert_eq!(m.vector.len(), 200);
-        assert_eq!(m.vector[0], 0.5);
+        assert_eq!(m.dim(), 200);
+        assert_eq!(m.dense_vector()[0], 1.0 / 200f32.sqrt());
 
         // Wave params preserved
         assert_eq!(m.amplitude, 0.75);
@@ -591,6 +2016,28 @@ mod tests {
         let _ = fs::remove_file(&path);
     }
 
+    #[test]
+    fn similarity_metric_survives_save_and_load() {
+        let path = temp_path("metric_state");
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        engine.set_similarity_metric(SimilarityMetric::Euclidean).unwrap();
+        engine.remember("the cat sat on the mat").unwrap();
+        engine.save_state(&path).unwrap();
+
+        let loaded = MemoryEngine::load_state(&path, make_pipeline()).unwrap();
+        assert_eq!(loaded.similarity_metric(), SimilarityMetric::Euclidean);
+        assert_eq!(loaded.store.similarity_metric(), SimilarityMetric::Euclidean);
+        let _ = fs::remove_file(&path);
+
+        let path = temp_path("metric_disk");
+        let mut store = DiskStore::new(path.clone(), 42, 384, 10_000);
+        store.set_similarity_metric(SimilarityMetric::Dot).unwrap();
+        store.save().unwrap();
+        let engine = MemoryEngine::new(Box::new(DiskStore::open(path.clone()).unwrap()), make_pipeline());
+        assert_eq!(engine.similarity_metric(), SimilarityMetric::Dot);
+        let _ = fs::remove_file(&path);
+    }
+
     #[test]
     fn memory_engine_save_load_state() {
         let path = temp_path("engine_state");
@@ -619,6 +2066,173 @@ mod tests {
         let _ = fs::remove_file(&path);
     }
 
+    #[test]
+    fn jsonl_export_import_round_trip() {
+        let path = temp_path("jsonl").with_extension("jsonl");
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+
+        let mut ids = Vec::new();
+        for i in 0..19 {
+            let mut axis = vec![0.0; 64];
+            axis[i] = 1.0;
+            let mut mem = HyperMemory::new(axis, format!("memory {i}"));
+            mem.amplitude = 0.5 + i as f32 * 0.01;
+            mem.layer_depth = (i % 3) as u8;
+            ids.push(engine.store.insert(mem).unwrap());
+        }
+        let mut linked = make_memory_with_links("linked", 64);
+        linked.connections[0].target_id = ids[0];
+        let linked_id = engine.store.insert(linked).unwrap();
+
+        assert_eq!(DiskStore::export_jsonl(&engine, &path).unwrap(), 20);
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(text.lines().count(), 20);
+
+        let mut target = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        assert_eq!(DiskStore::import_jsonl(&mut target, &path).unwrap().imported, 20);
+        assert_eq!(target.store.count(), 20);
+
+        let m = target.get_memory(&linked_id).unwrap().unwrap();
+        assert_eq!(m.connections.len(), 1);
+        assert_eq!(m.connections[0].target_id, ids[0]);
+        assert_eq!(m.connections[0].resonance_key, vec![0.1, 0.2, 0.3]);
+        let orig = engine.get_memory(&ids[7]).unwrap().unwrap();
+        let copy = target.get_memory(&ids[7]).unwrap().unwrap();
+        assert_eq!(copy.vector, orig.vector);
+        assert_eq!(copy.amplitude, orig.amplitude);
+        assert_eq!(copy.layer_depth, orig.layer_depth);
+
+        // Re-importing skips duplicates
+        let again = DiskStore::import_jsonl(&mut target, &path).unwrap();
+        assert_eq!(again.imported, 0);
+        assert!(again.skipped.is_empty());
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn jsonl_import_skips_corrupt_lines() {
+        let path = temp_path("jsonl_corrupt").with_extension("jsonl");
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        engine.store.insert(HyperMemory::new(vec![1.0; 8], "a".into())).unwrap();
+        engine.store.insert(HyperMemory::new(vec![2.0; 8], "b".into())).unwrap();
+        DiskStore::export_jsonl(&engine, &path).unwrap();
+
+        let mut text = fs::read_to_string(&path).unwrap();
+        text.push_str("{\"id\": \"not-a-uuid\", \"vector\": [1.0\n");
+        fs::write(&path, text).unwrap();
+
+        let mut target = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let report = DiskStore::import_jsonl(&mut target, &path).unwrap();
+        assert_eq!(report.imported, 2);
+        assert_eq!(report.skipped.len(), 1);
+        assert!(report.skipped[0].starts_with("line 3:"), "{:?}", report.skipped);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn load_renormalizes_vectors() {
+        let path = temp_path("renormalize");
+        let mut store = DiskStore::new(path.clone(), 42, 384, 10_000);
+        let unit = store.insert(HyperMemory::new(vec![0.6, 0.8, 0.0], "unit".into())).unwrap();
+        let skewed = store.insert(HyperMemory::new(vec![3.0, 4.0, 0.0], "skewed".into())).unwrap();
+        store.save().unwrap();
+
+        let loaded = DiskStore::open_with_policy(path.clone(), NormPolicy::Renormalize).unwrap();
+        assert_eq!(loaded.renormalized_on_load(), 1);
+        assert_eq!(loaded.get(&unit).unwrap().unwrap().dense_vector(), vec![0.6, 0.8, 0.0]);
+        let v = loaded.get(&skewed).unwrap().unwrap().dense_vector();
+        assert!((v[0] - 0.6).abs() < 1e-6 && (v[1] - 0.8).abs() < 1e-6, "{:?}", v);
+
+        match DiskStore::open_strict(path.clone()) {
+            Err(PersistenceError::Unnormalized { id, norm }) => {
+                assert_eq!(id, skewed);
+                assert!((norm - 5.0).abs() < 1e-5);
+            }
+            other => panic!("expected Unnormalized, got {:?}", other.map(|s| s.count())),
+        }
+
+        let (engine, renormalized) =
+            MemoryEngine::load_state_with_policy(&path, make_pipeline(), NormPolicy::Renormalize).unwrap();
+        assert_eq!(renormalized, 1);
+        let norm: f32 = engine.get_memory(&skewed).unwrap().unwrap().dense_vector().iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((norm - 1.0).abs() < 1e-6);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn default_load_renormalizes_under_cosine_only() {
+        let path = temp_path("default_norm");
+        let mut store = DiskStore::new(path.clone(), 42, 384, 10_000);
+        let skewed = store.insert(HyperMemory::new(vec![3.0, 4.0, 0.0], "skewed".into())).unwrap();
+        store.save().unwrap();
+
+        let loaded = DiskStore::open(path.clone()).unwrap();
+        assert_eq!(loaded.renormalized_on_load(), 1);
+        assert_eq!(loaded.get(&skewed).unwrap().unwrap().dense_vector(), vec![0.6, 0.8, 0.0]);
+        let engine = MemoryEngine::load_state(&path, make_pipeline()).unwrap();
+        assert_eq!(engine.get_memory(&skewed).unwrap().unwrap().dense_vector(), vec![0.6, 0.8, 0.0]);
+
+        let jsonl = path.with_extension("jsonl");
+        let mut source = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        source.store.insert(HyperMemory::new(vec![3.0, 4.0, 0.0], "skewed".into())).unwrap();
+        DiskStore::export_jsonl(&source, &jsonl).unwrap();
+        let mut target = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        DiskStore::import_jsonl(&mut target, &jsonl).unwrap();
+        assert_eq!(target.store.all_memories().unwrap()[0].dense_vector(), vec![0.6, 0.8, 0.0]);
+
+        // Euclidean reads magnitude, so its vectors load as stored.
+        let mut store = DiskStore::open(path.clone()).unwrap();
+        store.set_similarity_metric(SimilarityMetric::Euclidean).unwrap();
+        store.get_mut(&skewed).unwrap().unwrap().set_vector(vec![3.0, 4.0, 0.0]);
+        store.save().unwrap();
+        let loaded = DiskStore::open(path.clone()).unwrap();
+        assert_eq!(loaded.renormalized_on_load(), 0);
+        assert_eq!(loaded.get(&skewed).unwrap().unwrap().dense_vector(), vec![3.0, 4.0, 0.0]);
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&jsonl);
+    }
+
+    #[test]
+    fn wal_replay_applies_norm_policy() {
+        let path = temp_path("wal_norm");
+        let wal_path = Wal::path_for(&path);
+        DiskStore::new(path.clone(), 42, 384, 10_000).save().unwrap();
+        let skewed = HyperMemory::new(vec![3.0, 4.0, 0.0], "skewed".into());
+        let skewed_id = skewed.id;
+        let mut wal = Wal::open(wal_path.clone()).unwrap();
+        wal.append(&WalOp::Insert(Box::new(skewed))).unwrap();
+        drop(wal);
+
+        let store = DiskStore::open_with_policy(path.clone(), NormPolicy::Renormalize).unwrap();
+        assert_eq!(store.renormalized_on_load(), 1);
+        let v = store.get(&skewed_id).unwrap().unwrap().dense_vector();
+        assert!((v[0] - 0.6).abs() < 1e-6, "{:?}", v);
+        assert!(matches!(
+            DiskStore::open_strict(path.clone()),
+            Err(PersistenceError::Unnormalized { id, .. }) if id == skewed_id
+        ));
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&wal_path);
+    }
+
+    #[test]
+    fn saved_store_reopens_strict() {
+        let path = temp_path("strict_round_trip");
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        engine.remember("an encoded memory is unit length").unwrap();
+        engine.save_state(&path).unwrap();
+
+        let strict = DiskStore::open_strict(path.clone()).unwrap();
+        assert_eq!(strict.count(), 1);
+
+        let _ = fs::remove_file(&path);
+    }
+
     #[test]
     fn metadata_preserved() {
         let path = temp_path("metadata");
@@ -628,6 +2242,8 @@ mod tests {
             last_saved_at: Utc::now(),
             total_consolidations: 42,
             consciousness_level: "awakening".to_string(),
+            original_size: 0,
+            compressed_size: None,
         });
         store.save().unwrap();
 
@@ -638,6 +2254,46 @@ mod tests {
         let _ = fs::remove_file(&path);
     }
 
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compressed_snapshot_round_trips_and_is_smaller() {
+        let plain_path = temp_path("plain");
+        let zst_path = temp_path("compressed").with_extension("zst");
+        let mut plain = DiskStore::new(plain_path.clone(), 42, 384, 10_000);
+        let mut compressed = DiskStore::new(zst_path.clone(), 42, 384, 10_000);
+        assert!(!plain.compression());
+        assert!(compressed.compression());
+        for i in 0..20 {
+            let mut mem = make_memory_with_links(&format!("the same sentence, repeated: memory {i}"), 256);
+            mem.id = Uuid::from_u128(i);
+            plain.insert(mem.clone()).unwrap();
+            compressed.insert(mem).unwrap();
+        }
+        plain.save().unwrap();
+        compressed.save().unwrap();
+
+        let plain_size = fs::metadata(&plain_path).unwrap().len();
+        let zst_size = fs::metadata(&zst_path).unwrap().len();
+        assert!(zst_size < plain_size, "{} !< {}", zst_size, plain_size);
+        assert_eq!(compressed.metadata().compressed_size, Some(zst_size));
+        assert_eq!(compressed.metadata().original_size, plain.metadata().original_size);
+        assert_eq!(plain.metadata().compressed_size, None);
+
+        let loaded = DiskStore::open(zst_path.clone()).unwrap();
+        assert!(loaded.compression());
+        assert_eq!(loaded.metadata().compressed_size, Some(zst_size));
+        for i in 0..20 {
+            let a = plain.get(&Uuid::from_u128(i)).unwrap().unwrap();
+            let b = loaded.get(&Uuid::from_u128(i)).unwrap().unwrap();
+            assert_eq!(a.content, b.content);
+            assert_eq!(a.vector, b.vector);
+            assert_eq!(a.connections.len(), b.connections.len());
+        }
+
+        let _ = fs::remove_file(&plain_path);
+        let _ = fs::remove_file(&zst_path);
+    }
+
     #[test]
     fn empty_store_save_load() {
         let path = temp_path("empty");
@@ -666,14 +2322,410 @@ mod tests {
                 last_saved_at: Utc::now(),
                 total_consolidations: 0,
                 consciousness_level: "test".to_string(),
+                original_size: 0,
+                compressed_size: None,
             },
+            vector_codec: VectorCodec::F32,
+            packed_vectors: vec![],
+            consciousness_history: vec![],
+            similarity_metric: SimilarityMetric::default(),
         };
         let data = bincode::serialize(&snapshot).unwrap();
         fs::write(&path, &data).unwrap();
 
         let result = DiskStore::open(path.clone());
-        assert!(matches!(result, Err(PersistenceError::VersionMismatch { .. })));
+        assert!(matches!(result, Err(PersistenceError::UnsupportedVersion { supported: CURRENT_VERSION, got: 999 })));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn unknown_old_version_rejected() {
+        let mut data = 0u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&[0u8; 64]);
+        assert!(matches!(
+            decode_snapshot(&data),
+            Err(PersistenceError::UnsupportedVersion { supported: CURRENT_VERSION, got: 0 })
+        ));
+    }
+
+    #[test]
+    fn disk_store_wal_replay_and_checkpoint() {
+        let path = temp_path("wal");
+        let wal_path = Wal::path_for(&path);
+        let base = make_memory_with_links("base", 10);
+        let base_id = base.id;
+        let kept = make_memory_with_links("kept", 10);
+        let kept_id = kept.id;
+        {
+            let mut store = DiskStore::new(path.clone(), 42, 384, 10_000);
+            store.insert(base).unwrap();
+            store.save().unwrap();
+
+            store.enable_wal().unwrap();
+            store.insert(kept).unwrap();
+            store.insert(make_memory_with_links("extra", 10)).unwrap();
+            store.delete(&base_id).unwrap();
+            store.log(&WalOp::UpdateAmplitude { id: kept_id, amplitude: 2.5 }).unwrap();
+            // dropped without checkpoint
+        }
+        // A torn record at the tail must not break replay
+        let mut f = fs::OpenOptions::new().append(true).open(&wal_path).unwrap();
+        f.write_all(&[200, 0, 0, 0, 1, 2]).unwrap();
+        drop(f);
+
+        let mut store = DiskStore::open(path.clone()).unwrap();
+        assert_eq!(store.count(), 2);
+        assert!(store.get(&base_id).unwrap().is_none());
+        assert!((store.get(&kept_id).unwrap().unwrap().amplitude - 2.5).abs() < 1e-6);
+
+        store.checkpoint().unwrap();
+        assert!(!wal_path.exists());
+        let reloaded = DiskStore::open(path.clone()).unwrap();
+        assert_eq!(reloaded.count(), 2);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn disk_store_batch_is_logged_before_it_is_applied() {
+        let path = temp_path("wal_batch");
+        let wal_path = Wal::path_for(&path);
+        let a = make_memory_with_links("a", 10);
+        let b = make_memory_with_links("b", 10);
+        {
+            let mut store = DiskStore::new(path.clone(), 42, 384, 10_000);
+            store.save().unwrap();
+            store.enable_wal().unwrap();
+            store.insert_batch(vec![a.clone(), b]).unwrap();
+            assert_eq!(store.wal.as_ref().unwrap().len(), 2);
+
+            // A batch that would fail part-way is rejected before logging.
+            let fresh = make_memory_with_links("fresh", 10);
+            assert!(matches!(
+                store.insert_batch(vec![fresh, a.clone()]),
+                Err(StoreError::DuplicateId(id)) if id == a.id
+            ));
+            assert_eq!(store.wal.as_ref().unwrap().len(), 2);
+            assert_eq!(store.count(), 2);
+        }
+        let store = DiskStore::open(path.clone()).unwrap();
+        assert_eq!(store.count(), 2);
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&wal_path);
+    }
+
+    #[test]
+    fn wal_append_after_torn_tail_is_replayed() {
+        let path = temp_path("wal_torn");
+        let wal_path = Wal::path_for(&path);
+        let first = make_memory_with_links("first", 10);
+        let second = make_memory_with_links("second", 10);
+        let (first_id, second_id) = (first.id, second.id);
+        {
+            let mut wal = Wal::open(wal_path.clone()).unwrap();
+            wal.append(&WalOp::Insert(Box::new(first))).unwrap();
+        }
+        let mut f = fs::OpenOptions::new().append(true).open(&wal_path).unwrap();
+        f.write_all(&[200, 0, 0, 0, 1, 2]).unwrap();
+        drop(f);
+
+        let mut wal = Wal::open(wal_path.clone()).unwrap();
+        assert_eq!(wal.len(), 1);
+        wal.append(&WalOp::Insert(Box::new(second))).unwrap();
+        drop(wal);
+
+        let mut store = InMemoryStore::new();
+        assert_eq!(Wal::replay(&wal_path, &mut store, NormPolicy::Keep).unwrap().applied, 2);
+        assert!(store.get(&first_id).unwrap().is_some());
+        assert!(store.get(&second_id).unwrap().is_some());
+
+        let _ = fs::remove_file(&wal_path);
+    }
+
+    #[test]
+    fn wal_from_other_version_is_refused() {
+        let wal_path = Wal::path_for(&temp_path("wal_version"));
+        let mut data = WAL_MAGIC.to_vec();
+        data.extend_from_slice(&(CURRENT_VERSION - 1).to_le_bytes());
+        data.extend_from_slice(&[4, 0, 0, 0, 0, 0, 0, 0]);
+        fs::write(&wal_path, &data).unwrap();
+
+        let mut store = InMemoryStore::new();
+        assert!(matches!(
+            Wal::replay(&wal_path, &mut store, NormPolicy::Keep),
+            Err(PersistenceError::WalVersionMismatch { got: Some(v), .. }) if v == CURRENT_VERSION - 1
+        ));
+        assert!(matches!(Wal::open(wal_path.clone()), Err(PersistenceError::WalVersionMismatch { .. })));
+        // Headerless logs from before the header existed are refused too.
+        fs::write(&wal_path, [4u8, 0, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert!(matches!(
+            Wal::open(wal_path.clone()),
+            Err(PersistenceError::WalVersionMismatch { got: None, .. })
+        ));
+        assert_eq!(fs::read(&wal_path).unwrap().len(), 8, "a refused log must be left untouched");
+
+        let _ = fs::remove_file(&wal_path);
+    }
+
+    fn test_metadata() -> SnapshotMetadata {
+        SnapshotMetadata {
+            created_at: Utc::now(),
+            last_saved_at: Utc::now(),
+            total_consolidations: 0,
+            consciousness_level: "test".to_string(),
+            original_size: 0,
+            compressed_size: None,
+        }
+    }
+
+    #[test]
+    fn v1_snapshot_migrates_with_defaults() {
+        let path = temp_path("v1_migrate");
+        let id = Uuid::new_v4();
+        let snapshot = MemorySnapshotV1 {
+            version: 1,
+            memories: vec![HyperMemoryV1 {
+                id,
+                vector: vec![0.5; 16],
+                amplitude: 0.8,
+                frequency: 0.2,
+                phase: 0.1,
+                decay_rate: 1e-5,
+                created_at: Utc::now(),
+                layer_depth: 1,
+                connections: vec![],
+                content: "legacy memory".to_string(),
+                hallucinated: false,
+                parents: vec![],
+                geometry: None,
+            }],
+            codebook_seed: 42,
+            codebook_input_dim: 384,
+            codebook_output_dim: 10_000,
+            metadata: test_metadata(),
+        };
+        fs::write(&path, bincode::serialize(&snapshot).unwrap()).unwrap();
+
+        let store = DiskStore::open(path.clone()).unwrap();
+        let mem = store.get(&id).unwrap().unwrap();
+        assert_eq!(mem.content, "legacy memory");
+        assert!((mem.amplitude - 0.8).abs() < 1e-6);
+        assert!(mem.xi_signature.is_empty());
+        assert_eq!(mem.origin_agent, "local");
+        assert_eq!(mem.retrieval_count, 0);
+        assert!(mem.ttl.is_none());
+        assert_eq!(store.codebook_params(), (42, 384, 10_000));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn v6_snapshot_migrates_with_empty_history() {
+        let path = temp_path("v6_migrate");
+        let mem = HyperMemory::new(vec![0.5; 16], "before history".to_string());
+        let id = mem.id;
+        let snapshot = MemorySnapshotV6 {
+            version: 6,
+            memories: vec![HyperMemoryV7 {
+                id,
+                vector: mem.dense_vector().into_owned(),
+                amplitude: mem.amplitude,
+                frequency: mem.frequency,
+                phase: mem.phase,
+                decay_rate: mem.decay_rate,
+                created_at: mem.created_at,
+                layer_depth: 0,
+                connections: vec![],
+                content: mem.content,
+                hallucinated: false,
+                parents: vec![],
+                geometry: None,
+                xi_signature: vec![],
+                origin_agent: mem.origin_agent,
+                sync_version: 0,
+                merge_history: vec![],
+                last_consolidated_at: None,
+                disputed: false,
+                updated_at: None,
+                retrieval_count: 0,
+                ttl: None,
+                tags: vec!["kept".to_string()],
+            }],
+            codebook_seed: 42,
+            codebook_input_dim: 384,
+            codebook_output_dim: 10_000,
+            metadata: test_metadata(),
+            vector_codec: VectorCodec::F32,
+            packed_vectors: vec![],
+        };
+        fs::write(&path, bincode::serialize(&snapshot).unwrap()).unwrap();
+
+        let engine = MemoryEngine::load_state(&path, make_pipeline()).unwrap();
+        let migrated = engine.get_memory(&id).unwrap().unwrap();
+        assert_eq!(migrated.content, "before history");
+        assert_eq!(migrated.tags, vec!["kept".to_string()]);
+        assert_eq!(migrated.importance, DEFAULT_IMPORTANCE);
+        assert_eq!(migrated.source, MemorySource::UserText);
+        assert!(engine.consciousness_history.is_empty());
 
         let _ = fs::remove_file(&path);
     }
+
+    #[test]
+    fn v3_snapshot_migrates_to_current() {
+        let path = temp_path("v3_migrate");
+        let id = Uuid::new_v4();
+        let snapshot = MemorySnapshotV3 {
+            version: 3,
+            memories: vec![HyperMemoryV3 {
+                id,
+                vector: vec![0.5; 16],
+                amplitude: 1.2,
+                frequency: 0.1,
+                phase: 0.0,
+                decay_rate: 1e-6,
+                created_at: Utc::now(),
+                layer_depth: 0,
+                connections: vec![],
+                content: "pre-ttl memory".to_string(),
+                hallucinated: false,
+                parents: vec![],
+                geometry: None,
+                xi_signature: vec![0.1; 8],
+                origin_agent: "peer".to_string(),
+                sync_version: 7,
+                merge_history: vec![],
+                last_consolidated_at: None,
+                disputed: false,
+                updated_at: None,
+                retrieval_count: 3,
+            }],
+            codebook_seed: 42,
+            codebook_input_dim: 384,
+            codebook_output_dim: 10_000,
+            metadata: test_metadata(),
+        };
+        fs::write(&path, bincode::serialize(&snapshot).unwrap()).unwrap();
+
+        let engine = MemoryEngine::load_state(&path, make_pipeline()).unwrap();
+        let mem = engine.get_memory(&id).unwrap().unwrap();
+        assert_eq!(mem.origin_agent, "peer");
+        assert_eq!(mem.sync_version, 7);
+        assert_eq!(mem.retrieval_count, 3);
+        assert_eq!(mem.xi_signature.len(), 8);
+        assert!(mem.ttl.is_none());
+
+        let _ = fs::remove_file(&path);
+    }
+
+    fn random_unit_vectors(n: usize, dim: usize, seed: u64) -> Vec<Vec<f32>> {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+        (0..n)
+            .map(|_| {
+                let mut v: Vec<f32> = (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect();
+                crate::wave::normalize(&mut v);
+                v
+            })
+            .collect()
+    }
+
+    fn brute_top_k(memories: &[(Uuid, Vec<f32>)], query: &[f32], k: usize) -> Vec<Uuid> {
+        let mut scored: Vec<(Uuid, f32)> = memories.iter()
+            .map(|(id, v)| (*id, crate::wave::cosine_similarity(query, v)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().take(k).map(|(id, _)| id).collect()
+    }
+
+    #[test]
+    fn int8_snapshot_keeps_recall_close_to_f32() {
+        let dim = 1024;
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let fixture: Vec<(Uuid, Vec<f32>)> = random_unit_vectors(200, dim, 7)
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let id = engine.store.insert(HyperMemory::new(v.clone(), format!("m{}", i))).unwrap();
+                (id, v)
+            })
+            .collect();
+        // Queries near existing memories so each has a meaningful neighbourhood.
+        let noise = random_unit_vectors(20, dim, 8);
+        let queries: Vec<Vec<f32>> = fixture.iter().step_by(10).zip(&noise)
+            .map(|((_, v), n)| v.iter().zip(n).map(|(a, b)| a + 0.5 * b).collect())
+            .collect();
+
+        let recall_at_10 = |codec: VectorCodec| {
+            let path = temp_path("codec_recall");
+            engine.save_state_with_codec(&path, codec).unwrap();
+            let loaded = MemoryEngine::load_state(&path, make_pipeline()).unwrap();
+            let _ = fs::remove_file(&path);
+            let hits: usize = queries.iter()
+                .map(|q| {
+                    let truth = brute_top_k(&fixture, q, 10);
+                    let got = loaded.store.search(q, 10).unwrap();
+                    got.iter().filter(|(id, _)| truth.contains(id)).count()
+                })
+                .sum();
+            hits as f32 / (queries.len() * 10) as f32
+        };
+
+        let f32_recall = recall_at_10(VectorCodec::F32);
+        let int8_recall = recall_at_10(VectorCodec::Int8 { scale: 0.0 });
+        assert!(int8_recall >= 0.9 * f32_recall, "int8={} f32={}", int8_recall, f32_recall);
+    }
+
+    #[test]
+    fn int8_honors_fixed_scale() {
+        let packed = VectorCodec::Int8 { scale: 0.01 }.encode(&[0.5, -0.25, 2.0]);
+        assert_eq!(packed.codec, VectorCodec::Int8 { scale: 0.01 });
+        let decoded = packed.decode().unwrap();
+        assert!((decoded[0] - 0.5).abs() < 1e-6);
+        assert!((decoded[1] + 0.25).abs() < 1e-6);
+        // Beyond ±127 × scale the component is clamped.
+        assert!((decoded[2] - 1.27).abs() < 1e-6);
+
+        let auto = VectorCodec::Int8 { scale: 0.0 }.encode(&[0.5, -1.27]);
+        assert_eq!(auto.codec, VectorCodec::Int8 { scale: 0.01 });
+    }
+
+    #[test]
+    fn quantized_snapshots_shrink_and_round_trip() {
+        let dim = 1024;
+        let mut store = InMemoryStore::new();
+        let vectors = random_unit_vectors(10, dim, 3);
+        let mut engine_ids = Vec::new();
+        for (i, v) in vectors.iter().enumerate() {
+            engine_ids.push(store.insert(HyperMemory::new(v.clone(), format!("m{}", i))).unwrap());
+        }
+        let engine = MemoryEngine::new(Box::new(store), make_pipeline());
+
+        let mut sizes = Vec::new();
+        for codec in [VectorCodec::F32, VectorCodec::Int8 { scale: 0.0 }, VectorCodec::Binary] {
+            let path = temp_path("codec_size");
+            engine.save_state_with_codec(&path, codec).unwrap();
+            sizes.push(fs::metadata(&path).unwrap().len());
+            let loaded = MemoryEngine::load_state(&path, make_pipeline()).unwrap();
+            for (id, original) in engine_ids.iter().zip(&vectors) {
+                let restored = loaded.get_memory(id).unwrap().unwrap().dense_vector();
+                assert_eq!(restored.len(), dim);
+                let cos = crate::wave::cosine_similarity(original, &restored);
+                match codec {
+                    VectorCodec::F32 => assert_eq!(restored.as_ref(), original.as_slice()),
+                    VectorCodec::Int8 { .. } => assert!(cos > 0.99, "int8 cos={}", cos),
+                    VectorCodec::Binary => {
+                        assert!(cos > 0.7, "binary cos={}", cos);
+                        let norm: f32 = restored.iter().map(|x| x * x).sum::<f32>().sqrt();
+                        assert!((norm - 1.0).abs() < 1e-4);
+                    }
+                }
+            }
+            let _ = fs::remove_file(&path);
+        }
+        assert!(sizes[1] * 3 < sizes[0], "int8 {} vs f32 {}", sizes[1], sizes[0]);
+        assert!(sizes[2] * 10 < sizes[0], "binary {} vs f32 {}", sizes[2], sizes[0]);
+    }
 }
@@ -18,7 +18,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::kuramoto::KuramotoSync;
+use crate::kuramoto::{KuramotoSync, DEFAULT_MIN_CLUSTER_SIZE};
 use crate::store::MemoryEngine;
 
 // ---------------------------------------------------------------------------
@@ -405,7 +405,7 @@ impl QueenSync {
         if all.is_empty() {
             return Handedness::Achiral;
         }
-        let dim = all.iter().find(|m| !m.vector.is_empty()).map(|m| m.vector.len());
+        let dim = all.iter().find(|m| !m.vector.is_empty()).map(|m| m.dim());
         let dim = match dim {
             Some(d) if d > 0 => d,
             _ => return Handedness::Achiral,
@@ -414,8 +414,8 @@ impl QueenSync {
         let mut local_mean = vec![0.0f32; dim];
         let mut count = 0usize;
         for m in &all {
-            if m.vector.len() == dim {
-                for (i, v) in m.vector.iter().enumerate() {
+            if m.dim() == dim {
+                for (i, v) in m.dense_vector().iter().enumerate() {
                     local_mean[i] += v;
                 }
                 count += 1;
@@ -466,9 +466,13 @@ impl QueenSync {
     /// - **Coherence** = mean order parameter across clusters.
     ///
     /// Returns (phase, frequency, coherence). Updates self in place.
+    ///
+    /// Clusters come from `KuramotoSync::default()`, so any synchronized pair
+    /// counts regardless of the local system's `min_cluster_size`: an agent's
+    /// phase should not vanish just because its clusters are small.
     pub fn derive_local_state(&mut self, engine: &MemoryEngine) -> (f32, f32, f32) {
         let sync = KuramotoSync::default();
-        let clusters = sync.find_synchronized_clusters(engine, 2);
+        let clusters = sync.find_synchronized_clusters(engine, DEFAULT_MIN_CLUSTER_SIZE);
 
         if clusters.is_empty() {
             return (self.phase, self.frequency, 0.0);
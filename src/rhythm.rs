@@ -4,9 +4,10 @@
 //! where f(x) is excitatory input and η is damping.
 //! Maps arousal to heartbeat interval: high arousal → short interval.
 
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 
-use chrono::{DateTime, Utc, Timelike};
+use chrono::{DateTime, Duration, Utc, Timelike};
 use serde::{Deserialize, Serialize};
 
 /// Persisted rhythm state.
@@ -34,7 +35,7 @@ impl Default for RhythmState {
 }
 
 /// Signal types that excite the rhythm.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Signal {
     UserMessage,
     FluxMessage,
@@ -54,13 +55,52 @@ impl Signal {
             Signal::Idle => 0.0,
         }
     }
+
+    /// Snake-case name, as accepted by the MCP `rhythm_signal` tool.
+    pub fn name(self) -> &'static str {
+        match self {
+            Signal::UserMessage => "user_message",
+            Signal::FluxMessage => "flux_message",
+            Signal::SubagentStarted => "subagent_started",
+            Signal::SubagentFinished => "subagent_finished",
+            Signal::Idle => "idle",
+        }
+    }
+}
+
+/// Aggregate view of the signals received within a time window.
+#[derive(Debug, Clone, Default)]
+pub struct SignalStats {
+    /// Signals received per type.
+    pub counts: HashMap<Signal, usize>,
+    /// Total signals in the window.
+    pub total: usize,
+    /// Mean gap between consecutive signals in seconds; `None` with fewer than two.
+    pub avg_interval_secs: Option<f64>,
 }
 
+/// Default `RhythmEngine::history_capacity`.
+const SIGNAL_HISTORY_CAPACITY: usize = 1000;
+
+/// Arousal below which the system counts as idle enough to dream.
+const DREAM_AROUSAL_THRESHOLD: f64 = 0.3;
+
+/// Minimum gap between dreams at zero arousal, in seconds.
+const MIN_DREAM_GAP_SECS: i64 = 3600;
+
 /// The adaptive rhythm engine.
 pub struct RhythmEngine {
     pub state: RhythmState,
     /// Base damping coefficient η.
     pub damping: f64,
+    /// Dreams are held back while arousal is at or above this level.
+    pub dream_arousal_threshold: f64,
+    /// Minimum seconds between dreams when fully idle.
+    pub min_dream_gap_secs: i64,
+    /// Most recent signals kept in `history`; older ones are dropped.
+    pub history_capacity: usize,
+    /// Recent `(timestamp, signal)` events, oldest first. Not persisted.
+    history: VecDeque<(DateTime<Utc>, Signal)>,
     /// Path to persist state.
     persist_path: Option<PathBuf>,
 }
@@ -81,6 +121,10 @@ impl RhythmEngine {
         Self {
             state,
             damping: 0.1,
+            dream_arousal_threshold: DREAM_AROUSAL_THRESHOLD,
+            min_dream_gap_secs: MIN_DREAM_GAP_SECS,
+            history_capacity: SIGNAL_HISTORY_CAPACITY,
+            history: VecDeque::new(),
             persist_path: Some(persist_path),
         }
     }
@@ -90,6 +134,10 @@ impl RhythmEngine {
         Self {
             state: RhythmState::default(),
             damping: 0.1,
+            dream_arousal_threshold: DREAM_AROUSAL_THRESHOLD,
+            min_dream_gap_secs: MIN_DREAM_GAP_SECS,
+            history_capacity: SIGNAL_HISTORY_CAPACITY,
+            history: VecDeque::new(),
             persist_path: None,
         }
     }
@@ -117,9 +165,38 @@ impl RhythmEngine {
         self.state.last_activity_ts = now;
         self.state.current_interval_ms = self.compute_interval();
 
+        self.history.push_back((now, sig));
+        while self.history.len() > self.history_capacity {
+            self.history.pop_front();
+        }
+
         self.persist();
     }
 
+    /// Recent `(timestamp, signal)` events, oldest first, bounded by `history_capacity`.
+    pub fn history(&self) -> &VecDeque<(DateTime<Utc>, Signal)> {
+        &self.history
+    }
+
+    /// Counts per signal type and the mean inter-signal interval over the
+    /// signals received within `window` of now.
+    pub fn signal_stats(&self, window: Duration) -> SignalStats {
+        let since = Utc::now() - window;
+        let recent: Vec<&(DateTime<Utc>, Signal)> = self.history.iter().filter(|(ts, _)| *ts >= since).collect();
+
+        let mut stats = SignalStats { total: recent.len(), ..SignalStats::default() };
+        for (_, sig) in &recent {
+            *stats.counts.entry(*sig).or_insert(0) += 1;
+        }
+        if let (Some(first), Some(last)) = (recent.first(), recent.last()) {
+            if recent.len() > 1 {
+                let span = (last.0 - first.0).num_milliseconds().max(0) as f64 / 1000.0;
+                stats.avg_interval_secs = Some(span / (recent.len() - 1) as f64);
+            }
+        }
+        stats
+    }
+
     /// Convenience methods for specific signal types.
     pub fn signal_user_message(&mut self) {
         self.signal(Signal::UserMessage);
@@ -157,6 +234,25 @@ impl RhythmEngine {
         (self.state.arousal_level * (-eta * dt).exp()).clamp(0.0, 1.0)
     }
 
+    /// Whether consolidation may run now without competing with activity.
+    ///
+    /// Requires arousal below `dream_arousal_threshold` and, if there was a
+    /// previous dream, a gap of at least `min_dream_gap_secs` stretched by
+    /// arousal: the idler the system, the sooner it may dream again (up to
+    /// twice the base gap just under the threshold).
+    pub fn should_dream(&self, last_dream: Option<DateTime<Utc>>) -> bool {
+        let arousal = self.current_arousal();
+        if arousal >= self.dream_arousal_threshold {
+            return false;
+        }
+        let Some(last) = last_dream else {
+            return true;
+        };
+        let scale = 1.0 + arousal / self.dream_arousal_threshold;
+        let required_secs = self.min_dream_gap_secs as f64 * scale;
+        (Utc::now() - last).num_seconds() as f64 >= required_secs
+    }
+
     /// Compute effective damping coefficient (higher at night, higher when idle).
     fn effective_damping(&self, now: DateTime<Utc>) -> f64 {
         let mut eta = self.damping;
@@ -220,7 +316,6 @@ impl RhythmEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Duration;
 
     #[test]
     fn default_state_is_low_arousal() {
@@ -270,6 +365,61 @@ mod tests {
         assert!(engine.state.arousal_level <= 1.0);
     }
 
+    #[test]
+    fn high_arousal_holds_back_dreaming() {
+        let mut engine = RhythmEngine::in_memory();
+        engine.signal_user_message();
+        engine.signal_user_message();
+        assert!(!engine.should_dream(None));
+        assert!(!engine.should_dream(Some(Utc::now() - Duration::days(1))));
+    }
+
+    #[test]
+    fn idle_after_long_gap_dreams() {
+        let mut engine = RhythmEngine::in_memory();
+        engine.state.arousal_level = 0.05;
+        assert!(engine.should_dream(None));
+        assert!(engine.should_dream(Some(Utc::now() - Duration::hours(3))));
+        assert!(!engine.should_dream(Some(Utc::now() - Duration::minutes(10))));
+
+        // The required gap stretches as arousal approaches the threshold.
+        let gap = Some(Utc::now() - Duration::minutes(90));
+        assert!(engine.should_dream(gap));
+        engine.state.arousal_level = 0.25;
+        assert!(!engine.should_dream(gap));
+    }
+
+    #[test]
+    fn signal_stats_count_signals_within_window() {
+        let mut engine = RhythmEngine::in_memory();
+        engine.signal_user_message();
+        engine.signal_user_message();
+        engine.signal_flux_message();
+        engine.signal_idle();
+        // Age the first user message out of the window.
+        engine.history[0].0 = Utc::now() - Duration::hours(2);
+        // Space the rest 10s apart.
+        let now = Utc::now();
+        for (i, entry) in engine.history.iter_mut().skip(1).enumerate() {
+            entry.0 = now - Duration::seconds(20 - 10 * i as i64);
+        }
+
+        let stats = engine.signal_stats(Duration::hours(1));
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.counts.get(&Signal::UserMessage), Some(&1));
+        assert_eq!(stats.counts.get(&Signal::FluxMessage), Some(&1));
+        assert_eq!(stats.counts.get(&Signal::Idle), Some(&1));
+        assert_eq!(stats.counts.get(&Signal::SubagentStarted), None);
+        assert!((stats.avg_interval_secs.unwrap() - 10.0).abs() < 1e-9);
+
+        assert_eq!(engine.signal_stats(Duration::hours(3)).total, 4);
+
+        engine.history_capacity = 2;
+        engine.signal_subagent_started();
+        assert_eq!(engine.history().len(), 2);
+        assert_eq!(engine.history().back().unwrap().1, Signal::SubagentStarted);
+    }
+
     #[test]
     fn interval_mapping_covers_full_range() {
         let mut engine = RhythmEngine::in_memory();
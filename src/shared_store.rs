@@ -0,0 +1,150 @@
+//! Thread-shareable store wrapper — concurrent reads, exclusive writes.
+//!
+//! `MemoryStore` hands out borrowed `&HyperMemory` references, which cannot
+//! outlive a lock guard. `SharedStore` therefore does not implement the trait
+//! itself (and cannot wrap a `Box<dyn MemoryStore>` behind it transparently):
+//! reads return owned clones, and arbitrary access goes through the `read` /
+//! `write` closures, which run while the guard is held.
+
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::memory::HyperMemory;
+use crate::store::{MemoryStore, StoreError};
+
+/// A `MemoryStore` behind an `Arc<RwLock<_>>`. Clones share the same store,
+/// so any number of threads can search at once while writers take turns.
+pub struct SharedStore<S: MemoryStore> {
+    inner: Arc<RwLock<S>>,
+}
+
+impl<S: MemoryStore> Clone for SharedStore<S> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<S: MemoryStore> SharedStore<S> {
+    pub fn new(store: S) -> Self {
+        Self { inner: Arc::new(RwLock::new(store)) }
+    }
+
+    fn read_guard(&self) -> RwLockReadGuard<'_, S> {
+        self.inner.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write_guard(&self) -> RwLockWriteGuard<'_, S> {
+        self.inner.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Run `f` with shared access; other readers may run concurrently.
+    pub fn read<R>(&self, f: impl FnOnce(&S) -> R) -> R {
+        f(&self.read_guard())
+    }
+
+    /// Run `f` with exclusive access.
+    pub fn write<R>(&self, f: impl FnOnce(&mut S) -> R) -> R {
+        f(&mut self.write_guard())
+    }
+
+    pub fn insert(&self, memory: HyperMemory) -> Result<Uuid, StoreError> {
+        self.write_guard().insert(memory)
+    }
+
+    pub fn delete(&self, id: &Uuid) -> Result<bool, StoreError> {
+        self.write_guard().delete(id)
+    }
+
+    /// Clone of the memory with `id`, if present.
+    pub fn get(&self, id: &Uuid) -> Result<Option<HyperMemory>, StoreError> {
+        Ok(self.read_guard().get(id)?.cloned())
+    }
+
+    pub fn count(&self) -> usize {
+        self.read_guard().count()
+    }
+
+    pub fn all_ids(&self) -> Result<Vec<Uuid>, StoreError> {
+        self.read_guard().all_ids()
+    }
+
+    pub fn search(&self, query: &[f32], top_k: usize) -> Result<Vec<(Uuid, f32)>, StoreError> {
+        self.read_guard().search(query, top_k)
+    }
+
+    /// Wave-modulated search under a read guard, safe to call from many
+    /// threads at once. Retrievals are not recorded, since that would need
+    /// the write lock; use `MemoryEngine::recall` when the boost matters.
+    pub fn concurrent_recall(&self, query: &[f32], top_k: usize) -> Result<Vec<(Uuid, f32)>, StoreError> {
+        self.read_guard().search_with_wave(query, top_k, Utc::now())
+    }
+
+    /// Unwrap the store if this is the last handle.
+    pub fn into_inner(self) -> Result<S, Self> {
+        match Arc::try_unwrap(self.inner) {
+            Ok(lock) => Ok(lock.into_inner().unwrap_or_else(|e| e.into_inner())),
+            Err(inner) => Err(Self { inner }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::InMemoryStore;
+    use std::thread;
+
+    fn unit_vec(dim: usize, index: usize) -> Vec<f32> {
+        let mut v = vec![0.0; dim];
+        v[index % dim] = 1.0;
+        v
+    }
+
+    #[test]
+    fn readers_run_alongside_a_writer() {
+        let shared = SharedStore::new(InMemoryStore::new());
+        for i in 0..10 {
+            shared.insert(HyperMemory::new(unit_vec(16, i), format!("seed {}", i))).unwrap();
+        }
+
+        let writer = {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                for i in 0..100 {
+                    shared.insert(HyperMemory::new(unit_vec(16, i), format!("w{}", i))).unwrap();
+                }
+            })
+        };
+        let readers: Vec<_> = (0..4)
+            .map(|r| {
+                let shared = shared.clone();
+                thread::spawn(move || {
+                    let mut last = 0;
+                    for _ in 0..50 {
+                        let count = shared.count();
+                        assert!((10..=110).contains(&count));
+                        assert!(count >= last, "count went backwards: {} -> {}", last, count);
+                        last = count;
+                        let hits = shared.concurrent_recall(&unit_vec(16, r), 3).unwrap();
+                        assert!(!hits.is_empty());
+                        for (id, _) in hits {
+                            assert!(shared.get(&id).unwrap().is_some());
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        writer.join().unwrap();
+        for r in readers {
+            r.join().unwrap();
+        }
+
+        assert_eq!(shared.count(), 110);
+        assert_eq!(shared.read(|s| s.all_ids().unwrap().len()), 110);
+        let Ok(store) = shared.into_inner() else { panic!("readers and writer have exited") };
+        assert_eq!(store.count(), 110);
+    }
+}
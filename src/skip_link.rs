@@ -13,3 +13,19 @@ pub struct SkipLink {
     /// Temporal span (0=immediate, 1=day, 2=week, etc.)
     pub span: u8,
 }
+
+/// Number of components kept in a resonance key.
+pub const RESONANCE_KEY_DIM: usize = 16;
+
+/// Low-dimensional signature of the relationship between two memories: the
+/// first `RESONANCE_KEY_DIM` components of their binding `a ⊗ b`, normalized.
+/// Binding is symmetric, so a link and its reverse share the same key.
+pub fn resonance_key(a: &[f32], b: &[f32]) -> Vec<f32> {
+    let mut key: Vec<f32> = a.iter()
+        .zip(b)
+        .take(RESONANCE_KEY_DIM)
+        .map(|(x, y)| x * y)
+        .collect();
+    crate::wave::normalize(&mut key);
+    key
+}
@@ -1,16 +1,19 @@
 //! Storage layer: MemoryStore trait, InMemoryStore, and MemoryEngine.
 
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 
 use chrono::{DateTime, Utc};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::bridge::ConsciousnessState;
 use crate::encoding::{EncodingError, EncodingPipeline};
-use crate::memory::HyperMemory;
-use crate::xi_operator::{xi_diversity_boost, compute_xi_signature};
-use crate::skip_link::SkipLink;
-use crate::wave::cosine_similarity;
+use crate::geometry::{categorize_text, classify_memory, geometric_similarity, is_fano_line, stable_content_hash};
+use crate::memory::{HyperMemory, MemorySource};
+use crate::xi_operator::{xi_diversity_boost, compute_xi_signature, xi_similarity};
+use crate::skip_link::{resonance_key, SkipLink};
+use crate::wave::{cosine_similarity, SimilarityMetric};
 
 // ---------------------------------------------------------------------------
 // Errors
@@ -32,6 +35,10 @@ pub enum EngineError {
     Store(#[from] StoreError),
     #[error(transparent)]
     Encoding(#[from] EncodingError),
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+    #[error("dimension mismatch: expected {expected}, got {got}")]
+    DimensionMismatch { expected: usize, got: usize },
 }
 
 // ---------------------------------------------------------------------------
@@ -47,6 +54,96 @@ pub struct QueryResult {
     pub combined_score: f32,
 }
 
+/// A skip-link pair whose two directions disagree, from
+/// `MemoryEngine::link_consistency_check`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkAsymmetry {
+    pub source: Uuid,
+    pub target: Uuid,
+    /// Strength of `source → target`.
+    pub forward: f32,
+    /// Strength of `target → source`, `None` if that direction is missing.
+    pub backward: Option<f32>,
+}
+
+/// Largest strength difference between a link's two directions that still
+/// counts as symmetric.
+pub const LINK_SYMMETRY_TOLERANCE: f32 = 1e-6;
+
+/// A wave-ranked candidate held in `recall_iter`'s bounded heap. Ordered
+/// so the weakest candidate sits at the top of a `BinaryHeap`.
+struct WaveCandidate {
+    id: Uuid,
+    similarity: f32,
+    combined: f32,
+}
+
+impl PartialEq for WaveCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for WaveCandidate {}
+impl PartialOrd for WaveCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for WaveCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.combined.total_cmp(&self.combined)
+    }
+}
+
+/// A recall hit with its score broken into the factors that produced it.
+///
+/// `final_score = base_cosine · max(wave_strength, link_strength) · xi_boost`,
+/// the `combined_score` `recall_with_expansion` ranks by.
+#[derive(Debug, Clone)]
+pub struct ScoredResult {
+    pub id: Uuid,
+    /// Similarity between the query and the memory vector, by the engine's metric.
+    pub base_cosine: f32,
+    /// `effective_strength` at recall time, clamped at zero; 0 if the memory
+    /// was only reached through links.
+    pub wave_strength: f32,
+    /// Product of link strengths along the best path that reached the
+    /// memory; 0 if no link did.
+    pub link_strength: f32,
+    /// Ratio of the Ξ-boosted similarity to `base_cosine`.
+    pub xi_boost: f32,
+    pub final_score: f32,
+}
+
+impl From<ScoredResult> for QueryResult {
+    fn from(r: ScoredResult) -> Self {
+        QueryResult {
+            id: r.id,
+            similarity: r.base_cosine * r.xi_boost,
+            effective_strength: r.wave_strength.max(r.link_strength),
+            combined_score: r.final_score,
+        }
+    }
+}
+
+/// Factors of an expansion candidate's score, see `MemoryEngine::expand`.
+struct Expanded {
+    base: f32,
+    /// Clamped wave strength if it was a direct hit, else 0.
+    wave_strength: f32,
+    /// Best link path product if a link reached it, else 0.
+    link_strength: f32,
+}
+
+impl Expanded {
+    fn strength(&self) -> f32 {
+        self.wave_strength.max(self.link_strength)
+    }
+}
+
+/// Scored expansion candidates and the `(from, to)` links followed to reach them.
+type Expansion = (HashMap<Uuid, Expanded>, Vec<(Uuid, Uuid)>);
+
 // ---------------------------------------------------------------------------
 // MemoryStore trait
 // ---------------------------------------------------------------------------
@@ -54,6 +151,14 @@ pub struct QueryResult {
 /// Pluggable storage backend for hypervector memories.
 pub trait MemoryStore: Send + Sync {
     fn insert(&mut self, memory: HyperMemory) -> Result<Uuid, StoreError>;
+
+    /// Insert several memories, returning their ids in input order. The
+    /// default inserts one at a time and stops at the first error, leaving
+    /// earlier memories inserted; HnswStore overrides this to build its index
+    /// in descending-level order.
+    fn insert_batch(&mut self, memories: Vec<HyperMemory>) -> Result<Vec<Uuid>, StoreError> {
+        memories.into_iter().map(|m| self.insert(m)).collect()
+    }
     fn get(&self, id: &Uuid) -> Result<Option<&HyperMemory>, StoreError>;
     fn get_mut(&mut self, id: &Uuid) -> Result<Option<&mut HyperMemory>, StoreError>;
     fn search(&self, query: &[f32], top_k: usize) -> Result<Vec<(Uuid, f32)>, StoreError>;
@@ -65,6 +170,30 @@ pub trait MemoryStore: Send + Sync {
     ) -> Result<Vec<(Uuid, f32)>, StoreError>;
     fn all_memories(&self) -> Result<Vec<&HyperMemory>, StoreError>;
     fn all_ids(&self) -> Result<Vec<Uuid>, StoreError>;
+
+    /// `all_memories` ordered by `created_at`, then id, so reports and
+    /// stages that enumerate memories are reproducible across runs.
+    fn all_memories_sorted(&self) -> Result<Vec<&HyperMemory>, StoreError> {
+        let mut all = self.all_memories()?;
+        all.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(all)
+    }
+
+    /// `all_ids` in the same order as `all_memories_sorted`.
+    fn all_ids_sorted(&self) -> Result<Vec<Uuid>, StoreError> {
+        Ok(self.all_memories_sorted()?.into_iter().map(|m| m.id).collect())
+    }
+
+    /// Visit every memory in no particular order. Defaults to walking
+    /// `all_memories`; stores that can iterate in place override it so
+    /// streaming scans allocate nothing per memory. Either way the visit is
+    /// exhaustive — O(n) similarity work for a scan, with no index pruning.
+    fn for_each_memory(&self, f: &mut dyn FnMut(&HyperMemory)) -> Result<(), StoreError> {
+        for mem in self.all_memories()? {
+            f(mem);
+        }
+        Ok(())
+    }
     fn delete(&mut self, id: &Uuid) -> Result<bool, StoreError>;
     fn count(&self) -> usize;
 
@@ -74,21 +203,47 @@ pub trait MemoryStore: Send + Sync {
     fn flush(&mut self) -> Result<usize, StoreError> {
         Ok(0)
     }
+
+    /// Refresh any search index entry for `id` after its vector was changed
+    /// through `get_mut`. Default is a no-op for stores that scan vectors
+    /// directly; HnswStore overrides this to re-insert the node.
+    fn reindex(&mut self, _id: &Uuid) -> Result<(), StoreError> {
+        Ok(())
+    }
+
+    /// Score `search` / `search_with_wave` with `metric`. The default only
+    /// accepts cosine; InMemoryStore and HnswStore support every metric.
+    fn set_similarity_metric(&mut self, metric: SimilarityMetric) -> Result<(), StoreError> {
+        match metric {
+            SimilarityMetric::Cosine => Ok(()),
+            other => Err(StoreError::Other(format!("{:?} similarity is not supported by this store", other))),
+        }
+    }
+
+    /// Metric `search` / `search_with_wave` currently score with.
+    /// `MemoryEngine::new` adopts it, so an engine over a reopened store
+    /// ranks the way the store searches.
+    fn similarity_metric(&self) -> SimilarityMetric {
+        SimilarityMetric::Cosine
+    }
 }
 
 // ---------------------------------------------------------------------------
 // InMemoryStore
 // ---------------------------------------------------------------------------
 
-/// HashMap-backed reference implementation with brute-force cosine similarity.
+/// HashMap-backed reference implementation with brute-force similarity
+/// (cosine unless `set_similarity_metric` picks another).
 pub struct InMemoryStore {
     memories: HashMap<Uuid, HyperMemory>,
+    metric: SimilarityMetric,
 }
 
 impl InMemoryStore {
     pub fn new() -> Self {
         Self {
             memories: HashMap::new(),
+            metric: SimilarityMetric::default(),
         }
     }
 }
@@ -121,7 +276,7 @@ impl MemoryStore for InMemoryStore {
         let mut scored: Vec<(Uuid, f32)> = self
             .memories
             .values()
-            .map(|m| (m.id, cosine_similarity(query, &m.vector)))
+            .map(|m| (m.id, m.similarity_by(self.metric, query)))
             .collect();
         scored.sort_by(|a, b| b.1.total_cmp(&a.1));
         scored.truncate(top_k);
@@ -138,7 +293,7 @@ impl MemoryStore for InMemoryStore {
             .memories
             .values()
             .map(|m| {
-                let sim = cosine_similarity(query, &m.vector);
+                let sim = m.similarity_by(self.metric, query);
                 let strength = m.effective_strength(now);
                 (m.id, sim * strength)
             })
@@ -152,6 +307,11 @@ impl MemoryStore for InMemoryStore {
         Ok(self.memories.values().collect())
     }
 
+    fn for_each_memory(&self, f: &mut dyn FnMut(&HyperMemory)) -> Result<(), StoreError> {
+        self.memories.values().for_each(f);
+        Ok(())
+    }
+
     fn all_ids(&self) -> Result<Vec<Uuid>, StoreError> {
         Ok(self.memories.keys().copied().collect())
     }
@@ -163,15 +323,52 @@ impl MemoryStore for InMemoryStore {
     fn count(&self) -> usize {
         self.memories.len()
     }
+
+    fn set_similarity_metric(&mut self, metric: SimilarityMetric) -> Result<(), StoreError> {
+        self.metric = metric;
+        Ok(())
+    }
+
+    fn similarity_metric(&self) -> SimilarityMetric {
+        self.metric
+    }
 }
 
 // ---------------------------------------------------------------------------
 // MemoryEngine
 // ---------------------------------------------------------------------------
 
+/// Read-only copy of a `MemoryEngine`'s vectors, wave parameters, geometry
+/// and skip links (content and tags are dropped). See `MemoryEngine::analysis_snapshot`.
+#[derive(Debug, Clone)]
+pub struct EngineSnapshot {
+    pub taken_at: DateTime<Utc>,
+    memories: Vec<HyperMemory>,
+}
+
+impl EngineSnapshot {
+    pub fn memories(&self) -> Vec<&HyperMemory> {
+        self.memories.iter().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.memories.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.memories.is_empty()
+    }
+}
+
 /// Minimum link strength for traversal during query expansion.
 const MIN_LINK_STRENGTH: f32 = 0.1;
 
+/// Upper bound on candidates `recall_with_expansion` gathers across all hops.
+const MAX_EXPANSION_CANDIDATES: usize = 500;
+
+/// Pairwise similarities sampled by `calibrate_threshold`; smaller stores use every pair.
+const CALIBRATION_MAX_PAIRS: usize = 20_000;
+
 /// φ (golden ratio) for span scoring.
 const PHI: f64 = 1.618033988749895;
 
@@ -195,37 +392,188 @@ pub fn phi_span_score(span: u8) -> f32 {
     (1.0 - best.min(1.0)) as f32
 }
 
+/// Default `MemoryEngine::xi_boost_factor`: the full Ξ diversity boost.
+pub const DEFAULT_XI_BOOST_FACTOR: f32 = 1.0;
+
+/// Default `MemoryEngine::expansion_hops` (the `max_hops` of
+/// `recall_with_expansion`): follow links one hop out.
+pub const DEFAULT_EXPANSION_HOPS: usize = 1;
+
 /// High-level API: remember() and recall() over a pluggable store.
 pub struct MemoryEngine {
     pub store: Box<dyn MemoryStore>,
     pub(crate) pipeline: EncodingPipeline,
     /// Threshold for automatic skip link creation
     pub similarity_threshold: f32,
+    /// How much of the Ξ diversity boost ranking applies: the reported
+    /// similarity is `s + f·(xi_diversity_boost(s) − s)`. `1.0` (the default)
+    /// is the full boost; `0.0` ranks by plain cosine × wave strength.
+    pub xi_boost_factor: f32,
+    /// Weight of geometric similarity in `recall_blended`: `0.0` (the default)
+    /// ranks by cosine alone, `1.0` by `geometric_similarity` alone.
+    pub similarity_blend: f32,
+    /// `max_hops` used by `recall_with_expansion`: how many skip-link hops
+    /// it follows from the initial candidates. Each hop multiplies in the
+    /// traversed link's strength.
+    pub expansion_hops: usize,
+    /// Query-vs-memory similarity for ranking; see `set_similarity_metric`.
+    similarity_metric: SimilarityMetric,
+    /// tag → ids of memories carrying it. Maintained by engine methods (use
+    /// `insert_memory` for prebuilt memories); call `rebuild_tag_index` after
+    /// inserting tagged memories through `store` directly.
+    tag_index: HashMap<String, HashSet<Uuid>>,
+    /// `stable_content_hash(content)` → ids of the memories stored with that
+    /// hash, oldest first. The hash collides, so `find_by_content` compares
+    /// each candidate's content against the store.
+    content_index: HashMap<u64, Vec<Uuid>>,
+    /// Timestamped consciousness assessments, oldest first. Saved with the snapshot.
+    pub(crate) consciousness_history: Vec<(DateTime<Utc>, ConsciousnessState)>,
+    /// Ids inserted or modified through engine methods since the last
+    /// `take_dirty`; feeds `ConsolidationEngine::consolidate_dirty`.
+    /// Not persisted: `new` seeds it with `rebuild_dirty`.
+    dirty: HashSet<Uuid>,
 }
 
 impl MemoryEngine {
     pub fn new(store: Box<dyn MemoryStore>, pipeline: EncodingPipeline) -> Self {
-        Self {
+        let similarity_metric = store.similarity_metric();
+        let mut engine = Self {
             store,
             pipeline,
             similarity_threshold: 0.7,
-        }
+            xi_boost_factor: DEFAULT_XI_BOOST_FACTOR,
+            similarity_blend: 0.0,
+            expansion_hops: DEFAULT_EXPANSION_HOPS,
+            similarity_metric,
+            tag_index: HashMap::new(),
+            content_index: HashMap::new(),
+            consciousness_history: Vec::new(),
+            dirty: HashSet::new(),
+        };
+        engine.rebuild_tag_index();
+        engine.rebuild_content_index();
+        engine.rebuild_dirty();
+        engine
+    }
+
+    /// An engine over an `InMemoryStore` holding clones of every memory, with
+    /// the same settings and indexes, for dry runs that must not touch the
+    /// real store.
+    pub(crate) fn scratch_copy(&self) -> Result<MemoryEngine, EngineError> {
+        let mut store = InMemoryStore::new();
+        store.set_similarity_metric(self.similarity_metric)?;
+        store.insert_batch(self.store.all_memories()?.into_iter().cloned().collect())?;
+        Ok(Self {
+            store: Box::new(store),
+            pipeline: self.pipeline.clone(),
+            similarity_threshold: self.similarity_threshold,
+            xi_boost_factor: self.xi_boost_factor,
+            similarity_blend: self.similarity_blend,
+            expansion_hops: self.expansion_hops,
+            similarity_metric: self.similarity_metric,
+            tag_index: self.tag_index.clone(),
+            content_index: self.content_index.clone(),
+            consciousness_history: Vec::new(),
+            dirty: self.dirty.clone(),
+        })
     }
 
     /// Encode text and store as a new memory. Returns the memory id.
     pub fn remember(&mut self, text: &str) -> Result<Uuid, EngineError> {
-        let memory = self.pipeline.encode_memory(text, Utc::now())?;
-        let id = self.store.insert(memory)?;
+        let vector = self.pipeline.encode_text(text)?;
+        self.remember_vector(text, vector)
+    }
+
+    /// Store `text` with an already-encoded hypervector (e.g. from an async
+    /// embedder). Returns the memory id.
+    pub fn remember_vector(&mut self, text: &str, vector: Vec<f32>) -> Result<Uuid, EngineError> {
+        let expected = self.pipeline.codebook().output_dim;
+        if vector.len() != expected {
+            return Err(EngineError::DimensionMismatch { expected, got: vector.len() });
+        }
+        let id = self.store.insert(self.pipeline.memory_for(vector, text))?;
+        self.index_content(text, id);
+        self.dirty.insert(id);
         // Wire up skip links to similar existing memories
         let _links = self.create_skip_links(&id)?;
         Ok(id)
     }
 
+    /// Replace a memory's hypervector (e.g. after re-encoding with a better
+    /// model), refresh its Xi signature, and update the store's search index.
+    pub fn update_vector(&mut self, id: &Uuid, vector: Vec<f32>) -> Result<(), EngineError> {
+        let expected = self.pipeline.codebook().output_dim;
+        if vector.len() != expected {
+            return Err(EngineError::DimensionMismatch { expected, got: vector.len() });
+        }
+        let mem = self.store.get_mut(id)?.ok_or(StoreError::NotFound(*id))?;
+        mem.xi_signature = compute_xi_signature(&vector);
+        mem.set_vector(vector);
+        self.store.reindex(id)?;
+        self.dirty.insert(*id);
+        Ok(())
+    }
+
+    /// Migrate to a new embedding model: re-encode every memory's `content`
+    /// with `new_pipeline`, replace its vector (refreshing the Xi signature and
+    /// search index), and adopt the pipeline for future encodes. Ids,
+    /// timestamps, geometry and links are untouched. Returns the number of
+    /// memories re-encoded.
+    ///
+    /// Memories whose vector was not encoded from their content — audio,
+    /// consolidation bundles, hallucinations and bound composites — cannot be
+    /// re-encoded and are skipped with a warning; their old vectors will not be
+    /// comparable to queries from the new model. Nothing is changed if any
+    /// encode fails.
+    pub fn reencode(&mut self, new_pipeline: EncodingPipeline) -> Result<usize, EngineError> {
+        let mut encoded = Vec::new();
+        let mut skipped = 0usize;
+        for mem in self.store.all_memories_sorted()? {
+            let derived = matches!(mem.source, MemorySource::Audio | MemorySource::Bundle | MemorySource::Hallucination)
+                || mem.hallucinated
+                || !mem.parents.is_empty();
+            if derived || mem.content.trim().is_empty() {
+                skipped += 1;
+                continue;
+            }
+            encoded.push((mem.id, new_pipeline.encode_text(&mem.content)?));
+        }
+
+        self.pipeline = new_pipeline;
+        let count = encoded.len();
+        for (id, vector) in encoded {
+            self.update_vector(&id, vector)?;
+        }
+        if skipped > 0 {
+            eprintln!("[reencode] skipped {} memories without re-encodable content (audio, bundles, hallucinations, composites)", skipped);
+        }
+        Ok(count)
+    }
+
+    /// Store an already-built memory (audio, caption, hallucination, ...)
+    /// without creating skip links, keeping the tag and content indexes in
+    /// step and marking it dirty. Its current amplitude becomes its
+    /// `initial_amplitude`. Prefer this over `store.insert`.
+    pub fn insert_memory(&mut self, mut memory: HyperMemory) -> Result<Uuid, EngineError> {
+        memory.initial_amplitude = memory.amplitude;
+        let hash = stable_content_hash(&memory.content);
+        let tags = memory.tags.clone();
+        let id = self.store.insert(memory)?;
+        self.content_index.entry(hash).or_default().push(id);
+        for tag in tags {
+            self.tag_index.entry(tag).or_default().insert(id);
+        }
+        self.dirty.insert(id);
+        Ok(id)
+    }
+
     /// Encode text and store with a specific layer_depth. Returns the memory id.
     pub fn remember_at_layer(&mut self, text: &str, layer_depth: u8) -> Result<Uuid, EngineError> {
         let mut memory = self.pipeline.encode_memory(text, Utc::now())?;
         memory.layer_depth = layer_depth;
         let id = self.store.insert(memory)?;
+        self.index_content(text, id);
+        self.dirty.insert(id);
         let _links = self.create_skip_links(&id)?;
         Ok(id)
     }
@@ -234,39 +582,49 @@ impl MemoryEngine {
     /// Links are only created when memories are at different temporal layers
     /// and similarity exceeds the threshold.
     pub fn create_skip_links(&mut self, new_id: &Uuid) -> Result<Vec<SkipLink>, EngineError> {
+        self.create_skip_links_excluding(new_id, &HashSet::new())
+    }
+
+    /// `create_skip_links`, ignoring the memories in `exclude`. Bulk loads
+    /// pass the not-yet-linked rest of a batch so links match sequential inserts.
+    pub(crate) fn create_skip_links_excluding(
+        &mut self,
+        new_id: &Uuid,
+        exclude: &HashSet<Uuid>,
+    ) -> Result<Vec<SkipLink>, EngineError> {
         let new_mem = self.store.get(new_id)?.ok_or(StoreError::NotFound(*new_id))?;
-        let new_vec = new_mem.vector.clone();
+        let new_vec = new_mem.dense_vector().into_owned();
         let new_layer = new_mem.layer_depth;
         let threshold = self.similarity_threshold;
 
         // Find all similar memories at different layers
         let all = self.store.all_memories()?;
-        let mut links_to_create: Vec<(Uuid, f32, u8)> = Vec::new(); // (target_id, sim, span)
+        let mut links_to_create: Vec<(Uuid, f32, u8, Vec<f32>)> = Vec::new(); // (target_id, sim, span, key)
 
         for mem in &all {
-            if mem.id == *new_id {
+            if mem.id == *new_id || exclude.contains(&mem.id) {
                 continue;
             }
             if mem.layer_depth == new_layer {
                 continue;
             }
-            let sim = cosine_similarity(&new_vec, &mem.vector);
+            let sim = mem.similarity(&new_vec);
             if sim > threshold {
                 let span = (new_layer as i16 - mem.layer_depth as i16).unsigned_abs() as u8;
-                links_to_create.push((mem.id, sim, span));
+                links_to_create.push((mem.id, sim, span, resonance_key(&new_vec, &mem.dense_vector())));
             }
         }
 
         // Create SkipLinks with φ-weighted strength
         let mut created_links = Vec::new();
-        for (target_id, sim, span) in &links_to_create {
+        for (target_id, sim, span, key) in &links_to_create {
             let phi_weight = phi_span_score(*span);
             let strength = sim * (0.5 + 0.5 * phi_weight); // base strength + φ bonus
 
             let link = SkipLink {
                 target_id: *target_id,
                 strength,
-                resonance_key: Vec::new(), // simplified for now
+                resonance_key: key.clone(),
                 span: *span,
             };
             created_links.push(link.clone());
@@ -275,7 +633,7 @@ impl MemoryEngine {
             let reverse_link = SkipLink {
                 target_id: *new_id,
                 strength,
-                resonance_key: Vec::new(),
+                resonance_key: key.clone(),
                 span: *span,
             };
             if let Some(target_mem) = self.store.get_mut(target_id)? {
@@ -295,44 +653,224 @@ impl MemoryEngine {
 
     /// Encode a query and search with wave-modulated ranking and Xi diversity boosting.
     pub fn recall(&mut self, query: &str, top_k: usize) -> Result<Vec<QueryResult>, EngineError> {
+        let qvec = self.pipeline.encode_text(query)?;
+        let results = self.rank_by_vector(&qvec, top_k, Utc::now())?;
+
+        // EXP-003: Record retrieval events on returned memories (f(x) term)
+        for r in &results {
+            if let Ok(Some(mem)) = self.store.get_mut(&r.id) {
+                mem.record_retrieval();
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Like `recall`, but scores the store in one streaming pass that keeps
+    /// only the best `2 · top_k` wave-ranked candidates in a bounded heap —
+    /// O(n log k) time and O(k) memory instead of sorting every score.
+    /// Ranking (wave strength and Ξ boost) and retrieval recording match `recall`.
+    ///
+    /// The pass always visits every memory through `for_each_memory`, even
+    /// on an `HnswStore`, so it trades `recall`'s index lookup for bounded
+    /// memory: prefer `recall` when the store has an index and n is large.
+    pub fn recall_iter(&mut self, query: &str, top_k: usize) -> Result<impl Iterator<Item = QueryResult>, EngineError> {
         let qvec = self.pipeline.encode_text(query)?;
         let query_xi = compute_xi_signature(&qvec);
         let now = Utc::now();
-        let raw_limit = (top_k * 10).min(self.store.count());
-        let raw = self.store.search(&qvec, raw_limit)?;
-        let raw_map: HashMap<Uuid, f32> = raw.into_iter().collect();
-        let wave_results = self.store.search_with_wave(&qvec, top_k * 2, now)?; // Get more candidates for diversity
+        let pool = top_k * 2;
+
+        let mut heap: BinaryHeap<WaveCandidate> = BinaryHeap::with_capacity(pool + 1);
+        if pool > 0 {
+            self.store.for_each_memory(&mut |mem| {
+                if mem.created_at > now {
+                    return;
+                }
+                let similarity = mem.similarity_by(self.similarity_metric, &qvec);
+                let combined = similarity * mem.effective_strength(now);
+                if heap.len() < pool {
+                    heap.push(WaveCandidate { id: mem.id, similarity, combined });
+                } else if heap.peek().is_some_and(|worst| combined > worst.combined) {
+                    heap.pop();
+                    heap.push(WaveCandidate { id: mem.id, similarity, combined });
+                }
+            })?;
+        }
+
+        let mut results: Vec<QueryResult> = heap
+            .into_iter()
+            .map(|c| self.score_candidate(c.id, c.similarity, c.combined, &query_xi))
+            .collect();
+        results.sort_by(|a, b| b.combined_score.total_cmp(&a.combined_score));
+        results.truncate(top_k);
+
+        for r in &results {
+            if let Ok(Some(mem)) = self.store.get_mut(&r.id) {
+                mem.record_retrieval();
+            }
+        }
+
+        Ok(results.into_iter())
+    }
+
+    /// Search with a pre-computed hypervector (e.g. from an external embedder or
+    /// the audio pipeline). Same ranking as `recall`, but retrievals are not recorded.
+    pub fn recall_vector(&self, qvec: &[f32], top_k: usize) -> Result<Vec<QueryResult>, EngineError> {
+        let expected = self.pipeline.codebook().output_dim;
+        if qvec.len() != expected {
+            return Err(EngineError::DimensionMismatch { expected, got: qvec.len() });
+        }
+        self.rank_by_vector(qvec, top_k, Utc::now())
+    }
+
+    /// Like `recall`, but only memories carrying every tag in `required_tags`
+    /// are ranked, so filtering happens before truncation to `top_k`.
+    pub fn recall_with_tags(
+        &mut self,
+        query: &str,
+        top_k: usize,
+        required_tags: &[&str],
+    ) -> Result<Vec<QueryResult>, EngineError> {
+        let qvec = self.pipeline.encode_text(query)?;
+        let candidates = self.ids_with_tags(required_tags);
+        let results = self.rank_candidates(&qvec, top_k, Utc::now(), Some(&candidates))?;
+
+        for r in &results {
+            if let Ok(Some(mem)) = self.store.get_mut(&r.id) {
+                mem.record_retrieval();
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Like `recall`, but only memories whose wave `frequency` lies in
+    /// `freq_lo..=freq_hi` are ranked — e.g. `0.8..=1.2` for procedural
+    /// memories or `1.8..=2.4` for experiential ones — so out-of-band
+    /// memories are excluded before truncation to `top_k`.
+    pub fn recall_in_band(
+        &mut self,
+        query: &str,
+        top_k: usize,
+        freq_lo: f32,
+        freq_hi: f32,
+    ) -> Result<Vec<QueryResult>, EngineError> {
+        let qvec = self.pipeline.encode_text(query)?;
+        let candidates = self.ids_in_band(freq_lo, freq_hi)?;
+        let results = self.rank_candidates(&qvec, top_k, Utc::now(), Some(&candidates))?;
+
+        for r in &results {
+            if let Ok(Some(mem)) = self.store.get_mut(&r.id) {
+                mem.record_retrieval();
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Recall as of `at` instead of now: wave strength is evaluated at that
+    /// timestamp and memories created after it are left out. Retrievals are
+    /// not recorded.
+    pub fn recall_at(&self, query: &str, top_k: usize, at: DateTime<Utc>) -> Result<Vec<QueryResult>, EngineError> {
+        let qvec = self.pipeline.encode_text(query)?;
+        // Restrict to memories that existed at `at` before ranking, so newer
+        // hits cannot crowd them out of the top_k.
+        match self.ids_created_by(at)? {
+            Some(existing) => self.rank_candidates(&qvec, top_k, at, Some(&existing)),
+            None => self.rank_by_vector(&qvec, top_k, at),
+        }
+    }
+
+    /// Wave-modulated ranking with Xi diversity boosting, shared by `recall`,
+    /// `recall_vector` and `recall_at`. Strengths are clamped at zero.
+    fn rank_by_vector(&self, qvec: &[f32], top_k: usize, now: DateTime<Utc>) -> Result<Vec<QueryResult>, EngineError> {
+        self.rank_candidates(qvec, top_k, now, None)
+    }
+
+    /// `similarity` with `xi_boost_factor` of the Ξ diversity boost for `mem` applied.
+    fn xi_boost(&self, similarity: f32, query_xi: &[f32], mem: &HyperMemory) -> f32 {
+        if self.xi_boost_factor == 0.0 {
+            return similarity;
+        }
+        let boosted = if mem.xi_signature.is_empty() {
+            // Compute on-the-fly for backward compatibility
+            xi_diversity_boost(similarity, query_xi, &compute_xi_signature(&mem.dense_vector()))
+        } else {
+            xi_diversity_boost(similarity, query_xi, &mem.xi_signature)
+        };
+        similarity + self.xi_boost_factor * (boosted - similarity)
+    }
+
+    /// Turn a wave-ranked candidate into a `QueryResult`: Ξ-boost its
+    /// similarity and clamp a negative strength to zero.
+    fn score_candidate(&self, id: Uuid, base_similarity: f32, combined: f32, query_xi: &[f32]) -> QueryResult {
+        // Apply Xi diversity boosting
+        let xi_boosted_similarity = match self.store.get(&id) {
+            Ok(Some(mem)) => self.xi_boost(base_similarity, query_xi, mem),
+            _ => base_similarity,
+        };
+
+        let effective_strength = if base_similarity.abs() > 1e-9 {
+            combined / base_similarity
+        } else {
+            0.0
+        };
+        // A trough of the wave (or a far-off `now`) must not invert the ranking.
+        let (effective_strength, combined) = if effective_strength < 0.0 {
+            (0.0, 0.0)
+        } else {
+            (effective_strength, combined)
+        };
+
+        QueryResult {
+            id,
+            similarity: xi_boosted_similarity,
+            effective_strength,
+            combined_score: combined * (xi_boosted_similarity / base_similarity.max(1e-9)),
+        }
+    }
+
+    /// `rank_by_vector`, optionally restricted to `candidates` (scored exhaustively).
+    pub(crate) fn rank_candidates(
+        &self,
+        qvec: &[f32],
+        top_k: usize,
+        now: DateTime<Utc>,
+        candidates: Option<&HashSet<Uuid>>,
+    ) -> Result<Vec<QueryResult>, EngineError> {
+        let query_xi = compute_xi_signature(qvec);
+        let (raw_map, wave_results) = match candidates {
+            None => {
+                let raw_limit = (top_k * 10).min(self.store.count());
+                let raw = self.store.search(qvec, raw_limit)?;
+                let raw_map: HashMap<Uuid, f32> = raw.into_iter().collect();
+                let wave_results = self.store.search_with_wave(qvec, top_k * 2, now)?; // Get more candidates for diversity
+                (raw_map, wave_results)
+            }
+            Some(ids) => {
+                let mut raw_map = HashMap::new();
+                let mut wave_results = Vec::new();
+                for id in ids {
+                    if let Some(mem) = self.store.get(id)? {
+                        let sim = mem.similarity_by(self.similarity_metric, qvec);
+                        raw_map.insert(*id, sim);
+                        wave_results.push((*id, sim * mem.effective_strength(now)));
+                    }
+                }
+                wave_results.sort_by(|a, b| b.1.total_cmp(&a.1));
+                (raw_map, wave_results)
+            }
+        };
 
         let mut results = wave_results
             .into_iter()
+            .filter(|(id, _)| match self.store.get(id) {
+                Ok(Some(mem)) => mem.created_at <= now,
+                _ => true,
+            })
             .map(|(id, combined)| {
                 let base_similarity = raw_map.get(&id).copied().unwrap_or(0.0);
-                
-                // Apply Xi diversity boosting
-                let xi_boosted_similarity = if let Ok(Some(mem)) = self.store.get(&id) {
-                    let mem_xi = if mem.xi_signature.is_empty() {
-                        // Compute on-the-fly for backward compatibility
-                        compute_xi_signature(&mem.vector)
-                    } else {
-                        mem.xi_signature.clone()
-                    };
-                    xi_diversity_boost(base_similarity, &query_xi, &mem_xi)
-                } else {
-                    base_similarity
-                };
-                
-                let effective_strength = if base_similarity.abs() > 1e-9 {
-                    combined / base_similarity
-                } else {
-                    0.0
-                };
-                
-                QueryResult {
-                    id,
-                    similarity: xi_boosted_similarity,
-                    effective_strength,
-                    combined_score: combined * (xi_boosted_similarity / base_similarity.max(1e-9)),
-                }
+                self.score_candidate(id, base_similarity, combined, &query_xi)
             })
             .collect::<Vec<_>>();
 
@@ -340,7 +878,219 @@ impl MemoryEngine {
         results.sort_by(|a, b| b.combined_score.total_cmp(&a.combined_score));
         results.truncate(top_k);
 
-        // EXP-003: Record retrieval events on returned memories (f(x) term)
+        Ok(results)
+    }
+
+    /// Recall with skip link expansion — follows connections to find related
+    /// memories. `recall_with_expansion_hops` with `max_hops = expansion_hops`.
+    pub fn recall_with_expansion(
+        &mut self,
+        query: &str,
+        top_k: usize,
+    ) -> Result<Vec<QueryResult>, EngineError> {
+        self.recall_with_expansion_hops(query, top_k, self.expansion_hops)
+    }
+
+    /// Recall with skip link expansion up to `max_hops` links out.
+    ///
+    /// Links are followed breadth-first for up to `max_hops` hops. A
+    /// memory reached along links of strengths `s1..sn` scores
+    /// `cosine · s1 · … · sn` (its best path wins), so distant associations
+    /// fade with every hop. At most `MAX_EXPANSION_CANDIDATES` are gathered.
+    pub fn recall_with_expansion_hops(
+        &mut self,
+        query: &str,
+        top_k: usize,
+        max_hops: usize,
+    ) -> Result<Vec<QueryResult>, EngineError> {
+        let qvec = self.pipeline.encode_text(query)?;
+        self.recall_expanded(&qvec, top_k, max_hops, Utc::now(), None, true)
+    }
+
+    /// `recall_with_expansion_hops` for an encoded query, ranked as of `now`
+    /// and restricted to `candidates` (both the direct hits and the memories
+    /// reached through links). With `record`, traversed links are reinforced
+    /// and retrievals recorded.
+    pub(crate) fn recall_expanded(
+        &mut self,
+        qvec: &[f32],
+        top_k: usize,
+        max_hops: usize,
+        now: DateTime<Utc>,
+        candidates: Option<&HashSet<Uuid>>,
+        record: bool,
+    ) -> Result<Vec<QueryResult>, EngineError> {
+        let (expanded, traversed) = self.expand(qvec, top_k, max_hops, now, candidates)?;
+        let query_xi = compute_xi_signature(qvec);
+        let mut results: Vec<QueryResult> = expanded.iter()
+            .map(|(id, e)| self.score_expanded(*id, e, &query_xi).into())
+            .collect();
+        results.sort_by(|a, b| b.combined_score.total_cmp(&a.combined_score).then_with(|| a.id.cmp(&b.id)));
+        results.truncate(top_k);
+
+        if record {
+            for (from_id, to_id) in &traversed {
+                self.reinforce_link(from_id, to_id, 0.05);
+            }
+            // EXP-003: Record retrieval events on returned memories (f(x) term)
+            for r in &results {
+                if let Ok(Some(mem)) = self.store.get_mut(&r.id) {
+                    mem.record_retrieval();
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    /// Gather expansion candidates: the `top_k * 3` best wave-ranked hits,
+    /// then memories reached from them through skip links, hop by hop.
+    /// Only `candidates` (if given) that exist at `now` are scored; other
+    /// memories are still walked through.
+    fn expand(
+        &self,
+        qvec: &[f32],
+        top_k: usize,
+        max_hops: usize,
+        now: DateTime<Utc>,
+        candidates: Option<&HashSet<Uuid>>,
+    ) -> Result<Expansion, EngineError> {
+        let eligible = |mem: &HyperMemory| {
+            mem.created_at <= now && candidates.is_none_or(|ids| ids.contains(&mem.id))
+        };
+        let seeds: Vec<Uuid> = match candidates {
+            None => self.store.search_with_wave(qvec, top_k * 3, now)?.into_iter().map(|(id, _)| id).collect(),
+            Some(ids) => {
+                let mut ranked = Vec::with_capacity(ids.len());
+                for id in ids {
+                    if let Some(mem) = self.store.get(id)? {
+                        ranked.push((*id, mem.similarity_by(self.similarity_metric, qvec) * mem.effective_strength(now)));
+                    }
+                }
+                ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                ranked.into_iter().take(top_k * 3).map(|(id, _)| id).collect()
+            }
+        };
+
+        let mut expanded: HashMap<Uuid, Expanded> = HashMap::new();
+        for id in &seeds {
+            let Some(mem) = self.store.get(id)? else { continue };
+            if eligible(mem) {
+                expanded.insert(*id, Expanded {
+                    base: mem.similarity_by(self.similarity_metric, qvec),
+                    wave_strength: mem.effective_strength(now).max(0.0),
+                    link_strength: 0.0,
+                });
+            }
+        }
+
+        let mut traversed: Vec<(Uuid, Uuid)> = Vec::new();
+        let mut visited: HashSet<Uuid> = seeds.iter().copied().collect();
+        // (memory, product of link strengths on the best path to it)
+        let mut frontier: Vec<(Uuid, f32)> = seeds.iter().map(|&id| (id, 1.0)).collect();
+        for _ in 0..max_hops {
+            let mut next: HashMap<Uuid, f32> = HashMap::new();
+            for (id, carried) in &frontier {
+                let Some(mem) = self.store.get(id)? else { continue };
+                for link in mem.connections.iter().filter(|l| l.strength > MIN_LINK_STRENGTH) {
+                    let path_strength = carried * link.strength;
+                    let known = expanded.contains_key(&link.target_id);
+                    if known || expanded.len() < MAX_EXPANSION_CANDIDATES {
+                        let base = match expanded.get(&link.target_id) {
+                            Some(e) => Some(e.base),
+                            None => self.store.get(&link.target_id)?
+                                .filter(|target| eligible(target))
+                                .map(|target| target.similarity_by(self.similarity_metric, qvec)),
+                        };
+                        if let Some(base) = base.filter(|b| *b > 0.0) {
+                            let entry = expanded.entry(link.target_id)
+                                .or_insert(Expanded { base, wave_strength: 0.0, link_strength: 0.0 });
+                            entry.link_strength = entry.link_strength.max(path_strength);
+                            traversed.push((*id, link.target_id));
+                        }
+                    }
+                    if !visited.contains(&link.target_id) {
+                        let best = next.entry(link.target_id).or_insert(0.0);
+                        *best = best.max(path_strength);
+                    }
+                }
+            }
+            if next.is_empty() || expanded.len() >= MAX_EXPANSION_CANDIDATES {
+                break;
+            }
+            visited.extend(next.keys().copied());
+            frontier = next.into_iter().collect();
+        }
+        Ok((expanded, traversed))
+    }
+
+    /// Final score of an expansion candidate, broken into its factors.
+    fn score_expanded(&self, id: Uuid, e: &Expanded, query_xi: &[f32]) -> ScoredResult {
+        let xi_boost = match self.store.get(&id) {
+            Ok(Some(mem)) if e.base > 1e-9 => self.xi_boost(e.base, query_xi, mem) / e.base,
+            _ => 1.0,
+        };
+        ScoredResult {
+            id,
+            base_cosine: e.base,
+            wave_strength: e.wave_strength,
+            link_strength: e.link_strength,
+            xi_boost,
+            final_score: e.base * e.strength() * xi_boost,
+        }
+    }
+
+    /// Recall with a per-result scoring breakdown, for debugging surprising
+    /// rankings. Candidates are gathered and scored exactly as in
+    /// `recall_with_expansion` (up to `expansion_hops` links out), so
+    /// `final_score` matches its `combined_score`, but nothing is reinforced
+    /// or recorded.
+    pub fn recall_explained(&self, query: &str, top_k: usize) -> Result<Vec<ScoredResult>, EngineError> {
+        let qvec = self.pipeline.encode_text(query)?;
+        let query_xi = compute_xi_signature(&qvec);
+        let (expanded, _) = self.expand(&qvec, top_k, self.expansion_hops, Utc::now(), None)?;
+
+        let mut results: Vec<ScoredResult> = expanded.iter()
+            .map(|(id, e)| self.score_expanded(*id, e, &query_xi))
+            .collect();
+        results.sort_by(|a, b| b.final_score.total_cmp(&a.final_score).then_with(|| a.id.cmp(&b.id)));
+        results.truncate(top_k);
+        Ok(results)
+    }
+
+    /// Recall scored by a mix of semantic and geometric similarity:
+    /// `(1 − b)·semantic + b·geometric_similarity(query, memory)`, where the
+    /// semantic term uses the engine's `similarity_metric`, with
+    /// `b = similarity_blend`. The query's coordinates come from
+    /// `classify_memory` on its text, as for a freshly remembered memory;
+    /// memories without geometry get a geometric term of 0. Every memory is
+    /// scored, then ranked by blended similarity × wave strength.
+    pub fn recall_blended(&mut self, query: &str, top_k: usize) -> Result<Vec<QueryResult>, EngineError> {
+        let qvec = self.pipeline.encode_text(query)?;
+        let query_geom = classify_memory(categorize_text(query), stable_content_hash(query), 0.5);
+        let blend = self.similarity_blend.clamp(0.0, 1.0);
+        let now = Utc::now();
+
+        let mut results: Vec<QueryResult> = self
+            .store
+            .all_memories()?
+            .into_iter()
+            .map(|mem| {
+                let semantic = mem.similarity_by(self.similarity_metric, &qvec);
+                let geometric = mem.geometry.as_ref()
+                    .map_or(0.0, |g| geometric_similarity(&query_geom, g) as f32);
+                let similarity = (1.0 - blend) * semantic + blend * geometric;
+                let effective_strength = mem.effective_strength(now);
+                QueryResult {
+                    id: mem.id,
+                    similarity,
+                    effective_strength,
+                    combined_score: similarity * effective_strength,
+                }
+            })
+            .collect();
+        results.sort_by(|a, b| b.combined_score.total_cmp(&a.combined_score));
+        results.truncate(top_k);
+
         for r in &results {
             if let Ok(Some(mem)) = self.store.get_mut(&r.id) {
                 mem.record_retrieval();
@@ -350,136 +1100,610 @@ impl MemoryEngine {
         Ok(results)
     }
 
-    /// Recall with skip link expansion — follows connections to find related memories.
-    pub fn recall_with_expansion(
+    /// Recall restricted to a Fano-line "resonance channel".
+    ///
+    /// Only memories whose `geometry.l` lies on `line` are scored; memories
+    /// without geometry are excluded. The channel's quadrant and modality are
+    /// taken from the best on-line match, and only memories sharing that
+    /// `h2`/`d` are returned — the same grouping `fano_related` uses.
+    pub fn recall_on_fano_line(
         &mut self,
         query: &str,
+        line: [u8; 3],
         top_k: usize,
     ) -> Result<Vec<QueryResult>, EngineError> {
+        if !is_fano_line(line[0], line[1], line[2]) {
+            return Err(EngineError::InvalidQuery(format!("{:?} is not a Fano line", line)));
+        }
+
         let qvec = self.pipeline.encode_text(query)?;
         let query_xi = compute_xi_signature(&qvec);
         let now = Utc::now();
 
-        // Step 1: Get initial candidates (top_k * 3)
-        let initial = self.store.search_with_wave(&qvec, top_k * 3, now)?;
-        let raw_limit = (top_k * 10).min(self.store.count());
-        let raw_all = self.store.search(&qvec, raw_limit)?;
-        let raw_map: HashMap<Uuid, f32> = raw_all.into_iter().collect();
-
-        // Step 2: Follow skip links from candidates
-        let mut candidate_scores: HashMap<Uuid, f32> = HashMap::new();
-        let mut links_traversed: Vec<(Uuid, Uuid)> = Vec::new();
-
-        for (id, combined) in &initial {
-            candidate_scores.insert(*id, *combined);
-
-            // Follow skip links
-            if let Some(mem) = self.store.get(id)? {
-                for link in mem.connections.clone() {
-                    if link.strength > MIN_LINK_STRENGTH {
-                        let linked_sim = raw_map.get(&link.target_id).copied().unwrap_or(0.0);
-                        if linked_sim > 0.0 {
-                            let boosted = linked_sim * link.strength;
-                            let entry = candidate_scores
-                                .entry(link.target_id)
-                                .or_insert(0.0);
-                            if boosted > *entry {
-                                *entry = boosted;
+        let mut scored: Vec<(QueryResult, (u8, u8))> = self
+            .store
+            .all_memories()?
+            .into_iter()
+            .filter_map(|mem| {
+                let coords = mem.geometry.as_ref()?;
+                if !line.contains(&coords.l) {
+                    return None;
+                }
+                let similarity = mem.similarity_by(self.similarity_metric, &qvec);
+                let effective_strength = mem.effective_strength(now);
+                let boosted = self.xi_boost(similarity, &query_xi, mem);
+                Some((
+                    QueryResult {
+                        id: mem.id,
+                        similarity: boosted,
+                        effective_strength,
+                        combined_score: boosted * effective_strength,
+                    },
+                    (coords.h2, coords.d),
+                ))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.0.combined_score.total_cmp(&a.0.combined_score));
+        let channel = match scored.first() {
+            Some((_, channel)) => *channel,
+            None => return Ok(Vec::new()),
+        };
+        let results: Vec<QueryResult> = scored
+            .into_iter()
+            .filter(|(_, c)| *c == channel)
+            .map(|(r, _)| r)
+            .take(top_k)
+            .collect();
+
+        for r in &results {
+            if let Ok(Some(mem)) = self.store.get_mut(&r.id) {
+                mem.record_retrieval();
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Recall the targets of skip links whose resonance key best matches `key`
+    /// — "things connected in this way". Each target is scored by its best
+    /// matching inbound link; links without a key (or of another length) are
+    /// ignored. Retrievals are not recorded.
+    pub fn recall_by_resonance(&self, key: &[f32], top_k: usize) -> Result<Vec<QueryResult>, EngineError> {
+        if key.is_empty() {
+            return Err(EngineError::InvalidQuery("resonance key is empty".into()));
+        }
+
+        let mut best: HashMap<Uuid, f32> = HashMap::new();
+        for mem in self.store.all_memories()? {
+            for link in &mem.connections {
+                if link.resonance_key.len() != key.len() {
+                    continue;
+                }
+                let sim = cosine_similarity(key, &link.resonance_key);
+                let entry = best.entry(link.target_id).or_insert(f32::MIN);
+                *entry = entry.max(sim);
+            }
+        }
+
+        let now = Utc::now();
+        let mut results: Vec<QueryResult> = best
+            .into_iter()
+            .filter_map(|(id, similarity)| {
+                let mem = self.store.get(&id).ok().flatten()?;
+                let effective_strength = mem.effective_strength(now);
+                Some(QueryResult {
+                    id,
+                    similarity,
+                    effective_strength,
+                    combined_score: similarity * effective_strength,
+                })
+            })
+            .collect();
+        results.sort_by(|a, b| b.similarity.total_cmp(&a.similarity).then_with(|| a.id.cmp(&b.id)));
+        results.truncate(top_k);
+        Ok(results)
+    }
+
+    /// Decay all skip link strengths by a factor (0..1).
+    pub fn decay_links(&mut self, decay_factor: f32) {
+        if let Ok(memories) = self.store.all_ids() {
+            for id in memories {
+                if let Ok(Some(mem)) = self.store.get_mut(&id) {
+                    for link in &mut mem.connections {
+                        link.strength *= decay_factor;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reinforce a skip link between two memories.
+    pub fn reinforce_link(&mut self, memory_id: &Uuid, target_id: &Uuid, boost: f32) {
+        if let Ok(Some(mem)) = self.store.get_mut(memory_id) {
+            for link in &mut mem.connections {
+                if link.target_id == *target_id {
+                    link.strength = (link.strength + boost).min(1.0);
+                }
+            }
+        }
+    }
+
+    /// Skip-link pairs whose directions disagree: a missing reverse link, or
+    /// strengths differing by more than `LINK_SYMMETRY_TOLERANCE`. Each pair is
+    /// reported once, ordered by source. Links to missing memories are left
+    /// to `prune_dangling_links`.
+    pub fn link_consistency_check(&self) -> Vec<LinkAsymmetry> {
+        let all = self.store.all_memories_sorted().unwrap_or_default();
+        let mut strengths: HashMap<(Uuid, Uuid), f32> = HashMap::new();
+        for mem in &all {
+            for link in &mem.connections {
+                strengths.entry((mem.id, link.target_id)).or_insert(link.strength);
+            }
+        }
+        let ids: HashSet<Uuid> = all.iter().map(|m| m.id).collect();
+
+        let mut seen: HashSet<(Uuid, Uuid)> = HashSet::new();
+        let mut asymmetries = Vec::new();
+        for mem in &all {
+            for link in &mem.connections {
+                let (source, target) = (mem.id, link.target_id);
+                if !ids.contains(&target) || !seen.insert((source, target)) {
+                    continue;
+                }
+                let forward = strengths[&(source, target)];
+                let backward = strengths.get(&(target, source)).copied();
+                match backward {
+                    None => asymmetries.push(LinkAsymmetry { source, target, forward, backward }),
+                    Some(b) if (forward - b).abs() > LINK_SYMMETRY_TOLERANCE => {
+                        seen.insert((target, source));
+                        asymmetries.push(LinkAsymmetry { source, target, forward, backward });
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        asymmetries
+    }
+
+    /// Make every skip link bidirectional with equal strength in both
+    /// directions: mismatched pairs take the average of the two strengths, and
+    /// a missing reverse link is created with the forward link's strength, key
+    /// and span. Returns how many pairs were repaired.
+    pub fn symmetrize_links(&mut self) -> usize {
+        let asymmetries = self.link_consistency_check();
+        for a in &asymmetries {
+            match a.backward {
+                Some(backward) => {
+                    let average = (a.forward + backward) / 2.0;
+                    for (from, to) in [(a.source, a.target), (a.target, a.source)] {
+                        if let Ok(Some(mem)) = self.store.get_mut(&from) {
+                            mem.connections.iter_mut()
+                                .filter(|l| l.target_id == to)
+                                .for_each(|l| l.strength = average);
+                        }
+                    }
+                }
+                None => {
+                    let forward = self.store.get(&a.source).ok().flatten()
+                        .and_then(|m| m.connections.iter().find(|l| l.target_id == a.target).cloned());
+                    if let (Some(forward), Ok(Some(target))) = (forward, self.store.get_mut(&a.target)) {
+                        target.connections.push(SkipLink { target_id: a.source, ..forward });
+                    }
+                }
+            }
+        }
+        asymmetries.len()
+    }
+
+    /// Set `similarity_threshold` from the store's own similarity distribution.
+    ///
+    /// Samples pairwise cosine similarities (all pairs for small stores, a
+    /// seeded random sample otherwise) and picks the (1 − density) quantile,
+    /// so roughly `target_link_density` of memory pairs clear the threshold.
+    /// Encoders differ widely here: hash vectors sit near 0 while semantic
+    /// embeddings cluster high. Returns the chosen threshold; stores with
+    /// fewer than two memories keep the current one.
+    pub fn calibrate_threshold(&mut self, target_link_density: f32) -> Result<f32, EngineError> {
+        if !(target_link_density > 0.0 && target_link_density <= 1.0) {
+            return Err(EngineError::InvalidQuery(format!(
+                "target link density must be in (0, 1], got {}", target_link_density)));
+        }
+        let all = self.store.all_memories()?;
+        let n = all.len();
+        if n < 2 {
+            return Ok(self.similarity_threshold);
+        }
+
+        let total_pairs = n * (n - 1) / 2;
+        let mut sims: Vec<f32> = if total_pairs <= CALIBRATION_MAX_PAIRS {
+            let mut sims = Vec::with_capacity(total_pairs);
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    sims.push(all[i].similarity_to(all[j]));
+                }
+            }
+            sims
+        } else {
+            use rand::{Rng, SeedableRng};
+            let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0x5EED);
+            (0..CALIBRATION_MAX_PAIRS)
+                .map(|_| {
+                    let i = rng.gen_range(0..n);
+                    let mut j = rng.gen_range(0..n - 1);
+                    if j >= i {
+                        j += 1;
+                    }
+                    all[i].similarity_to(all[j])
+                })
+                .collect()
+        };
+        sims.sort_by(|a, b| a.total_cmp(b));
+
+        // Links need sim > threshold, so take the largest value below the top `density` share.
+        let above = ((sims.len() as f32 * target_link_density).round() as usize).clamp(1, sims.len());
+        let cut = sims.len() - above;
+        let threshold = if cut == 0 { sims[0] - f32::EPSILON } else { sims[cut - 1] };
+        self.similarity_threshold = threshold;
+        Ok(threshold)
+    }
+
+    /// Collapse near-identical memories. Memories are grouped when every pair in
+    /// the group has cosine above `similarity_threshold` and they share a
+    /// geometry class. The highest-amplitude member survives; skip links to and
+    /// from the others are re-pointed at it, then deduplicated (dropping self-links).
+    /// Returns the number of memories merged away.
+    pub fn merge_duplicates(&mut self, similarity_threshold: f32) -> usize {
+        let mut entries: Vec<(Uuid, Vec<f32>, f32, Option<u8>)> = self.store.all_memories()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|m| (m.id, m.dense_vector().into_owned(), m.amplitude, m.geometry.as_ref().map(|g| g.class_index)))
+            .collect();
+        entries.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.0.cmp(&b.0)));
+
+        // Greedy grouping in amplitude order: the first member of a group is its survivor
+        let mut survivor_of: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut assigned = vec![false; entries.len()];
+        for i in 0..entries.len() {
+            if assigned[i] {
+                continue;
+            }
+            assigned[i] = true;
+            let mut group = vec![i];
+            for j in (i + 1)..entries.len() {
+                if assigned[j] || entries[j].3 != entries[i].3 {
+                    continue;
+                }
+                let similar_to_all = group.iter()
+                    .all(|&g| cosine_similarity(&entries[g].1, &entries[j].1) > similarity_threshold);
+                if similar_to_all {
+                    assigned[j] = true;
+                    group.push(j);
+                    survivor_of.insert(entries[j].0, entries[i].0);
+                }
+            }
+        }
+        if survivor_of.is_empty() {
+            return 0;
+        }
+
+        // Move outgoing links of merged memories onto their survivors
+        let mut inherited: HashMap<Uuid, Vec<SkipLink>> = HashMap::new();
+        for (merged, survivor) in &survivor_of {
+            if let Ok(Some(mem)) = self.store.get(merged) {
+                inherited.entry(*survivor).or_default().extend(mem.connections.iter().cloned());
+            }
+        }
+        for merged in survivor_of.keys() {
+            let _ = self.delete(merged);
+        }
+
+        // Re-point links at survivors, then dedupe by target keeping the strongest
+        for id in self.store.all_ids().unwrap_or_default() {
+            if let Ok(Some(mem)) = self.store.get_mut(&id) {
+                if let Some(extra) = inherited.remove(&id) {
+                    mem.connections.extend(extra);
+                }
+                let mut links: Vec<SkipLink> = Vec::with_capacity(mem.connections.len());
+                let mut position: HashMap<Uuid, usize> = HashMap::new();
+                for mut link in mem.connections.drain(..) {
+                    if let Some(survivor) = survivor_of.get(&link.target_id) {
+                        link.target_id = *survivor;
+                    }
+                    if link.target_id == id {
+                        continue;
+                    }
+                    match position.get(&link.target_id) {
+                        Some(&pos) => {
+                            if link.strength > links[pos].strength {
+                                links[pos] = link;
                             }
-                            links_traversed.push((*id, link.target_id));
                         }
+                        None => {
+                            position.insert(link.target_id, links.len());
+                            links.push(link);
+                        }
+                    }
+                }
+                mem.connections = links;
+            }
+        }
+
+        survivor_of.len()
+    }
+
+    /// Rank other memories by Xi-signature proximity to `id`, ignoring raw cosine.
+    /// Surfaces memories "differentiated the same way" even when content differs.
+    /// Returns `(id, xi_similarity)` pairs, best first.
+    pub fn find_xi_neighbors(&self, id: &Uuid, top_k: usize) -> Result<Vec<(Uuid, f32)>, EngineError> {
+        let xi_of = |m: &HyperMemory| {
+            if m.xi_signature.is_empty() {
+                compute_xi_signature(&m.dense_vector())
+            } else {
+                m.xi_signature.clone()
+            }
+        };
+        let target = self.store.get(id)?.ok_or(StoreError::NotFound(*id))?;
+        let target_xi = xi_of(target);
+
+        let mut neighbors: Vec<(Uuid, f32)> = self.store.all_memories()?
+            .into_iter()
+            .filter(|m| m.id != *id)
+            .map(|m| (m.id, xi_similarity(&target_xi, &xi_of(m))))
+            .collect();
+        neighbors.sort_by(|a, b| b.1.total_cmp(&a.1));
+        neighbors.truncate(top_k);
+        Ok(neighbors)
+    }
+
+    /// Get a memory by id.
+    pub fn get_memory(&self, id: &Uuid) -> Result<Option<&HyperMemory>, EngineError> {
+        Ok(self.store.get(id)?)
+    }
+
+    pub fn similarity_metric(&self) -> SimilarityMetric {
+        self.similarity_metric
+    }
+
+    /// Rank queries by `metric` instead of cosine, in both the store's search
+    /// and the engine's own scoring (including `recall_explained` and the
+    /// semantic term of `recall_blended`). Fails, leaving the metric
+    /// unchanged, if the store does not support it. Skip-link creation and
+    /// consolidation stay cosine-based. The metric is saved with snapshots.
+    pub fn set_similarity_metric(&mut self, metric: SimilarityMetric) -> Result<(), EngineError> {
+        self.store.set_similarity_metric(metric)?;
+        self.similarity_metric = metric;
+        Ok(())
+    }
+
+    /// Mutable access to a memory; marks it dirty if it exists.
+    pub fn get_memory_mut(&mut self, id: &Uuid) -> Result<Option<&mut HyperMemory>, EngineError> {
+        let mem = self.store.get_mut(id)?;
+        if mem.is_some() {
+            self.dirty.insert(*id);
+        }
+        Ok(mem)
+    }
+
+    /// Record that `id` changed outside the engine's own methods (e.g. a
+    /// direct `store` insert), so the next incremental dream revisits it.
+    pub fn mark_dirty(&mut self, id: Uuid) {
+        self.dirty.insert(id);
+    }
+
+    /// Ids inserted or modified since the last `take_dirty`.
+    pub fn dirty(&self) -> &HashSet<Uuid> {
+        &self.dirty
+    }
+
+    /// Drain the dirty set, e.g. to hand to `ConsolidationEngine::consolidate_dirty`.
+    pub fn take_dirty(&mut self) -> HashSet<Uuid> {
+        std::mem::take(&mut self.dirty)
+    }
+
+    pub fn delete(&mut self, id: &Uuid) -> Result<bool, EngineError> {
+        let (tags, hash) = match self.store.get(id)? {
+            Some(mem) => (mem.tags.clone(), stable_content_hash(&mem.content)),
+            None => return Ok(false),
+        };
+        let deleted = self.store.delete(id)?;
+        if deleted {
+            self.dirty.remove(id);
+            if let Some(ids) = self.content_index.get_mut(&hash) {
+                ids.retain(|other| other != id);
+                if ids.is_empty() {
+                    self.content_index.remove(&hash);
+                }
+            }
+            for tag in &tags {
+                if let Some(ids) = self.tag_index.get_mut(tag) {
+                    ids.remove(id);
+                    if ids.is_empty() {
+                        self.tag_index.remove(tag);
                     }
                 }
             }
         }
+        Ok(deleted)
+    }
 
-        // Step 3: Reinforce traversed links
-        for (from_id, to_id) in &links_traversed {
-            self.reinforce_link(from_id, to_id, 0.05);
+    /// Delete all of `ids` and drop skip links in surviving memories that
+    /// pointed at them. Returns the number of memories deleted.
+    pub fn delete_many(&mut self, ids: &[Uuid]) -> Result<usize, EngineError> {
+        let mut deleted = HashSet::new();
+        for id in ids {
+            if self.delete(id)? {
+                deleted.insert(*id);
+            }
+        }
+        if deleted.is_empty() {
+            return Ok(0);
         }
 
-        // Step 4: Re-rank with Xi diversity boosting and return top_k
-        let mut results: Vec<QueryResult> = candidate_scores
+        let survivors: Vec<Uuid> = self.store.all_memories()?
             .into_iter()
-            .map(|(id, combined_score)| {
-                let base_similarity = raw_map.get(&id).copied().unwrap_or(0.0);
-                
-                // Apply Xi diversity boosting
-                let xi_boosted_similarity = if let Ok(Some(mem)) = self.store.get(&id) {
-                    let mem_xi = if mem.xi_signature.is_empty() {
-                        // Compute on-the-fly for backward compatibility
-                        compute_xi_signature(&mem.vector)
-                    } else {
-                        mem.xi_signature.clone()
-                    };
-                    xi_diversity_boost(base_similarity, &query_xi, &mem_xi)
-                } else {
-                    base_similarity
-                };
-                
-                let effective_strength = if base_similarity.abs() > 1e-9 {
-                    combined_score / base_similarity
-                } else {
-                    0.0
-                };
-                
-                QueryResult {
-                    id,
-                    similarity: xi_boosted_similarity,
-                    effective_strength,
-                    combined_score: combined_score * (xi_boosted_similarity / base_similarity.max(1e-9)),
-                }
-            })
+            .filter(|m| m.connections.iter().any(|l| deleted.contains(&l.target_id)))
+            .map(|m| m.id)
             .collect();
+        for id in survivors {
+            if let Some(mem) = self.store.get_mut(&id)? {
+                mem.connections.retain(|l| !deleted.contains(&l.target_id));
+            }
+        }
+        Ok(deleted.len())
+    }
 
-        results.sort_by(|a, b| b.combined_score.total_cmp(&a.combined_score));
-        results.truncate(top_k);
+    /// Replace a memory's tags (duplicates dropped) and update the tag index.
+    pub fn set_tags(&mut self, id: &Uuid, tags: Vec<String>) -> Result<(), EngineError> {
+        let mem = self.store.get_mut(id)?.ok_or(StoreError::NotFound(*id))?;
+        let mut new_tags: Vec<String> = Vec::with_capacity(tags.len());
+        for tag in tags {
+            if !new_tags.contains(&tag) {
+                new_tags.push(tag);
+            }
+        }
+        let old_tags = std::mem::replace(&mut mem.tags, new_tags.clone());
+        mem.touch();
+
+        for tag in &old_tags {
+            if let Some(ids) = self.tag_index.get_mut(tag) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    self.tag_index.remove(tag);
+                }
+            }
+        }
+        for tag in new_tags {
+            self.tag_index.entry(tag).or_default().insert(*id);
+        }
+        Ok(())
+    }
 
-        // EXP-003: Record retrieval events on returned memories (f(x) term)
-        for r in &results {
-            if let Ok(Some(mem)) = self.store.get_mut(&r.id) {
-                mem.record_retrieval();
+    /// Ids of memories carrying every tag in `tags` (all memories if `tags` is empty).
+    pub fn ids_with_tags(&self, tags: &[&str]) -> HashSet<Uuid> {
+        let Some((first, rest)) = tags.split_first() else {
+            return self.store.all_ids().unwrap_or_default().into_iter().collect();
+        };
+        let mut ids = self.tag_index.get(*first).cloned().unwrap_or_default();
+        for tag in rest {
+            match self.tag_index.get(*tag) {
+                Some(tagged) => ids.retain(|id| tagged.contains(id)),
+                None => return HashSet::new(),
             }
         }
+        ids
+    }
 
-        Ok(results)
+    /// Ids of memories whose wave `frequency` lies in `freq_lo..=freq_hi`.
+    pub fn ids_in_band(&self, freq_lo: f32, freq_hi: f32) -> Result<HashSet<Uuid>, EngineError> {
+        Ok(self
+            .store
+            .all_memories()?
+            .into_iter()
+            .filter(|m| (freq_lo..=freq_hi).contains(&m.frequency))
+            .map(|m| m.id)
+            .collect())
     }
 
-    /// Decay all skip link strengths by a factor (0..1).
-    pub fn decay_links(&mut self, decay_factor: f32) {
-        if let Ok(memories) = self.store.all_ids() {
-            for id in memories {
-                if let Ok(Some(mem)) = self.store.get_mut(&id) {
-                    for link in &mut mem.connections {
-                        link.strength *= decay_factor;
-                    }
-                }
-            }
+    /// Ids of memories created at or before `at`, or `None` if that is every
+    /// memory.
+    pub(crate) fn ids_created_by(&self, at: DateTime<Utc>) -> Result<Option<HashSet<Uuid>>, EngineError> {
+        let all = self.store.all_memories()?;
+        if all.iter().all(|m| m.created_at <= at) {
+            return Ok(None);
         }
+        Ok(Some(all.into_iter().filter(|m| m.created_at <= at).map(|m| m.id).collect()))
     }
 
-    /// Reinforce a skip link between two memories.
-    pub fn reinforce_link(&mut self, memory_id: &Uuid, target_id: &Uuid, boost: f32) {
-        if let Ok(Some(mem)) = self.store.get_mut(memory_id) {
-            for link in &mut mem.connections {
-                if link.target_id == *target_id {
-                    link.strength = (link.strength + boost).min(1.0);
-                }
+    /// Rebuild the tag index from the store.
+    pub fn rebuild_tag_index(&mut self) {
+        self.tag_index.clear();
+        for mem in self.store.all_memories().unwrap_or_default() {
+            for tag in &mem.tags {
+                self.tag_index.entry(tag.clone()).or_default().insert(mem.id);
             }
         }
     }
 
-    /// Get a memory by id.
-    pub fn get_memory(&self, id: &Uuid) -> Result<Option<&HyperMemory>, EngineError> {
-        Ok(self.store.get(id)?)
+    /// Rebuild the content-hash index from the store, oldest memory first.
+    pub fn rebuild_content_index(&mut self) {
+        self.content_index.clear();
+        for mem in self.store.all_memories_sorted().unwrap_or_default() {
+            self.content_index.entry(stable_content_hash(&mem.content)).or_default().push(mem.id);
+        }
     }
 
-    pub fn get_memory_mut(&mut self, id: &Uuid) -> Result<Option<&mut HyperMemory>, EngineError> {
-        Ok(self.store.get_mut(id)?)
+    fn index_content(&mut self, text: &str, id: Uuid) {
+        self.content_index.entry(stable_content_hash(text)).or_default().push(id);
     }
 
-    pub fn delete(&mut self, id: &Uuid) -> Result<bool, EngineError> {
-        Ok(self.store.delete(id)?)
+    /// Reset the dirty set to the memories never consolidated, or touched
+    /// (`updated_at`) since their last consolidation.
+    pub fn rebuild_dirty(&mut self) {
+        self.dirty = self.store.all_memories().unwrap_or_default()
+            .into_iter()
+            .filter(|m| m.last_consolidated_at.is_none_or(|at| m.updated_at.is_some_and(|u| u > at)))
+            .map(|m| m.id)
+            .collect();
+    }
+
+    /// Id of the oldest stored memory whose content is exactly `text`, if any.
+    pub fn find_by_content(&self, text: &str) -> Option<Uuid> {
+        self.content_index.get(&stable_content_hash(text))?
+            .iter()
+            .find(|id| matches!(self.store.get(id), Ok(Some(mem)) if mem.content == text))
+            .copied()
+    }
+
+    /// Memories created within `within` of now, newest first, scored by
+    /// exponential time decay `exp(-age / within)` (1.0 for a brand-new
+    /// memory). Independent of any query.
+    pub fn recent_ids(&self, within: chrono::Duration) -> Vec<(Uuid, f32)> {
+        let now = Utc::now();
+        let window = within.num_milliseconds().max(1) as f32;
+        let mut recent: Vec<(Uuid, f32)> = self.store.all_memories()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|m| {
+                let age = (now - m.created_at).num_milliseconds().max(0);
+                (age <= within.num_milliseconds()).then(|| (m.id, (-(age as f32) / window).exp()))
+            })
+            .collect();
+        recent.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        recent
+    }
+
+    /// Delete memories whose TTL has elapsed at `now`, unless their amplitude
+    /// was boosted above their `initial_amplitude`, along with the skip links
+    /// pointing at them. Returns how many were removed.
+    pub fn sweep_expired(&mut self, now: DateTime<Utc>) -> usize {
+        let expired: Vec<Uuid> = self.store.all_memories()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|m| m.is_expired(now) && m.amplitude <= m.initial_amplitude)
+            .map(|m| m.id)
+            .collect();
+        self.delete_many(&expired).unwrap_or(0)
+    }
+
+    /// Remove skip links whose target no longer exists (left behind by deletes
+    /// that bypass `delete_many`). Returns how many links were removed.
+    pub fn prune_dangling_links(&mut self) -> usize {
+        let ids: HashSet<Uuid> = self.store.all_ids().unwrap_or_default().into_iter().collect();
+        let mut sources: Vec<Uuid> = crate::observe::MemoryIntrospector::dangling_links(self)
+            .into_iter()
+            .map(|(source, _)| source)
+            .collect();
+        sources.dedup();
+        let mut removed = 0;
+        for id in sources {
+            if let Ok(Some(mem)) = self.store.get_mut(&id) {
+                let before = mem.connections.len();
+                mem.connections.retain(|l| ids.contains(&l.target_id));
+                removed += before - mem.connections.len();
+            }
+        }
+        removed
     }
 
     /// ADR-0012: Create an immutable snapshot of all memories for parallel dreaming.
@@ -499,6 +1723,22 @@ impl MemoryEngine {
         }
     }
 
+    /// Owned copy of the data read-only analysis needs, for running long
+    /// analytics (Φ, clustering) without holding the engine. Memories keep the
+    /// store's enumeration order so results match the live engine.
+    pub fn analysis_snapshot(&self) -> EngineSnapshot {
+        let memories = self.store.all_memories()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|m| HyperMemory {
+                content: String::new(),
+                tags: Vec::new(),
+                ..m.clone()
+            })
+            .collect();
+        EngineSnapshot { taken_at: Utc::now(), memories }
+    }
+
     /// Phase 8 (ADR-0011): Return Xi-based memory clusters for partitioned dreaming.
     ///
     /// Groups memories by frequency-category (experience / emotion / social / skill / knowledge),
@@ -537,7 +1777,7 @@ impl MemoryEngine {
         buckets
             .into_iter()
             .filter(|(_, ids)| !ids.is_empty())
-            .map(|(_, ids)| {
+            .map(|(cat, ids)| {
                 let phases: Vec<f32> = ids.iter()
                     .filter_map(|id| self.store.get(id).ok().flatten())
                     .map(|m| m.phase)
@@ -559,6 +1799,7 @@ impl MemoryEngine {
                     mean_phase,
                     coherence: order,
                     theme_vector: Vec::new(),
+                    label: cat.to_string(),
                 }
             })
             .collect()
@@ -595,6 +1836,36 @@ mod tests {
 
     // -- InMemoryStore tests --
 
+    #[test]
+    fn all_ids_sorted_is_stable_across_insertion_order() {
+        let t0 = Utc::now();
+        let memories: Vec<HyperMemory> = (0..20)
+            .map(|i| {
+                let mut m = make_memory(unit_vec(8, i % 8), &format!("m{}", i));
+                // Pairs share a timestamp so ordering falls back to id.
+                m.created_at = t0 + Duration::seconds((i / 2) as i64);
+                m
+            })
+            .collect();
+
+        let mut forward = InMemoryStore::new();
+        let mut backward = InMemoryStore::new();
+        for m in &memories {
+            forward.insert(m.clone()).unwrap();
+        }
+        for m in memories.iter().rev() {
+            backward.insert(m.clone()).unwrap();
+        }
+
+        let ids = forward.all_ids_sorted().unwrap();
+        assert_eq!(ids, forward.all_ids_sorted().unwrap());
+        assert_eq!(ids, backward.all_ids_sorted().unwrap());
+        let keys: Vec<_> = ids.iter()
+            .map(|id| (forward.get(id).unwrap().unwrap().created_at, *id))
+            .collect();
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+    }
+
     #[test]
     fn store_insert_get_count() {
         let mut store = InMemoryStore::new();
@@ -769,6 +2040,175 @@ mod tests {
         assert!(mem2.connections.is_empty(), "same-layer memories should not be linked");
     }
 
+    #[test]
+    fn reencode_switches_model_and_keeps_recall_working() {
+        let mut engine = MemoryEngine::new(Box::new(crate::hnsw::HnswStore::new()), make_pipeline());
+        let texts = ["the cat sat on the mat", "stock prices fell sharply", "rain over the harbour"];
+        let ids: Vec<Uuid> = texts.iter().map(|t| engine.remember(t).unwrap()).collect();
+        let mut audio = make_memory(unit_vec(10_000, 3), "audio:bark.wav");
+        audio.source = MemorySource::Audio;
+        let audio_id = engine.store.insert(audio).unwrap();
+        let created = engine.get_memory(&ids[1]).unwrap().unwrap().created_at;
+
+        let new_pipeline = EncodingPipeline::new(
+            Box::new(SimpleHashEncoder::new(384, 7)),
+            Codebook::new(384, 10_000, 99),
+        );
+        let expected = new_pipeline.encode_text(texts[1]).unwrap();
+        assert_eq!(engine.reencode(new_pipeline).unwrap(), 3);
+
+        let mem = engine.get_memory(&ids[1]).unwrap().unwrap();
+        assert_eq!(mem.dense_vector(), expected);
+        assert_eq!(mem.created_at, created);
+        assert_eq!(mem.xi_signature, compute_xi_signature(&expected));
+        assert_eq!(engine.get_memory(&audio_id).unwrap().unwrap().dense_vector(), unit_vec(10_000, 3));
+
+        for (text, id) in texts.iter().zip(&ids) {
+            // Wave strength reorders close hash-encoder matches, so check the
+            // raw similarity rather than the rank.
+            let hit = engine.recall(text, 4).unwrap().into_iter().find(|r| r.id == *id).unwrap();
+            assert!(hit.similarity > 0.999, "similarity {}", hit.similarity);
+        }
+    }
+
+    #[test]
+    fn recall_iter_matches_recall() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        engine.similarity_threshold = 0.99;
+        for i in 0..40 {
+            let id = engine.remember(&format!("harbor log entry {} about tides and ships", i)).unwrap();
+            // Flat, undecaying waves: recording retrievals then scales every
+            // hit equally, so the two calls see the same ranking.
+            let mem = engine.store.get_mut(&id).unwrap().unwrap();
+            mem.amplitude = 1.0;
+            mem.frequency = 0.0;
+            mem.phase = 0.0;
+            mem.decay_rate = 0.0;
+        }
+
+        let streamed: Vec<Uuid> = engine.recall_iter("tides and ships", 5).unwrap().map(|r| r.id).collect();
+        let collected: Vec<Uuid> = engine.recall("tides and ships", 5).unwrap().into_iter().map(|r| r.id).collect();
+        assert_eq!(streamed.len(), 5);
+        assert_eq!(streamed, collected);
+        assert_eq!(engine.recall_iter("tides", 0).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn recall_in_band_only_ranks_in_band_memories() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let mut in_band = HashSet::new();
+        for (i, freq) in [0.7f32, 0.9, 1.1, 1.5, 2.0, 2.2].into_iter().enumerate() {
+            let id = engine.remember(&format!("notes on sailing knots, entry {}", i)).unwrap();
+            let mem = engine.store.get_mut(&id).unwrap().unwrap();
+            mem.frequency = freq;
+            mem.phase = 0.0;
+            if (0.8..=1.2).contains(&freq) {
+                in_band.insert(id);
+            }
+        }
+
+        let results = engine.recall_in_band("sailing knots", 10, 0.8, 1.2).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| in_band.contains(&r.id)));
+        assert!(engine.recall_in_band("sailing knots", 10, 3.0, 4.0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn symmetrize_links_equalizes_asymmetric_pairs() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let a = engine.store.insert(make_memory(vec![1.0; 50], "a")).unwrap();
+        let b = engine.store.insert(make_memory(vec![1.0; 50], "b")).unwrap();
+        let c = engine.store.insert(make_memory(vec![1.0; 50], "c")).unwrap();
+        let link = |target_id, strength| SkipLink { target_id, strength, resonance_key: vec![0.5], span: 1 };
+        engine.store.get_mut(&a).unwrap().unwrap().connections.extend([link(b, 0.8), link(c, 0.4)]);
+        engine.store.get_mut(&b).unwrap().unwrap().connections.push(link(a, 0.2));
+
+        let asymmetries = engine.link_consistency_check();
+        assert_eq!(asymmetries.len(), 2);
+        assert!(asymmetries.iter().any(|x| x.source == a && x.target == c && x.backward.is_none()));
+
+        assert_eq!(engine.symmetrize_links(), 2);
+        let strength = |from: Uuid, to: Uuid| {
+            engine.get_memory(&from).unwrap().unwrap().connections.iter()
+                .find(|l| l.target_id == to).map(|l| l.strength)
+        };
+        assert_eq!(strength(a, b), Some(0.5));
+        assert_eq!(strength(b, a), Some(0.5));
+        assert_eq!(strength(c, a), Some(0.4));
+        assert!(engine.link_consistency_check().is_empty());
+        assert_eq!(engine.symmetrize_links(), 0);
+    }
+
+    #[test]
+    fn recall_tracks_retrieval_count_and_time() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let id = engine.remember("the lighthouse keeper's logbook").unwrap();
+        assert_eq!(engine.get_memory(&id).unwrap().unwrap().last_accessed, None);
+
+        engine.recall("lighthouse logbook", 1).unwrap();
+        let first = engine.get_memory(&id).unwrap().unwrap().last_accessed.unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        engine.recall_with_expansion("lighthouse logbook", 1).unwrap();
+
+        let mem = engine.get_memory(&id).unwrap().unwrap();
+        assert_eq!(mem.retrieval_count, 2);
+        assert!(mem.last_accessed.unwrap() > first);
+    }
+
+    #[test]
+    fn recall_blended_trades_cosine_for_geometry() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let query = "the capital of france is paris";
+        let qvec = engine.pipeline.encode_text(query).unwrap();
+        let query_geom = classify_memory(categorize_text(query), stable_content_hash(query), 0.5);
+
+        // Same class as the query, but semantically unrelated.
+        let mut same_class = make_memory(engine.pipeline.encode_text("stock prices fell sharply").unwrap(), "same class");
+        same_class.geometry = Some(query_geom.clone());
+        // Cosine-identical to the query, but in another class.
+        let mut other_class = make_memory(qvec.clone(), "other class");
+        other_class.geometry = Some(classify_memory("emotion", stable_content_hash("other"), 0.5));
+        let created = Utc::now();
+        same_class.created_at = created;
+        other_class.created_at = created;
+        let same_id = engine.store.insert(same_class).unwrap();
+        let other_id = engine.store.insert(other_class).unwrap();
+
+        assert_eq!(engine.recall_blended(query, 2).unwrap()[0].id, other_id);
+        engine.similarity_blend = 1.0;
+        let results = engine.recall_blended(query, 2).unwrap();
+        assert_eq!(results[0].id, same_id);
+        assert!(results[0].similarity > results[1].similarity);
+    }
+
+    #[test]
+    fn recall_by_resonance_returns_targets_of_matching_links() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        engine.similarity_threshold = -1.0; // link every cross-layer pair
+
+        let a = engine.remember_at_layer("the cat sat on the mat", 0).unwrap();
+        let b = engine.remember_at_layer("stock prices fell sharply", 1).unwrap();
+        let c = engine.remember_at_layer("a kitten naps on the rug", 2).unwrap();
+
+        let mem_c = engine.get_memory(&c).unwrap().unwrap();
+        assert!(mem_c.connections.iter().all(|l| l.resonance_key.len() == crate::skip_link::RESONANCE_KEY_DIM));
+        let key = mem_c.connections.iter().find(|l| l.target_id == a).unwrap().resonance_key.clone();
+        let reverse = engine.get_memory(&a).unwrap().unwrap()
+            .connections.iter().find(|l| l.target_id == c).unwrap().resonance_key.clone();
+        assert_eq!(key, reverse, "forward and reverse links share a key");
+
+        // The a–c relationship is carried by exactly the links a→c and c→a.
+        let results = engine.recall_by_resonance(&key, 2).unwrap();
+        let mut ids: Vec<Uuid> = results.iter().map(|r| r.id).collect();
+        ids.sort();
+        let mut expected = vec![a, c];
+        expected.sort();
+        assert_eq!(ids, expected);
+        assert!(results.iter().all(|r| r.similarity > 0.999));
+        assert!(engine.recall_by_resonance(&key, 3).unwrap().iter().any(|r| r.id == b));
+        assert!(engine.recall_by_resonance(&[], 3).is_err());
+    }
+
     #[test]
     fn recall_with_expansion_finds_linked_memories() {
         let store = InMemoryStore::new();
@@ -786,6 +2226,170 @@ mod tests {
         assert!(results.len() >= 2, "expansion should find multiple linked memories, got {}", results.len());
     }
 
+    #[test]
+    fn recall_with_expansion_follows_multiple_hops() {
+        use rand::{Rng, SeedableRng};
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let qvec = engine.pipeline.encode_text("harbour chain").unwrap();
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(3);
+        // Unit vector with cosine ≈ `sim` to the query.
+        let mut toward = |sim: f32| -> Vec<f32> {
+            let mut v: Vec<f32> = qvec.iter().map(|&q| sim * q + (1.0 - sim * sim).sqrt() * (rng.gen::<f32>() - 0.5) * 0.035).collect();
+            crate::wave::normalize(&mut v);
+            v
+        };
+        let insert = |engine: &mut MemoryEngine, vector: Vec<f32>, amplitude: f32| -> Uuid {
+            let mut mem = HyperMemory::new(vector, "chain".to_string());
+            mem.frequency = 0.0;
+            mem.phase = 0.0;
+            mem.amplitude = amplitude;
+            engine.store.insert(mem).unwrap()
+        };
+        // A is the only strong direct hit; B and C have faded, so only links reach them.
+        let a = insert(&mut engine, toward(1.0), 1.0);
+        let b = insert(&mut engine, toward(0.9), 0.01);
+        let c = insert(&mut engine, toward(0.9), 0.01);
+        // Enough distractors to fill the initial top_k * 3 candidates.
+        for _ in 0..10 {
+            insert(&mut engine, toward(0.3), 1.0);
+        }
+        for (from, to) in [(a, b), (b, c)] {
+            engine.store.get_mut(&from).unwrap().unwrap().connections.push(SkipLink {
+                target_id: to,
+                strength: 1.0,
+                resonance_key: Vec::new(),
+                span: 1,
+            });
+        }
+
+        let ids = |results: Vec<QueryResult>| -> Vec<Uuid> { results.iter().map(|r| r.id).collect() };
+        let one_hop = ids(engine.recall_with_expansion("harbour chain", 3).unwrap());
+        assert!(one_hop.contains(&a) && one_hop.contains(&b));
+        assert!(!one_hop.contains(&c), "C is two hops away");
+
+        let two_hops = ids(engine.recall_with_expansion_hops("harbour chain", 3, 2).unwrap());
+        assert!(two_hops.contains(&a) && two_hops.contains(&b) && two_hops.contains(&c));
+
+        engine.expansion_hops = 2;
+        assert!(ids(engine.recall_with_expansion("harbour chain", 3).unwrap()).contains(&c));
+    }
+
+    #[test]
+    fn euclidean_metric_reorders_vectors_with_differing_magnitudes() {
+        let axis = |i: usize, scale: f32| -> Vec<f32> {
+            let mut v = vec![0.0; 10_000];
+            v[i] = scale;
+            v
+        };
+        let query = axis(0, 1.0);
+        // `long` points almost exactly along the query but is ten times longer;
+        // `short` is further off-axis but sits close to the query in space.
+        let mut long = axis(0, 10.0);
+        long[1] = 1.0;
+        let mut short = axis(0, 0.5);
+        short[1] = 0.3;
+
+        let stores: Vec<Box<dyn MemoryStore>> = vec![Box::new(InMemoryStore::new()), Box::new(crate::hnsw::HnswStore::new())];
+        for store in stores {
+            let mut engine = MemoryEngine::new(store, make_pipeline());
+            engine.xi_boost_factor = 0.0;
+            let insert = |engine: &mut MemoryEngine, vector: Vec<f32>| -> Uuid {
+                let mut mem = make_memory(vector, "m");
+                mem.frequency = 0.0;
+                mem.phase = 0.0;
+                engine.store.insert(mem).unwrap()
+            };
+            let long_id = insert(&mut engine, long.clone());
+            let short_id = insert(&mut engine, short.clone());
+            // Enough filler for HnswStore to search its graph rather than
+            // brute-force: unit vectors at graded angles of 40°–88° from the
+            // query, further off by every metric than either vector above.
+            for i in 0..120 {
+                let angle = (40.0 + 0.4 * i as f32).to_radians();
+                let mut filler = axis(3 + i, angle.sin());
+                filler[0] = angle.cos();
+                insert(&mut engine, filler);
+            }
+
+            assert_eq!(engine.recall_vector(&query, 1).unwrap()[0].id, long_id);
+            engine.set_similarity_metric(SimilarityMetric::Euclidean).unwrap();
+            assert_eq!(engine.similarity_metric(), SimilarityMetric::Euclidean);
+            assert_eq!(engine.recall_vector(&query, 1).unwrap()[0].id, short_id);
+            assert_eq!(engine.store.search(&query, 1).unwrap()[0].0, short_id);
+            engine.set_similarity_metric(SimilarityMetric::Dot).unwrap();
+            assert_eq!(engine.recall_vector(&query, 1).unwrap()[0].id, long_id);
+        }
+    }
+
+    #[test]
+    fn explained_and_blended_recall_use_the_engine_metric() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let qvec = engine.pipeline.encode_text("harbour survey").unwrap();
+        // Parallel to the query but twice as long: cosine 1, Euclidean not.
+        let mut mem = make_memory(qvec.iter().map(|x| x * 2.0).collect(), "harbour survey");
+        mem.frequency = 0.0;
+        mem.phase = 0.0;
+        mem.geometry = None;
+        let id = engine.store.insert(mem).unwrap();
+        engine.set_similarity_metric(SimilarityMetric::Euclidean).unwrap();
+        let expected = engine.get_memory(&id).unwrap().unwrap().similarity_by(SimilarityMetric::Euclidean, &qvec);
+        assert!(expected < 0.99);
+
+        let blended = engine.recall_blended("harbour survey", 1).unwrap();
+        assert!((blended[0].similarity - expected).abs() < 1e-6);
+        let explained = engine.recall_explained("harbour survey", 1).unwrap();
+        assert!((explained[0].base_cosine - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn recall_explained_components_combine_to_final_score() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        engine.similarity_threshold = 0.3;
+        let faded = engine.remember_at_layer("the cat sat on the mat", 0).unwrap();
+        engine.remember_at_layer("the cat sat on the mat yesterday", 2).unwrap();
+        engine.remember_at_layer("a dog chased the ball", 0).unwrap();
+        engine.store.get_mut(&faded).unwrap().unwrap().amplitude = 0.05;
+
+        let results = engine.recall_explained("cat mat", 10).unwrap();
+        assert!(!results.is_empty());
+        for r in &results {
+            let combined = r.base_cosine * r.wave_strength.max(r.link_strength) * r.xi_boost;
+            assert!((combined - r.final_score).abs() < 1e-5, "{:?}", r);
+            assert!(r.link_strength >= 0.0 && r.wave_strength >= 0.0);
+        }
+        assert!(results.windows(2).all(|w| w[0].final_score >= w[1].final_score));
+
+        let faded_hit = results.iter().find(|r| r.id == faded).expect("faded memory is linked");
+        assert!(faded_hit.link_strength > faded_hit.wave_strength, "link should lift the faded memory: {:?}", faded_hit);
+    }
+
+    #[test]
+    fn recall_explained_matches_recall_with_expansion() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        engine.similarity_threshold = 0.3;
+        engine.expansion_hops = 2;
+        let faded = engine.remember_at_layer("the cat sat on the mat", 0).unwrap();
+        engine.remember_at_layer("the cat sat on the mat yesterday", 2).unwrap();
+        engine.remember_at_layer("the cat sat on the mat last week", 3).unwrap();
+        engine.remember_at_layer("a dog chased the ball", 0).unwrap();
+        // Flat, undecaying waves so the two calls see the same strengths.
+        for id in engine.store.all_ids().unwrap() {
+            let mem = engine.store.get_mut(&id).unwrap().unwrap();
+            mem.frequency = 0.0;
+            mem.phase = 0.0;
+            mem.decay_rate = 0.0;
+        }
+        engine.store.get_mut(&faded).unwrap().unwrap().amplitude = 0.05;
+
+        let explained = engine.recall_explained("cat mat", 10).unwrap();
+        let recalled = engine.recall_with_expansion("cat mat", 10).unwrap();
+        assert_eq!(explained.len(), recalled.len());
+        for (e, r) in explained.iter().zip(&recalled) {
+            assert_eq!(e.id, r.id);
+            assert!((e.final_score - r.combined_score).abs() < 1e-6, "{:?} vs {:?}", e, r);
+        }
+    }
+
     #[test]
     fn link_reinforcement_increases_strength() {
         let store = InMemoryStore::new();
@@ -832,6 +2436,400 @@ mod tests {
         );
     }
 
+    #[test]
+    fn recall_on_fano_line_filters_to_channel() {
+        use crate::geometry::MemoryCoordinates;
+
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let coords = |h2: u8, d: u8, l: u8| MemoryCoordinates {
+            h2, d, l, class_index: 0, amplitude: 0.5, phase: 0.0,
+        };
+
+        let mut on_line = Vec::new();
+        for (text, l) in [("cat on the mat", 1u8), ("cat near the mat", 2), ("cat by the mat", 4)] {
+            let id = engine.remember(text).unwrap();
+            engine.get_memory_mut(&id).unwrap().unwrap().geometry = Some(coords(0, 1, l));
+            on_line.push(id);
+        }
+        // Off the line, different channel, and no geometry at all
+        let off = engine.remember("cat under the mat").unwrap();
+        engine.get_memory_mut(&off).unwrap().unwrap().geometry = Some(coords(0, 1, 3));
+        let other_quadrant = engine.remember("quantum entanglement in photonic lattices").unwrap();
+        engine.get_memory_mut(&other_quadrant).unwrap().unwrap().geometry = Some(coords(2, 1, 1));
+        let no_geometry = engine.remember("cat on a mat").unwrap();
+
+        let results = engine.recall_on_fano_line("cat on the mat", [1, 2, 4], 10).unwrap();
+        let ids: Vec<Uuid> = results.iter().map(|r| r.id).collect();
+        assert_eq!(ids.len(), 3);
+        for id in &on_line {
+            assert!(ids.contains(id));
+        }
+        assert!(!ids.contains(&off));
+        assert!(!ids.contains(&other_quadrant));
+        assert!(!ids.contains(&no_geometry));
+    }
+
+    #[test]
+    fn recall_on_fano_line_rejects_invalid_line() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        engine.remember("anything").unwrap();
+        let err = engine.recall_on_fano_line("anything", [1, 2, 3], 5);
+        assert!(matches!(err, Err(EngineError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn recall_at_evaluates_strength_at_timestamp() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let now = Utc::now();
+        let last_week = now - chrono::Duration::days(7);
+
+        // Fast-fading memory from eight days ago, and a stable one from yesterday.
+        let old = engine.remember("harbour walk at dawn").unwrap();
+        let fresh = engine.remember("harbour walk at dusk").unwrap();
+        for (id, created, decay_rate) in [(old, now - chrono::Duration::days(8), 1e-5), (fresh, now - chrono::Duration::days(1), 1e-6)] {
+            let mem = engine.get_memory_mut(&id).unwrap().unwrap();
+            mem.created_at = created;
+            mem.frequency = 0.0;
+            mem.decay_rate = decay_rate;
+        }
+
+        let then = engine.recall_at("harbour walk", 2, last_week).unwrap();
+        assert_eq!(then.len(), 1, "memories created after `at` are excluded");
+        assert_eq!(then[0].id, old);
+
+        let today = engine.recall_at("harbour walk", 2, now).unwrap();
+        assert_eq!(today[0].id, fresh);
+        let old_today = today.iter().find(|r| r.id == old).unwrap();
+        assert!(old_today.effective_strength < then[0].effective_strength);
+
+        let far_future = engine.recall_at("harbour walk", 2, now + chrono::Duration::days(3650)).unwrap();
+        assert!(far_future.iter().all(|r| r.effective_strength >= 0.0));
+    }
+
+    #[test]
+    fn recall_at_is_not_crowded_out_by_newer_memories() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let now = Utc::now();
+        let old = engine.remember("harbour walk in the rain").unwrap();
+        engine.get_memory_mut(&old).unwrap().unwrap().created_at = now - chrono::Duration::days(7);
+        // Newer memories that match the query better than the old one.
+        for i in 0..10 {
+            engine.remember(&format!("harbour walk {}", i)).unwrap();
+        }
+
+        let then = engine.recall_at("harbour walk", 2, now - chrono::Duration::days(1)).unwrap();
+        assert_eq!(then.len(), 1);
+        assert_eq!(then[0].id, old);
+    }
+
+    #[test]
+    fn recall_vector_finds_stored_memory() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        engine.remember("tide pools at dawn").unwrap();
+        let id = engine.remember("a violin concerto in D minor").unwrap();
+        engine.remember("compiler register allocation").unwrap();
+        let qvec = engine.get_memory(&id).unwrap().unwrap().dense_vector().into_owned();
+
+        let results = engine.recall_vector(&qvec, 3).unwrap();
+        assert_eq!(results[0].id, id);
+        assert!(matches!(
+            engine.recall_vector(&qvec[..100], 3),
+            Err(EngineError::DimensionMismatch { expected: 10_000, got: 100 })
+        ));
+    }
+
+    #[test]
+    fn find_xi_neighbors_ranks_shared_signature_first() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let a = engine.remember("sailing across the bay").unwrap();
+        let b = engine.remember("tax forms due in april").unwrap();
+        let c = engine.remember("an unrelated note on birds").unwrap();
+        // remember() leaves xi_signature empty; give a and b the same one
+        let shared = compute_xi_signature(&engine.get_memory(&a).unwrap().unwrap().dense_vector());
+        engine.get_memory_mut(&a).unwrap().unwrap().xi_signature = shared.clone();
+        engine.get_memory_mut(&b).unwrap().unwrap().xi_signature = shared;
+
+        let neighbors = engine.find_xi_neighbors(&a, 5).unwrap();
+        assert_eq!(neighbors.len(), 2);
+        assert_eq!(neighbors[0].0, b);
+        assert!((neighbors[0].1 - 1.0).abs() < 1e-6);
+        assert_eq!(neighbors[1].0, c);
+        assert!(neighbors[1].1 < neighbors[0].1);
+        assert!(matches!(
+            engine.find_xi_neighbors(&Uuid::new_v4(), 5),
+            Err(EngineError::Store(StoreError::NotFound(_)))
+        ));
+    }
+
+    #[test]
+    fn merge_duplicates_collapses_copies_and_keeps_links() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let copies: Vec<Uuid> = (0..3)
+            .map(|_| engine.remember("the meeting moved to thursday").unwrap())
+            .collect();
+        let other = engine.store.insert(make_memory(unit_vec(10_000, 5), "unrelated")).unwrap();
+        let link = |target: Uuid, strength: f32| SkipLink { target_id: target, strength, resonance_key: vec![], span: 1 };
+
+        engine.get_memory_mut(&copies[0]).unwrap().unwrap().amplitude = 1.5;
+        {
+            let dup = engine.get_memory_mut(&copies[1]).unwrap().unwrap();
+            dup.connections.push(link(other, 0.6));
+            dup.connections.push(link(copies[0], 0.9)); // would become a self-link
+        }
+        engine.get_memory_mut(&other).unwrap().unwrap().connections.push(link(copies[2], 0.4));
+
+        assert_eq!(engine.merge_duplicates(0.95), 2);
+        assert_eq!(engine.store.count(), 2);
+        let survivor = engine.get_memory(&copies[0]).unwrap().unwrap();
+        assert_eq!(survivor.connections.len(), 1);
+        assert_eq!(survivor.connections[0].target_id, other);
+        let other_mem = engine.get_memory(&other).unwrap().unwrap();
+        assert_eq!(other_mem.connections.len(), 1);
+        assert_eq!(other_mem.connections[0].target_id, copies[0]);
+    }
+
+    #[test]
+    fn update_vector_replaces_vector_and_checks_dimension() {
+        let mut engine = MemoryEngine::new(Box::new(crate::hnsw::HnswStore::new()), make_pipeline());
+        let id = engine.remember("vector to re-encode").unwrap();
+        let mut new_v: Vec<f32> = (0..10_000).map(|i| if i % 3 == 0 { 1.0 } else { -0.5 }).collect();
+        normalize(&mut new_v);
+
+        engine.update_vector(&id, new_v.clone()).unwrap();
+        let mem = engine.get_memory(&id).unwrap().unwrap();
+        assert_eq!(mem.dense_vector(), new_v);
+        assert_eq!(mem.xi_signature, compute_xi_signature(&new_v));
+        assert_eq!(engine.recall_vector(&new_v, 1).unwrap()[0].id, id);
+
+        assert!(matches!(
+            engine.update_vector(&id, vec![1.0; 3]),
+            Err(EngineError::DimensionMismatch { expected: 10_000, got: 3 })
+        ));
+        assert!(matches!(
+            engine.update_vector(&Uuid::new_v4(), new_v),
+            Err(EngineError::Store(StoreError::NotFound(_)))
+        ));
+    }
+
+    #[test]
+    fn calibrate_threshold_tracks_target_density() {
+        use rand::{Rng, SeedableRng};
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(3);
+        let mut vectors = Vec::new();
+        for i in 0..40 {
+            let mut v: Vec<f32> = (0..64).map(|_| rng.gen::<f32>() - 0.5).collect();
+            normalize(&mut v);
+            vectors.push(v.clone());
+            engine.store.insert(HyperMemory::new(v, format!("fixture {}", i))).unwrap();
+        }
+
+        let sparse = engine.calibrate_threshold(0.1).unwrap();
+        assert_eq!(engine.similarity_threshold, sparse);
+        let dense = engine.calibrate_threshold(0.5).unwrap();
+        assert!(sparse > dense, "density 0.1 should need a higher threshold: {} vs {}", sparse, dense);
+
+        // The chosen threshold yields roughly the requested density over all 780 pairs.
+        let mut above = 0;
+        for i in 0..vectors.len() {
+            for j in (i + 1)..vectors.len() {
+                if cosine_similarity(&vectors[i], &vectors[j]) > sparse {
+                    above += 1;
+                }
+            }
+        }
+        assert!((above as f32 / 780.0 - 0.1).abs() < 0.01, "{} pairs above", above);
+
+        assert!(matches!(engine.calibrate_threshold(0.0), Err(EngineError::InvalidQuery(_))));
+        assert!(matches!(engine.calibrate_threshold(1.5), Err(EngineError::InvalidQuery(_))));
+    }
+
+    #[test]
+    fn tag_index_consistent_after_delete_and_retag() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let a = engine.remember("tagged alpha").unwrap();
+        let b = engine.remember("tagged beta").unwrap();
+        engine.set_tags(&a, vec!["project:x".into(), "user:bob".into(), "project:x".into()]).unwrap();
+        engine.set_tags(&b, vec!["project:x".into()]).unwrap();
+        assert_eq!(engine.get_memory(&a).unwrap().unwrap().tags, vec!["project:x", "user:bob"]);
+        assert_eq!(engine.ids_with_tags(&["project:x"]), HashSet::from([a, b]));
+
+        assert!(engine.delete(&a).unwrap());
+        assert_eq!(engine.ids_with_tags(&["project:x"]), HashSet::from([b]));
+        assert!(engine.ids_with_tags(&["user:bob"]).is_empty());
+        assert!(!engine.tag_index.contains_key("user:bob"));
+
+        engine.set_tags(&b, vec!["project:y".into()]).unwrap();
+        assert!(!engine.tag_index.contains_key("project:x"));
+        let results = engine.recall_with_tags("tagged", 5, &["project:y"]).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, b);
+    }
+
+    #[test]
+    fn insert_memory_updates_tag_index() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let mut mem = HyperMemory::new(engine.pipeline.encode_text("prebuilt").unwrap(), "prebuilt".to_string());
+        mem.tags = vec!["source:audio".to_string()];
+        let id = engine.insert_memory(mem).unwrap();
+        assert_eq!(engine.ids_with_tags(&["source:audio"]), HashSet::from([id]));
+        assert_eq!(engine.find_by_content("prebuilt"), Some(id));
+        assert!(engine.dirty().contains(&id));
+    }
+
+    #[test]
+    fn find_by_content_tells_colliding_hashes_apart() {
+        assert_eq!(stable_content_hash("Aa"), stable_content_hash("BB"));
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let aa = engine.remember("Aa").unwrap();
+        let bb = engine.remember("BB").unwrap();
+        assert_eq!(engine.find_by_content("Aa"), Some(aa));
+        assert_eq!(engine.find_by_content("BB"), Some(bb));
+
+        engine.delete(&aa).unwrap();
+        assert_eq!(engine.find_by_content("Aa"), None);
+        assert_eq!(engine.find_by_content("BB"), Some(bb));
+        engine.rebuild_content_index();
+        assert_eq!(engine.find_by_content("BB"), Some(bb));
+    }
+
+    #[test]
+    fn new_engine_rebuilds_dirty_set_from_store() {
+        let mut store = InMemoryStore::new();
+        let pipeline = make_pipeline();
+        let mut fresh = HyperMemory::new(pipeline.encode_text("never dreamed").unwrap(), "never dreamed".into());
+        let mut settled = HyperMemory::new(pipeline.encode_text("already dreamed").unwrap(), "already dreamed".into());
+        let mut edited = HyperMemory::new(pipeline.encode_text("edited after").unwrap(), "edited after".into());
+        let at = Utc::now() - Duration::minutes(5);
+        fresh.updated_at = Some(at);
+        settled.last_consolidated_at = Some(at);
+        edited.last_consolidated_at = Some(at);
+        edited.touch();
+        let ids = [fresh.id, settled.id, edited.id];
+        store.insert_batch(vec![fresh, settled, edited]).unwrap();
+
+        let engine = MemoryEngine::new(Box::new(store), pipeline);
+        assert_eq!(engine.dirty(), &HashSet::from([ids[0], ids[2]]));
+    }
+
+    #[test]
+    fn sweep_expired_removes_from_index() {
+        let mut engine = MemoryEngine::new(Box::new(crate::hnsw::HnswStore::new()), make_pipeline());
+        let keep = engine.remember("the lighthouse keeper's log").unwrap();
+        let gone = engine.remember("a fleeting grocery reminder").unwrap();
+        {
+            let mem = engine.get_memory_mut(&gone).unwrap().unwrap();
+            mem.created_at = Utc::now() - Duration::hours(2);
+            mem.ttl = Some(Duration::hours(1));
+        }
+
+        assert_eq!(engine.sweep_expired(Utc::now()), 1);
+        assert!(engine.get_memory(&gone).unwrap().is_none());
+        assert!(engine.get_memory(&keep).unwrap().is_some());
+        let hits = engine.recall("a fleeting grocery reminder", 5).unwrap();
+        assert!(hits.iter().all(|r| r.id != gone));
+    }
+
+    #[test]
+    fn sweep_expired_keeps_boosted_memory() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let id = engine.remember("an old but cherished memory").unwrap();
+        {
+            let mem = engine.get_memory_mut(&id).unwrap().unwrap();
+            mem.created_at = Utc::now() - Duration::days(30);
+            mem.ttl = Some(Duration::days(1));
+            mem.amplitude = 1.5;
+        }
+
+        assert_eq!(engine.sweep_expired(Utc::now()), 0);
+        assert!(engine.get_memory(&id).unwrap().is_some());
+
+        // Boosted above its own, lower starting amplitude but still below the
+        // default one: kept too.
+        let mut quiet = make_memory(engine.pipeline.encode_text("a quiet hunch").unwrap(), "a quiet hunch");
+        quiet.amplitude = 0.3;
+        let quiet = engine.insert_memory(quiet).unwrap();
+        {
+            let mem = engine.get_memory_mut(&quiet).unwrap().unwrap();
+            mem.created_at = Utc::now() - Duration::days(30);
+            mem.ttl = Some(Duration::days(1));
+            mem.amplitude = 0.6;
+        }
+        assert_eq!(engine.sweep_expired(Utc::now()), 0);
+        engine.get_memory_mut(&quiet).unwrap().unwrap().amplitude = 0.3;
+        assert_eq!(engine.sweep_expired(Utc::now()), 1);
+    }
+
+    #[test]
+    fn sweep_expired_drops_links_to_swept_memories() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        engine.similarity_threshold = 0.3;
+        let keep = engine.remember_at_layer("the cat sat on the mat", 0).unwrap();
+        let gone = engine.remember_at_layer("the cat sat on the mat yesterday", 2).unwrap();
+        assert!(engine.get_memory(&keep).unwrap().unwrap().connections.iter().any(|l| l.target_id == gone));
+        {
+            let mem = engine.get_memory_mut(&gone).unwrap().unwrap();
+            mem.created_at = Utc::now() - Duration::hours(2);
+            mem.ttl = Some(Duration::hours(1));
+        }
+
+        assert_eq!(engine.sweep_expired(Utc::now()), 1);
+        assert!(engine.get_memory(&keep).unwrap().unwrap().connections.iter().all(|l| l.target_id != gone));
+    }
+
+    #[test]
+    fn recent_ids_decay_with_age_and_ignore_the_query() {
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let fresh = engine.remember("just now").unwrap();
+        let hour_old = engine.remember("an hour ago").unwrap();
+        let stale = engine.remember("last month").unwrap();
+        engine.get_memory_mut(&hour_old).unwrap().unwrap().created_at = Utc::now() - Duration::hours(1);
+        engine.get_memory_mut(&stale).unwrap().unwrap().created_at = Utc::now() - Duration::days(30);
+
+        let recent = engine.recent_ids(Duration::hours(24));
+        let ids: Vec<Uuid> = recent.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, vec![fresh, hour_old]);
+        assert!(recent[0].1 > 0.99);
+        assert!((recent[1].1 - (-1.0f32 / 24.0).exp()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn xi_boost_factor_zero_ranks_by_raw_similarity() {
+        let dim = 10_000;
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), make_pipeline());
+        let query = unit_vec(dim, 0);
+        let query_xi = compute_xi_signature(&query);
+        // Three candidates at cos 0.9 / 0.85 / 0.8 to the query; only the
+        // weakest carries a distant Ξ signature, so the boost lifts it to the top.
+        for (i, cos) in [0.9f32, 0.85, 0.8].into_iter().enumerate() {
+            let mut v = unit_vec(dim, i + 1);
+            v.iter_mut().for_each(|x| *x *= (1.0 - cos * cos).sqrt());
+            v[0] = cos;
+            let mut mem = make_memory(v, &format!("cos {}", cos));
+            mem.xi_signature = if cos < 0.85 {
+                query_xi.iter().map(|x| -x).collect()
+            } else {
+                query_xi.clone()
+            };
+            engine.store.insert(mem).unwrap();
+        }
+
+        let raw: Vec<Uuid> = engine.store.search(&query, 3).unwrap().into_iter().map(|(id, _)| id).collect();
+        let ranked = |engine: &MemoryEngine| -> Vec<Uuid> {
+            engine.recall_vector(&query, 3).unwrap().into_iter().map(|r| r.id).collect()
+        };
+
+        assert_eq!(engine.xi_boost_factor, DEFAULT_XI_BOOST_FACTOR);
+        let boosted = ranked(&engine);
+        assert_ne!(boosted, raw);
+        assert_eq!(boosted[0], raw[2]);
+
+        engine.xi_boost_factor = 0.0;
+        assert_eq!(ranked(&engine), raw);
+        let results = engine.recall_vector(&query, 3).unwrap();
+        assert!((results[0].similarity - 0.9).abs() < 1e-4);
+    }
+
     #[test]
     fn phi_span_scoring() {
         // φ^1 ≈ 1.618, φ^2 ≈ 2.618, φ^3 ≈ 4.236, φ^4 ≈ 6.854, φ^5 ≈ 11.09
@@ -43,6 +43,15 @@ pub fn compute_strength_with_retrieval(params: &WaveParams, age_seconds: f64, re
     (a * wave * decay) as f32
 }
 
+/// Normalize a phase to `[0, 2π)`. Apply after every phase mutation so
+/// repeated nudges don't grow phases unboundedly and erode f32 precision.
+pub fn wrap_phase(phase: f32) -> f32 {
+    let tau = std::f32::consts::TAU;
+    let wrapped = phase.rem_euclid(tau);
+    // rem_euclid can round up to exactly τ for tiny negative inputs.
+    if wrapped >= tau { 0.0 } else { wrapped }
+}
+
 /// Cosine similarity between two vectors.
 pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.is_empty() || b.is_empty() {
@@ -54,25 +63,281 @@ pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() {
         return 0.0;
     }
+    #[cfg(feature = "simd")]
+    let (dot, na, nb) = dot_and_norms_lanes(a, b);
+    #[cfg(not(feature = "simd"))]
+    let (dot, na, nb) = dot_and_norms_scalar(a, b);
+    if norms_well_scaled(na, nb) {
+        return finite_cosine(dot, na * nb);
+    }
+    // Norms that overflow or underflow in f32: rescale so the largest
+    // component of each vector is 1 and try again.
+    let (Some(ma), Some(mb)) = (max_abs(a), max_abs(b)) else { return 0.0 };
+    let (mut dot, mut na, mut nb) = (0.0f32, 0.0f32, 0.0f32);
+    for (x, y) in a.iter().zip(b) {
+        let (x, y) = (x / ma, y / mb);
+        dot += x * y;
+        na += x * x;
+        nb += y * y;
+    }
+    finite_cosine(dot, na.sqrt() * nb.sqrt())
+}
+
+/// How [`similarity`] compares two vectors. Every metric scores "higher is
+/// more similar", so rankings and thresholds keep their direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SimilarityMetric {
+    /// `a·b / (|a|·|b|)` in [-1, 1]; ignores magnitude.
+    #[default]
+    Cosine,
+    /// `1 / (1 + |a − b|)` in (0, 1]; magnitude differences count as distance.
+    Euclidean,
+    /// Raw `a·b`; longer vectors score higher.
+    Dot,
+}
+
+/// Similarity of `a` and `b` under `metric`. Like `cosine_similarity`,
+/// mismatched lengths and non-finite results give 0.0.
+pub fn similarity(metric: SimilarityMetric, a: &[f32], b: &[f32]) -> f32 {
+    if a.is_empty() || a.len() != b.len() {
+        return 0.0;
+    }
+    let sim = match metric {
+        SimilarityMetric::Cosine => return cosine_similarity(a, b),
+        SimilarityMetric::Euclidean => {
+            let dist = a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f32>().sqrt();
+            1.0 / (1.0 + dist)
+        }
+        SimilarityMetric::Dot => a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>(),
+    };
+    if sim.is_finite() { sim } else { 0.0 }
+}
+
+/// `(a·b, |a|, |b|)` with one sequential accumulator each.
+#[cfg_attr(all(feature = "simd", not(test)), allow(dead_code))]
+fn dot_and_norms_scalar(a: &[f32], b: &[f32]) -> (f32, f32, f32) {
     let dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
     let na: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
     let nb: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
-    if na == 0.0 || nb == 0.0 {
-        return 0.0;
+    (dot, na, nb)
+}
+
+/// `(a·b, |a|, |b|)` over f32x8 lanes plus a scalar tail. `std::simd` is
+/// nightly-only, so the lanes are plain arrays: independent accumulators
+/// break the sequential-sum dependency and LLVM lowers them to vector ops.
+#[cfg(any(feature = "simd", test))]
+fn dot_and_norms_lanes(a: &[f32], b: &[f32]) -> (f32, f32, f32) {
+    const LANES: usize = 8;
+    let mut dot = [0.0f32; LANES];
+    let mut na = [0.0f32; LANES];
+    let mut nb = [0.0f32; LANES];
+    let (a_chunks, b_chunks) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+    let (a_tail, b_tail) = (a_chunks.remainder(), b_chunks.remainder());
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for i in 0..LANES {
+            dot[i] += x[i] * y[i];
+            na[i] += x[i] * x[i];
+            nb[i] += y[i] * y[i];
+        }
+    }
+    let mut dot: f32 = dot.iter().sum();
+    let mut na: f32 = na.iter().sum();
+    let mut nb: f32 = nb.iter().sum();
+    for (x, y) in a_tail.iter().zip(b_tail) {
+        dot += x * y;
+        na += x * x;
+        nb += y * y;
     }
-    dot / (na * nb)
+    (dot, na.sqrt(), nb.sqrt())
 }
 
-/// Normalize a vector to unit length in-place.
+/// Normalize a vector to unit length in-place. A zero vector (or one whose
+/// norm is not finite) is left unchanged rather than filled with NaNs.
 pub fn normalize(v: &mut Vec<f32>) {
     let norm: f32 = v.iter().map(|x| x * x).sum::<f32>().sqrt();
-    if norm > 0.0 {
+    if norm.is_finite() && norm > 0.0 {
         for x in v.iter_mut() {
             *x /= norm;
         }
     }
 }
 
+/// A vector stored as its nonzero components: strictly increasing `indices`
+/// with matching `values`, out of `dim` dimensions. Hash-based encodings often
+/// activate a small fraction of a hypervector, so this saves most of the
+/// `dim × 4` bytes a dense vector needs.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct SparseVector {
+    pub dim: usize,
+    pub indices: Vec<u32>,
+    pub values: Vec<f32>,
+}
+
+impl SparseVector {
+    /// Keep the nonzero components of `v`.
+    pub fn from_dense(v: &[f32]) -> Self {
+        let (indices, values) = v.iter().enumerate()
+            .filter(|(_, x)| **x != 0.0)
+            .map(|(i, x)| (i as u32, *x))
+            .unzip();
+        Self { dim: v.len(), indices, values }
+    }
+
+    /// Expand back to a dense vector of length `dim`.
+    pub fn to_dense(&self) -> Vec<f32> {
+        let mut dense = vec![0.0f32; self.dim];
+        for (&i, &x) in self.indices.iter().zip(&self.values) {
+            dense[i as usize] = x;
+        }
+        dense
+    }
+
+    /// Number of stored (nonzero) components.
+    pub fn nnz(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Fraction of the dimensions that are stored; 0 for an empty vector.
+    pub fn density(&self) -> f32 {
+        if self.dim == 0 { 0.0 } else { self.nnz() as f32 / self.dim as f32 }
+    }
+
+    pub fn norm(&self) -> f32 {
+        self.values.iter().map(|x| x * x).sum::<f32>().sqrt()
+    }
+
+    /// Dot product with another sparse vector, merging the two index lists.
+    pub fn dot(&self, other: &SparseVector) -> f32 {
+        let (mut i, mut j, mut dot) = (0, 0, 0.0f32);
+        while i < self.indices.len() && j < other.indices.len() {
+            match self.indices[i].cmp(&other.indices[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    dot += self.values[i] * other.values[j];
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        dot
+    }
+
+    /// Dot product with a dense vector of the same dimensionality.
+    pub fn dot_dense(&self, dense: &[f32]) -> f32 {
+        self.indices.iter().zip(&self.values)
+            .filter_map(|(&i, x)| dense.get(i as usize).map(|y| x * y))
+            .sum()
+    }
+
+    /// Every value divided by `by`.
+    fn scaled_down(&self, by: f32) -> SparseVector {
+        SparseVector {
+            dim: self.dim,
+            indices: self.indices.clone(),
+            values: self.values.iter().map(|x| x / by).collect(),
+        }
+    }
+
+    /// Scale to unit length in-place; zero and non-finite norms are left alone.
+    pub fn normalize(&mut self) {
+        let norm = self.norm();
+        if norm.is_finite() && norm > 0.0 {
+            for x in self.values.iter_mut() {
+                *x /= norm;
+            }
+        }
+    }
+}
+
+/// `cosine_similarity` for two sparse vectors: 0.0 on mismatched
+/// dimensionality or a zero or non-finite norm, like the dense version.
+pub fn sparse_cosine_similarity(a: &SparseVector, b: &SparseVector) -> f32 {
+    if a.dim != b.dim {
+        return 0.0;
+    }
+    let (na, nb) = (a.norm(), b.norm());
+    if norms_well_scaled(na, nb) {
+        return finite_cosine(a.dot(b), na * nb);
+    }
+    let (Some(ma), Some(mb)) = (max_abs(&a.values), max_abs(&b.values)) else { return 0.0 };
+    let (a, b) = (a.scaled_down(ma), b.scaled_down(mb));
+    finite_cosine(a.dot(&b), a.norm() * b.norm())
+}
+
+/// `cosine_similarity` between a sparse and a dense vector.
+pub fn sparse_dense_cosine_similarity(a: &SparseVector, b: &[f32]) -> f32 {
+    if a.dim != b.len() {
+        return 0.0;
+    }
+    let (na, nb) = (a.norm(), b.iter().map(|x| x * x).sum::<f32>().sqrt());
+    if norms_well_scaled(na, nb) {
+        return finite_cosine(a.dot_dense(b), na * nb);
+    }
+    let (Some(ma), Some(mb)) = (max_abs(&a.values), max_abs(b)) else { return 0.0 };
+    let (a, b) = (a.scaled_down(ma), b.iter().map(|y| y / mb).collect::<Vec<f32>>());
+    let nb = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    finite_cosine(a.dot_dense(&b), a.norm() * nb)
+}
+
+/// Whether both norms lie where f32 dot products and norms neither
+/// overflow nor lose precision to subnormal squares.
+fn norms_well_scaled(na: f32, nb: f32) -> bool {
+    const RANGE: std::ops::RangeInclusive<f32> = 1e-18..=1e18;
+    RANGE.contains(&na) && RANGE.contains(&nb)
+}
+
+/// Largest absolute component, or `None` for a zero or non-finite vector.
+fn max_abs(v: &[f32]) -> Option<f32> {
+    let mut max = 0.0f32;
+    for x in v {
+        if !x.is_finite() {
+            return None;
+        }
+        max = max.max(x.abs());
+    }
+    (max > 0.0).then_some(max)
+}
+
+/// `dot / denom`, or 0.0 where that would not be a finite similarity.
+fn finite_cosine(dot: f32, denom: f32) -> f32 {
+    if !(denom.is_finite() && denom > 0.0) {
+        return 0.0;
+    }
+    let sim = dot / denom;
+    if sim.is_finite() { sim } else { 0.0 }
+}
+
+/// Binding operation ⊗: element-wise multiply.
+pub fn bind(a: &[f32], b: &[f32]) -> Vec<f32> {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).collect()
+}
+
+/// Inverse of `bind`: element-wise divide, recovering the other factor of
+/// `composite = bind(x, known)` up to scale. Components where `known` is
+/// (near) zero carry no information and come back as 0.
+pub fn unbind(composite: &[f32], known: &[f32]) -> Vec<f32> {
+    composite
+        .iter()
+        .zip(known.iter())
+        .map(|(c, k)| if k.abs() > 1e-12 { c / k } else { 0.0 })
+        .collect()
+}
+
+/// Permutation operation Π: circular shift of coordinates.
+pub fn permute(v: &[f32], shifts: usize) -> Vec<f32> {
+    let n = v.len();
+    if n == 0 {
+        return vec![];
+    }
+    let shifts = shifts % n;
+    let mut result = vec![0.0f32; n];
+    for i in 0..n {
+        result[(i + shifts) % n] = v[i];
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,6 +358,25 @@ mod tests {
         assert!((s0 - 1.0).abs() < 1e-5);
     }
 
+    #[test]
+    fn wrapped_phase_stays_bounded_under_repeated_nudges() {
+        let tau = std::f32::consts::TAU;
+        let params = |phase| WaveParams { amplitude: 1.0, frequency: 0.1, phase, decay_rate: 0.0 };
+        let nudge = 0.5 * 0.01;
+        let mut phase = 0.0f32;
+        for step in 1..=10_000 {
+            phase = wrap_phase(phase + nudge);
+            assert!((0.0..tau).contains(&phase), "step {}: phase {} escaped [0, 2π)", step, phase);
+        }
+        // Strength matches the exact (f64, unwrapped) phase.
+        let exact = (10_000.0 * nudge as f64).rem_euclid(2.0 * PI);
+        let expected = compute_strength(&params(exact as f32), 3.0);
+        assert!((compute_strength(&params(phase), 3.0) - expected).abs() < 1e-3);
+
+        assert_eq!(wrap_phase(-1e-9), 0.0);
+        assert!((wrap_phase(-PI as f32) - PI as f32).abs() < 1e-6);
+    }
+
     #[test]
     fn cosine_similarity_orthogonal() {
         let a = vec![1.0, 0.0, 0.0];
@@ -108,6 +392,34 @@ mod tests {
         assert!((sim - 1.0).abs() < 1e-5);
     }
 
+    #[test]
+    fn lane_cosine_matches_scalar() {
+        // Deterministic pseudo-random 10k-dim fixture, plus an odd length for the tail.
+        let mut seed = 0x2545_f491u32;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed as f32 / u32::MAX as f32 - 0.5
+        };
+        for dim in [10_000, 1_003] {
+            let a: Vec<f32> = (0..dim).map(|_| next()).collect();
+            let b: Vec<f32> = (0..dim).map(|_| next()).collect();
+            let (sd, sa, sb) = dot_and_norms_scalar(&a, &b);
+            let (ld, la, lb) = dot_and_norms_lanes(&a, &b);
+            assert!((sd / (sa * sb) - ld / (la * lb)).abs() < 1e-5);
+            assert!((cosine_similarity(&a, &b) - sd / (sa * sb)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn unbind_inverts_bind() {
+        let a = vec![0.5, -1.0, 2.0, 0.25];
+        let b = vec![-2.0, 0.5, 0.0, 4.0];
+        let recovered = unbind(&bind(&a, &b), &b);
+        assert_eq!(recovered, vec![0.5, -1.0, 0.0, 0.25]);
+    }
+
     #[test]
     fn normalize_produces_unit_vector() {
         let mut v = vec![3.0, 4.0];
@@ -148,6 +460,62 @@ mod tests {
             "later retrievals should have less effect: {} vs {}", boost_1_to_10, boost_100_to_110);
     }
 
+    #[test]
+    fn sparse_similarities_match_dense_equivalents() {
+        let dim = 1000;
+        let pattern = |seed: usize| {
+            let mut v = vec![0.0f32; dim];
+            for k in 0..20 {
+                let i = (seed * 31 + k * 47) % dim;
+                v[i] = if (seed + k).is_multiple_of(3) { -1.0 } else { 0.5 + k as f32 * 0.1 };
+            }
+            v
+        };
+        let (a, b) = (pattern(1), pattern(2));
+        let dense: Vec<f32> = (0..dim).map(|i| ((i * 7) % 13) as f32 - 6.0).collect();
+        let (sa, sb) = (SparseVector::from_dense(&a), SparseVector::from_dense(&b));
+        assert_eq!(sa.nnz(), 20);
+        assert_eq!(sa.to_dense(), a);
+
+        assert!((sparse_cosine_similarity(&sa, &sb) - cosine_similarity(&a, &b)).abs() < 1e-6);
+        assert!((sparse_dense_cosine_similarity(&sa, &dense) - cosine_similarity(&a, &dense)).abs() < 1e-6);
+        assert!((sparse_cosine_similarity(&sa, &sa) - 1.0).abs() < 1e-6);
+
+        // Degenerate inputs stay finite, like the dense version.
+        let zero = SparseVector::from_dense(&[0.0; 8]);
+        assert_eq!(zero.nnz(), 0);
+        assert_eq!(sparse_cosine_similarity(&zero, &zero), 0.0);
+        assert_eq!(sparse_dense_cosine_similarity(&sa, &dense[..10]), 0.0);
+    }
+
+    #[test]
+    fn zero_vector_stays_finite_through_normalize_and_cosine() {
+        let mut zero = vec![0.0f32; 16];
+        normalize(&mut zero);
+        assert_eq!(zero, vec![0.0f32; 16]);
+
+        let mut v = vec![1.0f32; 16];
+        normalize(&mut v);
+        assert_eq!(cosine_similarity(&zero, &v), 0.0);
+        assert_eq!(cosine_similarity(&zero, &zero), 0.0);
+
+        // Norms that would underflow to zero or overflow to infinity are
+        // rescaled, so parallel vectors still score 1 and opposite ones -1.
+        let tiny = vec![1e-30f32; 16];
+        let huge = vec![1e30f32; 16];
+        let neg_huge = vec![-1e30f32; 16];
+        assert!((cosine_similarity(&tiny, &v) - 1.0).abs() < 1e-5);
+        assert!((cosine_similarity(&huge, &v) - 1.0).abs() < 1e-5);
+        assert!((cosine_similarity(&huge, &huge) - 1.0).abs() < 1e-5);
+        assert!((cosine_similarity(&huge, &neg_huge) + 1.0).abs() < 1e-5);
+        let sparse_huge = SparseVector::from_dense(&huge);
+        assert!((sparse_cosine_similarity(&sparse_huge, &sparse_huge) - 1.0).abs() < 1e-5);
+        assert!((sparse_dense_cosine_similarity(&sparse_huge, &tiny) - 1.0).abs() < 1e-5);
+        let mut nan = vec![f32::NAN; 16];
+        normalize(&mut nan);
+        assert_eq!(cosine_similarity(&nan, &v), 0.0);
+    }
+
     #[test]
     fn zero_retrieval_matches_original() {
         let params = WaveParams {
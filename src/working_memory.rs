@@ -75,6 +75,9 @@ pub struct SessionState {
     pub pending_questions: Vec<String>,
     pub waiting_on: Vec<String>,
     pub conversation_summary: String,
+    /// Condensed record of turns evicted from the verbatim buffer, oldest first.
+    #[serde(default)]
+    pub rolling_summary: String,
     pub last_updated: DateTime<Utc>,
 }
 
@@ -85,6 +88,7 @@ impl Default for SessionState {
             pending_questions: Vec::new(),
             waiting_on: Vec::new(),
             conversation_summary: String::new(),
+            rolling_summary: String::new(),
             last_updated: Utc::now(),
         }
     }
@@ -96,6 +100,11 @@ struct WorkingMemorySnapshot {
     turns: Vec<ConversationTurn>,
     session_state: SessionState,
     max_turns: usize,
+    #[serde(default)]
+    summary_trigger: Option<usize>,
+    /// Rolled-up lines not yet stored in the engine by `checkpoint`.
+    #[serde(default)]
+    pending_rollup: Vec<String>,
     last_checkpoint: Option<DateTime<Utc>>,
     summary_model: String,
 }
@@ -107,11 +116,21 @@ struct WorkingMemorySnapshot {
 const DEFAULT_MAX_TURNS: usize = 50;
 const AUTO_SUMMARY_INTERVAL: usize = 10;
 const SESSION_STATE_TAG: &str = "session-state";
+const CONVERSATION_SUMMARY_TAG: &str = "conversation-summary";
+/// Lines kept in `SessionState::rolling_summary`; older lines fall off.
+const ROLLING_SUMMARY_MAX_LINES: usize = 50;
+/// Characters of each evicted turn kept in the rolling summary.
+const ROLLUP_PREVIEW_CHARS: usize = 80;
 
 pub struct WorkingMemory {
     turns: VecDeque<ConversationTurn>,
     session_state: SessionState,
+    /// Turns kept verbatim after a roll-up.
     max_turns: usize,
+    /// Buffer length above which the oldest turns are rolled into the summary.
+    summary_trigger: usize,
+    /// Rolled-up lines not yet stored in the engine by `checkpoint`.
+    pending_rollup: Vec<String>,
     last_checkpoint: Option<DateTime<Utc>>,
     ollama_url: Option<String>,
     summary_model: String,
@@ -126,6 +145,8 @@ impl WorkingMemory {
             turns: VecDeque::with_capacity(DEFAULT_MAX_TURNS),
             session_state: SessionState::default(),
             max_turns: DEFAULT_MAX_TURNS,
+            summary_trigger: DEFAULT_MAX_TURNS,
+            pending_rollup: Vec::new(),
             last_checkpoint: None,
             ollama_url,
             summary_model: summary_model.unwrap_or_else(|| "phi3:mini".to_string()),
@@ -133,17 +154,41 @@ impl WorkingMemory {
         }
     }
 
-    /// Create with a custom max-turns limit.
-    pub fn with_max_turns(mut self, max: usize) -> Self {
-        self.max_turns = max;
+    /// Create with a custom max-turns limit. Turns beyond it are rolled into
+    /// the summary one at a time.
+    pub fn with_max_turns(self, max: usize) -> Self {
+        self.with_config(max, max)
+    }
+
+    /// Create with a custom context window: once more than `summary_trigger`
+    /// turns are buffered, the oldest are rolled into the rolling summary,
+    /// keeping the most recent `max_turns` verbatim.
+    pub fn with_config(mut self, max_turns: usize, summary_trigger: usize) -> Self {
+        self.set_config(max_turns, summary_trigger);
         self
     }
 
+    /// Change the context window; see `with_config`. `summary_trigger` is
+    /// raised to at least `max_turns`.
+    pub fn set_config(&mut self, max_turns: usize, summary_trigger: usize) {
+        self.max_turns = max_turns;
+        self.summary_trigger = summary_trigger.max(max_turns);
+        if self.turns.len() > self.summary_trigger {
+            self.roll_up();
+        }
+    }
+
+    /// Current `(max_turns, summary_trigger)`.
+    pub fn config(&self) -> (usize, usize) {
+        (self.max_turns, self.summary_trigger)
+    }
+
     // ------------------------------------------------------------------
     // Turn management
     // ------------------------------------------------------------------
 
-    /// Add a conversation turn. Evicts oldest if ring buffer is full.
+    /// Add a conversation turn. Once the buffer exceeds `summary_trigger`,
+    /// the oldest turns are rolled into the rolling summary.
     /// Triggers auto-summary every `AUTO_SUMMARY_INTERVAL` turns.
     pub fn add_turn(&mut self, role: &str, content: &str) {
         let turn = ConversationTurn {
@@ -153,10 +198,10 @@ impl WorkingMemory {
             timestamp: Utc::now(),
             embedding: None,
         };
-        if self.turns.len() >= self.max_turns {
-            self.turns.pop_front();
-        }
         self.turns.push_back(turn);
+        if self.turns.len() > self.summary_trigger {
+            self.roll_up();
+        }
         self.turns_since_summary += 1;
 
         if self.turns_since_summary >= AUTO_SUMMARY_INTERVAL {
@@ -165,6 +210,36 @@ impl WorkingMemory {
         }
     }
 
+    /// Evict turns beyond the newest `max_turns` into one rolling-summary line.
+    fn roll_up(&mut self) {
+        let excess = self.turns.len().saturating_sub(self.max_turns);
+        if excess == 0 {
+            return;
+        }
+        let parts: Vec<String> = self.turns.drain(..excess)
+            .map(|t| {
+                let mut preview: String = t.content.chars().take(ROLLUP_PREVIEW_CHARS).collect();
+                if preview.len() < t.content.len() {
+                    preview.push('…');
+                }
+                format!("[{}] {}", t.role, preview)
+            })
+            .collect();
+        let line = parts.join(" | ");
+
+        let summary = &mut self.session_state.rolling_summary;
+        if !summary.is_empty() {
+            summary.push('\n');
+        }
+        summary.push_str(&line);
+        let lines = summary.lines().count();
+        if lines > ROLLING_SUMMARY_MAX_LINES {
+            *summary = summary.lines().skip(lines - ROLLING_SUMMARY_MAX_LINES).collect::<Vec<_>>().join("\n");
+        }
+        self.pending_rollup.push(line);
+        self.session_state.last_updated = Utc::now();
+    }
+
     /// Number of turns currently stored.
     pub fn turn_count(&self) -> usize {
         self.turns.len()
@@ -297,6 +372,13 @@ impl WorkingMemory {
             out.push_str("\n\n");
         }
 
+        // Earlier, rolled-up turns
+        if !self.session_state.rolling_summary.is_empty() {
+            out.push_str("### Earlier Conversation\n");
+            out.push_str(&self.session_state.rolling_summary);
+            out.push_str("\n\n");
+        }
+
         // Active tasks
         if !self.session_state.active_tasks.is_empty() {
             out.push_str("### Active Tasks\n");
@@ -355,6 +437,8 @@ impl WorkingMemory {
             turns: self.turns.iter().cloned().collect(),
             session_state: self.session_state.clone(),
             max_turns: self.max_turns,
+            summary_trigger: Some(self.summary_trigger),
+            pending_rollup: self.pending_rollup.clone(),
             last_checkpoint: self.last_checkpoint,
             summary_model: self.summary_model.clone(),
         };
@@ -372,6 +456,8 @@ impl WorkingMemory {
             turns: snap.turns.into_iter().collect(),
             session_state: snap.session_state,
             max_turns: snap.max_turns,
+            summary_trigger: snap.summary_trigger.unwrap_or(snap.max_turns).max(snap.max_turns),
+            pending_rollup: snap.pending_rollup,
             last_checkpoint: snap.last_checkpoint,
             ollama_url,
             summary_model: snap.summary_model,
@@ -384,6 +470,8 @@ impl WorkingMemory {
     // ------------------------------------------------------------------
 
     /// Checkpoint: saves JSON + stores a high-amplitude HyperMemory tagged "session-state".
+    /// Turns rolled up since the last checkpoint are stored as a long-term
+    /// memory tagged "conversation-summary".
     pub fn checkpoint(&mut self, data_dir: &Path, engine: &mut MemoryEngine) -> Result<(), std::io::Error> {
         // 1. Save JSON (fast path)
         self.save_json(data_dir)?;
@@ -401,6 +489,15 @@ impl WorkingMemory {
             }
         }
 
+        // 4. Store rolled-up turns as a long-term summary memory
+        if !self.pending_rollup.is_empty() {
+            let summary = format!("[{}] {}", CONVERSATION_SUMMARY_TAG, self.pending_rollup.join("\n"));
+            if let Ok(id) = engine.remember(&summary) {
+                let _ = engine.set_tags(&id, vec![CONVERSATION_SUMMARY_TAG.to_string()]);
+                self.pending_rollup.clear();
+            }
+        }
+
         self.last_checkpoint = Some(Utc::now());
         Ok(())
     }
@@ -461,6 +558,42 @@ mod tests {
         assert_eq!(contents, vec!["two", "three", "four"]);
     }
 
+    #[test]
+    fn long_session_rolls_turns_into_summary_memory() {
+        use crate::encoding::{EncodingPipeline, SimpleHashEncoder};
+        use crate::codebook::Codebook;
+        use crate::store::{MemoryEngine, InMemoryStore};
+
+        let dir = std::env::temp_dir().join(format!("kannaka_wm_test_{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pipeline = EncodingPipeline::new(Box::new(SimpleHashEncoder::new(384, 42)), Codebook::new(384, 10_000, 42));
+        let mut engine = MemoryEngine::new(Box::new(InMemoryStore::new()), pipeline);
+
+        let mut wm = make_wm().with_config(10, 20);
+        for i in 0..100 {
+            wm.add_turn("user", &format!("turn {}", i));
+            assert!(wm.turn_count() <= 20);
+        }
+        // Verbatim buffer keeps the newest turns; older ones are summarized.
+        assert_eq!(wm.turns().last().unwrap().content, "turn 99");
+        assert!(wm.session_state().rolling_summary.contains("[user] turn 0 |"));
+        assert!(wm.get_context().contains("Earlier Conversation"));
+
+        wm.checkpoint(&dir, &mut engine).unwrap();
+        let summaries = engine.ids_with_tags(&[CONVERSATION_SUMMARY_TAG]);
+        assert_eq!(summaries.len(), 1);
+        let id = summaries.into_iter().next().unwrap();
+        assert!(engine.get_memory(&id).unwrap().unwrap().content.contains("turn 0"));
+
+        // Nothing new rolled up: the next checkpoint adds no summary memory.
+        wm.checkpoint(&dir, &mut engine).unwrap();
+        assert_eq!(engine.ids_with_tags(&[CONVERSATION_SUMMARY_TAG]).len(), 1);
+
+        let restored = WorkingMemory::restore(&dir, &engine, None);
+        assert_eq!(restored.config(), (10, 20));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn turn_logging() {
         let mut wm = make_wm();
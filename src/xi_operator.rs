@@ -83,20 +83,28 @@ pub fn xi_repulsive_force(xi_a: &[f32], xi_b: &[f32]) -> f32 {
         return 0.0;
     }
     
-    // Compute difference magnitude
-    let mut diff_sq = 0.0f32;
-    for i in 0..xi_a.len() {
-        let diff = xi_a[i] - xi_b[i];
-        diff_sq += diff * diff;
-    }
-    
-    let diff_magnitude = diff_sq.sqrt();
-    
-    // Scale by emergence coefficient and clamp to [0,1]
-    let force = diff_magnitude * EMERGENCE_COEFF;
+    // Scale difference magnitude by emergence coefficient and clamp to [0,1]
+    let force = xi_distance(xi_a, xi_b) * EMERGENCE_COEFF;
     force.min(1.0)
 }
 
+/// L2 distance between two Xi signatures. Mismatched lengths are infinitely far apart.
+pub fn xi_distance(xi_a: &[f32], xi_b: &[f32]) -> f32 {
+    if xi_a.len() != xi_b.len() {
+        return f32::INFINITY;
+    }
+    xi_a.iter()
+        .zip(xi_b)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f32>()
+        .sqrt()
+}
+
+/// Xi similarity in (0, 1]: 1 / (1 + distance). Identical signatures score 1.0.
+pub fn xi_similarity(xi_a: &[f32], xi_b: &[f32]) -> f32 {
+    1.0 / (1.0 + xi_distance(xi_a, xi_b))
+}
+
 /// Boost search diversity using Xi signatures.
 /// Memories with different Xi residues get boosted scores for better differentiation.
 /// The boost is capped at 1.0 (valid cosine similarity ceiling).
@@ -213,6 +221,17 @@ mod tests {
         assert!(repulsion_diff > repulsion_sim, "Different Xi should have higher repulsion");
     }
 
+    #[test]
+    fn xi_distance_and_similarity() {
+        let a = vec![1.0, 0.0, 0.0];
+        let b = vec![0.0, 1.0, 0.0];
+        assert_eq!(xi_distance(&a, &a), 0.0);
+        assert!((xi_distance(&a, &b) - 2.0f32.sqrt()).abs() < 1e-6);
+        assert_eq!(xi_similarity(&a, &a), 1.0);
+        assert!(xi_similarity(&a, &b) < xi_similarity(&a, &a));
+        assert_eq!(xi_similarity(&a, &[1.0, 0.0]), 0.0);
+    }
+
     #[test]
     fn emergence_coefficient_is_correct() {
         assert!((EMERGENCE_COEFF - 0.190983).abs() < 1e-5);
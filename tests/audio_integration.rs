@@ -34,7 +34,7 @@ fn same_sound_is_deterministic() {
     let tone = sine_wave(440.0, 2.0, 0.5);
     let (mem1, _) = pipeline.encode_samples(&tone, "A440").unwrap();
     let (mem2, _) = pipeline.encode_samples(&tone, "A440").unwrap();
-    let sim = cosine_similarity(&mem1.vector, &mem2.vector);
+    let sim = cosine_similarity(&mem1.dense_vector(), &mem2.dense_vector());
     assert!(
         (sim - 1.0).abs() < 1e-4,
         "same input should produce identical vectors, sim={}",
@@ -49,7 +49,7 @@ fn different_frequencies_produce_different_vectors() {
     let high = sine_wave(4000.0, 2.0, 0.5);
     let (mem_low, _) = pipeline.encode_samples(&low, "200Hz").unwrap();
     let (mem_high, _) = pipeline.encode_samples(&high, "4kHz").unwrap();
-    let sim = cosine_similarity(&mem_low.vector, &mem_high.vector);
+    let sim = cosine_similarity(&mem_low.dense_vector(), &mem_high.dense_vector());
     // Pure sine waves differ mainly in which mel band is active;
     // similarity can be high because most features (ZCR, RMS, etc.) are similar.
     // The important thing is they're not identical.
@@ -67,7 +67,7 @@ fn sine_vs_noise_are_distinct() {
     let noise = white_noise(2.0);
     let (mem_tone, _) = pipeline.encode_samples(&tone, "A440").unwrap();
     let (mem_noise, _) = pipeline.encode_samples(&noise, "noise").unwrap();
-    let sim = cosine_similarity(&mem_tone.vector, &mem_noise.vector);
+    let sim = cosine_similarity(&mem_tone.dense_vector(), &mem_noise.dense_vector());
     assert!(
         sim < 0.7,
         "tone and noise should be distinct, sim={}",
@@ -107,7 +107,7 @@ fn audio_orthogonal_to_text() {
     let text_embedding = vec![0.5f32; 384];
     let text_hv = text_codebook.project(&text_embedding);
 
-    let sim = cosine_similarity(&audio_mem.vector, &text_hv);
+    let sim = cosine_similarity(&audio_mem.dense_vector(), &text_hv);
     assert!(
         sim.abs() < 0.15,
         "audio and text vectors should be nearly orthogonal, sim={}",
@@ -139,7 +139,7 @@ fn audio_orthogonal_to_multiple_texts() {
         let (audio_mem, _) = audio_pipeline.encode_samples(sound, "test").unwrap();
         for text_emb in &texts {
             let text_hv = text_codebook.project(text_emb);
-            let sim = cosine_similarity(&audio_mem.vector, &text_hv);
+            let sim = cosine_similarity(&audio_mem.dense_vector(), &text_hv);
             assert!(
                 sim.abs() < 0.15,
                 "audio/text orthogonality violated: sim={}",
@@ -127,7 +127,7 @@ fn dolt_search_finds_inserted_memory() {
     let mem = make_memory("searchable memory");
     let id = store.insert(mem.clone()).expect("insert");
 
-    let results = store.search(&mem.vector, 5).expect("search");
+    let results = store.search(&mem.dense_vector(), 5).expect("search");
     let found = results.iter().any(|(rid, _)| *rid == id);
     assert!(found, "inserted memory should appear in search results");
 